@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+use std::process::{Command, Stdio};
+
+/// A user-defined command from the `[actions]` config section.
+#[derive(Debug, Clone)]
+pub struct CustomAction {
+    pub name: String,
+    pub template: String,
+}
+
+/// Values available to `{placeholder}` substitution for the highlighted item.
+#[derive(Debug, Default)]
+pub struct Placeholders<'a> {
+    pub path: Option<&'a str>,
+    pub project: Option<&'a str>,
+    pub branch: Option<&'a str>,
+    pub hash: Option<&'a str>,
+    pub url: Option<&'a str>,
+}
+
+impl Placeholders<'_> {
+    /// The value for a known placeholder name, `None` for any other name.
+    fn get(&self, name: &str) -> Option<Option<&str>> {
+        match name {
+            "path" => Some(self.path),
+            "project" => Some(self.project),
+            "branch" => Some(self.branch),
+            "hash" => Some(self.hash),
+            "url" => Some(self.url),
+            _ => None,
+        }
+    }
+}
+
+pub fn from_config(actions: &BTreeMap<String, String>) -> Vec<CustomAction> {
+    actions
+        .iter()
+        .map(|(name, template)| CustomAction {
+            name: name.clone(),
+            template: template.clone(),
+        })
+        .collect()
+}
+
+impl CustomAction {
+    /// Substitute placeholders (shell-quoted). Only the known names count as
+    /// placeholders, and `{{name}}` of a known name is the literal text
+    /// `{name}`; all other braces are kept as written. Returns `None` if the
+    /// template needs a value that is not available at the current level.
+    pub fn expand(&self, values: &Placeholders) -> Option<String> {
+        let mut out = String::new();
+        let mut rest = self.template.as_str();
+        while let Some(at) = rest.find('{') {
+            out.push_str(&rest[..at]);
+            let tail = &rest[at..];
+            let escaped = tail
+                .strip_prefix("{{")
+                .and_then(|t| t.split_once("}}"))
+                .filter(|(name, _)| values.get(name).is_some());
+            if let Some((name, after)) = escaped {
+                out.push_str(&format!("{{{name}}}"));
+                rest = after;
+                continue;
+            }
+            let placeholder = tail
+                .strip_prefix('{')
+                .and_then(|t| t.split_once('}'))
+                .and_then(|(name, after)| Some((values.get(name)?, after)));
+            match placeholder {
+                Some((value, after)) => {
+                    out.push_str(&shell_quote(value?));
+                    rest = after;
+                }
+                None => {
+                    out.push('{');
+                    rest = &tail[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+        Some(out)
+    }
+
+    /// Run the expanded command through `sh`, inside the project directory.
+    pub fn run(&self, values: &Placeholders) {
+        let Some(command) = self.expand(values) else {
+            eprintln!("Action \"{}\" is not available here.", self.name);
+            return;
+        };
+        let mut cmd = Command::new("sh");
+        // Stdout may be read by the shell, as with `eval "$(devcap -i)"`.
+        cmd.args(["-c", &command])
+            .stdout(Stdio::from(std::io::stderr()));
+        if let Some(path) = values.path {
+            cmd.current_dir(path);
+        }
+        match cmd.status() {
+            Ok(status) if !status.success() => {
+                eprintln!("Action \"{}\" exited with {status}", self.name);
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("Warning: could not run action \"{}\": {e}", self.name),
+        }
+    }
+}
+
+/// Quote a value for POSIX shells.
+pub fn shell_quote(s: &str) -> String {
+    if !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/._-~+:@".contains(c))
+    {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(template: &str) -> CustomAction {
+        CustomAction {
+            name: "test".to_string(),
+            template: template.to_string(),
+        }
+    }
+
+    #[test]
+    fn expand_substitutes_placeholders() {
+        let values = Placeholders {
+            hash: Some("abc1234"),
+            branch: Some("feature/auth"),
+            ..Default::default()
+        };
+        assert_eq!(
+            action("gh pr view {branch} # {hash}").expand(&values),
+            Some("gh pr view feature/auth # abc1234".to_string())
+        );
+    }
+
+    #[test]
+    fn expand_quotes_values() {
+        let values = Placeholders {
+            path: Some("/tmp/my app"),
+            ..Default::default()
+        };
+        assert_eq!(
+            action("ls {path}").expand(&values),
+            Some("ls '/tmp/my app'".to_string())
+        );
+    }
+
+    #[test]
+    fn expand_missing_value_is_unavailable() {
+        let values = Placeholders {
+            path: Some("/tmp"),
+            ..Default::default()
+        };
+        assert_eq!(action("git show {hash}").expand(&values), None);
+    }
+
+    #[test]
+    fn expand_keeps_other_braces_and_escapes() {
+        let values = Placeholders {
+            path: Some("/tmp"),
+            ..Default::default()
+        };
+        assert_eq!(
+            action("awk '{print $1}' {path}/log").expand(&values),
+            Some("awk '{print $1}' /tmp/log".to_string())
+        );
+        assert_eq!(
+            action("echo {unknown} ${HOME}").expand(&values),
+            Some("echo {unknown} ${HOME}".to_string())
+        );
+        assert_eq!(
+            action("awk '{ if (a) { b }}' {path}").expand(&values),
+            Some("awk '{ if (a) { b }}' /tmp".to_string())
+        );
+        assert_eq!(
+            action("echo {{path}} {{unknown}} }}").expand(&values),
+            Some("echo {path} {{unknown}} }}".to_string())
+        );
+    }
+
+    #[test]
+    fn expand_keeps_unclosed_brace() {
+        let values = Placeholders::default();
+        assert_eq!(
+            action("awk '{print $1'").expand(&values),
+            Some("awk '{print $1'".to_string())
+        );
+    }
+
+    #[test]
+    fn shell_quote_plain_path_unchanged() {
+        assert_eq!(shell_quote("/home/me/my-app"), "/home/me/my-app");
+    }
+
+    #[test]
+    fn shell_quote_wraps_spaces_and_quotes() {
+        assert_eq!(shell_quote("/tmp/my app"), "'/tmp/my app'");
+        assert_eq!(shell_quote("/tmp/it's"), "'/tmp/it'\\''s'");
+    }
+}
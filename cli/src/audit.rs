@@ -0,0 +1,192 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+use devcap_core::model::ProjectLog;
+use devcap_core::period::TimeRange;
+use devcap_core::zone::Time;
+
+/// One line of the run audit log.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub started_at: DateTime<Local>,
+    pub args: Vec<String>,
+    pub duration_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<Time>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<Time>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub repos_scanned: usize,
+    #[serde(default)]
+    pub projects: usize,
+    #[serde(default)]
+    pub commits: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RunRecord {
+    pub fn start(args: Vec<String>) -> Self {
+        Self {
+            started_at: Local::now(),
+            args,
+            duration_ms: 0,
+            since: None,
+            until: None,
+            path: None,
+            author: None,
+            repos_scanned: 0,
+            projects: 0,
+            commits: 0,
+            error: None,
+        }
+    }
+
+    pub fn set_range(&mut self, range: &TimeRange) {
+        self.since = Some(range.since);
+        self.until = range.until;
+    }
+
+    pub fn set_results(&mut self, projects: &[ProjectLog]) {
+        self.projects = projects.len();
+        self.commits = projects.iter().map(ProjectLog::total_commits).sum();
+    }
+
+    pub fn finish(&mut self, elapsed: Duration, error: Option<String>) {
+        self.duration_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        self.error = error;
+    }
+}
+
+/// `<data dir>/runs.jsonl`.
+pub fn log_path() -> Option<PathBuf> {
+    Some(paths::data_dir()?.join("runs.jsonl"))
+}
+
+pub fn append(record: &RunRecord) -> Result<()> {
+    let path = log_path().context("cannot determine data directory")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    }
+    let line = serde_json::to_string(record)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("cannot open {}", path.display()))?;
+    writeln!(file, "{line}")?;
+    Ok(())
+}
+
+/// Read all records, skipping lines that do not parse.
+pub fn read_all() -> Result<Vec<RunRecord>> {
+    let Some(path) = log_path() else {
+        return Ok(Vec::new());
+    };
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+    Ok(parse_lines(&content))
+}
+
+fn parse_lines(content: &str) -> Vec<RunRecord> {
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect()
+}
+
+/// Most recent runs last, like a log.
+pub fn render_terminal(records: &[RunRecord]) {
+    if records.is_empty() {
+        eprintln!(
+            "{}",
+            "No runs recorded. Set `audit_log = true` in ~/.devcap.toml to enable.".dimmed()
+        );
+        return;
+    }
+    for record in records {
+        println!("{}", format_record(record));
+    }
+}
+
+pub fn render_json(records: &[RunRecord]) -> String {
+    serde_json::to_string_pretty(records).unwrap_or_else(|_| "[]".to_string())
+}
+
+fn format_record(record: &RunRecord) -> String {
+    let when = record.started_at.format("%Y-%m-%d %H:%M:%S").to_string();
+    let range = match (record.since, record.until) {
+        (Some(s), Some(u)) => format!(
+            "{} \u{2192} {}",
+            s.format("%m-%d %H:%M"),
+            u.format("%m-%d %H:%M")
+        ),
+        (Some(s), None) => format!("{} \u{2192} now", s.format("%m-%d %H:%M")),
+        _ => "-".to_string(),
+    };
+    let outcome = match &record.error {
+        Some(e) => format!("error: {e}").red().to_string(),
+        None => format!(
+            "{} repos, {} projects, {} commits",
+            record.repos_scanned, record.projects, record.commits
+        ),
+    };
+    let args = if record.args.is_empty() {
+        "(no args)".to_string()
+    } else {
+        record.args.join(" ")
+    };
+    format!(
+        "{}  {:>6}  {}  {}  {}",
+        when.dimmed(),
+        format!("{:.1}s", record.duration_ms as f64 / 1000.0),
+        range,
+        outcome,
+        args.dimmed()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_round_trips_through_jsonl() {
+        let mut record = RunRecord::start(vec!["-p".to_string(), "week".to_string()]);
+        record.repos_scanned = 12;
+        record.commits = 7;
+        record.finish(Duration::from_millis(1500), None);
+        let line = serde_json::to_string(&record).expect("serialize");
+        let parsed = parse_lines(&format!("{line}\nnot json\n\n"));
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].args, ["-p", "week"]);
+        assert_eq!(parsed[0].repos_scanned, 12);
+        assert_eq!(parsed[0].duration_ms, 1500);
+    }
+
+    #[test]
+    fn format_record_shows_error() {
+        let mut record = RunRecord::start(vec![]);
+        record.finish(Duration::from_millis(20), Some("bad period".to_string()));
+        colored::control::set_override(false);
+        let line = format_record(&record);
+        assert!(line.contains("error: bad period"));
+        assert!(line.contains("(no args)"));
+    }
+}
@@ -0,0 +1,5 @@
+//! `wg`: short alias of the `devcap` binary.
+
+fn main() -> anyhow::Result<()> {
+    devcap_cli::main()
+}
@@ -0,0 +1,148 @@
+//! On-disk cache for data fetched from forge APIs (GitHub, GitLab, ...).
+//!
+//! Each kind of data lives in its own `<cache dir>/forge/<name>.json`. Entries
+//! older than the TTL are re-fetched, but are kept as a fallback for when the
+//! API cannot be reached.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Duration, Local};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+
+/// Default time-to-live when `cache_ttl` is not configured.
+pub const DEFAULT_TTL_MINUTES: u32 = 60;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry<T> {
+    fetched_at: DateTime<Local>,
+    value: T,
+}
+
+/// How entries are treated when read.
+#[derive(Debug, Clone, Copy)]
+pub struct CachePolicy {
+    pub ttl: Duration,
+    /// Treat every entry as expired (`--refresh`).
+    pub refresh: bool,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::minutes(i64::from(DEFAULT_TTL_MINUTES)),
+            refresh: false,
+        }
+    }
+}
+
+pub struct ForgeCache<T> {
+    path: Option<PathBuf>,
+    entries: HashMap<String, Entry<T>>,
+    policy: CachePolicy,
+    dirty: bool,
+}
+
+impl<T: Serialize + DeserializeOwned + Clone> ForgeCache<T> {
+    pub fn open(name: &str, policy: CachePolicy) -> Self {
+        let path = paths::cache_dir().map(|d| d.join("forge").join(format!("{name}.json")));
+        let entries = path
+            .as_ref()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            entries,
+            policy,
+            dirty: false,
+        }
+    }
+
+    /// Value younger than the TTL, unless refreshing.
+    pub fn fresh(&self, key: &str) -> Option<&T> {
+        if self.policy.refresh {
+            return None;
+        }
+        let entry = self.entries.get(key)?;
+        (Local::now() - entry.fetched_at < self.policy.ttl).then_some(&entry.value)
+    }
+
+    /// Value of any age, for when fetching failed.
+    pub fn stale(&self, key: &str) -> Option<&T> {
+        self.entries.get(key).map(|e| &e.value)
+    }
+
+    pub fn insert(&mut self, key: String, value: T) {
+        self.entries.insert(
+            key,
+            Entry {
+                fetched_at: Local::now(),
+                value,
+            },
+        );
+        self.dirty = true;
+    }
+
+    /// Write back to disk if anything was inserted. Errors are ignored: a
+    /// missing cache only costs another request next time.
+    pub fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if !self.dirty {
+            return;
+        }
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        if let Ok(json) = serde_json::to_string(&self.entries) {
+            let _ = std::fs::write(path, json);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(policy: CachePolicy) -> ForgeCache<u32> {
+        ForgeCache {
+            path: None,
+            entries: HashMap::new(),
+            policy,
+            dirty: false,
+        }
+    }
+
+    #[test]
+    fn expired_entries_are_only_stale() {
+        let mut c = cache(CachePolicy::default());
+        c.insert("new".to_string(), 1);
+        c.entries.insert(
+            "old".to_string(),
+            Entry {
+                fetched_at: Local::now() - Duration::hours(3),
+                value: 2,
+            },
+        );
+        assert_eq!(c.fresh("new"), Some(&1));
+        assert_eq!(c.fresh("old"), None);
+        assert_eq!(c.stale("old"), Some(&2));
+        assert_eq!(c.stale("missing"), None);
+    }
+
+    #[test]
+    fn refresh_ignores_fresh_entries() {
+        let mut c = cache(CachePolicy {
+            refresh: true,
+            ..CachePolicy::default()
+        });
+        c.insert("key".to_string(), 1);
+        assert_eq!(c.fresh("key"), None);
+        assert_eq!(c.stale("key"), Some(&1));
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+
+use devcap_core::model::{Commit, ProjectLog};
+
+const SECTIONS: [(&str, &[&str]); 2] = [("Features", &["feat"]), ("Fixes", &["fix"])];
+
+/// Render a Markdown changelog draft (keep-a-changelog style): one section
+/// per project, commits grouped under Features, Fixes and Other.
+pub fn render(projects: &[ProjectLog]) -> String {
+    let mut out = String::new();
+    for (i, project) in projects.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(&format!("## {}\n", project.project));
+
+        let mut rest = unique_commits(project);
+        for (heading, types) in SECTIONS {
+            let (matching, others): (Vec<&Commit>, Vec<&Commit>) = rest
+                .into_iter()
+                .partition(|c| c.commit_type.as_deref().is_some_and(|t| types.contains(&t)));
+            push_section(&mut out, heading, &matching, true);
+            rest = others;
+        }
+        push_section(&mut out, "Other", &rest, false);
+    }
+    out
+}
+
+/// Append the summary and totals line below a horizontal rule; the
+/// summary's indented figures become list items.
+pub fn with_footer(mut markdown: String, footer: Option<&str>) -> String {
+    if let Some(text) = footer {
+        markdown.push_str("\n---\n\n");
+        for line in text.lines() {
+            match line.strip_prefix("  ") {
+                Some(item) => markdown.push_str(&format!("- {item}\n")),
+                None if line.is_empty() => markdown.push('\n'),
+                None => markdown.push_str(&format!("{line}\n")),
+            }
+        }
+    }
+    markdown
+}
+
+/// `strip` drops the type prefix, which the heading already states.
+fn push_section(out: &mut String, heading: &str, commits: &[&Commit], strip: bool) {
+    if commits.is_empty() {
+        return;
+    }
+    out.push_str(&format!("\n### {heading}\n\n"));
+    for commit in commits {
+        out.push_str(&format!("- {}\n", entry(commit, strip)));
+    }
+}
+
+fn entry(commit: &Commit, strip: bool) -> String {
+    let text = if strip {
+        strip_type(&commit.message)
+    } else {
+        commit.message.clone()
+    };
+    let link = match &commit.url {
+        Some(url) => format!("[{}]({url})", commit.hash),
+        None => format!("`{}`", commit.hash),
+    };
+    format!("{text} ({link})")
+}
+
+/// `feat(api)!: add x` → `**api:** add x`, `fix: y` → `y`.
+fn strip_type(message: &str) -> String {
+    let Some((prefix, rest)) = message.split_once(':') else {
+        return message.to_string();
+    };
+    let rest = rest.trim_start();
+    if rest.is_empty() {
+        return message.to_string();
+    }
+    let scope = prefix
+        .split_once('(')
+        .and_then(|(_, s)| s.split_once(')'))
+        .map(|(scope, _)| scope.trim())
+        .filter(|s| !s.is_empty());
+    match scope {
+        Some(scope) => format!("**{scope}:** {rest}"),
+        None => rest.to_string(),
+    }
+}
+
+/// Commits across all branches, each hash once, in branch order.
+fn unique_commits(project: &ProjectLog) -> Vec<&Commit> {
+    let mut seen = HashSet::new();
+    project
+        .branches
+        .iter()
+        .flat_map(|b| &b.commits)
+        .filter(|c| seen.insert(c.hash.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Local;
+    use devcap_core::model::BranchLog;
+
+    fn make_commit(hash: &str, msg: &str, commit_type: Option<&str>) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            message: msg.to_string(),
+            commit_type: commit_type.map(String::from),
+            time: Local::now().fixed_offset(),
+            relative_time: "1h ago".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn make_project(branches: Vec<(&str, Vec<Commit>)>) -> ProjectLog {
+        ProjectLog {
+            project: "my-app".to_string(),
+            path: "/test/my-app".to_string(),
+            branches: branches
+                .into_iter()
+                .map(|(name, commits)| BranchLog {
+                    name: name.to_string(),
+                    commits,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn commits_grouped_under_headings() {
+        let mut linked = make_commit("abc1234", "feat(auth): add login", Some("feat"));
+        linked.url = Some("https://github.com/o/r/commit/abc1234".to_string());
+        let project = make_project(vec![
+            (
+                "main",
+                vec![
+                    linked,
+                    make_commit("def5678", "fix: resolve crash", Some("fix")),
+                    make_commit("aaa0000", "docs: update README", Some("docs")),
+                ],
+            ),
+            (
+                "feature",
+                vec![make_commit(
+                    "abc1234",
+                    "feat(auth): add login",
+                    Some("feat"),
+                )],
+            ),
+        ]);
+        let text = render(&[project]);
+        assert_eq!(
+            text,
+            "## my-app\n\
+             \n### Features\n\n\
+             - **auth:** add login ([abc1234](https://github.com/o/r/commit/abc1234))\n\
+             \n### Fixes\n\n\
+             - resolve crash (`def5678`)\n\
+             \n### Other\n\n\
+             - docs: update README (`aaa0000`)\n"
+        );
+    }
+
+    #[test]
+    fn empty_sections_are_omitted() {
+        let project = make_project(vec![(
+            "main",
+            vec![make_commit("def5678", "fix: resolve crash", Some("fix"))],
+        )]);
+        let text = render(&[project]);
+        assert!(text.contains("### Fixes"));
+        assert!(!text.contains("### Features"));
+        assert!(!text.contains("### Other"));
+    }
+
+    #[test]
+    fn strip_type_keeps_scope() {
+        assert_eq!(strip_type("feat(api)!: add x"), "**api:** add x");
+        assert_eq!(strip_type("chore: bump deps"), "bump deps");
+        assert_eq!(strip_type("wip"), "wip");
+    }
+}
@@ -1,8 +1,12 @@
 use chrono::NaiveDate;
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
+use devcap_core::bucket::GroupBy;
 use devcap_core::period::Period;
+pub use devcap_core::sort::SortSpec;
+use std::fmt;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum Depth {
@@ -14,69 +18,97 @@ pub enum Depth {
     Commits,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum SortField {
-    Time,
-    Commits,
-    Name,
-    Lines,
+/// What `--select` lets the user pick and prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SelectTarget {
+    /// Print the project's path
+    Project,
+    /// Print the branch name
+    Branch,
+    /// Print the commit hash
+    Commit,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum SortDirection {
-    Asc,
-    Desc,
+/// Where `--post` sends the report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PostTarget {
+    /// The webhook or channel configured in `[slack]`
+    Slack,
+    /// The recipients configured in `[email]`
+    Email,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct SortSpec {
-    pub field: SortField,
-    pub direction: SortDirection,
+/// Forge data `--enrich` adds to the report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Enrichment {
+    /// The latest pull/merge request of each branch, open, merged or closed
+    Prs,
 }
 
-impl Default for SortSpec {
-    fn default() -> Self {
-        Self {
-            field: SortField::Time,
-            direction: SortDirection::Desc,
-        }
-    }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    /// Markdown changelog draft grouped by commit type
+    Changelog,
+    /// One row per commit for spreadsheets
+    Csv,
+    /// A `<name>.wasm` module from the plugin directory
+    Plugin(String),
 }
 
-impl FromStr for SortSpec {
+impl FromStr for OutputFormat {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (field_str, dir_str) = match s.split_once(':') {
-            Some((f, d)) => (f, Some(d)),
-            None => (s, None),
-        };
-
-        let field = match field_str {
-            "time" => SortField::Time,
-            "commits" => SortField::Commits,
-            "name" => SortField::Name,
-            "lines" => SortField::Lines,
-            _ => {
-                return Err(format!(
-                    "unknown sort field: {field_str} (expected: time, commits, name, lines)"
-                ))
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "changelog" => Ok(Self::Changelog),
+            "csv" => Ok(Self::Csv),
+            name if !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') =>
+            {
+                Ok(Self::Plugin(name.to_string()))
             }
-        };
+            other => Err(format!(
+                "invalid format: {other} (expected: text, json, changelog, csv, or a plugin name)"
+            )),
+        }
+    }
+}
 
-        let default_dir = match field {
-            SortField::Name => SortDirection::Asc,
-            _ => SortDirection::Desc,
-        };
+/// Refresh interval of `--watch`: `30s`, `5m`, `1h`, or bare seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchInterval(pub Duration);
+
+impl FromStr for WatchInterval {
+    type Err = String;
 
-        let direction = match dir_str {
-            Some("asc") => SortDirection::Asc,
-            Some("desc") => SortDirection::Desc,
-            None => default_dir,
-            Some(d) => return Err(format!("unknown sort direction: {d} (expected: asc, desc)")),
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (number, factor) = match s.char_indices().last() {
+            Some((i, 's')) => (&s[..i], 1),
+            Some((i, 'm')) => (&s[..i], 60),
+            Some((i, 'h')) => (&s[..i], 3600),
+            _ => (s, 1),
         };
+        match number.parse::<u64>() {
+            Ok(n) if n > 0 => Ok(Self(Duration::from_secs(n * factor))),
+            _ => Err(format!("invalid interval: {s} (expected e.g. 30s, 5m, 1h)")),
+        }
+    }
+}
 
-        Ok(Self { field, direction })
+impl fmt::Display for WatchInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.as_secs() {
+            s if s % 3600 == 0 => write!(f, "{}h", s / 3600),
+            s if s % 60 == 0 => write!(f, "{}m", s / 60),
+            s => write!(f, "{s}s"),
+        }
     }
 }
 
@@ -87,57 +119,304 @@ impl FromStr for SortSpec {
     version
 )]
 pub struct Cli {
-    /// Time period: today, yesterday, 24h, 3d, 7d, week
-    #[arg(short, long)]
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Time period: today, yesterday, 24h, 3d, 7d, week, last-week, month, last-month, quarter, cw21, 2024-W21, FROM..TO
+    #[arg(short, long, global = true)]
     pub period: Option<Period>,
 
     /// Start date (inclusive, YYYY-MM-DD). Overrides --period start.
-    #[arg(long, value_name = "DATE")]
+    #[arg(long, value_name = "DATE", global = true)]
     pub since: Option<NaiveDate>,
 
     /// End date (inclusive, YYYY-MM-DD). Overrides --period end.
-    #[arg(long, value_name = "DATE")]
+    #[arg(long, value_name = "DATE", global = true)]
     pub until: Option<NaiveDate>,
 
+    /// Time zone for commit times, period boundaries and days, e.g. Europe/Berlin
+    #[arg(long, value_name = "ZONE", global = true)]
+    pub timezone: Option<String>,
+
     /// Root directory to scan for git repos
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub path: Option<PathBuf>,
 
+    /// Scan the roots of a workspace defined in ~/.devcap.toml
+    #[arg(
+        short = 'w',
+        long,
+        value_name = "NAME",
+        conflicts_with = "path",
+        global = true
+    )]
+    pub workspace: Option<String>,
+
     /// Output as JSON
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub json: bool,
 
-    /// Disable colored output (overrides TTY auto-detection)
+    /// Print the JSON Schema of the --json report and exit
     #[arg(long)]
+    pub schema: bool,
+
+    /// Output format: text, json, changelog, csv, or the name of an installed plugin
+    #[arg(long, value_name = "FORMAT", global = true)]
+    pub format: Option<OutputFormat>,
+
+    /// Field separator for --format csv, e.g. ';' for spreadsheets in European locales [default: ,]
+    #[arg(long, value_name = "CHAR", global = true)]
+    pub csv_delimiter: Option<char>,
+
+    /// Render the report through a Handlebars template file
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["json", "format", "interactive", "tui", "select", "watch"],
+        global = true
+    )]
+    pub template: Option<PathBuf>,
+
+    /// Suppress the progress bar, summary line and informational messages on stderr
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// Disable colored output (overrides TTY auto-detection)
+    #[arg(long, global = true)]
     pub no_color: bool,
 
     /// Interactive drill-down mode (projects > branches > commits)
-    #[arg(short, long, conflicts_with = "json")]
+    #[arg(short, long, conflicts_with = "json", global = true)]
     pub interactive: bool,
 
+    /// Full-screen browser with projects, branches and commits side by side
+    #[arg(long, conflicts_with = "json", global = true)]
+    pub tui: bool,
+
+    /// Keep running and redraw the report every INTERVAL (e.g. 30s, 5m) [default: 1m]
+    #[arg(
+        long,
+        value_name = "INTERVAL",
+        num_args = 0..=1,
+        default_missing_value = "1m",
+        conflicts_with_all = ["json", "interactive", "tui", "select", "by_author", "group_by", "copy", "post"],
+        global = true
+    )]
+    pub watch: Option<WatchInterval>,
+
+    /// Pick a project, branch or commit with the fuzzy selector and print its path, name or hash
+    #[arg(long, value_name = "ITEM", conflicts_with_all = ["json", "interactive", "tui"], global = true)]
+    pub select: Option<SelectTarget>,
+
     /// Output depth: projects, branches, commits
-    #[arg(short, long, default_value = "commits", conflicts_with = "json")]
+    #[arg(short, long, default_value = "commits", global = true)]
     pub depth: Depth,
 
-    /// Filter by author name (defaults to git config user.name)
-    #[arg(short, long)]
-    pub author: Option<String>,
+    /// Filter by author name; repeat to include several authors (defaults to each repo's git user.name)
+    #[arg(short, long, global = true)]
+    pub author: Vec<String>,
+
+    /// List each branch's commits under date headers
+    #[arg(long, global = true)]
+    pub by_day: bool,
+
+    /// Split the report into sections per commit author
+    #[arg(long, conflicts_with = "group_by", global = true)]
+    pub by_author: bool,
 
     /// Show repository origin (GitHub, GitLab, etc.)
-    #[arg(short = 'o', long)]
+    #[arg(short = 'o', long, global = true)]
     pub show_origin: bool,
 
+    /// Keep only commits whose message matches this regex (repeatable)
+    #[arg(long, value_name = "REGEX", global = true)]
+    pub grep: Vec<String>,
+
+    /// Keep commits whose message matches none of the --grep patterns
+    #[arg(long, requires = "grep", global = true)]
+    pub invert_grep: bool,
+
+    /// Drop commits whose message matches this regex (repeatable)
+    #[arg(long, value_name = "REGEX", global = true)]
+    pub exclude_grep: Vec<String>,
+
+    /// Include merge commits, which are left out by default
+    #[arg(long, global = true)]
+    pub include_merges: bool,
+
+    /// Also report remote-tracking branches (origin/*) that have no local branch
+    #[arg(long, global = true)]
+    pub remote_branches: bool,
+
+    /// List each commit under one branch only, preferring the branch it was made on
+    #[arg(long, global = true)]
+    pub dedupe: bool,
+
     /// Show diff stats (+insertions -deletions ~files) per commit
-    #[arg(short = 's', long)]
+    #[arg(short = 's', long, visible_alias = "stats", global = true)]
     pub stat: bool,
 
-    /// Sort projects: time, commits, name, lines (append :asc or :desc)
-    #[arg(long)]
+    /// Sort projects: time (or activity), commits, name, lines (append :asc or :desc)
+    #[arg(long, global = true)]
     pub sort: Option<SortSpec>,
 
+    /// Do not descend into network mounts (NFS, SMB, sshfs) below --path
+    #[arg(long, global = true)]
+    pub skip_network: bool,
+
+    /// Show the open pull/merge request title next to each branch (GitHub, GitLab)
+    #[arg(long, global = true)]
+    pub pr_titles: bool,
+
+    /// Add forge data to the report (repeatable): prs = latest pull/merge request of each branch, in any state
+    #[arg(long, value_enum, value_name = "WHAT", global = true)]
+    pub enrich: Vec<Enrichment>,
+
+    /// Ignore cached forge data and fetch it again
+    #[arg(long, global = true)]
+    pub refresh: bool,
+
+    /// Show each repo's working-copy state: branch, uncommitted changes, stashes, unpushed commits
+    #[arg(long, global = true)]
+    pub health: bool,
+
+    /// Rescan every repository instead of reusing today's cached commits
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+
+    /// Never touch the network: no fetching, no forge API requests, no network mounts
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Run `git fetch --all` in every repository before scanning
+    #[arg(long, global = true)]
+    pub fetch: bool,
+
+    /// Deepen shallow clones whose history does not cover the period
+    #[arg(long, global = true)]
+    pub fetch_shallow: bool,
+
+    /// Skip directories matching this glob while scanning, by name or by path when it contains a / (repeatable)
+    #[arg(long, value_name = "GLOB", global = true)]
+    pub exclude: Vec<String>,
+
+    /// Look for repositories at most N directory levels below each root
+    #[arg(long, value_name = "N", global = true)]
+    pub max_depth: Option<usize>,
+
+    /// Also read commits from a git bundle file (repeatable)
+    #[arg(long, value_name = "FILE", global = true)]
+    pub bundle: Vec<PathBuf>,
+
+    /// Section the report: project (default), category, or by calendar day, week, month
+    #[arg(long, value_name = "UNIT", global = true)]
+    pub group_by: Option<GroupBy>,
+
+    /// Sum time logged in this commit trailer (e.g. Time-Spent: 1h30m)
+    #[arg(long, value_name = "KEY", global = true)]
+    pub time_trailer: Option<String>,
+
+    /// Show at most N commit lines, summarizing older ones per branch
+    #[arg(long, value_name = "N", global = true)]
+    pub max_lines: Option<usize>,
+
+    /// List at most N commits per branch, summarizing older ones
+    #[arg(long, value_name = "N", global = true)]
+    pub max_commits: Option<usize>,
+
+    /// End the report with a totals line (commits, projects, branches, estimate)
+    #[arg(long, global = true)]
+    pub footer: bool,
+
+    /// End the report with a summary: commits by type, active and busiest projects, first and last commit
+    #[arg(long, global = true)]
+    pub summary: bool,
+
+    /// Show when commits were made: an hour-of-day by day-of-week grid after the tree
+    #[arg(
+        long,
+        conflicts_with_all = ["json", "format", "template", "interactive", "tui", "select"],
+        global = true
+    )]
+    pub heatmap: bool,
+
+    /// Also list uncommitted changes and stashes created in the period, per repository
+    #[arg(long, conflicts_with_all = ["by_author", "group_by"], global = true)]
+    pub wip: bool,
+
+    /// Also list every tag created in the period, per repository, in a "Releases" section
+    #[arg(long, conflicts_with_all = ["by_author", "group_by"], global = true)]
+    pub tags: bool,
+
     /// Copy output to clipboard as plain text (for stand-ups)
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub copy: bool,
+
+    /// Also send the report to a chat service or by email: slack, email
+    #[arg(long, value_name = "TARGET", conflicts_with_all = ["interactive", "tui", "select"], global = true)]
+    pub post: Option<PostTarget>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportKind {
+    Patches,
+    Bundle,
+    /// Flattened commit rows for analytics tools
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Fuzzy-search commit messages, projects, and branches across all repos
+    Search {
+        /// Search term (fuzzy-matched)
+        term: String,
+    },
+    /// One day's report with first/last activity and estimated working time
+    Day {
+        /// Day to report (YYYY-MM-DD), defaults to today
+        date: Option<NaiveDate>,
+    },
+    /// Working-day statistics: active days, expected workdays, holiday and weekend work
+    Stats {
+        /// Report the share of commits following Conventional Commits instead
+        #[arg(long)]
+        conventional: bool,
+        /// Report who you paired with, from Co-authored-by trailers, instead
+        #[arg(long, conflicts_with = "conventional")]
+        pairing: bool,
+    },
+    /// List commits without a Signed-off-by trailer in repos requiring DCO
+    Dco {
+        /// Check every repository, not only those listed in `dco_repos`
+        #[arg(long)]
+        all: bool,
+    },
+    /// List commits not yet pushed to any remote
+    Unpushed,
+    /// Write the period's commits per repo as patch files, git bundles or Parquet tables
+    Export {
+        /// What to write: patches (git format-patch), bundle, or parquet
+        kind: ExportKind,
+        /// Target directory
+        #[arg(long, value_name = "DIR")]
+        output: PathBuf,
+    },
+    /// Book the period's hours per repository and day in Clockify or Harvest (`[timesheet]`)
+    Timesheet {
+        /// List the entries without booking them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List installed output format plugins
+    Plugins,
+    /// List recent runs from the audit log (enable with `audit_log = true`)
+    Runs {
+        /// Number of most recent runs to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
 }
 
 #[cfg(test)]
@@ -145,58 +424,29 @@ mod tests {
     use super::*;
 
     #[test]
-    fn parse_sort_field_only_defaults_direction() {
-        let spec: SortSpec = "time".parse().expect("parse time");
-        assert_eq!(spec.field, SortField::Time);
-        assert_eq!(spec.direction, SortDirection::Desc);
-
-        let spec: SortSpec = "commits".parse().expect("parse commits");
-        assert_eq!(spec.field, SortField::Commits);
-        assert_eq!(spec.direction, SortDirection::Desc);
-
-        let spec: SortSpec = "name".parse().expect("parse name");
-        assert_eq!(spec.field, SortField::Name);
-        assert_eq!(spec.direction, SortDirection::Asc);
-
-        let spec: SortSpec = "lines".parse().expect("parse lines");
-        assert_eq!(spec.field, SortField::Lines);
-        assert_eq!(spec.direction, SortDirection::Desc);
-    }
-
-    #[test]
-    fn parse_sort_with_explicit_direction() {
-        let spec: SortSpec = "time:asc".parse().expect("parse time:asc");
-        assert_eq!(spec.field, SortField::Time);
-        assert_eq!(spec.direction, SortDirection::Asc);
-
-        let spec: SortSpec = "name:desc".parse().expect("parse name:desc");
-        assert_eq!(spec.field, SortField::Name);
-        assert_eq!(spec.direction, SortDirection::Desc);
-
-        let spec: SortSpec = "commits:asc".parse().expect("parse commits:asc");
-        assert_eq!(spec.field, SortField::Commits);
-        assert_eq!(spec.direction, SortDirection::Asc);
-
-        let spec: SortSpec = "lines:asc".parse().expect("parse lines:asc");
-        assert_eq!(spec.field, SortField::Lines);
-        assert_eq!(spec.direction, SortDirection::Asc);
-    }
-
-    #[test]
-    fn parse_sort_unknown_field_errors() {
-        assert!("foo".parse::<SortSpec>().is_err());
-        assert!("foo:asc".parse::<SortSpec>().is_err());
-    }
-
-    #[test]
-    fn parse_sort_unknown_direction_errors() {
-        assert!("time:up".parse::<SortSpec>().is_err());
+    fn parse_watch_interval() {
+        let secs = |s: &str| s.parse::<WatchInterval>().map(|i| i.0.as_secs());
+        assert_eq!(secs("30s"), Ok(30));
+        assert_eq!(secs("5m"), Ok(300));
+        assert_eq!(secs("1h"), Ok(3600));
+        assert_eq!(secs("45"), Ok(45));
+        assert!(secs("0s").is_err());
+        assert!(secs("soon").is_err());
+        assert_eq!(WatchInterval(Duration::from_secs(90)).to_string(), "90s");
+        assert_eq!(WatchInterval(Duration::from_secs(120)).to_string(), "2m");
     }
 
     #[test]
-    fn default_sort_spec_is_time_desc() {
-        let spec = SortSpec::default();
-        assert_eq!(spec.field, SortField::Time);
-        assert_eq!(spec.direction, SortDirection::Desc);
+    fn parse_output_format() {
+        assert_eq!("text".parse(), Ok(OutputFormat::Text));
+        assert_eq!("json".parse(), Ok(OutputFormat::Json));
+        assert_eq!("changelog".parse(), Ok(OutputFormat::Changelog));
+        assert_eq!("csv".parse(), Ok(OutputFormat::Csv));
+        assert_eq!(
+            "org-mode".parse(),
+            Ok(OutputFormat::Plugin("org-mode".to_string()))
+        );
+        assert!("../evil".parse::<OutputFormat>().is_err());
+        assert!("".parse::<OutputFormat>().is_err());
     }
 }
@@ -1,6 +1,15 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+
 use crate::cli::Depth;
 use crate::output;
-use devcap_core::model::ProjectLog;
+use devcap_core::authors::AuthorGroup;
+use devcap_core::bucket::Bucket;
+use devcap_core::category::CategoryGroup;
+use devcap_core::groups::ProjectGroup;
+use devcap_core::model::{total_time_spent, BranchLog, Commit, ProjectLog, WorkTime};
+use devcap_core::tags::RepoTags;
+use devcap_core::wip::WorkInProgress;
 
 /// Render projects as clean plain text without ANSI codes.
 pub fn render_plain(projects: &[ProjectLog], depth: Depth, show_origin: bool) -> String {
@@ -8,17 +17,216 @@ pub fn render_plain(projects: &[ProjectLog], depth: Depth, show_origin: bool) ->
         return "No commits found for the given period.".to_string();
     }
     let mut out = String::new();
+    render_projects(&mut out, projects, depth, show_origin);
+    push_total_logged(&mut out, total_time_spent(projects));
+    out
+}
+
+/// Append the plain-text "Work in progress" section of `--wip`.
+pub fn with_wip(mut text: String, wip: &[WorkInProgress]) -> String {
+    if wip.is_empty() {
+        return text;
+    }
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text.push_str("\nWork in progress\n");
+    for repo in wip {
+        let branch = repo
+            .branch
+            .as_ref()
+            .map(|b| format!(" ({b})"))
+            .unwrap_or_default();
+        text.push_str(&format!(
+            ":: {}{branch}  {}\n",
+            repo.project,
+            output::wip_summary(repo)
+        ));
+        for stash in &repo.stashes {
+            text.push_str(&format!(
+                "    stash {}  {}\n",
+                stash.message, stash.relative_time
+            ));
+        }
+    }
+    text
+}
+
+/// Append the plain-text "Releases" section of `--tags`.
+pub fn with_tags(mut text: String, tags: &[RepoTags]) -> String {
+    if tags.is_empty() {
+        return text;
+    }
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text.push_str("\nReleases\n");
+    for repo in tags {
+        text.push_str(&format!(":: {}\n", repo.project));
+        for tag in &repo.tags {
+            text.push_str(&format!(
+                "    {}  {}\n",
+                output::tag_summary(tag),
+                tag.relative_time
+            ));
+        }
+    }
+    text
+}
+
+/// Plain-text counterpart of `output::render_grouped`.
+pub fn render_plain_grouped(buckets: &[Bucket], depth: Depth, show_origin: bool) -> String {
+    if buckets.is_empty() {
+        return "No commits found for the given period.".to_string();
+    }
+    let mut out = String::new();
+    for (i, bucket) in buckets.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        let span = bucket
+            .span
+            .as_ref()
+            .map(|s| format!("  {}", output::span_line(s)))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "== {}  ({}){span}\n",
+            bucket.label,
+            output::bucket_summary(bucket)
+        ));
+        render_projects(&mut out, &bucket.projects, depth, show_origin);
+    }
+    push_total_logged(
+        &mut out,
+        buckets
+            .iter()
+            .filter_map(|b| b.time_spent)
+            .reduce(|a, b| a + b),
+    );
+    out
+}
+
+/// Plain-text variant of [`output::render_by_author`].
+pub fn render_plain_by_author(groups: &[AuthorGroup], depth: Depth, show_origin: bool) -> String {
+    let mut out = render_named_sections(
+        groups.iter().map(|g| {
+            (
+                g.author.as_str(),
+                output::author_summary(g),
+                g.projects.as_slice(),
+            )
+        }),
+        depth,
+        show_origin,
+    );
+    push_total_logged(
+        &mut out,
+        groups
+            .iter()
+            .filter_map(|g| g.time_spent)
+            .reduce(|a, b| a + b),
+    );
+    out
+}
+
+/// Plain-text variant of [`output::render_by_category`].
+pub fn render_plain_by_category(
+    groups: &[CategoryGroup],
+    depth: Depth,
+    show_origin: bool,
+) -> String {
+    let mut out = render_named_sections(
+        groups.iter().map(|g| {
+            (
+                g.category.as_str(),
+                output::category_summary(g),
+                g.projects.as_slice(),
+            )
+        }),
+        depth,
+        show_origin,
+    );
+    push_total_logged(
+        &mut out,
+        groups
+            .iter()
+            .filter_map(|g| g.time_spent)
+            .reduce(|a, b| a + b),
+    );
+    out
+}
+
+/// Plain-text variant of [`output::render_by_group`].
+pub fn render_plain_by_group(groups: &[ProjectGroup], depth: Depth, show_origin: bool) -> String {
+    let mut out = render_named_sections(
+        groups.iter().map(|g| {
+            (
+                g.group.as_str(),
+                output::group_summary(g),
+                g.projects.as_slice(),
+            )
+        }),
+        depth,
+        show_origin,
+    );
+    push_total_logged(
+        &mut out,
+        groups
+            .iter()
+            .filter_map(|g| g.time_spent)
+            .reduce(|a, b| a + b),
+    );
+    out
+}
+
+fn render_named_sections<'a>(
+    sections: impl ExactSizeIterator<Item = (&'a str, String, &'a [ProjectLog])>,
+    depth: Depth,
+    show_origin: bool,
+) -> String {
+    if sections.len() == 0 {
+        return "No commits found for the given period.".to_string();
+    }
+    let mut out = String::new();
+    for (i, (name, summary, projects)) in sections.enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(&format!("== {name}  ({summary})\n"));
+        render_projects(&mut out, projects, depth, show_origin);
+    }
+    out
+}
+
+fn render_projects(out: &mut String, projects: &[ProjectLog], depth: Depth, show_origin: bool) {
     for (i, project) in projects.iter().enumerate() {
         if i > 0 && depth != Depth::Projects {
             out.push('\n');
         }
         match depth {
-            Depth::Projects => render_project_summary(&mut out, project, show_origin),
-            Depth::Branches => render_project_branches(&mut out, project, show_origin),
-            Depth::Commits => render_project_full(&mut out, project, show_origin),
+            Depth::Projects => render_project_summary(out, project, show_origin),
+            Depth::Branches => render_project_branches(out, project, show_origin),
+            Depth::Commits => render_project_full(out, project, show_origin),
         }
     }
-    out
+}
+
+fn push_total_logged(out: &mut String, total: Option<WorkTime>) {
+    if let Some(total) = total {
+        out.push_str(&format!("\nTotal logged: {total}\n"));
+    }
+}
+
+fn push_health(out: &mut String, project: &ProjectLog) {
+    if let Some(line) = output::health_line(project) {
+        out.push_str(&format!("  {line}\n"));
+    }
+}
+
+fn push_releases(out: &mut String, project: &ProjectLog) {
+    for line in output::release_lines(project) {
+        out.push_str(&format!("  {line}\n"));
+    }
 }
 
 fn origin_suffix(project: &ProjectLog, show_origin: bool) -> String {
@@ -26,7 +234,7 @@ fn origin_suffix(project: &ProjectLog, show_origin: bool) -> String {
         return String::new();
     }
     match &project.origin {
-        Some(origin) => format!(" [{origin}]"),
+        Some(origin) => format!(" [{}]", output::origin_badge(origin)),
         None => String::new(),
     }
 }
@@ -37,27 +245,38 @@ fn render_project_summary(out: &mut String, project: &ProjectLog, show_origin: b
     let latest = project.latest_activity().unwrap_or("-");
     let origin = origin_suffix(project, show_origin);
     let stat = output::stat_suffix_standalone(project.diff_stat.as_ref());
+    let logged = output::time_suffix_standalone(project.time_spent());
     out.push_str(&format!(
-        ":: {}{}  ({commits} commits, {branches} branches, {latest}{stat})\n",
+        ":: {}{}  ({commits} commits, {branches} branches, {latest}{stat}{logged})\n",
         project.project, origin
     ));
+    push_health(out, project);
+    push_releases(out, project);
 }
 
 fn render_project_branches(out: &mut String, project: &ProjectLog, show_origin: bool) {
     let latest = project.latest_activity().unwrap_or("-");
     let origin = origin_suffix(project, show_origin);
     let stat = output::stat_suffix_standalone(project.diff_stat.as_ref());
+    let logged = output::time_suffix_standalone(project.time_spent());
     out.push_str(&format!(
-        ":: {}{}  ({latest}{stat})\n",
+        ":: {}{}  ({latest}{stat}{logged})\n",
         project.project, origin
     ));
+    push_health(out, project);
+    push_releases(out, project);
+    let now = Local::now().fixed_offset();
     for branch in &project.branches {
         let count = branch.commits.len();
         let branch_latest = branch.latest_activity().unwrap_or("-");
         let bstat = output::stat_suffix_standalone(branch.diff_stat.as_ref());
+        let blogged = output::time_suffix_standalone(branch.time_spent());
+        let bsync = output::sync_suffix(branch);
+        let bspan = output::branch_span_suffix(branch, now);
         out.push_str(&format!(
-            "  >> {}  ({count} commits, {branch_latest}{bstat})\n",
-            branch.name
+            "  >> {}{}  ({count} commits, {branch_latest}{bstat}{blogged}{bsync}{bspan})\n",
+            branch.name,
+            output::branch_suffix(branch)
         ));
     }
 }
@@ -65,40 +284,151 @@ fn render_project_branches(out: &mut String, project: &ProjectLog, show_origin:
 fn render_project_full(out: &mut String, project: &ProjectLog, show_origin: bool) {
     let origin = origin_suffix(project, show_origin);
     let stat = output::stat_suffix_inline(project.diff_stat.as_ref());
-    out.push_str(&format!(":: {}{}{stat}\n", project.project, origin));
+    let logged = output::time_suffix_inline(project.time_spent());
+    out.push_str(&format!(":: {}{}{stat}{logged}\n", project.project, origin));
+    push_health(out, project);
+    push_releases(out, project);
     for branch in &project.branches {
-        let bstat = output::stat_suffix_inline(branch.diff_stat.as_ref());
-        out.push_str(&format!("  >> {}{bstat}\n", branch.name));
-        for commit in &branch.commits {
-            let tag = match commit.commit_type.as_deref() {
-                Some(t) => format!("{t} - "),
-                None => String::new(),
-            };
-            let msg = output::strip_type_prefix(&commit.message);
-            let cstat = output::stat_suffix_inline(commit.diff_stat.as_ref());
+        out.push_str(&render_branch(branch));
+    }
+}
+
+/// Render a single branch with its commits as plain text.
+pub fn render_branch(branch: &BranchLog) -> String {
+    let bstat = output::stat_suffix_inline(branch.diff_stat.as_ref());
+    let blogged = output::time_suffix_inline(branch.time_spent());
+    let mut out = format!(
+        "  >> {}{}{bstat}{blogged}\n",
+        branch.name,
+        output::branch_suffix(branch)
+    );
+    let mut day = None;
+    for commit in &branch.commits {
+        if !output::by_day() {
+            out.push_str(&format!("    * {}\n", render_commit_line(commit)));
+            continue;
+        }
+        if day != Some(commit.time.date_naive()) {
+            day = Some(commit.time.date_naive());
             out.push_str(&format!(
-                "    * {} {}{msg}  {}{cstat}\n",
-                commit.hash, tag, commit.relative_time
+                "    {}\n",
+                output::day_header(commit.time.date_naive())
             ));
         }
+        out.push_str(&format!("      * {}\n", render_commit_line(commit)));
+    }
+    if let Some(omitted) = &branch.omitted {
+        out.push_str(&format!("    {}\n", output::omitted_line(omitted)));
+    }
+    out
+}
+
+/// Render a single commit as one plain-text line (without the bullet).
+pub fn render_commit_line(commit: &Commit) -> String {
+    let tag = match commit.commit_type.as_deref() {
+        Some(t) => format!("{t} - "),
+        None => String::new(),
+    };
+    let msg = output::strip_type_prefix(&commit.message);
+    let cstat = output::stat_suffix_inline(commit.diff_stat.as_ref());
+    let clogged = output::time_suffix_inline(commit.time_spent);
+    format!(
+        "{} {}{msg}  {}{cstat}{clogged}{}{}",
+        commit.hash,
+        tag,
+        output::commit_byline(commit),
+        output::co_authored_badge(commit),
+        output::unpushed_badge(commit)
+    )
+}
+
+/// Render one branch, or one of its commits, below the project header (and
+/// the branch header for a commit), so a copied item says where it is from.
+pub fn render_item(
+    project: &ProjectLog,
+    branch: &BranchLog,
+    commit: Option<&Commit>,
+    show_origin: bool,
+) -> String {
+    let origin = origin_suffix(project, show_origin);
+    let body = match commit {
+        Some(commit) => format!(
+            "  >> {}\n    * {}\n",
+            branch.name,
+            render_commit_line(commit)
+        ),
+        None => render_branch(branch),
+    };
+    format!(":: {}{origin}\n{body}", project.project)
+}
+
+/// Render only the commits accepted by `selected(project, branch, commit)` (all
+/// indices), keeping the project and branch headers for context.
+pub fn render_selected(
+    projects: &[ProjectLog],
+    show_origin: bool,
+    selected: impl Fn(usize, usize, usize) -> bool,
+) -> String {
+    let mut out = String::new();
+    for (pi, project) in projects.iter().enumerate() {
+        let mut section = String::new();
+        for (bi, branch) in project.branches.iter().enumerate() {
+            let lines: Vec<String> = branch
+                .commits
+                .iter()
+                .enumerate()
+                .filter(|(ci, _)| selected(pi, bi, *ci))
+                .map(|(_, c)| format!("    * {}\n", render_commit_line(c)))
+                .collect();
+            if !lines.is_empty() {
+                section.push_str(&format!("  >> {}\n", branch.name));
+                section.extend(lines);
+            }
+        }
+        if !section.is_empty() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            let origin = origin_suffix(project, show_origin);
+            out.push_str(&format!(":: {}{}\n", project.project, origin));
+            out.push_str(&section);
+        }
+    }
+    out
+}
+
+/// Put text on the system clipboard.
+/// Append the totals line, separated by a blank line.
+pub fn with_footer(mut text: String, footer: Option<&str>) -> String {
+    if let Some(line) = footer {
+        text.push('\n');
+        text.push_str(line);
+        text.push('\n');
     }
+    text
+}
+
+pub fn copy_text(text: &str) -> Result<()> {
+    let mut cb = arboard::Clipboard::new().context("clipboard unavailable")?;
+    cb.set_text(text).context("could not copy to clipboard")?;
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Local;
-    use devcap_core::model::{BranchLog, Commit, ProjectLog, RepoOrigin};
+    use chrono::TimeZone;
+    use devcap_core::model::{CoAuthor, Release, RepoOrigin};
+    use devcap_core::tags::Tag;
 
     fn make_commit(hash: &str, msg: &str, commit_type: Option<&str>) -> Commit {
         Commit {
             hash: hash.to_string(),
             message: msg.to_string(),
             commit_type: commit_type.map(String::from),
-            time: Local::now(),
+            time: Local::now().fixed_offset(),
             relative_time: "1h ago".to_string(),
-            url: None,
-            diff_stat: None,
+            ..Default::default()
         }
     }
 
@@ -107,17 +437,15 @@ mod tests {
             project: name.to_string(),
             path: format!("/test/{name}"),
             origin,
-            remote_url: None,
             branches: vec![BranchLog {
                 name: "main".to_string(),
-                url: None,
                 commits: vec![
                     make_commit("abc1234", "feat: add login", Some("feat")),
                     make_commit("def5678", "fix: resolve crash", Some("fix")),
                 ],
-                diff_stat: None,
+                ..Default::default()
             }],
-            diff_stat: None,
+            ..Default::default()
         }
     }
 
@@ -170,14 +498,14 @@ mod tests {
     fn origin_shown_when_enabled() {
         let projects = vec![make_project("repo", Some(RepoOrigin::GitHub))];
         let text = render_plain(&projects, Depth::Projects, true);
-        assert!(text.contains("[GitHub]"));
+        assert!(text.contains("[GH]"));
     }
 
     #[test]
     fn origin_hidden_when_disabled() {
         let projects = vec![make_project("repo", Some(RepoOrigin::GitHub))];
         let text = render_plain(&projects, Depth::Projects, false);
-        assert!(!text.contains("[GitHub]"));
+        assert!(!text.contains("[GH]"));
     }
 
     #[test]
@@ -185,17 +513,149 @@ mod tests {
         let projects = vec![ProjectLog {
             project: "test".to_string(),
             path: "/test".to_string(),
-            origin: None,
-            remote_url: None,
             branches: vec![BranchLog {
                 name: "main".to_string(),
-                url: None,
                 commits: vec![make_commit("aaa1111", "update readme", None)],
-                diff_stat: None,
+                ..Default::default()
             }],
-            diff_stat: None,
+            ..Default::default()
         }];
         let text = render_plain(&projects, Depth::Commits, false);
         assert!(text.contains("aaa1111 update readme"));
     }
+
+    #[test]
+    fn commit_line_has_hash_type_and_time() {
+        let commit = make_commit("abc1234", "feat: add login", Some("feat"));
+        assert_eq!(
+            render_commit_line(&commit),
+            "abc1234 feat - add login  1h ago"
+        );
+    }
+
+    #[test]
+    fn co_authored_commit_is_marked() {
+        let mut commit = make_commit("abc1234", "feat: add login", Some("feat"));
+        commit.co_authors = vec![CoAuthor {
+            name: "Max".to_string(),
+            email: "max@example.com".to_string(),
+        }];
+        assert_eq!(
+            render_commit_line(&commit),
+            "abc1234 feat - add login  1h ago  [co-authored]"
+        );
+    }
+
+    #[test]
+    fn branch_render_lists_commits() {
+        let project = make_project("repo", None);
+        let text = render_branch(&project.branches[0]);
+        assert!(text.starts_with("  >> main\n"));
+        assert!(text.contains("    * def5678 fix - resolve crash"));
+    }
+
+    #[test]
+    fn selected_render_keeps_only_marked_commits() {
+        let projects = vec![make_project("repo", None), make_project("other", None)];
+        let text = render_selected(&projects, false, |p, b, c| (p, b, c) == (0, 0, 1));
+        assert!(text.contains(":: repo"));
+        assert!(text.contains("def5678 fix - resolve crash"));
+        assert!(!text.contains("abc1234"));
+        assert!(!text.contains(":: other"));
+    }
+
+    #[test]
+    fn logged_time_summed_per_level() {
+        let mut project = make_project("repo", None);
+        project.branches[0].commits[0].time_spent = Some(WorkTime::from_minutes(90));
+        project.branches[0].commits[1].time_spent = Some(WorkTime::from_minutes(30));
+        let projects = vec![project];
+
+        let text = render_plain(&projects, Depth::Branches, false);
+        assert!(text.contains(">> main  (2 commits, 1h ago, 2h logged)"));
+
+        let text = render_plain(&projects, Depth::Commits, false);
+        assert!(text.contains("add login  1h ago  (1h30m logged)"));
+        assert!(text.ends_with("Total logged: 2h\n"));
+    }
+
+    #[test]
+    fn logged_time_omitted_without_trailers() {
+        let projects = vec![make_project("repo", None)];
+        let text = render_plain(&projects, Depth::Commits, false);
+        assert!(!text.contains("logged"));
+    }
+
+    #[test]
+    fn releases_listed_under_project() {
+        let mut project = make_project("repo", None);
+        project.releases = Some(vec![Release {
+            tag: "v2.3.0".to_string(),
+            time: Local
+                .with_ymd_and_hms(2026, 3, 5, 14, 0, 0)
+                .single()
+                .expect("valid time")
+                .fixed_offset(),
+            url: None,
+        }]);
+        let projects = vec![project];
+
+        for depth in [Depth::Projects, Depth::Branches, Depth::Commits] {
+            let text = render_plain(&projects, depth, false);
+            let lines: Vec<&str> = text.lines().collect();
+            assert_eq!(lines[1], "  Released v2.3.0  (2026-03-05)");
+        }
+    }
+
+    #[test]
+    fn wip_section_follows_the_report() {
+        let wip = WorkInProgress {
+            project: "shop".to_string(),
+            branch: Some("feature/cart".to_string()),
+            changed: 4,
+            staged: 1,
+            untracked: 2,
+            ..WorkInProgress::default()
+        };
+        let text = with_wip(render_plain(&[], Depth::Commits, false), &[wip]);
+        assert_eq!(
+            text,
+            "No commits found for the given period.\n\nWork in progress\n\
+             :: shop (feature/cart)  4 files modified, not committed (1 staged), 2 untracked files\n"
+        );
+        assert_eq!(with_wip("report\n".to_string(), &[]), "report\n");
+    }
+
+    #[test]
+    fn tags_section_lists_annotation_and_target() {
+        let tags = RepoTags {
+            project: "shop".to_string(),
+            path: "/src/shop".to_string(),
+            tags: vec![Tag {
+                name: "v2.0.0".to_string(),
+                commit: "b2c3d4e".to_string(),
+                annotation: Some("Release 2.0".to_string()),
+                time: Local::now().fixed_offset(),
+                relative_time: "2h ago".to_string(),
+            }],
+        };
+        assert_eq!(
+            with_tags("report\n".to_string(), &[tags]),
+            "report\n\nReleases\n:: shop\n    v2.0.0  b2c3d4e  Release 2.0  2h ago\n"
+        );
+        assert_eq!(with_tags("report\n".to_string(), &[]), "report\n");
+    }
+
+    #[test]
+    fn copied_item_keeps_its_headers() {
+        let project = make_project("my-app", Some(RepoOrigin::GitHub));
+        let branch = &project.branches[0];
+        assert_eq!(
+            render_item(&project, branch, Some(&branch.commits[1]), true),
+            ":: my-app [GH]\n  >> main\n    * def5678 fix - resolve crash  1h ago\n"
+        );
+        let text = render_item(&project, branch, None, false);
+        assert!(text.starts_with(":: my-app\n  >> main\n"));
+        assert_eq!(text.lines().count(), 4);
+    }
 }
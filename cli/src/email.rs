@@ -0,0 +1,301 @@
+//! `--post email`: the report as a plain-text and HTML email, sent to the
+//! recipients in `[email]` through an SMTP server.
+
+use anyhow::Result;
+
+use crate::cli::Depth;
+use crate::clipboard;
+use crate::config::EmailConfig;
+use crate::curl::Curl;
+use crate::output;
+use devcap_core::model::{BranchLog, Commit, ProjectLog};
+use devcap_core::zone::Time;
+
+/// Port of SMTP over implicit TLS; any other port is upgraded with STARTTLS.
+const SMTPS_PORT: u16 = 465;
+const SUBMISSION_PORT: u16 = 587;
+
+/// The SMTP server and envelope the report is sent with.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Mailer {
+    /// `smtp://` or `smtps://` URL of the server.
+    pub url: String,
+    /// Require TLS, via STARTTLS on `smtp://` URLs.
+    pub tls: bool,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+/// The configured server and recipients, falling back to
+/// `DEVCAP_SMTP_PASSWORD` for the password.
+pub fn mailer(cfg: Option<&EmailConfig>) -> Result<Mailer> {
+    resolve(cfg, |key| std::env::var(key).ok().filter(|v| !v.is_empty()))
+}
+
+fn resolve(cfg: Option<&EmailConfig>, env: impl Fn(&str) -> Option<String>) -> Result<Mailer> {
+    let Some(cfg) = cfg else {
+        anyhow::bail!("no [email] section in ~/.devcap.toml");
+    };
+    let Some(host) = &cfg.host else {
+        anyhow::bail!("set the SMTP server's `host` in [email]");
+    };
+    let Some(from) = &cfg.from else {
+        anyhow::bail!("set the sender's `from` address in [email]");
+    };
+    let to = cfg.to.clone().unwrap_or_default();
+    if to.is_empty() {
+        anyhow::bail!("list the recipients in `to` in [email]");
+    }
+    let port = cfg.port.unwrap_or(SUBMISSION_PORT);
+    let scheme = if port == SMTPS_PORT { "smtps" } else { "smtp" };
+    Ok(Mailer {
+        url: format!("{scheme}://{host}:{port}"),
+        tls: cfg.tls.unwrap_or(true),
+        username: cfg.username.clone(),
+        password: cfg.password.clone().or_else(|| env("DEVCAP_SMTP_PASSWORD")),
+        from: from.clone(),
+        to,
+    })
+}
+
+/// The message: headers, and the report as plain text with an HTML
+/// alternative. Lines end in CRLF as SMTP requires.
+pub fn message(
+    mailer: &Mailer,
+    title: &str,
+    projects: &[ProjectLog],
+    depth: Depth,
+    show_origin: bool,
+    now: Time,
+) -> String {
+    let boundary = format!("devcap-{}", now.timestamp_nanos_opt().unwrap_or_default());
+    let text = format!(
+        "{title}\n\n{}",
+        clipboard::render_plain(projects, depth, show_origin)
+    );
+    let message = format!(
+        "From: {}\n\
+         To: {}\n\
+         Subject: {}\n\
+         Date: {}\n\
+         MIME-Version: 1.0\n\
+         Content-Type: multipart/alternative; boundary=\"{boundary}\"\n\
+         \n\
+         --{boundary}\n\
+         Content-Type: text/plain; charset=utf-8\n\
+         Content-Transfer-Encoding: 8bit\n\
+         \n\
+         {}\n\
+         --{boundary}\n\
+         Content-Type: text/html; charset=utf-8\n\
+         Content-Transfer-Encoding: 8bit\n\
+         \n\
+         {}\n\
+         --{boundary}--\n",
+        mailer.from,
+        mailer.to.join(", "),
+        encode_header(title),
+        now.to_rfc2822(),
+        text.trim_end(),
+        html(title, projects, depth),
+    );
+    message.replace('\n', "\r\n")
+}
+
+/// Send `message` to the recipients of `mailer`.
+pub fn send(mailer: &Mailer, message: &str) -> Result<()> {
+    let mut curl = Curl::new(&mailer.url, 30)
+        .arg("--mail-from")
+        .arg(address(&mailer.from));
+    for recipient in &mailer.to {
+        curl = curl.arg("--mail-rcpt").arg(address(recipient));
+    }
+    if mailer.tls {
+        curl = curl.arg("--ssl-reqd");
+    }
+    if let Some(username) = &mailer.username {
+        curl = curl.user(username, mailer.password.as_deref().unwrap_or_default());
+    }
+    curl.upload(message.as_bytes()).send("sending email")?;
+    Ok(())
+}
+
+fn html(title: &str, projects: &[ProjectLog], depth: Depth) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<body style=\"font-family: sans-serif\">\n<h1>{}</h1>\n<p>{}</p>\n",
+        escape(title),
+        escape(&output::summary_line(projects))
+    );
+    if depth == Depth::Projects {
+        out.push_str("<ul>\n");
+        for project in projects {
+            let commits = project.total_commits();
+            out.push_str(&format!(
+                "<li><b>{}</b> {commits} {}, {}</li>\n",
+                escape(&project.project),
+                if commits == 1 { "commit" } else { "commits" },
+                escape(project.latest_activity().unwrap_or("-"))
+            ));
+        }
+        out.push_str("</ul>\n");
+    } else {
+        for project in projects {
+            out.push_str(&format!("<h2>{}</h2>\n", escape(&project.project)));
+            for branch in &project.branches {
+                out.push_str(&branch_html(branch, depth));
+            }
+        }
+    }
+    out.push_str("</body>\n</html>");
+    out
+}
+
+fn branch_html(branch: &BranchLog, depth: Depth) -> String {
+    let count = branch.commits.len();
+    let mut out = format!(
+        "<p><code>{}</code> <small>{count} {}, {}</small></p>\n",
+        link(branch.url.as_deref(), &branch.name),
+        if count == 1 { "commit" } else { "commits" },
+        escape(branch.latest_activity().unwrap_or("-"))
+    );
+    if depth == Depth::Commits {
+        out.push_str("<ul>\n");
+        for commit in &branch.commits {
+            out.push_str(&format!("<li>{}</li>\n", commit_html(commit)));
+        }
+        if let Some(omitted) = &branch.omitted {
+            out.push_str(&format!(
+                "<li><i>{}</i></li>\n",
+                escape(&output::omitted_line(omitted))
+            ));
+        }
+        out.push_str("</ul>\n");
+    }
+    out
+}
+
+fn commit_html(commit: &Commit) -> String {
+    format!(
+        "<code>{}</code> {} <small>{}</small>",
+        link(commit.url.as_deref(), &commit.hash),
+        escape(&commit.message),
+        escape(&output::commit_byline(commit))
+    )
+}
+
+fn link(url: Option<&str>, text: &str) -> String {
+    match url {
+        Some(url) => format!("<a href=\"{}\">{}</a>", escape(url), escape(text)),
+        None => escape(text),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The bare address of a mailbox such as `Jane Doe <jane@example.com>`.
+fn address(mailbox: &str) -> &str {
+    match (mailbox.rfind('<'), mailbox.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &mailbox[start + 1..end],
+        _ => mailbox.trim(),
+    }
+}
+
+/// A header value as is when it is plain ASCII, else as an RFC 2047
+/// encoded word.
+fn encode_header(value: &str) -> String {
+    if value.is_ascii() {
+        return value.to_string();
+    }
+    let encoded: String = value
+        .bytes()
+        .map(|b| match b {
+            b' ' => "_".to_string(),
+            b if b.is_ascii_alphanumeric() || b"-.:".contains(&b) => (b as char).to_string(),
+            b => format!("={b:02X}"),
+        })
+        .collect();
+    format!("=?UTF-8?Q?{encoded}?=")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Local;
+    use serde_json::json;
+
+    fn config() -> EmailConfig {
+        EmailConfig {
+            host: Some("smtp.example.com".to_string()),
+            from: Some("devcap <devcap@example.com>".to_string()),
+            to: Some(vec!["Lead <lead@example.com>".to_string()]),
+            ..EmailConfig::default()
+        }
+    }
+
+    #[test]
+    fn mailer_needs_host_sender_and_recipients() {
+        let mailer = resolve(Some(&config()), |_| Some("secret".to_string())).expect("mailer");
+        assert_eq!(mailer.url, "smtp://smtp.example.com:587");
+        assert!(mailer.tls);
+        assert_eq!(mailer.password.as_deref(), Some("secret"));
+        let smtps = EmailConfig {
+            port: Some(465),
+            ..config()
+        };
+        let mailer = resolve(Some(&smtps), |_| None).expect("mailer");
+        assert_eq!(mailer.url, "smtps://smtp.example.com:465");
+
+        let nobody = EmailConfig {
+            to: Some(Vec::new()),
+            ..config()
+        };
+        let err = resolve(Some(&nobody), |_| None).expect_err("no recipients");
+        assert!(err.to_string().contains("`to`"));
+        assert!(resolve(None, |_| None).is_err());
+        assert_eq!(address("Lead <lead@example.com>"), "lead@example.com");
+        assert_eq!(address(" lead@example.com "), "lead@example.com");
+    }
+
+    #[test]
+    fn message_has_text_and_html_parts() {
+        let projects: Vec<ProjectLog> = serde_json::from_value(json!([{
+            "project": "api",
+            "path": "/src/api",
+            "branches": [{ "name": "main", "commits": [{
+                "hash": "abc1234",
+                "message": "fix: handle <none> & empty",
+                "author": "Jane",
+                "timestamp": "2026-03-02T10:00:00+00:00",
+                "relative_time": "2h ago",
+                "url": "https://github.com/acme/api/commit/abc1234",
+            }]}],
+        }]))
+        .expect("valid projects");
+        let mailer = resolve(Some(&config()), |_| None).expect("mailer");
+        let title = "Worklog Mon 2026-03-02 \u{2013} Sun 2026-03-08";
+        let message = message(
+            &mailer,
+            title,
+            &projects,
+            Depth::Commits,
+            false,
+            Local::now().fixed_offset(),
+        );
+        assert!(message
+            .contains("Subject: =?UTF-8?Q?Worklog_Mon_2026-03-02_=E2=80=93_Sun_2026-03-08?=\r\n"));
+        assert!(message.contains("To: Lead <lead@example.com>\r\n"));
+        assert!(message.contains("Content-Type: text/plain; charset=utf-8\r\n"));
+        assert!(message.contains("* abc1234 handle <none> & empty  2h ago"));
+        assert!(message.contains(
+            "<a href=\"https://github.com/acme/api/commit/abc1234\">abc1234</a></code> fix: handle &lt;none&gt; &amp; empty"
+        ));
+        assert!(!message.replace("\r\n", "").contains('\n'));
+    }
+}
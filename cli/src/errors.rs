@@ -0,0 +1,91 @@
+//! Fatal errors as a JSON object on stdout, for callers using `--json`.
+
+use std::fmt;
+
+use serde::Serialize;
+
+/// Stable identifier of a failure class, part of the JSON error object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// A scan root does not exist.
+    PathNotFound,
+    /// `git` could not be run.
+    GitNotFound,
+    /// Flags or their values were rejected.
+    InvalidArgument,
+    /// Anything not classified more precisely.
+    Failed,
+}
+
+/// An error message tagged with its [`ErrorCode`].
+#[derive(Debug)]
+pub struct CodedError {
+    code: ErrorCode,
+    message: String,
+}
+
+impl fmt::Display for CodedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CodedError {}
+
+pub fn coded(code: ErrorCode, message: impl Into<String>) -> anyhow::Error {
+    CodedError {
+        code,
+        message: message.into(),
+    }
+    .into()
+}
+
+/// The code attached anywhere in the error's chain.
+pub fn code_of(err: &anyhow::Error) -> ErrorCode {
+    err.chain()
+        .find_map(|e| e.downcast_ref::<CodedError>())
+        .map_or(ErrorCode::Failed, |e| e.code)
+}
+
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    code: ErrorCode,
+    message: &'a str,
+}
+
+#[derive(Serialize)]
+struct ErrorObject<'a> {
+    error: ErrorBody<'a>,
+}
+
+/// `{"error": {"code": "...", "message": "..."}}`
+pub fn render_json(code: ErrorCode, message: &str) -> String {
+    serde_json::to_string_pretty(&ErrorObject {
+        error: ErrorBody { code, message },
+    })
+    .unwrap_or_else(|_| "{}".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn code_survives_added_context() {
+        let err: anyhow::Result<()> = Err(coded(ErrorCode::PathNotFound, "no such path"));
+        let err = err.context("scanning").expect_err("still an error");
+        assert_eq!(code_of(&err), ErrorCode::PathNotFound);
+        assert_eq!(code_of(&anyhow::anyhow!("plain")), ErrorCode::Failed);
+    }
+
+    #[test]
+    fn json_object_shape() {
+        let value: serde_json::Value =
+            serde_json::from_str(&render_json(ErrorCode::GitNotFound, "git not found"))
+                .expect("valid json");
+        assert_eq!(value["error"]["code"], "git_not_found");
+        assert_eq!(value["error"]["message"], "git not found");
+    }
+}
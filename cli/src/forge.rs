@@ -0,0 +1,443 @@
+//! Pull/merge request and commit verification lookups for repositories
+//! hosted on GitHub and GitLab.
+
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+use serde_json::Value;
+
+use crate::cache::{CachePolicy, ForgeCache};
+use crate::curl::Curl;
+use devcap_core::git;
+use devcap_core::model::{BranchLog, PrState, ProjectLog, PullRequest, RepoOrigin};
+
+/// Attach the open pull/merge request to every non-primary branch of a
+/// GitHub or GitLab project; with `any_state`, the latest request whether
+/// open, merged or closed. Answers are cached; when the API cannot be
+/// reached (or in offline mode), the last known answer is used.
+/// `gitlab_tokens` maps self-hosted GitLab hosts to their access token.
+pub fn annotate_pull_requests(
+    projects: &mut [ProjectLog],
+    policy: CachePolicy,
+    any_state: bool,
+    gitlab_tokens: &HashMap<String, String>,
+) {
+    let name = if any_state {
+        "pull-requests-any-state"
+    } else {
+        "pull-requests"
+    };
+    let mut cache: ForgeCache<Option<PullRequest>> = ForgeCache::open(name, policy);
+
+    let lookups: Vec<(String, String)> = projects
+        .iter()
+        .flat_map(|project| {
+            project.branches.iter().filter_map(|branch| {
+                let key = cache_key(project, pr_branch(branch))?;
+                if cache.fresh(&key).is_some() {
+                    return None;
+                }
+                Some((key, api_url(project, pr_branch(branch), any_state)?))
+            })
+        })
+        .collect();
+
+    if !lookups.is_empty() {
+        let fetched: Vec<(String, Option<PullRequest>)> = lookups
+            .par_iter()
+            .filter_map(|(key, url)| Some((key.clone(), fetch(url, gitlab_tokens)?)))
+            .collect();
+        for (key, pr) in fetched {
+            cache.insert(key, pr);
+        }
+        cache.save();
+    }
+
+    for project in projects.iter_mut() {
+        let keys: Vec<Option<String>> = project
+            .branches
+            .iter()
+            .map(|b| cache_key(project, pr_branch(b)))
+            .collect();
+        for (branch, key) in project.branches.iter_mut().zip(keys) {
+            branch.pr = key.and_then(|k| cache.stale(&k).cloned()).flatten();
+        }
+    }
+}
+
+/// Mark each commit of a GitHub or GitLab project with whether the forge
+/// shows it as verified. Cached like pull requests; commits whose status
+/// cannot be determined stay unmarked.
+pub fn annotate_verification(
+    projects: &mut [ProjectLog],
+    policy: CachePolicy,
+    gitlab_tokens: &HashMap<String, String>,
+) {
+    let mut cache: ForgeCache<bool> = ForgeCache::open("verified-commits", policy);
+
+    let mut lookups: Vec<(String, String)> = projects
+        .iter()
+        .flat_map(|project| {
+            let cache = &cache;
+            project
+                .branches
+                .iter()
+                .flat_map(|b| &b.commits)
+                .filter_map(move |commit| {
+                    let key = format!("{}@{}", project.remote_url.as_deref()?, commit.hash);
+                    if cache.fresh(&key).is_some() {
+                        return None;
+                    }
+                    Some((key, commit_api_url(project, &commit.hash)?))
+                })
+        })
+        .collect();
+    lookups.sort();
+    lookups.dedup();
+
+    if !lookups.is_empty() {
+        let fetched: Vec<(String, bool)> = lookups
+            .par_iter()
+            .filter_map(|(key, url)| Some((key.clone(), fetch_verified(url, gitlab_tokens)?)))
+            .collect();
+        for (key, verified) in fetched {
+            cache.insert(key, verified);
+        }
+        cache.save();
+    }
+
+    for project in projects.iter_mut() {
+        let Some(remote) = project.remote_url.clone() else {
+            continue;
+        };
+        for commit in project.branches.iter_mut().flat_map(|b| &mut b.commits) {
+            commit.verified = cache.stale(&format!("{remote}@{}", commit.hash)).copied();
+        }
+    }
+}
+
+/// The name a pull/merge request knows `branch` by: remote-tracking
+/// branches without their remote prefix.
+fn pr_branch(branch: &BranchLog) -> &str {
+    branch.remote_name.as_deref().unwrap_or(&branch.name)
+}
+
+fn cache_key(project: &ProjectLog, branch: &str) -> Option<String> {
+    if git::is_primary_branch(branch) || branch == git::DETACHED_BRANCH {
+        return None;
+    }
+    let remote = project.remote_url.as_deref()?;
+    Some(format!("{remote}#{branch}"))
+}
+
+/// Host and repository path of the project's web remote.
+fn remote_parts(project: &ProjectLog) -> Option<(&str, &str)> {
+    let remote = project.remote_url.as_deref()?;
+    remote
+        .strip_prefix("https://")
+        .or_else(|| remote.strip_prefix("http://"))?
+        .split_once('/')
+}
+
+/// API endpoint listing the PRs/MRs with `branch` as source, newest first:
+/// the open ones, or all of them with `any_state`.
+fn api_url(project: &ProjectLog, branch: &str, any_state: bool) -> Option<String> {
+    let (host, path) = remote_parts(project)?;
+    match project.origin.as_ref()? {
+        RepoOrigin::GitHub => {
+            let owner = path.split('/').next()?;
+            Some(format!(
+                "https://api.github.com/repos/{path}/pulls?state={}&head={}",
+                if any_state { "all" } else { "open" },
+                query_encode(&format!("{owner}:{branch}"))
+            ))
+        }
+        RepoOrigin::GitLab | RepoOrigin::GitLabSelfHosted => Some(format!(
+            "https://{host}/api/v4/projects/{}/merge_requests?state={}&source_branch={}",
+            query_encode(path),
+            if any_state { "all" } else { "opened" },
+            query_encode(branch)
+        )),
+        _ => None,
+    }
+}
+
+/// API endpoint describing the signature of commit `hash`.
+fn commit_api_url(project: &ProjectLog, hash: &str) -> Option<String> {
+    let (host, path) = remote_parts(project)?;
+    match project.origin.as_ref()? {
+        RepoOrigin::GitHub => Some(format!(
+            "https://api.github.com/repos/{path}/commits/{hash}"
+        )),
+        RepoOrigin::GitLab | RepoOrigin::GitLabSelfHosted => Some(format!(
+            "https://{host}/api/v4/projects/{}/repository/commits/{hash}/signature",
+            query_encode(path)
+        )),
+        _ => None,
+    }
+}
+
+/// `Some(None)` means the API answered without an open PR; `None` means the
+/// request failed and nothing should be cached.
+fn fetch(url: &str, gitlab_tokens: &HashMap<String, String>) -> Option<Option<PullRequest>> {
+    let (status, body) = request(url, gitlab_tokens)?;
+    if !(200..300).contains(&status) {
+        return None;
+    }
+    let body: Value = serde_json::from_slice(&body).ok()?;
+    Some(parse_first(&body))
+}
+
+/// Verification status of one commit, `None` if the request failed. GitLab
+/// answers 404 for commits without a signature.
+fn fetch_verified(url: &str, gitlab_tokens: &HashMap<String, String>) -> Option<bool> {
+    let (status, body) = request(url, gitlab_tokens)?;
+    if status == 404 && !url.starts_with("https://api.github.com/") {
+        return Some(false);
+    }
+    if !(200..300).contains(&status) {
+        return None;
+    }
+    parse_verified(&serde_json::from_slice(&body).ok()?)
+}
+
+/// GitHub commit (`commit.verification.verified`) or GitLab signature
+/// (`verification_status`) response.
+fn parse_verified(body: &Value) -> Option<bool> {
+    if let Some(verified) = body.pointer("/commit/verification/verified") {
+        return verified.as_bool();
+    }
+    body.get("verification_status")
+        .and_then(Value::as_str)
+        .map(|s| s == "verified")
+}
+
+/// GET `url` with the forge's token, returning the HTTP status and body.
+fn request(url: &str, gitlab_tokens: &HashMap<String, String>) -> Option<(u16, Vec<u8>)> {
+    let mut curl = Curl::new(url, 10);
+    if url.starts_with("https://api.github.com/") {
+        curl = curl.header("Accept: application/vnd.github+json");
+    }
+    if let Some((header, token)) = token_for(url, gitlab_tokens) {
+        curl = curl.secret_header(&format!("{header}: {token}"));
+    }
+    let response = curl.send("forge API request").ok()?;
+    Some((response.status, response.body))
+}
+
+/// The auth header and token sent along to `url`: `GITHUB_TOKEN` (or
+/// `GH_TOKEN`) only to api.github.com, `GITLAB_TOKEN` only to gitlab.com,
+/// and the configured token to a self-hosted GitLab host. Any other host
+/// gets no token.
+fn token_for(url: &str, gitlab_tokens: &HashMap<String, String>) -> Option<(&'static str, String)> {
+    let host = url
+        .strip_prefix("https://")?
+        .split('/')
+        .next()?
+        .to_ascii_lowercase();
+    match host.as_str() {
+        "api.github.com" => env_token(&["GITHUB_TOKEN", "GH_TOKEN"])
+            .map(|token| ("Authorization", format!("Bearer {token}"))),
+        "gitlab.com" => env_token(&["GITLAB_TOKEN"]).map(|token| ("PRIVATE-TOKEN", token)),
+        _ => gitlab_tokens
+            .iter()
+            .find(|(h, _)| h.eq_ignore_ascii_case(&host))
+            .map(|(_, token)| ("PRIVATE-TOKEN", token.clone()))
+            .filter(|(_, token)| !token.is_empty()),
+    }
+}
+
+fn env_token(names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .filter_map(|n| std::env::var(n).ok())
+        .find(|t| !t.is_empty())
+}
+
+/// First entry of a GitHub pulls or GitLab merge_requests listing.
+fn parse_first(body: &Value) -> Option<PullRequest> {
+    let item = body.as_array()?.first()?;
+    let number = item
+        .get("number")
+        .or_else(|| item.get("iid"))
+        .and_then(Value::as_u64)?;
+    let title = item.get("title").and_then(Value::as_str)?;
+    let url = item
+        .get("html_url")
+        .or_else(|| item.get("web_url"))
+        .and_then(Value::as_str)?;
+    // GitHub reports merged requests as closed with a merge time.
+    let merged = item.get("merged_at").is_some_and(|m| !m.is_null());
+    let state = match item.get("state").and_then(Value::as_str) {
+        _ if merged => PrState::Merged,
+        Some("merged") => PrState::Merged,
+        Some("closed" | "locked") => PrState::Closed,
+        _ => PrState::Open,
+    };
+    Some(PullRequest {
+        number,
+        title: title.to_string(),
+        url: url.to_string(),
+        state,
+    })
+}
+
+fn query_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(remote: &str, origin: RepoOrigin) -> ProjectLog {
+        ProjectLog {
+            project: "r".to_string(),
+            path: "/tmp/r".to_string(),
+            origin: Some(origin),
+            remote_url: Some(remote.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn github_api_url_filters_by_head() {
+        let p = project("https://github.com/acme/shop", RepoOrigin::GitHub);
+        assert_eq!(
+            api_url(&p, "feature/JIRA-42", false).as_deref(),
+            Some("https://api.github.com/repos/acme/shop/pulls?state=open&head=acme%3Afeature%2FJIRA-42")
+        );
+        assert_eq!(
+            api_url(&p, "feature/JIRA-42", true).as_deref(),
+            Some("https://api.github.com/repos/acme/shop/pulls?state=all&head=acme%3Afeature%2FJIRA-42")
+        );
+    }
+
+    #[test]
+    fn gitlab_api_url_encodes_project_path() {
+        let p = project(
+            "https://git.example.com/team/sub/shop",
+            RepoOrigin::GitLabSelfHosted,
+        );
+        assert_eq!(
+            api_url(&p, "fix-1", false).as_deref(),
+            Some("https://git.example.com/api/v4/projects/team%2Fsub%2Fshop/merge_requests?state=opened&source_branch=fix-1")
+        );
+        assert_eq!(
+            api_url(&p, "fix-1", true).as_deref(),
+            Some("https://git.example.com/api/v4/projects/team%2Fsub%2Fshop/merge_requests?state=all&source_branch=fix-1")
+        );
+    }
+
+    #[test]
+    fn commit_api_urls() {
+        let gh = project("https://github.com/acme/shop", RepoOrigin::GitHub);
+        assert_eq!(
+            commit_api_url(&gh, "abc1234").as_deref(),
+            Some("https://api.github.com/repos/acme/shop/commits/abc1234")
+        );
+        let gl = project("https://gitlab.com/team/shop", RepoOrigin::GitLab);
+        assert_eq!(
+            commit_api_url(&gl, "abc1234").as_deref(),
+            Some("https://gitlab.com/api/v4/projects/team%2Fshop/repository/commits/abc1234/signature")
+        );
+        let other = project(
+            "https://git.example.com/shop",
+            RepoOrigin::Custom("git.example.com".to_string()),
+        );
+        assert!(commit_api_url(&other, "abc1234").is_none());
+    }
+
+    #[test]
+    fn parse_verification_responses() {
+        let gh =
+            serde_json::json!({"commit": {"verification": {"verified": true, "reason": "valid"}}});
+        assert_eq!(parse_verified(&gh), Some(true));
+        let gl = serde_json::json!({"signature_type": "SSH", "verification_status": "unverified"});
+        assert_eq!(parse_verified(&gl), Some(false));
+        assert_eq!(parse_verified(&serde_json::json!({})), None);
+    }
+
+    #[test]
+    fn tokens_only_go_to_their_host() {
+        let tokens = HashMap::from([("git.example.com".to_string(), "self".to_string())]);
+        assert_eq!(
+            token_for("https://git.example.com/api/v4/projects/a", &tokens),
+            Some(("PRIVATE-TOKEN", "self".to_string()))
+        );
+        assert_eq!(
+            token_for("https://gitlab.attacker.example/api/v4/projects/a", &tokens),
+            None
+        );
+        assert_eq!(
+            token_for("http://git.example.com/api/v4/projects/a", &tokens),
+            None
+        );
+    }
+
+    #[test]
+    fn remote_branches_are_looked_up_without_their_remote() {
+        let p = project("https://github.com/acme/shop", RepoOrigin::GitHub);
+        let branch = BranchLog {
+            name: "origin/feature/x".to_string(),
+            remote_name: Some("feature/x".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(pr_branch(&branch), "feature/x");
+        assert_eq!(
+            api_url(&p, pr_branch(&branch), false).as_deref(),
+            Some("https://api.github.com/repos/acme/shop/pulls?state=open&head=acme%3Afeature%2Fx")
+        );
+        let remote_main = BranchLog {
+            name: "origin/main".to_string(),
+            remote_name: Some("main".to_string()),
+            ..Default::default()
+        };
+        assert!(cache_key(&p, pr_branch(&remote_main)).is_none());
+    }
+
+    #[test]
+    fn primary_branches_are_not_looked_up() {
+        let p = project("https://github.com/acme/shop", RepoOrigin::GitHub);
+        assert!(cache_key(&p, "main").is_none());
+        assert!(cache_key(&p, "feature/x").is_some());
+    }
+
+    #[test]
+    fn parse_github_and_gitlab_listings() {
+        let gh: Value = serde_json::from_str(
+            r#"[{"number": 12, "title": "Add payment retries", "html_url": "https://github.com/acme/shop/pull/12"}]"#,
+        )
+        .expect("valid json");
+        let pr = parse_first(&gh).expect("pr");
+        assert_eq!(pr.number, 12);
+        assert_eq!(pr.title, "Add payment retries");
+        assert_eq!(pr.state, PrState::Open);
+
+        let merged: Value = serde_json::from_str(
+            r#"[{"number": 9, "title": "Retry", "html_url": "https://github.com/acme/shop/pull/9", "state": "closed", "merged_at": "2026-03-02T10:00:00Z"}]"#,
+        )
+        .expect("valid json");
+        assert_eq!(parse_first(&merged).expect("pr").state, PrState::Merged);
+
+        let gl: Value = serde_json::from_str(
+            r#"[{"iid": 7, "title": "Fix login", "web_url": "https://gitlab.com/a/b/-/merge_requests/7"}]"#,
+        )
+        .expect("valid json");
+        assert_eq!(parse_first(&gl).expect("mr").number, 7);
+        let closed: Value = serde_json::from_str(
+            r#"[{"iid": 8, "title": "Drop", "web_url": "https://gitlab.com/a/b/-/merge_requests/8", "state": "closed"}]"#,
+        )
+        .expect("valid json");
+        assert_eq!(parse_first(&closed).expect("mr").state, PrState::Closed);
+
+        assert!(parse_first(&serde_json::json!([])).is_none());
+    }
+}
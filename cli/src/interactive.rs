@@ -1,17 +1,43 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use chrono::{Duration, NaiveDate};
 use colored::Colorize;
 use dialoguer::console::{self, strip_ansi_codes};
 use dialoguer::theme::{ColorfulTheme, Theme};
-use dialoguer::FuzzySelect;
+use dialoguer::Input;
+use std::collections::{BTreeSet, HashSet};
 use std::fmt;
-use std::process::Command;
+use std::path::Path;
+use std::process::{Command, Stdio};
 
+use crate::actions::{self, CustomAction, Placeholders};
+use crate::cli::SelectTarget;
+use crate::clipboard;
+use crate::keys::{Action, Keymap};
 use crate::output;
+use crate::picker::{Outcome, Picker};
+use crate::search;
+use devcap_core::git;
 use devcap_core::model::{BranchLog, Commit, ProjectLog};
+use devcap_core::period::{Period, TimeRange};
+use devcap_core::zone::Zone;
 
 const BACK_LABEL: &str = "\u{276e} Back";
 const QUIT_LABEL: &str = "\u{276e} Quit";
 const SHOW_ALL_LABEL: &str = "\u{2630} Show all";
+const SEARCH_LABEL: &str = "\u{2315} Search all commits";
+const PERIOD_LABEL: &str = "\u{25f7} Change period";
+const CUSTOM_RANGE_LABEL: &str = "Custom range\u{2026}";
+
+/// Periods offered by the period picker, as (label, period syntax).
+const PERIOD_PRESETS: [(&str, &str); 7] = [
+    ("Today", "today"),
+    ("Yesterday", "yesterday"),
+    ("This week", "week"),
+    ("Last week", "last-week"),
+    ("This month", "month"),
+    ("Last month", "last-month"),
+    ("This quarter", "quarter"),
+];
 
 struct DevcapTheme {
     inner: ColorfulTheme,
@@ -60,124 +86,652 @@ impl Theme for DevcapTheme {
 enum Selection {
     Back,
     ShowAll,
+    Search,
+    Period,
     Index(usize),
+    Action(Action, Option<usize>),
 }
 
-pub fn run(projects: &[ProjectLog], show_origin: bool) -> Result<()> {
-    let theme = DevcapTheme::new();
+/// Whether to keep browsing after handling an action.
+enum Flow {
+    Continue,
+    Quit,
+}
+
+/// How browsing the project list ended.
+enum Exit {
+    Quit,
+    /// The user picked another period to collect.
+    Rescan(TimeRange),
+}
 
+/// Collects the report again for another period.
+pub type Rescan<'a> = &'a dyn Fn(&TimeRange) -> Vec<ProjectLog>;
+
+/// Commits marked for copying, as (project, branch, commit) indices.
+type Marks = BTreeSet<(usize, usize, usize)>;
+
+pub struct Options {
+    pub show_origin: bool,
+    pub keymap: Keymap,
+    /// Editor command for the edit action; falls back to `$VISUAL` / `$EDITOR`.
+    pub editor: Option<String>,
+    /// File manager command for the reveal action; falls back to the OS default.
+    pub file_manager: Option<String>,
+    pub custom_actions: Vec<CustomAction>,
+    /// Zone the periods picked while browsing are counted in.
+    pub zone: Zone,
+}
+
+struct Session<'a> {
+    theme: DevcapTheme,
+    opts: &'a Options,
+    projects: &'a [ProjectLog],
+    marks: Marks,
+}
+
+/// Browse `projects`; picking another period re-collects them with `rescan`.
+pub fn run(projects: &[ProjectLog], opts: &Options, rescan: Rescan) -> Result<()> {
+    output::set_stderr(true);
+    let mut rescanned: Option<Vec<ProjectLog>> = None;
     loop {
-        match select_project(&theme, projects, show_origin)? {
-            Selection::Back => return Ok(()),
-            Selection::ShowAll => {
-                println!();
-                output::render_terminal(projects, crate::cli::Depth::Commits, show_origin);
-                println!();
-            }
-            Selection::Index(idx) => {
-                let project = &projects[idx];
-                browse_project(&theme, project, show_origin)?;
+        let mut session = Session {
+            theme: DevcapTheme::new(),
+            opts,
+            projects: rescanned.as_deref().unwrap_or(projects),
+            marks: Marks::new(),
+        };
+        match session.browse_projects()? {
+            Exit::Quit => return Ok(()),
+            Exit::Rescan(range) => {
+                let projects = rescan(&range);
+                if projects.is_empty() {
+                    eprintln!("No commits found for the selected period.");
+                }
+                rescanned = Some(projects);
             }
         }
     }
 }
 
-fn browse_project(theme: &DevcapTheme, project: &ProjectLog, show_origin: bool) -> Result<()> {
-    loop {
-        match select_branch(theme, project)? {
-            Selection::Back => return Ok(()),
-            Selection::ShowAll => {
-                println!();
-                output::render_project(project, show_origin);
-                println!();
+/// Start directly in the cross-project commit search, pre-filled with `query`.
+pub fn run_search(projects: &[ProjectLog], opts: &Options, query: &str) -> Result<()> {
+    output::set_stderr(true);
+    let mut session = Session {
+        theme: DevcapTheme::new(),
+        opts,
+        projects,
+        marks: Marks::new(),
+    };
+    session.browse_search(query).map(|_| ())
+}
+
+/// Pick one item of `target` kind across all projects and return what
+/// `--select` prints for it: the project path, branch name or commit hash.
+/// `None` when the picker was cancelled.
+pub fn select(
+    projects: &[ProjectLog],
+    opts: &Options,
+    target: SelectTarget,
+) -> Result<Option<String>> {
+    let mut items = Vec::new();
+    let mut values = Vec::new();
+    for project in projects {
+        match target {
+            SelectTarget::Project => {
+                items.push(format_project_item(project, opts.show_origin));
+                values.push(project.path.as_str());
             }
-            Selection::Index(idx) => {
-                let branch = &project.branches[idx];
-                browse_branch(theme, project, branch)?;
+            SelectTarget::Branch => {
+                for branch in &project.branches {
+                    items.push(format!(
+                        "{}  {}",
+                        project.project.bold(),
+                        format_branch_item(branch)
+                    ));
+                    values.push(git::branch_rev(&branch.name));
+                }
+            }
+            SelectTarget::Commit => {
+                let mut seen = HashSet::new();
+                for commit in project.branches.iter().flat_map(|b| &b.commits) {
+                    if seen.insert(&commit.hash) {
+                        items.push(format!(
+                            "{}  {}",
+                            project.project.bold(),
+                            format_commit_item(commit)
+                        ));
+                        values.push(&commit.hash);
+                    }
+                }
             }
         }
     }
+    let prompt = match target {
+        SelectTarget::Project => "Select project",
+        SelectTarget::Branch => "Select branch",
+        SelectTarget::Commit => "Select commit",
+    };
+    let theme = DevcapTheme::new();
+    let mut picker = Picker::new(&theme, &opts.keymap, prompt, &items);
+    loop {
+        match picker.interact()? {
+            Outcome::Pick(idx) => return Ok(values.get(idx).map(|v| v.to_string())),
+            Outcome::Cancel => return Ok(None),
+            // Only picking is meaningful here; keep the prompt open.
+            Outcome::Action(..) => {}
+        }
+    }
 }
 
-fn browse_branch(theme: &DevcapTheme, project: &ProjectLog, branch: &BranchLog) -> Result<()> {
-    loop {
-        match select_commit(theme, branch)? {
-            Selection::Back => return Ok(()),
-            Selection::ShowAll => {
-                println!();
-                output::render_branch(branch);
-                println!();
+impl Session<'_> {
+    fn browse_projects(&mut self) -> Result<Exit> {
+        let projects = self.projects;
+        let show_origin = self.opts.show_origin;
+        loop {
+            let items: Vec<String> = [QUIT_LABEL, SHOW_ALL_LABEL, SEARCH_LABEL, PERIOD_LABEL]
+                .into_iter()
+                .map(String::from)
+                .chain(projects.iter().map(|p| format_project_item(p, show_origin)))
+                .collect();
+            match self.pick("Select project", &items, 4, None)? {
+                Selection::Back => return Ok(Exit::Quit),
+                Selection::Search => {
+                    if let Flow::Quit = self.browse_search("")? {
+                        return Ok(Exit::Quit);
+                    }
+                }
+                Selection::Period => {
+                    if let Some(range) = self.choose_period()? {
+                        return Ok(Exit::Rescan(range));
+                    }
+                }
+                Selection::ShowAll => {
+                    output::outln!();
+                    output::render_terminal(projects, crate::cli::Depth::Commits, show_origin);
+                    output::outln!();
+                }
+                Selection::Index(idx) => {
+                    if let Flow::Quit = self.browse_project(idx)? {
+                        return Ok(Exit::Quit);
+                    }
+                }
+                Selection::Action(Action::Open, idx) => {
+                    open_url(idx.and_then(|i| projects[i].remote_url.as_deref()));
+                }
+                Selection::Action(Action::Copy, idx) => {
+                    let scope = match idx {
+                        Some(i) => &projects[i..=i],
+                        None => projects,
+                    };
+                    self.copy_or_marked(|| {
+                        clipboard::render_plain(scope, crate::cli::Depth::Commits, show_origin)
+                    });
+                }
+                Selection::Action(Action::Run, Some(idx)) => {
+                    self.run_custom(&project_placeholders(&projects[idx]))?;
+                }
+                Selection::Action(Action::CopyUrl, idx) => {
+                    copy_field(idx.and_then(|i| projects[i].remote_url.as_deref()), "URL");
+                }
+                Selection::Action(action, Some(idx)) => {
+                    if let Flow::Quit = self.project_action(action, &projects[idx]) {
+                        return Ok(Exit::Quit);
+                    }
+                }
+                Selection::Action(_, None) => {}
             }
-            Selection::Index(idx) => {
-                let commit = &branch.commits[idx];
-                show_commit_detail(project, commit)?;
+        }
+    }
+
+    fn browse_project(&mut self, project_idx: usize) -> Result<Flow> {
+        let project = &self.projects[project_idx];
+        let show_origin = self.opts.show_origin;
+        loop {
+            let items: Vec<String> = [BACK_LABEL, SHOW_ALL_LABEL]
+                .into_iter()
+                .map(String::from)
+                .chain(project.branches.iter().map(format_branch_item))
+                .collect();
+            match self.pick("Select branch", &items, 2, None)? {
+                Selection::Back => return Ok(Flow::Continue),
+                Selection::Search | Selection::Period => {}
+                Selection::ShowAll => {
+                    output::outln!();
+                    output::render_project(project, show_origin);
+                    output::outln!();
+                }
+                Selection::Index(idx) => {
+                    if let Flow::Quit = self.browse_branch(project_idx, idx)? {
+                        return Ok(Flow::Quit);
+                    }
+                }
+                Selection::Action(Action::Open, idx) => {
+                    let url = match idx {
+                        Some(i) => project.branches[i].url.as_deref(),
+                        None => project.remote_url.as_deref(),
+                    };
+                    open_url(url);
+                }
+                Selection::Action(Action::Copy, idx) => self.copy_or_marked(|| match idx {
+                    Some(i) => {
+                        clipboard::render_item(project, &project.branches[i], None, show_origin)
+                    }
+                    None => clipboard::render_plain(
+                        std::slice::from_ref(project),
+                        crate::cli::Depth::Commits,
+                        show_origin,
+                    ),
+                }),
+                Selection::Action(Action::Run, idx) => {
+                    let values = match idx {
+                        Some(i) => branch_placeholders(project, &project.branches[i]),
+                        None => project_placeholders(project),
+                    };
+                    self.run_custom(&values)?;
+                }
+                Selection::Action(Action::CopyUrl, idx) => {
+                    let url = match idx {
+                        Some(i) => project.branches[i].url.as_deref(),
+                        None => project.remote_url.as_deref(),
+                    };
+                    copy_field(url, "URL");
+                }
+                Selection::Action(action, _) => {
+                    if let Flow::Quit = self.project_action(action, project) {
+                        return Ok(Flow::Quit);
+                    }
+                }
+            }
+        }
+    }
+
+    fn browse_branch(&mut self, project_idx: usize, branch_idx: usize) -> Result<Flow> {
+        let project = &self.projects[project_idx];
+        let branch = &project.branches[branch_idx];
+        loop {
+            let items: Vec<String> = [BACK_LABEL, SHOW_ALL_LABEL]
+                .into_iter()
+                .map(String::from)
+                .chain(branch.commits.iter().map(format_commit_item))
+                .collect();
+            // Picker items are offset by the Back / Show all entries.
+            let mut local: BTreeSet<usize> = self
+                .marks
+                .iter()
+                .filter(|(p, b, _)| (*p, *b) == (project_idx, branch_idx))
+                .map(|(_, _, c)| c + 2)
+                .collect();
+            let selection = self.pick("Select commit", &items, 2, Some(&mut local))?;
+            self.marks
+                .retain(|(p, b, _)| (*p, *b) != (project_idx, branch_idx));
+            self.marks
+                .extend(local.into_iter().map(|i| (project_idx, branch_idx, i - 2)));
+
+            match selection {
+                Selection::Back => return Ok(Flow::Continue),
+                Selection::Search | Selection::Period => {}
+                Selection::ShowAll => {
+                    output::outln!();
+                    output::render_branch(branch);
+                    output::outln!();
+                }
+                Selection::Index(idx) => {
+                    let commit = &branch.commits[idx];
+                    show_commit_detail(project, commit)?;
+                }
+                Selection::Action(Action::Open, idx) => {
+                    let url = match idx {
+                        Some(i) => branch.commits[i].url.as_deref(),
+                        None => branch.url.as_deref(),
+                    };
+                    open_url(url);
+                }
+                Selection::Action(Action::Copy, idx) => self.copy_or_marked(|| {
+                    let commit = idx.map(|i| &branch.commits[i]);
+                    clipboard::render_item(project, branch, commit, self.opts.show_origin)
+                }),
+                Selection::Action(Action::Run, idx) => {
+                    let mut values = branch_placeholders(project, branch);
+                    if let Some(commit) = idx.map(|i| &branch.commits[i]) {
+                        values.hash = Some(&commit.hash);
+                        values.url = commit.url.as_deref();
+                    }
+                    self.run_custom(&values)?;
+                }
+                Selection::Action(
+                    action @ (Action::CopyHash | Action::CopyMessage | Action::CopyUrl),
+                    idx,
+                ) => match idx.map(|i| &branch.commits[i]) {
+                    Some(commit) => copy_commit_field(action, project, commit),
+                    None if action == Action::CopyUrl => copy_field(branch.url.as_deref(), "URL"),
+                    None => eprintln!("Highlight a commit to copy its hash or message."),
+                },
+                Selection::Action(action, _) => {
+                    if let Flow::Quit = self.project_action(action, project) {
+                        return Ok(Flow::Quit);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Let the user pick one of the configured actions that can run with the
+    /// given placeholder values, then run it.
+    fn run_custom(&self, values: &Placeholders) -> Result<()> {
+        let available: Vec<&CustomAction> = self
+            .opts
+            .custom_actions
+            .iter()
+            .filter(|a| a.expand(values).is_some())
+            .collect();
+        if available.is_empty() {
+            eprintln!(
+                "No custom actions available here. Add them under [actions] in ~/.devcap.toml."
+            );
+            return Ok(());
+        }
+        let items: Vec<String> = std::iter::once(BACK_LABEL.to_string())
+            .chain(
+                available
+                    .iter()
+                    .map(|a| format!("{}  {}", a.name, a.template.dimmed())),
+            )
+            .collect();
+        let picked =
+            Picker::new(&self.theme, &self.opts.keymap, "Run action", &items).interact()?;
+        if let Outcome::Pick(i) = picked {
+            if let Some(action) = i.checked_sub(1).and_then(|i| available.get(i)) {
+                action.run(values);
+            }
+        }
+        Ok(())
+    }
+
+    /// Flat list of every commit across all projects; typing filters by
+    /// project, branch, hash, and message at once.
+    fn browse_search(&mut self, initial_query: &str) -> Result<Flow> {
+        let entries = search::all_commits(self.projects);
+        let mut query = initial_query.to_string();
+        loop {
+            let items: Vec<String> = std::iter::once(BACK_LABEL.to_string())
+                .chain(entries.iter().map(search::format_entry))
+                .collect();
+            let mut picker = Picker::new(&self.theme, &self.opts.keymap, "Search commits", &items)
+                .with_query(&query);
+            let outcome = picker.interact()?;
+            query = picker.query().to_string();
+
+            let entry = |idx: Option<usize>| idx.map(|i| entries[i]);
+            match parse_selection(outcome, 1) {
+                Selection::Back => return Ok(Flow::Continue),
+                Selection::ShowAll | Selection::Search | Selection::Period => {
+                    output::outln!();
+                    search::render_terminal(&search::search(self.projects, &query));
+                    output::outln!();
+                }
+                Selection::Index(idx) => {
+                    let e = entries[idx];
+                    show_commit_detail(e.project, e.commit)?;
+                }
+                Selection::Action(Action::Open, idx) => {
+                    open_url(entry(idx).and_then(|e| e.commit.url.as_deref()));
+                }
+                Selection::Action(Action::Copy, idx) => {
+                    if let Some(e) = entry(idx) {
+                        self.copy_or_marked(|| {
+                            clipboard::render_item(
+                                e.project,
+                                e.branch,
+                                Some(e.commit),
+                                self.opts.show_origin,
+                            )
+                        });
+                    }
+                }
+                Selection::Action(
+                    action @ (Action::CopyHash | Action::CopyMessage | Action::CopyUrl),
+                    idx,
+                ) => {
+                    if let Some(e) = entry(idx) {
+                        copy_commit_field(action, e.project, e.commit);
+                    }
+                }
+                Selection::Action(Action::Run, idx) => {
+                    if let Some(e) = entry(idx) {
+                        let mut values = branch_placeholders(e.project, e.branch);
+                        values.hash = Some(&e.commit.hash);
+                        values.url = e.commit.url.as_deref();
+                        self.run_custom(&values)?;
+                    }
+                }
+                Selection::Action(action, idx) => {
+                    if let Some(e) = entry(idx) {
+                        if let Flow::Quit = self.project_action(action, e.project) {
+                            return Ok(Flow::Quit);
+                        }
+                    }
+                }
             }
         }
     }
+
+    /// Ask for a preset period or a custom date range; `None` to go back.
+    fn choose_period(&self) -> Result<Option<TimeRange>> {
+        let items: Vec<String> = [BACK_LABEL]
+            .into_iter()
+            .chain(PERIOD_PRESETS.iter().map(|(label, _)| *label))
+            .chain([CUSTOM_RANGE_LABEL])
+            .map(String::from)
+            .collect();
+        match self.pick("Select period", &items, 1, None)? {
+            Selection::Index(i) => match PERIOD_PRESETS.get(i) {
+                Some((_, period)) => {
+                    let period: Period = period.parse().map_err(|e: String| anyhow!(e))?;
+                    Ok(Some(period.to_time_range(self.opts.zone)))
+                }
+                None => self.prompt_range().map(Some),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Guided prompts for the first and last day of a custom range.
+    fn prompt_range(&self) -> Result<TimeRange> {
+        let today = self.opts.zone.now().date_naive();
+        let from: NaiveDate = Input::with_theme(&self.theme)
+            .with_prompt("From (YYYY-MM-DD)")
+            .default(today - Duration::days(7))
+            .validate_with(|d: &NaiveDate| -> Result<(), &str> {
+                if *d > today {
+                    Err("must not be in the future")
+                } else {
+                    Ok(())
+                }
+            })
+            .interact_text()?;
+        let to: NaiveDate = Input::with_theme(&self.theme)
+            .with_prompt("To (YYYY-MM-DD)")
+            .default(today)
+            .validate_with(|d: &NaiveDate| -> Result<(), &str> {
+                if *d < from {
+                    Err("must be on or after the start date")
+                } else {
+                    Ok(())
+                }
+            })
+            .interact_text()?;
+        TimeRange::from_dates(from, to, self.opts.zone).map_err(|e| anyhow!(e))
+    }
+
+    fn pick(
+        &self,
+        prompt: &str,
+        items: &[String],
+        headers: usize,
+        marks: Option<&mut BTreeSet<usize>>,
+    ) -> Result<Selection> {
+        let mut picker = Picker::new(&self.theme, &self.opts.keymap, prompt, items);
+        if let Some(marks) = marks {
+            picker = picker.with_marks(marks, headers);
+        }
+        Ok(parse_selection(picker.interact()?, headers))
+    }
+
+    /// Copy the marked commits if there are any, otherwise the current scope.
+    fn copy_or_marked(&self, render_scope: impl FnOnce() -> String) {
+        let text = if self.marks.is_empty() {
+            render_scope()
+        } else {
+            clipboard::render_selected(self.projects, self.opts.show_origin, |p, b, c| {
+                self.marks.contains(&(p, b, c))
+            })
+        };
+        copy_to_clipboard(&text);
+    }
+
+    /// Project-level launcher actions: editor, file manager, and `cd`.
+    fn project_action(&self, action: Action, project: &ProjectLog) -> Flow {
+        match action {
+            Action::Edit => {
+                let editor = self
+                    .opts
+                    .editor
+                    .clone()
+                    .or_else(|| std::env::var("VISUAL").ok())
+                    .or_else(|| std::env::var("EDITOR").ok())
+                    .filter(|e| !e.trim().is_empty());
+                match editor {
+                    Some(cmd) => launch(&cmd, &project.path),
+                    None => eprintln!(
+                        "No editor configured. Set $EDITOR or `editor` in ~/.devcap.toml."
+                    ),
+                }
+                Flow::Continue
+            }
+            Action::Reveal => {
+                let cmd = self
+                    .opts
+                    .file_manager
+                    .as_deref()
+                    .unwrap_or(default_opener());
+                launch(cmd, &project.path);
+                Flow::Continue
+            }
+            Action::Cd => {
+                // The only line on stdout, for `eval "$(devcap -i)"`.
+                println!("cd {}", actions::shell_quote(&project.path));
+                Flow::Quit
+            }
+            Action::CopyHash | Action::CopyMessage => {
+                eprintln!("Highlight a commit to copy its hash or message.");
+                Flow::Continue
+            }
+            _ => Flow::Continue,
+        }
+    }
 }
 
-fn select_project(
-    theme: &DevcapTheme,
-    projects: &[ProjectLog],
-    show_origin: bool,
-) -> Result<Selection> {
-    let items: Vec<String> = [QUIT_LABEL, SHOW_ALL_LABEL]
-        .into_iter()
-        .map(String::from)
-        .chain(projects.iter().map(|p| format_project_item(p, show_origin)))
-        .collect();
-
-    parse_selection(
-        FuzzySelect::with_theme(theme)
-            .with_prompt("Select project")
-            .items(&items)
-            .default(0)
-            .interact_opt()?,
-    )
+/// Map a picker outcome to a selection. The first `headers` items are the fixed
+/// Back / Show all / Search entries; actions on them apply to the whole scope
+/// rather than a single item.
+fn parse_selection(outcome: Outcome, headers: usize) -> Selection {
+    match outcome {
+        Outcome::Cancel | Outcome::Pick(0) => Selection::Back,
+        Outcome::Pick(1) if headers > 1 => Selection::ShowAll,
+        Outcome::Pick(2) if headers > 2 => Selection::Search,
+        Outcome::Pick(3) if headers > 3 => Selection::Period,
+        Outcome::Action(Action::ShowAll, _) => Selection::ShowAll,
+        Outcome::Pick(i) => Selection::Index(i - headers),
+        Outcome::Action(action, idx) => {
+            Selection::Action(action, idx.filter(|&i| i >= headers).map(|i| i - headers))
+        }
+    }
 }
 
-fn select_branch(theme: &DevcapTheme, project: &ProjectLog) -> Result<Selection> {
-    let items: Vec<String> = [BACK_LABEL, SHOW_ALL_LABEL]
-        .into_iter()
-        .map(String::from)
-        .chain(project.branches.iter().map(format_branch_item))
-        .collect();
-
-    parse_selection(
-        FuzzySelect::with_theme(theme)
-            .with_prompt("Select branch")
-            .items(&items)
-            .default(0)
-            .interact_opt()?,
-    )
+fn project_placeholders(project: &ProjectLog) -> Placeholders<'_> {
+    Placeholders {
+        path: Some(&project.path),
+        project: Some(&project.project),
+        url: project.remote_url.as_deref(),
+        ..Default::default()
+    }
 }
 
-fn select_commit(theme: &DevcapTheme, branch: &BranchLog) -> Result<Selection> {
-    let items: Vec<String> = [BACK_LABEL, SHOW_ALL_LABEL]
-        .into_iter()
-        .map(String::from)
-        .chain(branch.commits.iter().map(format_commit_item))
-        .collect();
-
-    parse_selection(
-        FuzzySelect::with_theme(theme)
-            .with_prompt("Select commit")
-            .items(&items)
-            .default(0)
-            .interact_opt()?,
-    )
+fn branch_placeholders<'a>(project: &'a ProjectLog, branch: &'a BranchLog) -> Placeholders<'a> {
+    Placeholders {
+        // A detached HEAD has no branch name to hand to a command.
+        branch: Some(branch.name.as_str()).filter(|name| *name != git::DETACHED_BRANCH),
+        url: branch.url.as_deref(),
+        ..project_placeholders(project)
+    }
+}
+
+/// Copy the hash, full message, or forge URL of `commit`.
+fn copy_commit_field(action: Action, project: &ProjectLog, commit: &Commit) {
+    match action {
+        Action::CopyHash => copy_field(Some(&commit.hash), "hash"),
+        Action::CopyMessage => {
+            let message = git::commit_message(Path::new(&project.path), &commit.hash)
+                .unwrap_or_else(|| commit.message.clone());
+            copy_field(Some(&message), "message");
+        }
+        _ => copy_field(commit.url.as_deref(), "URL"),
+    }
+}
+
+fn copy_field(value: Option<&str>, what: &str) {
+    let Some(value) = value else {
+        eprintln!("No {what} available.");
+        return;
+    };
+    match clipboard::copy_text(value) {
+        Ok(()) => eprintln!("Copied {what} to clipboard."),
+        Err(e) => eprintln!("Warning: {e:#}"),
+    }
+}
+
+fn copy_to_clipboard(text: &str) {
+    match clipboard::copy_text(text) {
+        Ok(()) => eprintln!("Copied to clipboard."),
+        Err(e) => eprintln!("Warning: {e:#}"),
+    }
+}
+
+pub(crate) fn default_opener() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    }
 }
 
-fn parse_selection(result: Option<usize>) -> Result<Selection> {
-    Ok(match result {
-        Some(0) | None => Selection::Back,
-        Some(1) => Selection::ShowAll,
-        Some(i) => Selection::Index(i - 2),
-    })
+fn open_url(url: Option<&str>) {
+    let Some(url) = url else {
+        eprintln!("No remote URL available.");
+        return;
+    };
+    launch(default_opener(), url);
+}
+
+/// Run a user-supplied command line (e.g. `code -w`) with `arg` appended.
+fn launch(command_line: &str, arg: &str) {
+    let mut parts = command_line.split_whitespace();
+    let Some(program) = parts.next() else {
+        return;
+    };
+    let status = Command::new(program)
+        .args(parts)
+        .arg(arg)
+        .stdout(Stdio::from(std::io::stderr()))
+        .status();
+    if let Err(e) = status {
+        eprintln!("Warning: could not run {program}: {e}");
+    }
 }
 
 fn show_commit_detail(project: &ProjectLog, commit: &Commit) -> Result<()> {
-    let output = Command::new("git")
+    let output = git::command()
         .args([
             "-C",
             &project.path,
@@ -189,7 +743,7 @@ fn show_commit_detail(project: &ProjectLog, commit: &Commit) -> Result<()> {
         .output()?;
 
     if output.status.success() {
-        println!("\n{}", String::from_utf8_lossy(&output.stdout));
+        output::outln!("\n{}", String::from_utf8_lossy(&output.stdout));
     } else {
         eprintln!("Failed to show commit {}", commit.hash);
     }
@@ -202,6 +756,7 @@ fn format_project_item(project: &ProjectLog, show_origin: bool) -> String {
     let branches = project.branches.len();
     let latest = project.latest_activity().unwrap_or("-");
     let origin = output::origin_tag(project, show_origin);
+    let activity = output::activity_suffix(project);
     let stat_suffix = output::stat_suffix_standalone(project.diff_stat.as_ref());
     let summary = format!(
         "({} {}, {} {}, {}{})",
@@ -215,18 +770,20 @@ fn format_project_item(project: &ProjectLog, show_origin: bool) -> String {
     .dimmed();
     if output::color_enabled() {
         format!(
-            "{} {}{}  {}",
+            "{} {}{}{}  {}",
             "::".bold().cyan(),
             project.project.bold().white(),
             origin,
+            activity,
             summary
         )
     } else {
         format!(
-            "{} {}{}  {}",
+            "{} {}{}{}  {}",
             "::".bold(),
             project.project.bold(),
             origin,
+            activity,
             summary
         )
     }
@@ -245,9 +802,21 @@ fn format_branch_item(branch: &BranchLog) -> String {
     )
     .dimmed();
     if output::color_enabled() {
-        format!("{} {}  {}", ">>".green(), branch.name.green(), summary)
+        format!(
+            "{} {}{}  {}",
+            ">>".green(),
+            branch.name.green(),
+            output::branch_suffix(branch),
+            summary
+        )
     } else {
-        format!("{} {}  {}", ">>", branch.name, summary)
+        format!(
+            "{} {}{}  {}",
+            ">>",
+            branch.name,
+            output::branch_suffix(branch),
+            summary
+        )
     }
 }
 
@@ -255,22 +824,29 @@ fn format_commit_item(commit: &Commit) -> String {
     let tag = output::commit_type_tag(commit);
     let msg = output::strip_type_prefix(&commit.message);
     let stat = output::stat_suffix_inline(commit.diff_stat.as_ref());
+    let badge = format!(
+        "{}{}",
+        output::co_authored_badge(commit).cyan(),
+        output::unpushed_badge(commit).yellow()
+    );
     if tag.is_empty() {
         format!(
-            "{} - {}  {}{}",
+            "{} - {}  {}{}{}",
             commit.hash.dimmed(),
             msg,
-            commit.relative_time.dimmed(),
+            output::commit_byline(commit).dimmed(),
             stat.dimmed(),
+            badge,
         )
     } else {
         format!(
-            "{} {} - {}  {}{}",
+            "{} {} - {}  {}{}{}",
             commit.hash.dimmed(),
             tag,
             msg,
-            commit.relative_time.dimmed(),
+            output::commit_byline(commit).dimmed(),
             stat.dimmed(),
+            badge,
         )
     }
 }
@@ -295,11 +871,9 @@ mod tests {
         Commit {
             hash: hash.to_string(),
             message: message.to_string(),
-            commit_type: None,
-            time: Local::now(),
+            time: Local::now().fixed_offset(),
             relative_time: relative.to_string(),
-            url: None,
-            diff_stat: None,
+            ..Default::default()
         }
     }
 
@@ -325,15 +899,12 @@ mod tests {
         let project = ProjectLog {
             project: "my-app".to_string(),
             path: "/test".to_string(),
-            origin: None,
-            remote_url: None,
             branches: vec![BranchLog {
                 name: "main".to_string(),
-                url: None,
                 commits: vec![make_commit("abc", "msg", "1h ago")],
-                diff_stat: None,
+                ..Default::default()
             }],
-            diff_stat: None,
+            ..Default::default()
         };
         let text = strip_ansi(&format_project_item(&project, false));
         assert!(text.contains("my-app"));
@@ -346,26 +917,22 @@ mod tests {
         let project = ProjectLog {
             project: "my-app".to_string(),
             path: "/test".to_string(),
-            origin: None,
-            remote_url: None,
             branches: vec![
                 BranchLog {
                     name: "main".to_string(),
-                    url: None,
                     commits: vec![
                         make_commit("a", "m1", "1h ago"),
                         make_commit("b", "m2", "2h ago"),
                     ],
-                    diff_stat: None,
+                    ..Default::default()
                 },
                 BranchLog {
                     name: "dev".to_string(),
-                    url: None,
                     commits: vec![make_commit("c", "m3", "3h ago")],
-                    diff_stat: None,
+                    ..Default::default()
                 },
             ],
-            diff_stat: None,
+            ..Default::default()
         };
         let text = strip_ansi(&format_project_item(&project, false));
         assert!(text.contains("3 commits"));
@@ -379,17 +946,15 @@ mod tests {
             project: "my-app".to_string(),
             path: "/test".to_string(),
             origin: Some(RepoOrigin::GitHub),
-            remote_url: None,
             branches: vec![BranchLog {
                 name: "main".to_string(),
-                url: None,
                 commits: vec![make_commit("abc", "msg", "1h ago")],
-                diff_stat: None,
+                ..Default::default()
             }],
-            diff_stat: None,
+            ..Default::default()
         };
         let text = strip_ansi(&format_project_item(&project, true));
-        assert!(text.contains("[GitHub]"));
+        assert!(text.contains("[GH]"));
     }
 
     #[test]
@@ -399,26 +964,40 @@ mod tests {
             project: "my-app".to_string(),
             path: "/test".to_string(),
             origin: Some(RepoOrigin::GitHub),
-            remote_url: None,
             branches: vec![BranchLog {
                 name: "main".to_string(),
-                url: None,
                 commits: vec![make_commit("abc", "msg", "1h ago")],
-                diff_stat: None,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let text = strip_ansi(&format_project_item(&project, false));
+        assert!(!text.contains("[GH]"));
+    }
+
+    #[test]
+    fn format_project_shows_activity_sparkline() {
+        let project = ProjectLog {
+            project: "my-app".to_string(),
+            path: "/test".to_string(),
+            branches: vec![BranchLog {
+                name: "main".to_string(),
+                commits: vec![make_commit("abc", "msg", "1h ago")],
+                ..Default::default()
             }],
-            diff_stat: None,
+            activity: Some(vec![0, 2, 4]),
+            ..Default::default()
         };
         let text = strip_ansi(&format_project_item(&project, false));
-        assert!(!text.contains("[GitHub]"));
+        assert!(text.contains("my-app  \u{2581}\u{2585}\u{2588}"));
     }
 
     #[test]
     fn format_branch_singular() {
         let branch = BranchLog {
             name: "feature/auth".to_string(),
-            url: None,
             commits: vec![make_commit("a", "m", "1h ago")],
-            diff_stat: None,
+            ..Default::default()
         };
         let text = strip_ansi(&format_branch_item(&branch));
         assert!(text.contains("feature/auth"));
@@ -429,12 +1008,11 @@ mod tests {
     fn format_branch_plural() {
         let branch = BranchLog {
             name: "main".to_string(),
-            url: None,
             commits: vec![
                 make_commit("a", "m1", "1h ago"),
                 make_commit("b", "m2", "2h ago"),
             ],
-            diff_stat: None,
+            ..Default::default()
         };
         let text = strip_ansi(&format_branch_item(&branch));
         assert!(text.contains("main"));
@@ -0,0 +1,397 @@
+use std::collections::HashMap;
+
+use dialoguer::console::Key;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Up,
+    Down,
+    Select,
+    Back,
+    ShowAll,
+    Open,
+    Copy,
+    Toggle,
+    Edit,
+    Reveal,
+    Cd,
+    Run,
+    CopyHash,
+    CopyMessage,
+    CopyUrl,
+}
+
+impl Action {
+    const ALL: [Action; 15] = [
+        Action::Up,
+        Action::Down,
+        Action::Select,
+        Action::Back,
+        Action::ShowAll,
+        Action::Open,
+        Action::Copy,
+        Action::Toggle,
+        Action::Edit,
+        Action::Reveal,
+        Action::Cd,
+        Action::Run,
+        Action::CopyHash,
+        Action::CopyMessage,
+        Action::CopyUrl,
+    ];
+
+    fn config_name(self) -> &'static str {
+        match self {
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::Select => "select",
+            Action::Back => "back",
+            Action::ShowAll => "show_all",
+            Action::Open => "open",
+            Action::Copy => "copy",
+            Action::Toggle => "toggle",
+            Action::Edit => "edit",
+            Action::Reveal => "reveal",
+            Action::Cd => "cd",
+            Action::Run => "run",
+            Action::CopyHash => "copy_hash",
+            Action::CopyMessage => "copy_message",
+            Action::CopyUrl => "copy_url",
+        }
+    }
+
+    fn hint_label(self) -> &'static str {
+        match self {
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::Select => "select",
+            Action::Back => "back",
+            Action::ShowAll => "show all",
+            Action::Open => "open",
+            Action::Copy => "copy",
+            Action::Toggle => "mark",
+            Action::Edit => "edit",
+            Action::Reveal => "reveal",
+            Action::Cd => "cd",
+            Action::Run => "run",
+            Action::CopyHash => "copy hash",
+            Action::CopyMessage => "copy message",
+            Action::CopyUrl => "copy url",
+        }
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Up => &["up", "shift-tab"],
+            Action::Down => &["down", "tab"],
+            Action::Select => &["enter"],
+            Action::Back => &["esc"],
+            Action::ShowAll => &["ctrl-t"],
+            Action::Open => &["ctrl-o"],
+            Action::Copy => &["ctrl-y"],
+            Action::Toggle => &["ctrl-x"],
+            Action::Edit => &["ctrl-e"],
+            Action::Reveal => &["ctrl-r"],
+            Action::Cd => &["ctrl-g"],
+            Action::Run => &["ctrl-u"],
+            Action::CopyHash => &["ctrl-k"],
+            Action::CopyMessage => &["ctrl-b"],
+            Action::CopyUrl => &["ctrl-l"],
+        }
+    }
+}
+
+/// One or more key names bound to an action in the `[keys]` config section.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    fn names(&self) -> Vec<&str> {
+        match self {
+            KeyList::One(s) => vec![s.as_str()],
+            KeyList::Many(v) => v.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(Action, Key, String)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut bindings = Vec::new();
+        for action in Action::ALL {
+            for name in action.default_keys() {
+                if let Ok(key) = parse_key(name) {
+                    bindings.push((action, key, (*name).to_string()));
+                }
+            }
+        }
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Build a keymap from the `[keys]` config section. Actions not listed keep
+    /// their default keys; listed actions replace them entirely. A key bound
+    /// to two actions, or to one the picker keeps for itself, is an error.
+    pub fn from_config(keys: &HashMap<String, KeyList>) -> Result<Self, String> {
+        let mut unknown: Vec<&String> = keys
+            .keys()
+            .filter(|name| !Action::ALL.iter().any(|a| a.config_name() == *name))
+            .collect();
+        unknown.sort();
+        if let Some(name) = unknown.first() {
+            let expected = Action::ALL.map(Action::config_name).join(", ");
+            return Err(format!("unknown action \"{name}\" (expected: {expected})"));
+        }
+        let mut keymap = Self::default();
+        for action in Action::ALL {
+            let Some(list) = keys.get(action.config_name()) else {
+                continue;
+            };
+            let mut parsed = Vec::new();
+            for key_name in list.names() {
+                let key = parse_key(key_name)?;
+                if let Some(reserved) = reserved(&key) {
+                    return Err(format!(
+                        "key \"{key_name}\" for {} is {reserved}, which cannot be rebound",
+                        action.config_name()
+                    ));
+                }
+                parsed.push((action, key, key_name.to_string()));
+            }
+            keymap.bindings.retain(|(a, _, _)| *a != action);
+            keymap.bindings.extend(parsed);
+        }
+        for (i, (action, key, name)) in keymap.bindings.iter().enumerate() {
+            let earlier = keymap.bindings[..i]
+                .iter()
+                .find(|(a, k, _)| k == key && a != action);
+            if let Some((other, _, other_name)) = earlier {
+                let (action, other) = (action.config_name(), other.config_name());
+                return Err(if name == other_name {
+                    format!("key \"{name}\" is bound to both {other} and {action}")
+                } else {
+                    format!(
+                        "keys \"{other_name}\" for {other} and \"{name}\" for {action} are the same key"
+                    )
+                });
+            }
+        }
+        Ok(keymap)
+    }
+
+    /// Resolve a key press to an action. Printable characters are only treated as
+    /// bindings while the search filter is empty, so they can still be typed.
+    pub fn action_for(&self, key: &Key, filter_empty: bool) -> Option<Action> {
+        if matches!(key, Key::Char(c) if !c.is_control()) && !filter_empty {
+            return None;
+        }
+        self.bindings
+            .iter()
+            .find(|(_, k, _)| k == key)
+            .map(|(action, _, _)| *action)
+    }
+
+    /// Short help line listing the keys for the given actions.
+    pub fn hint(&self, actions: &[Action]) -> String {
+        actions
+            .iter()
+            .copied()
+            .filter_map(|action| {
+                self.bindings
+                    .iter()
+                    .find(|(a, _, _)| *a == action)
+                    .map(|(_, _, name)| format!("{name} {}", action.hint_label()))
+            })
+            .collect::<Vec<_>>()
+            .join(" \u{b7} ")
+    }
+}
+
+/// Parse a key name like `esc`, `enter`, `ctrl-o`, or a single character.
+pub fn parse_key(name: &str) -> Result<Key, String> {
+    let lower = name.trim().to_lowercase();
+    let key = match lower.as_str() {
+        "esc" | "escape" => Key::Escape,
+        "enter" | "return" => Key::Enter,
+        "tab" => Key::Tab,
+        "shift-tab" | "backtab" => Key::BackTab,
+        "backspace" => Key::Backspace,
+        "del" | "delete" => Key::Del,
+        "up" => Key::ArrowUp,
+        "down" => Key::ArrowDown,
+        "left" => Key::ArrowLeft,
+        "right" => Key::ArrowRight,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "pageup" => Key::PageUp,
+        "pagedown" => Key::PageDown,
+        "space" => Key::Char(' '),
+        other => {
+            if let Some(rest) = other.strip_prefix("ctrl-") {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if c.is_ascii_lowercase() => ctrl_key(c),
+                    _ => return Err(format!("invalid key: {name}")),
+                }
+            } else {
+                // Keep the original case for single characters (e.g. "G").
+                let mut chars = name.trim().chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Key::Char(c),
+                    _ => return Err(format!("invalid key: {name}")),
+                }
+            }
+        }
+    };
+    Ok(key)
+}
+
+/// What a key the picker handles itself is used for: `ctrl-h` arrives as
+/// Backspace, which edits the search filter, and `ctrl-c` quits.
+fn reserved(key: &Key) -> Option<&'static str> {
+    match key {
+        Key::Backspace => Some("backspace, which edits the filter"),
+        Key::CtrlC => Some("ctrl-c, which quits"),
+        _ => None,
+    }
+}
+
+/// Map `ctrl-<letter>` to the key the terminal reports for it.
+pub(crate) fn ctrl_key(c: char) -> Key {
+    match c {
+        'a' => Key::Home,
+        'e' => Key::End,
+        'h' => Key::Backspace,
+        'i' => Key::Tab,
+        'm' | 'j' => Key::Enter,
+        'c' => Key::CtrlC,
+        other => Key::Char(((other as u8) & 0x1f) as char),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_named_keys() {
+        assert_eq!(parse_key("esc"), Ok(Key::Escape));
+        assert_eq!(parse_key("Enter"), Ok(Key::Enter));
+        assert_eq!(parse_key("shift-tab"), Ok(Key::BackTab));
+        assert_eq!(parse_key("space"), Ok(Key::Char(' ')));
+    }
+
+    #[test]
+    fn parse_ctrl_keys() {
+        assert_eq!(parse_key("ctrl-o"), Ok(Key::Char('\x0f')));
+        assert_eq!(parse_key("ctrl-a"), Ok(Key::Home));
+        assert!(parse_key("ctrl-").is_err());
+        assert!(parse_key("ctrl-ab").is_err());
+    }
+
+    #[test]
+    fn parse_single_char_keeps_case() {
+        assert_eq!(parse_key("q"), Ok(Key::Char('q')));
+        assert_eq!(parse_key("G"), Ok(Key::Char('G')));
+        assert!(parse_key("nope").is_err());
+    }
+
+    #[test]
+    fn default_keymap_binds_escape_to_back() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.action_for(&Key::Escape, true), Some(Action::Back));
+        assert_eq!(keymap.action_for(&Key::Enter, false), Some(Action::Select));
+    }
+
+    #[test]
+    fn config_replaces_action_keys() {
+        let mut keys = HashMap::new();
+        keys.insert("back".to_string(), KeyList::One("q".to_string()));
+        keys.insert(
+            "down".to_string(),
+            KeyList::Many(vec!["j".to_string(), "down".to_string()]),
+        );
+        let keymap = Keymap::from_config(&keys).expect("valid keymap");
+        assert_eq!(keymap.action_for(&Key::Char('q'), true), Some(Action::Back));
+        assert_eq!(keymap.action_for(&Key::Escape, true), None);
+        assert_eq!(keymap.action_for(&Key::Char('j'), true), Some(Action::Down));
+        assert_eq!(keymap.action_for(&Key::Tab, true), None);
+    }
+
+    #[test]
+    fn printable_bindings_ignored_while_filtering() {
+        let mut keys = HashMap::new();
+        keys.insert("back".to_string(), KeyList::One("q".to_string()));
+        let keymap = Keymap::from_config(&keys).expect("valid keymap");
+        assert_eq!(keymap.action_for(&Key::Char('q'), false), None);
+    }
+
+    #[test]
+    fn unknown_action_errors() {
+        let mut keys = HashMap::new();
+        keys.insert("explode".to_string(), KeyList::One("x".to_string()));
+        assert!(Keymap::from_config(&keys).is_err());
+    }
+
+    #[test]
+    fn keys_bound_twice_are_rejected() {
+        let mut keys = HashMap::new();
+        keys.insert("copy".to_string(), KeyList::One("ctrl-t".to_string()));
+        assert_eq!(
+            Keymap::from_config(&keys).expect_err("conflict"),
+            "key \"ctrl-t\" is bound to both show_all and copy"
+        );
+
+        let mut keys = HashMap::new();
+        keys.insert("copy_hash".to_string(), KeyList::One("ctrl-i".to_string()));
+        assert_eq!(
+            Keymap::from_config(&keys).expect_err("conflict"),
+            "keys \"tab\" for down and \"ctrl-i\" for copy_hash are the same key"
+        );
+
+        keys.insert("down".to_string(), KeyList::One("j".to_string()));
+        assert!(Keymap::from_config(&keys).is_ok());
+    }
+
+    #[test]
+    fn reserved_keys_are_rejected() {
+        for (key, reason) in [
+            ("ctrl-h", "backspace"),
+            ("backspace", "backspace"),
+            ("ctrl-c", "ctrl-c"),
+        ] {
+            let mut keys = HashMap::new();
+            keys.insert("open".to_string(), KeyList::One(key.to_string()));
+            let e = Keymap::from_config(&keys).expect_err("reserved");
+            assert!(
+                e.starts_with(&format!("key \"{key}\" for open is {reason}")),
+                "{e}"
+            );
+        }
+    }
+
+    #[test]
+    fn hint_lists_configured_keys() {
+        let hint = Keymap::default().hint(&[Action::Back, Action::Copy]);
+        assert_eq!(hint, "esc back \u{b7} ctrl-y copy");
+    }
+
+    #[test]
+    fn toggle_defaults_to_ctrl_x() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.action_for(&Key::Char('\x18'), false),
+            Some(Action::Toggle)
+        );
+    }
+}
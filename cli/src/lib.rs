@@ -0,0 +1,1721 @@
+mod actions;
+mod audit;
+mod cache;
+mod changelog;
+mod cli;
+mod clipboard;
+mod commit_cache;
+mod config;
+mod csv;
+mod curl;
+mod email;
+mod errors;
+mod forge;
+mod interactive;
+mod keys;
+mod onboarding;
+mod output;
+mod paths;
+mod picker;
+mod plugins;
+mod schema;
+mod search;
+mod slack;
+mod template;
+mod timesheet;
+mod tui;
+
+use std::collections::HashMap;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use clap::Parser;
+use devcap_core::{
+    activity, authors,
+    bucket::{self, GroupBy},
+    budget, bundle, category, color, conventional, dco, discovery, export, git, groups, health,
+    heatmap, model, offline, pairing,
+    period::{Period, TimeRange},
+    progress::ProgressEvent,
+    sort, stats, tags, totals, unpushed, wip,
+    zone::{Time, Zone},
+};
+use errors::{coded, ErrorCode};
+use indicatif::{ProgressBar, ProgressStyle};
+use rayon::prelude::*;
+
+const FETCH_CONCURRENCY: usize = 8;
+const FETCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Entry point shared by the `devcap` binary and its `wg` alias.
+pub fn main() -> Result<()> {
+    let cli = match cli::Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) if e.use_stderr() && wants_json(std::env::args().skip(1)) => {
+            let rendered = e.to_string();
+            let first = rendered.lines().next().unwrap_or_default();
+            let message = first.strip_prefix("error: ").unwrap_or(first);
+            println!(
+                "{}",
+                errors::render_json(ErrorCode::InvalidArgument, message)
+            );
+            std::process::exit(2);
+        }
+        Err(e) => e.exit(),
+    };
+    if cli.schema {
+        print!("{}", schema::REPORT);
+        return Ok(());
+    }
+    let cfg = config::load(cli.path.as_deref());
+    let json_errors = cli.json || cli.format == Some(cli::OutputFormat::Json);
+
+    match &cli.command {
+        Some(cli::Command::Runs { limit }) => {
+            output::set_color_enabled(color::should_color(
+                cli.no_color || cli.json,
+                cfg.color,
+                std::io::stdout().is_terminal(),
+            ));
+            return show_runs(*limit, cli.json);
+        }
+        Some(cli::Command::Plugins) => {
+            show_plugins();
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let audit_enabled = cfg.audit_log.unwrap_or(false);
+    let started = Instant::now();
+    let mut record = audit::RunRecord::start(std::env::args().skip(1).collect());
+    let result = run(cli, cfg, &mut record);
+    if audit_enabled {
+        record.finish(
+            started.elapsed(),
+            result.as_ref().err().map(|e| format!("{e:#}")),
+        );
+        if let Err(e) = audit::append(&record) {
+            eprintln!("Warning: could not write audit log: {e:#}");
+        }
+    }
+    match result {
+        Err(e) if json_errors => {
+            println!(
+                "{}",
+                errors::render_json(errors::code_of(&e), &format!("{e:#}"))
+            );
+            std::process::exit(1);
+        }
+        result => result,
+    }
+}
+
+/// Whether `args` ask for JSON output, with `--json`, `--format json` or
+/// `--format=json`: read before parsing, so a parse error can be reported
+/// as JSON too.
+fn wants_json(args: impl Iterator<Item = String>) -> bool {
+    let args: Vec<String> = args.take_while(|a| a != "--").collect();
+    args.iter().enumerate().any(|(i, arg)| {
+        arg == "--json"
+            || arg == "--format=json"
+            || (arg == "--format" && args.get(i + 1).is_some_and(|v| v == "json"))
+    })
+}
+
+fn show_plugins() {
+    let names = plugins::list();
+    if names.is_empty() {
+        match plugins::plugins_dir() {
+            Some(dir) => eprintln!("No plugins installed in {}", dir.display()),
+            None => eprintln!("No plugin directory available (HOME not set)"),
+        }
+        return;
+    }
+    for name in names {
+        println!("{name}");
+    }
+}
+
+fn show_runs(limit: usize, json: bool) -> Result<()> {
+    let records = audit::read_all()?;
+    let recent = &records[records.len().saturating_sub(limit)..];
+    if json {
+        println!("{}", audit::render_json(recent));
+    } else {
+        audit::render_terminal(recent);
+    }
+    Ok(())
+}
+
+/// The zone days are counted in: `--timezone`, else `timezone` from the
+/// config, else the machine's.
+fn resolve_zone(cli: &cli::Cli, cfg: &config::DevcapConfig) -> Result<Zone> {
+    match cli.timezone.as_deref().or(cfg.timezone.as_deref()) {
+        Some(zone) => zone
+            .parse()
+            .map_err(|e: String| coded(ErrorCode::InvalidArgument, e)),
+        None => Ok(Zone::Local),
+    }
+}
+
+/// What a run resolved from the flags and the config, shared by the report
+/// modes and the subcommands.
+struct Report<'a> {
+    cli: &'a cli::Cli,
+    cfg: &'a config::DevcapConfig,
+    range: TimeRange,
+    tui: bool,
+    format: cli::OutputFormat,
+    template: Option<template::Template>,
+    /// Anything but the terminal report: JSON, CSV, a changelog, a plugin
+    /// or a template.
+    machine: bool,
+    changelog: bool,
+    csv: bool,
+    csv_delimiter: char,
+    /// Command running `--format` plugins, see [`plugins`].
+    plugin_command: String,
+    show_origin: bool,
+    show_wip: bool,
+    show_tags: bool,
+    show_footer: bool,
+    show_summary: bool,
+    show_heatmap: bool,
+    /// `--group-by`, else `group_by` of the config.
+    group_by: Option<GroupBy>,
+    max_lines: Option<usize>,
+    max_commits: Option<usize>,
+    sort_spec: cli::SortSpec,
+    author_label: Option<String>,
+    spinner: Option<ProgressBar>,
+}
+
+impl Report<'_> {
+    /// Print a JSON document, through the plugin of `--format` if one is
+    /// chosen.
+    fn emit(&self, json: String) -> Result<()> {
+        match &self.format {
+            cli::OutputFormat::Plugin(name) => {
+                print!("{}", plugins::render(name, &json, &self.plugin_command)?)
+            }
+            _ => println!("{json}"),
+        }
+        Ok(())
+    }
+
+    /// Print a warning above the spinner, or on stderr without one.
+    fn note(&self, message: String) {
+        match &self.spinner {
+            Some(sp) if !sp.is_hidden() => sp.println(message),
+            _ => eprintln!("{message}"),
+        }
+    }
+
+    fn status(&self, message: &'static str) {
+        if let Some(sp) = &self.spinner {
+            sp.set_message(message);
+        }
+    }
+
+    fn limit<'p>(&self, projects: impl IntoIterator<Item = &'p mut model::ProjectLog>) {
+        limit_report(projects, self.max_commits, self.max_lines);
+    }
+
+    fn template_context(
+        &self,
+        projects: &[model::ProjectLog],
+        wip: &[wip::WorkInProgress],
+        tags: &[tags::RepoTags],
+    ) -> serde_json::Value {
+        template::context(
+            projects,
+            self.cli.depth,
+            self.range.since,
+            self.range.until,
+            self.author_label.as_deref(),
+            wip,
+            tags,
+        )
+    }
+
+    fn interactive_opts(&self) -> interactive::Options {
+        let cfg = self.cfg;
+        let keymap = match cfg.keys.as_ref().map(keys::Keymap::from_config) {
+            Some(Ok(keymap)) => keymap,
+            Some(Err(e)) => {
+                eprintln!("Warning: invalid [keys] in ~/.devcap.toml: {e}");
+                keys::Keymap::default()
+            }
+            None => keys::Keymap::default(),
+        };
+        interactive::Options {
+            show_origin: self.show_origin,
+            keymap,
+            editor: cfg.editor.clone(),
+            file_manager: cfg.file_manager.clone(),
+            custom_actions: cfg
+                .actions
+                .as_ref()
+                .map(actions::from_config)
+                .unwrap_or_default(),
+            zone: self.range.zone,
+        }
+    }
+}
+
+/// Reads the found repositories and bundles: once for the report, and again
+/// for the previous period of `stats` and each refresh of `--watch` and the
+/// interactive browser.
+struct Scanner<'a> {
+    report: &'a Report<'a>,
+    repos: &'a [PathBuf],
+    bundles: &'a [PathBuf],
+    log_opts: git::LogOptions<'a>,
+    aliases: &'a HashMap<String, Vec<String>>,
+    author_from_cli: bool,
+    author_fixed: bool,
+    cache: commit_cache::CommitCache,
+    pr_titles: bool,
+    enrich_prs: bool,
+    forge_policy: cache::CachePolicy,
+}
+
+impl Scanner<'_> {
+    fn scan(&self, range: &TimeRange) -> Vec<model::ProjectLog> {
+        let cfg = self.report.cfg;
+        let progress = |event: ProgressEvent| self.progress(event);
+        // A bar of the repositories done while scanning, the spinner again after.
+        let bar = self.report.spinner.as_ref().filter(|sp| !sp.is_finished());
+        if let (Some(sp), Some(style)) = (bar, scan_bar_style()) {
+            sp.set_message("");
+            sp.set_length((self.repos.len() + self.bundles.len()) as u64);
+            sp.set_position(0);
+            sp.set_style(style);
+        }
+        let projects = self
+            .repos
+            .par_iter()
+            .filter_map(|repo| {
+                let repo_author = cfg
+                    .repo_config(repo)
+                    .and_then(|r| r.author.clone())
+                    .filter(|_| !self.author_from_cli)
+                    .or_else(|| {
+                        (!self.author_fixed)
+                            .then(|| git::repo_author(repo))
+                            .flatten()
+                            .map(|a| git::literal_pattern(&a))
+                    })
+                    .map(|author| authors::expand_aliases(&[author], self.aliases));
+                let opts = git::LogOptions {
+                    authors: repo_author.as_deref().unwrap_or(self.log_opts.authors),
+                    ..self.log_opts
+                };
+                self.cache.project_log(repo, range, &opts, &progress, || {
+                    git::collect_project_log_with_progress(repo, range, &opts, &progress)
+                })
+            })
+            .chain(self.bundles.par_iter().filter_map(|file| {
+                let project = bundle::collect_bundle_log(file, range, &self.log_opts);
+                progress(ProgressEvent::RepoScanned {
+                    path: file.clone(),
+                    commits: project.as_ref().map_or(0, model::ProjectLog::total_commits),
+                });
+                project
+            }))
+            .collect();
+        if let (Some(sp), Some(style)) = (bar, spinner_style()) {
+            sp.set_style(style);
+            sp.set_message("Scanning repositories...");
+        }
+        projects
+    }
+
+    fn progress(&self, event: ProgressEvent) {
+        let path = match &event {
+            ProgressEvent::RepoFailed { path, error } => {
+                self.report.note(format!(
+                    "Warning: could not read {}: {error}",
+                    path.display()
+                ));
+                path
+            }
+            ProgressEvent::RepoScanned { path, .. } => path,
+            ProgressEvent::RepoDiscovered { .. } => return,
+        };
+        if let Some(sp) = self.report.spinner.as_ref().filter(|sp| !sp.is_finished()) {
+            sp.inc(1);
+            sp.set_message(
+                path.file_name()
+                    .unwrap_or(path.as_os_str())
+                    .to_string_lossy()
+                    .to_string(),
+            );
+        }
+    }
+
+    /// Display names, working-copy health and PR titles for a fresh scan.
+    fn annotate(&self, projects: &mut [model::ProjectLog]) {
+        let cfg = self.report.cfg;
+        cfg.apply_project_labels(projects);
+        if let Some(names) = &cfg.author_names {
+            authors::apply_display_names(projects, names);
+        }
+        // The "Releases" section lists these along with every other tag.
+        if self.report.show_tags {
+            projects.iter_mut().for_each(|p| p.releases = None);
+        }
+        if self.report.cli.health || cfg.health.unwrap_or(false) {
+            projects
+                .par_iter_mut()
+                .for_each(|p| p.health = health::check(Path::new(&p.path)));
+        }
+        if self.pr_titles {
+            forge::annotate_pull_requests(
+                projects,
+                self.forge_policy,
+                self.enrich_prs,
+                cfg.gitlab_tokens.as_ref().unwrap_or(&HashMap::new()),
+            );
+        }
+    }
+
+    /// An annotated and sorted scan of `range`.
+    fn rescan(&self, range: &TimeRange) -> Vec<model::ProjectLog> {
+        let mut projects = self.scan(range);
+        self.annotate(&mut projects);
+        sort::sort_projects(&mut projects, self.report.sort_spec);
+        projects
+    }
+
+    /// Uncommitted work counts in every repository, also those without commits.
+    fn wip(&self, range: &TimeRange) -> Vec<wip::WorkInProgress> {
+        if !self.report.show_wip {
+            return Vec::new();
+        }
+        let mut found: Vec<_> = self
+            .repos
+            .par_iter()
+            .filter_map(|repo| wip::check(repo, range, &self.log_opts.relative))
+            .collect();
+        found.sort_by_key(|w| w.project.to_lowercase());
+        found
+    }
+
+    /// Tags count in every repository, like uncommitted work.
+    fn tags(&self, range: &TimeRange) -> Vec<tags::RepoTags> {
+        if !self.report.show_tags {
+            return Vec::new();
+        }
+        let mut found: Vec<_> = self
+            .repos
+            .par_iter()
+            .filter_map(|repo| tags::check(repo, range, &self.log_opts.relative))
+            .collect();
+        found.sort_by_key(|t| t.project.to_lowercase());
+        found
+    }
+}
+
+fn run(mut cli: cli::Cli, cfg: config::DevcapConfig, record: &mut audit::RunRecord) -> Result<()> {
+    let zone = resolve_zone(&cli, &cfg)?;
+    let tui = cli.tui || (cli.interactive && cfg.tui.unwrap_or(false));
+    cli.interactive |= tui;
+    let cli = &cli;
+    let range = match &cli.command {
+        Some(cli::Command::Day { date }) => {
+            let day = date.unwrap_or_else(|| zone.now().date_naive());
+            TimeRange::from_dates(day, day, zone)
+                .map_err(|e| coded(ErrorCode::InvalidArgument, e))?
+        }
+        _ => resolve_time_range(cli.since, cli.until, cli.period.clone(), zone, &cfg)?,
+    };
+    record.set_range(&range);
+
+    let workspace = cli
+        .workspace
+        .as_deref()
+        .map(|name| cfg.workspace(name))
+        .transpose()
+        .map_err(|e| coded(ErrorCode::InvalidArgument, e.to_string()))?;
+    // A curated `repos` list replaces scanning unless a root is given.
+    let listed = cfg
+        .repos
+        .as_deref()
+        .filter(|repos| !repos.is_empty() && workspace.is_none() && cli.path.is_none());
+    let default_root =
+        workspace.is_none() && cli.path.is_none() && cfg.path.is_none() && listed.is_none();
+    let roots: Vec<PathBuf> = match workspace {
+        Some(ws) => match ws.paths.as_deref() {
+            Some(paths) if !paths.is_empty() => paths.to_vec(),
+            _ => {
+                return Err(coded(
+                    ErrorCode::InvalidArgument,
+                    format!(
+                        "workspace \"{}\" has no paths",
+                        cli.workspace.as_deref().unwrap_or_default()
+                    ),
+                ))
+            }
+        },
+        None => match listed {
+            Some(repos) => repos.to_vec(),
+            None => vec![cli
+                .path
+                .clone()
+                .or(cfg.path.clone())
+                .unwrap_or_else(|| PathBuf::from("."))],
+        },
+    };
+    let workspace_author = workspace.and_then(|ws| ws.author.clone());
+    let author_from_cli = !cli.author.is_empty();
+    let mut authors = if author_from_cli {
+        cli.author.clone()
+    } else {
+        workspace_author
+            .map(|a| vec![a])
+            .or_else(|| cfg.authors.clone().filter(|a| !a.is_empty()))
+            .or_else(|| cfg.author.clone().map(|a| vec![a]))
+            .unwrap_or_default()
+    };
+    let author_fixed = !authors.is_empty();
+    if !author_fixed {
+        authors.extend(git::default_author().map(|a| git::literal_pattern(&a)));
+    }
+    record.path = Some(
+        roots
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+    );
+    record.author = (!authors.is_empty()).then(|| authors.join(", "));
+    let offline = cli.offline || cfg.offline.unwrap_or(false);
+    if offline {
+        if let Some(flag) = [
+            (cli.fetch, "--fetch"),
+            (cli.fetch_shallow, "--fetch-shallow"),
+            (cli.post.is_some(), "--post"),
+        ]
+        .into_iter()
+        .find_map(|(set, flag)| set.then_some(flag))
+        {
+            return Err(coded(
+                ErrorCode::InvalidArgument,
+                format!("{flag} needs network access and cannot be used in offline mode"),
+            ));
+        }
+        offline::enable();
+    }
+    let with_stat = cli.stat || cfg.stat.unwrap_or(false);
+    let time_trailer = cli.time_trailer.clone().or(cfg.time_trailer.clone());
+    if let Some(key) = &time_trailer {
+        git::check_trailer_key(key).map_err(|e| coded(ErrorCode::InvalidArgument, e))?;
+    }
+
+    let format = match (cli.format.clone(), cli.json) {
+        (Some(format), _) => format,
+        (None, true) => cli::OutputFormat::Json,
+        (None, false) => cli::OutputFormat::Text,
+    };
+    let template = cli
+        .template
+        .as_deref()
+        .map(template::load)
+        .transpose()
+        .map_err(|e| coded(ErrorCode::InvalidArgument, e.to_string()))?;
+    let machine = format != cli::OutputFormat::Text || template.is_some();
+    let changelog = format == cli::OutputFormat::Changelog;
+    let csv = format == cli::OutputFormat::Csv;
+    if (changelog || csv) && cli.command.is_some() {
+        return Err(coded(
+            ErrorCode::InvalidArgument,
+            format!(
+                "--format {} only applies to the commit report",
+                if csv { "csv" } else { "changelog" }
+            ),
+        ));
+    }
+    if template.is_some() && cli.command.is_some() {
+        return Err(coded(
+            ErrorCode::InvalidArgument,
+            "--template only applies to the commit report",
+        ));
+    }
+    let csv_delimiter = cli.csv_delimiter.or(cfg.csv_delimiter).unwrap_or(',');
+    csv::check_delimiter(csv_delimiter).map_err(|e| coded(ErrorCode::InvalidArgument, e))?;
+    if cli.watch.is_some() && (cli.command.is_some() || machine) {
+        return Err(coded(
+            ErrorCode::InvalidArgument,
+            "--watch only applies to the terminal commit report",
+        ));
+    }
+    if cli.post.is_some() && cli.command.is_some() {
+        return Err(coded(
+            ErrorCode::InvalidArgument,
+            "--post only applies to the commit report",
+        ));
+    }
+    let post_to = match cli.post {
+        Some(cli::PostTarget::Slack) => Some(
+            slack::destination(cfg.slack.as_ref())
+                .map_err(|e| coded(ErrorCode::InvalidArgument, e.to_string()))?,
+        ),
+        _ => None,
+    };
+    let mail_with = match cli.post {
+        Some(cli::PostTarget::Email) => Some(
+            email::mailer(cfg.email.as_ref())
+                .map_err(|e| coded(ErrorCode::InvalidArgument, e.to_string()))?,
+        ),
+        _ => None,
+    };
+    let timesheet_backend = match cli.command {
+        Some(cli::Command::Timesheet { dry_run: false }) => Some(
+            timesheet::backend(cfg.timesheet.as_ref())
+                .map_err(|e| coded(ErrorCode::InvalidArgument, e.to_string()))?,
+        ),
+        _ => None,
+    };
+    if let cli::OutputFormat::Plugin(name) = &format {
+        plugins::module_path(name)?;
+    }
+
+    // The selector of --select draws on stderr; stdout is usually captured.
+    let is_terminal = if cli.select.is_some() {
+        std::io::stderr().is_terminal()
+    } else {
+        std::io::stdout().is_terminal()
+    };
+    let use_color = color::should_color(cli.no_color || machine, cfg.color, is_terminal);
+    output::set_color_enabled(use_color);
+    if let Some(badges) = cfg.origin_badges.clone() {
+        output::set_origin_badges(badges);
+    }
+    output::set_show_authors(authors.len() > 1 && !cli.by_author);
+    output::set_by_day(cli.by_day || cfg.by_day.unwrap_or(false));
+
+    if !git::is_available() {
+        return Err(coded(
+            ErrorCode::GitNotFound,
+            "git could not be run; is it installed and on $PATH?",
+        ));
+    }
+    let exclude: Vec<String> = cli
+        .exclude
+        .iter()
+        .chain(cfg.exclude.iter().flatten())
+        .chain(
+            workspace
+                .and_then(|ws| ws.exclude.as_ref())
+                .into_iter()
+                .flatten(),
+        )
+        .cloned()
+        .collect();
+    discovery::check_exclude(&exclude).map_err(|e| coded(ErrorCode::InvalidArgument, e))?;
+    if let Some(file) = cli.bundle.iter().find(|f| !bundle::is_bundle(f)) {
+        return Err(coded(
+            ErrorCode::InvalidArgument,
+            format!("not a git bundle: {}", file.display()),
+        ));
+    }
+    // Listed repositories on a drive that is not mounted are only warned about.
+    if let Some(root) = roots.iter().find(|r| listed.is_none() && !r.exists()) {
+        return Err(coded(
+            ErrorCode::PathNotFound,
+            format!("path does not exist: {}", root.display()),
+        ));
+    }
+
+    output::set_quiet(cli.quiet);
+    let spinner = if !(machine || cli.quiet) {
+        let sp = ProgressBar::new_spinner();
+        if let Some(style) = spinner_style() {
+            sp.set_style(style);
+        }
+        sp.set_message("Scanning repositories...");
+        sp.enable_steady_tick(std::time::Duration::from_millis(80));
+        Some(sp)
+    } else {
+        None
+    };
+    let report_only = cli.command.is_none()
+        && cli.select.is_none()
+        && !(cli.interactive || cli.by_author || changelog || csv);
+    // --group-by cannot be combined with --wip and --tags (see `cli::Cli`);
+    // between the command line and the config, the command line wins.
+    let show_wip = (cli.wip || (cfg.wip.unwrap_or(false) && cli.group_by.is_none())) && report_only;
+    let show_tags =
+        (cli.tags || (cfg.tags.unwrap_or(false) && cli.group_by.is_none())) && report_only;
+    let group_by = cli.group_by.or_else(|| {
+        let by = config_group_by(cfg.group_by.as_deref())?;
+        if by != GroupBy::Project && (show_wip || show_tags) {
+            eprintln!("Warning: ignoring group_by in ~/.devcap.toml: it cannot be combined with wip or tags");
+            return None;
+        }
+        Some(by)
+    });
+    let report = Report {
+        cli,
+        cfg: &cfg,
+        range,
+        tui,
+        template,
+        machine,
+        changelog,
+        csv,
+        csv_delimiter,
+        plugin_command: cfg
+            .plugin_command
+            .clone()
+            .unwrap_or_else(|| plugins::DEFAULT_COMMAND.to_string()),
+        show_origin: cli.show_origin || cfg.show_origin.unwrap_or(false),
+        show_wip,
+        show_tags,
+        group_by,
+        show_footer: cli.footer || cfg.footer.unwrap_or(false),
+        show_summary: cli.summary || cfg.summary.unwrap_or(false),
+        show_heatmap: cli.heatmap || cfg.heatmap.unwrap_or(false),
+        max_lines: cli
+            .max_lines
+            .or(cfg.max_lines)
+            .filter(|_| !machine && !cli.interactive && cli.select.is_none()),
+        max_commits: cli.max_commits.or(cfg.max_commits),
+        sort_spec: cli
+            .sort
+            .or_else(|| {
+                cfg.sort
+                    .as_deref()
+                    .and_then(|s| s.parse::<cli::SortSpec>().ok())
+            })
+            .unwrap_or_default(),
+        author_label: record.author.clone(),
+        format,
+        spinner,
+    };
+
+    let discovery_opts = discovery::DiscoveryOptions {
+        skip_network: cli.skip_network || cfg.skip_network.unwrap_or(false) || offline,
+        exclude,
+        max_depth: cli.max_depth.or(cfg.max_depth),
+    };
+    let (repos, bundles) = find_repos(
+        &report,
+        &roots,
+        listed.is_some(),
+        default_root,
+        &discovery_opts,
+        record,
+    )?;
+    if repos.is_empty() && bundles.is_empty() {
+        if let Some(sp) = &report.spinner {
+            sp.finish_and_clear();
+        }
+        if let Some(template) = &report.template {
+            print!(
+                "{}",
+                template.render(&report.template_context(&[], &[], &[]))?
+            );
+        } else if csv {
+            print!("{}", csv::render(&[], csv_delimiter));
+        } else if machine && !changelog {
+            report.emit("[]".to_string())?;
+        } else {
+            output::info(format!(
+                "No git repositories found in: {}",
+                record.path.as_deref().unwrap_or_default()
+            ));
+        }
+        return Ok(());
+    }
+    update_clones(&report, &repos);
+
+    // `--invert-grep` turns the wanted patterns into exclusions.
+    let (grep, inverted) = if cli.invert_grep {
+        (Vec::new(), cli.grep.as_slice())
+    } else {
+        (cli.grep.clone(), &[][..])
+    };
+    let exclude_grep: Vec<String> = cli
+        .exclude_grep
+        .iter()
+        .chain(cfg.exclude_grep.iter().flatten())
+        .chain(inverted)
+        .cloned()
+        .collect();
+    if let Some(repo) = repos.first() {
+        git::check_grep_patterns(repo, &grep)
+            .and_then(|()| git::check_grep_patterns(repo, &exclude_grep))
+            .map_err(|e| coded(ErrorCode::InvalidArgument, e))?;
+    }
+    let aliases = cfg.author_aliases.clone().unwrap_or_default();
+    let author_filters = authors::expand_aliases(&authors, &aliases);
+    let enrich = if cli.enrich.is_empty() {
+        config_enrichments(cfg.enrich.as_deref().unwrap_or_default())
+    } else {
+        cli.enrich.clone()
+    };
+    let enrich_prs = enrich.contains(&cli::Enrichment::Prs);
+    let scanner = Scanner {
+        report: &report,
+        repos: &repos,
+        bundles: &bundles,
+        log_opts: git::LogOptions {
+            authors: &author_filters,
+            with_stat,
+            time_trailer: time_trailer.as_deref(),
+            grep: &grep,
+            exclude_grep: &exclude_grep,
+            include_merges: cli.include_merges || cfg.include_merges.unwrap_or(false),
+            remote_branches: cli.remote_branches || cfg.remote_branches.unwrap_or(false),
+            dedupe: cli.dedupe || cfg.dedupe.unwrap_or(false),
+            relative: cfg
+                .relative_time
+                .as_ref()
+                .map(config::RelativeTimeConfig::format)
+                .unwrap_or_default(),
+        },
+        aliases: &aliases,
+        author_from_cli,
+        author_fixed,
+        cache: commit_cache::CommitCache::open(cli.no_cache),
+        pr_titles: cli.pr_titles || cfg.pr_titles.unwrap_or(false) || enrich_prs,
+        enrich_prs,
+        forge_policy: cache_policy(cli.refresh, cfg.cache_ttl.as_deref()),
+    };
+
+    let mut projects = scanner.scan(&report.range);
+    if scanner.pr_titles {
+        report.status("Looking up pull requests...");
+    }
+    scanner.annotate(&mut projects);
+    record.set_results(&projects);
+    let wip = scanner.wip(&report.range);
+    let tags = scanner.tags(&report.range);
+
+    if scanner.pr_titles
+        && matches!(
+            cli.command,
+            Some(cli::Command::Stats {
+                conventional: false,
+                pairing: false
+            })
+        )
+    {
+        report.status("Looking up commit verification...");
+        forge::annotate_verification(
+            &mut projects,
+            scanner.forge_policy,
+            cfg.gitlab_tokens.as_ref().unwrap_or(&HashMap::new()),
+        );
+    }
+
+    sort::sort_projects(&mut projects, report.sort_spec);
+
+    if let Some(sp) = &report.spinner {
+        sp.finish_with_message(format!("\u{2713} {}", output::summary_line(&projects)));
+    }
+
+    match &cli.command {
+        Some(cli::Command::Runs { .. } | cli::Command::Plugins) | None => {}
+        Some(command) => {
+            return run_command(
+                command,
+                &report,
+                &scanner,
+                projects,
+                timesheet_backend.as_deref(),
+            )
+        }
+    }
+    if let Some(target) = cli.select {
+        return select(&report, &projects, target);
+    }
+    post(&report, &projects, post_to.as_ref(), mail_with.as_ref())?;
+    if let Some(interval) = cli.watch {
+        return watch(&report, &scanner, projects, wip, tags, interval);
+    }
+    render_report(&report, &scanner, projects, &wip, &tags)
+}
+
+/// Find the repositories and bundles under `roots`, offering to set up a
+/// config on the first run when there are none. Notes what was skipped.
+fn find_repos(
+    report: &Report,
+    roots: &[PathBuf],
+    listed: bool,
+    default_root: bool,
+    opts: &discovery::DiscoveryOptions,
+    record: &mut audit::RunRecord,
+) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+    let cli = report.cli;
+    let found = AtomicUsize::new(0);
+    let discovered_one = |event: ProgressEvent| {
+        if let (Some(sp), ProgressEvent::RepoDiscovered { .. }) = (&report.spinner, event) {
+            let n = found.fetch_add(1, Ordering::Relaxed) + 1;
+            sp.set_message(format!("Discovering repositories... {n} found"));
+        }
+    };
+    let discover = |roots: &[PathBuf]| {
+        if listed {
+            discovery::from_list(roots, &discovered_one)
+        } else {
+            discovery::discover_all_with_progress(roots, opts, &discovered_one)
+        }
+    };
+    let mut discovered = discover(roots);
+    // First run: nothing configured and nothing found where we looked.
+    let first_run = config::config_path().filter(|path| {
+        !path.exists() && !config::xdg_config_paths().iter().any(|xdg| xdg.exists())
+    });
+    if let (Some(config_path), true) = (
+        first_run,
+        default_root
+            && discovered.repos.is_empty()
+            && discovered.bundles.is_empty()
+            && cli.bundle.is_empty()
+            && !(report.machine || cli.quiet)
+            && std::io::stdin().is_terminal()
+            && std::io::stderr().is_terminal(),
+    ) {
+        let chosen = match &report.spinner {
+            Some(sp) => sp.suspend(|| onboarding::run(&config_path))?,
+            None => onboarding::run(&config_path)?,
+        };
+        if let Some(root) = chosen {
+            record.path = Some(root.display().to_string());
+            discovered = discover(std::slice::from_ref(&root));
+        }
+    }
+    if !cli.quiet {
+        for mount in &discovered.skipped_mounts {
+            report.note(format!("Skipped network mount: {}", mount.display()));
+        }
+    }
+    for path in &discovered.not_found {
+        report.note(format!(
+            "Warning: not a git repository or bundle: {}",
+            path.display()
+        ));
+    }
+    let mut bundles = discovered.bundles;
+    for file in &cli.bundle {
+        if !bundles.contains(file) {
+            bundles.push(file.clone());
+        }
+    }
+    record.repos_scanned = discovered.repos.len() + bundles.len();
+    Ok((discovered.repos, bundles))
+}
+
+/// `--fetch` and `--fetch-shallow`, and a warning for every shallow clone
+/// that misses part of the period.
+fn update_clones(report: &Report, repos: &[PathBuf]) {
+    let cli = report.cli;
+    if cli.fetch {
+        report.status("Fetching remotes...");
+        for (repo, e) in fetch_repos(repos) {
+            let name = repo.file_name().unwrap_or_default().to_string_lossy();
+            report.note(format!("Warning: could not fetch {name}: {e:#}"));
+        }
+        report.status("Scanning repositories...");
+    }
+
+    let shallow: Vec<_> = repos
+        .par_iter()
+        .filter_map(|repo| Some((repo, git::shallow_history_start(repo, &report.range)?)))
+        .collect();
+    if cli.fetch_shallow && !shallow.is_empty() {
+        report.status("Deepening shallow clones...");
+    }
+    for (repo, start) in shallow {
+        let name = repo.file_name().unwrap_or_default().to_string_lossy();
+        if cli.fetch_shallow {
+            match git::deepen_since(repo, report.range.since) {
+                Ok(()) => continue,
+                Err(e) => report.note(format!("Warning: could not deepen {name}: {e:#}")),
+            }
+        }
+        report.note(format!(
+            "Warning: {name} is a shallow clone; commits before {} are missing (use --fetch-shallow)",
+            start.format("%Y-%m-%d %H:%M")
+        ));
+    }
+    if cli.fetch_shallow {
+        report.status("Scanning repositories...");
+    }
+}
+
+/// The subcommands, which report on the scanned commits in their own way.
+fn run_command(
+    command: &cli::Command,
+    report: &Report,
+    scanner: &Scanner,
+    mut projects: Vec<model::ProjectLog>,
+    timesheet_backend: Option<&dyn timesheet::Backend>,
+) -> Result<()> {
+    let cli = report.cli;
+    match command {
+        cli::Command::Search { term } => {
+            if cli.interactive {
+                interactive::run_search(&projects, &report.interactive_opts(), term)?;
+            } else {
+                let hits = search::search(&projects, term);
+                if report.machine {
+                    report.emit(search::render_json(&hits))?;
+                } else {
+                    search::render_terminal(&hits);
+                }
+            }
+        }
+        cli::Command::Day { .. } => {
+            let day = report.range.since.date_naive();
+            let spans = activity::daily_spans(&projects);
+            let span = spans.first();
+            report.limit(&mut projects);
+            if report.machine {
+                report.emit(output::render_day_json(day, span, &projects, cli.depth))?;
+            } else {
+                println!();
+                output::render_day(day, span, &projects, cli.depth, report.show_origin);
+            }
+            if cli.copy {
+                let header = match span {
+                    Some(s) => format!("{}  {}\n", day.format("%a %Y-%m-%d"), output::span_line(s)),
+                    None => format!("{}\n", day.format("%a %Y-%m-%d")),
+                };
+                let body = clipboard::render_plain(&projects, cli.depth, report.show_origin);
+                copy_report(&format!("{header}{body}"));
+            }
+        }
+        cli::Command::Stats { pairing: true, .. } => {
+            let stats = pairing::pairing(&projects);
+            if report.machine {
+                report.emit(output::render_pairing_json(&stats))?;
+            } else {
+                println!();
+                output::render_pairing(&stats);
+            }
+        }
+        cli::Command::Stats {
+            conventional: true, ..
+        } => {
+            let stats = conventional::compliance(&projects);
+            if report.machine {
+                report.emit(output::render_compliance_json(&stats))?;
+            } else {
+                println!();
+                output::render_compliance(&stats);
+            }
+        }
+        cli::Command::Stats { .. } => period_stats(report, scanner, &projects)?,
+        cli::Command::Dco { all } => {
+            let required = report.cfg.dco_repos.as_deref().unwrap_or_default();
+            if !*all && required.is_empty() {
+                anyhow::bail!(
+                    "no repositories require DCO: list them in `dco_repos` in ~/.devcap.toml or pass --all"
+                );
+            }
+            let statuses: Vec<_> = projects
+                .iter()
+                .filter(|p| *all || required.iter().any(|e| config::matches_repo(e, p)))
+                .map(dco::check)
+                .collect();
+            if report.machine {
+                report.emit(output::render_dco_json(&statuses))?;
+            } else {
+                println!();
+                output::render_dco(&statuses);
+            }
+        }
+        cli::Command::Unpushed => {
+            let statuses = unpushed::collect(&projects);
+            if report.machine {
+                report.emit(output::render_unpushed_json(&statuses))?;
+            } else {
+                println!();
+                output::render_unpushed(&statuses);
+            }
+        }
+        cli::Command::Export { kind, output } => export_commits(report, &projects, kind, output)?,
+        cli::Command::Timesheet { .. } => book_timesheet(report, &projects, timesheet_backend)?,
+        // Answered in `main` before anything is scanned.
+        cli::Command::Runs { .. } | cli::Command::Plugins => {}
+    }
+    Ok(())
+}
+
+/// `devcap stats`: the period's numbers with the trend against the period
+/// before.
+fn period_stats(report: &Report, scanner: &Scanner, projects: &[model::ProjectLog]) -> Result<()> {
+    let (cli, cfg) = (report.cli, report.cfg);
+    let calendar = cfg
+        .holidays
+        .as_ref()
+        .map(config::HolidayConfig::calendar)
+        .unwrap_or_default();
+    let now = report.range.zone.now();
+    let mut stats = stats::compute(projects, &report.range, &calendar, now);
+    report.status("Scanning previous period...");
+    let previous = previous_time_range(
+        cli.since.is_some() || cli.until.is_some(),
+        cli.period.clone(),
+        cfg,
+        &report.range,
+        now,
+    );
+    stats.trend = Some(stats::trend(&stats, &scanner.scan(&previous)));
+    if report.machine {
+        report.emit(output::render_stats_json(&stats))?;
+    } else {
+        println!();
+        output::render_stats(&stats);
+    }
+    Ok(())
+}
+
+/// `devcap export`: patches, bundles or Parquet files per working copy.
+fn export_commits(
+    report: &Report,
+    projects: &[model::ProjectLog],
+    kind: &cli::ExportKind,
+    output: &Path,
+) -> Result<()> {
+    let out_dir = std::path::absolute(output)
+        .with_context(|| format!("invalid output directory: {}", output.display()))?;
+    let mut written = Vec::new();
+    for (project, name) in projects.iter().zip(export::file_names(projects)) {
+        if !Path::new(&project.path).is_dir() {
+            output::info(format!("Skipping {}: not a working copy", project.project));
+            continue;
+        }
+        let exported = match kind {
+            cli::ExportKind::Patches => export::write_patches(project, &out_dir, &name)?,
+            cli::ExportKind::Bundle => {
+                export::write_bundle(project, &report.range, &out_dir, &name)?
+            }
+            #[cfg(feature = "parquet")]
+            cli::ExportKind::Parquet => export::write_parquet(project, &out_dir, &name)?,
+        };
+        if !report.machine {
+            output::render_exported(&exported);
+        }
+        written.push(exported);
+    }
+    if report.machine {
+        report.emit(output::render_exported_json(&written))?;
+    } else if written.is_empty() {
+        output::info("Nothing to export.");
+    }
+    Ok(())
+}
+
+/// `devcap timesheet`: the logged time per mapped project, booked in the
+/// time tracker unless it is a dry run.
+fn book_timesheet(
+    report: &Report,
+    projects: &[model::ProjectLog],
+    backend: Option<&dyn timesheet::Backend>,
+) -> Result<()> {
+    let targets = report
+        .cfg
+        .timesheet
+        .as_ref()
+        .and_then(|t| t.projects.as_ref());
+    let Some(targets) = targets.filter(|t| !t.is_empty()) else {
+        return Err(coded(
+            ErrorCode::InvalidArgument,
+            "no repositories mapped: list them in [timesheet.projects] in ~/.devcap.toml",
+        ));
+    };
+    let (entries, unmapped) = timesheet::entries(projects, targets);
+    if !unmapped.is_empty() {
+        output::info(format!(
+            "Skipping {}: not in [timesheet.projects]",
+            unmapped.join(", ")
+        ));
+    }
+    if let Some(backend) = backend {
+        for (booked, entry) in entries.iter().enumerate() {
+            timesheet::push(backend, entry)
+                .with_context(|| format!("{booked} of {} entries were booked", entries.len()))?;
+        }
+    }
+    if report.machine {
+        report.emit(output::render_timesheet_json(&entries))?;
+    } else {
+        println!();
+        output::render_timesheet(&entries);
+    }
+    let total: model::WorkTime = entries.iter().map(|e| e.time).sum();
+    let noun = if entries.len() == 1 {
+        "entry"
+    } else {
+        "entries"
+    };
+    match backend {
+        _ if entries.is_empty() => {}
+        Some(backend) => output::info(format!(
+            "Booked {} {noun} ({total}) in {}.",
+            entries.len(),
+            backend.name()
+        )),
+        None => output::info(format!(
+            "Dry run: {} {noun} ({total}) not booked.",
+            entries.len()
+        )),
+    }
+    Ok(())
+}
+
+/// `--select`: print the path, name or hash picked with the fuzzy selector.
+fn select(
+    report: &Report,
+    projects: &[model::ProjectLog],
+    target: cli::SelectTarget,
+) -> Result<()> {
+    if projects.is_empty() {
+        anyhow::bail!("No commits found for the given period.");
+    }
+    match interactive::select(projects, &report.interactive_opts(), target)? {
+        Some(value) => {
+            println!("{value}");
+            Ok(())
+        }
+        None => anyhow::bail!("Nothing selected."),
+    }
+}
+
+/// `--post`: send the report to Slack or by email.
+fn post(
+    report: &Report,
+    projects: &[model::ProjectLog],
+    slack_to: Option<&slack::Destination>,
+    mailer: Option<&email::Mailer>,
+) -> Result<()> {
+    let depth = report.cli.depth;
+    if let Some(destination) = slack_to {
+        slack::post(
+            destination,
+            &slack::message(projects, depth, &slack::title(&report.range)),
+        )?;
+        output::info("Posted to Slack.");
+    }
+    if let Some(mailer) = mailer {
+        email::send(
+            mailer,
+            &email::message(
+                mailer,
+                &slack::title(&report.range),
+                projects,
+                depth,
+                report.show_origin,
+                report.range.zone.now(),
+            ),
+        )?;
+        output::info(format!("Sent the report to {}.", mailer.to.join(", ")));
+    }
+    Ok(())
+}
+
+/// `--watch`: redraw the terminal report every `interval`, rescanning the
+/// period as it moves along.
+fn watch(
+    report: &Report,
+    scanner: &Scanner,
+    mut projects: Vec<model::ProjectLog>,
+    mut wip: Vec<wip::WorkInProgress>,
+    mut tags: Vec<tags::RepoTags>,
+    interval: cli::WatchInterval,
+) -> Result<()> {
+    let (cli, cfg) = (report.cli, report.cfg);
+    loop {
+        let footer = report_footer(&projects, report.show_summary, report.show_footer);
+        let heat = report.show_heatmap.then(|| heatmap::compute(&projects));
+        report.limit(&mut projects);
+        // Clear the screen and draw from the top.
+        print!("\x1b[H\x1b[2J");
+        output::render_watch_header(interval, report.range.zone.now());
+        println!();
+        if groups::has_groups(&projects) {
+            let groups = groups::group(std::mem::take(&mut projects));
+            output::render_by_group(&groups, cli.depth, report.show_origin);
+        } else {
+            output::render_terminal(&projects, cli.depth, report.show_origin);
+        }
+        output::render_wip(&wip);
+        output::render_tags(&tags);
+        output::render_heatmap(heat.as_ref());
+        output::render_footer(footer.as_deref());
+        std::io::stdout().flush()?;
+        std::thread::sleep(interval.0);
+
+        // Relative periods move along, e.g. `today` after midnight.
+        let range = resolve_time_range(
+            cli.since,
+            cli.until,
+            cli.period.clone(),
+            report.range.zone,
+            cfg,
+        )?;
+        projects = scanner.rescan(&range);
+        wip = scanner.wip(&range);
+        tags = scanner.tags(&range);
+    }
+}
+
+/// The commit report in the chosen layout and format, and `--copy`.
+fn render_report(
+    report: &Report,
+    scanner: &Scanner,
+    mut projects: Vec<model::ProjectLog>,
+    wip: &[wip::WorkInProgress],
+    tags: &[tags::RepoTags],
+) -> Result<()> {
+    let cli = report.cli;
+    let plain = !(cli.interactive || report.changelog || report.csv || report.template.is_some());
+    // Totals cover the whole report, before --max-lines trims it.
+    let footer = report_footer(&projects, report.show_summary, report.show_footer);
+    let footer = footer.as_deref();
+    let heat = report.show_heatmap.then(|| heatmap::compute(&projects));
+    let heat = heat.as_ref();
+
+    if cli.by_author && plain {
+        return by_author(report, projects, footer, heat);
+    }
+    let group_by = report.group_by.filter(|_| plain);
+    if group_by == Some(GroupBy::Category) {
+        return by_category(report, projects, footer, heat);
+    }
+    if let Some(by) = group_by.and_then(GroupBy::time_bucket) {
+        return by_time(report, projects, by, footer, heat);
+    }
+    // Group labels from the `[projects]` section divide the plain report.
+    if groups::has_groups(&projects) && plain && !report.machine {
+        by_group(report, projects, wip, tags, footer, heat);
+        return Ok(());
+    }
+
+    report.limit(&mut projects);
+    if cli.interactive {
+        browse(report, scanner, &projects)?;
+    } else {
+        print_report(report, &projects, wip, tags, footer, heat)?;
+    }
+    if cli.copy {
+        copy_report(&plain_report(report, &projects, wip, tags, footer)?);
+    }
+    Ok(())
+}
+
+/// `--by-author`: one section per author.
+fn by_author(
+    report: &Report,
+    projects: Vec<model::ProjectLog>,
+    footer: Option<&str>,
+    heat: Option<&heatmap::Heatmap>,
+) -> Result<()> {
+    let (depth, show_origin) = (report.cli.depth, report.show_origin);
+    let mut groups = authors::group(projects);
+    report.limit(groups.iter_mut().flat_map(|g| &mut g.projects));
+    if report.machine {
+        report.emit(output::render_by_author_json(&groups, depth))?;
+    } else {
+        if !groups.is_empty() {
+            println!();
+        }
+        output::render_by_author(&groups, depth, show_origin);
+        output::render_heatmap(heat);
+        output::render_footer(footer);
+    }
+    if report.cli.copy {
+        copy_report(&clipboard::with_footer(
+            clipboard::render_plain_by_author(&groups, depth, show_origin),
+            footer,
+        ));
+    }
+    Ok(())
+}
+
+/// `--group-by category`: one section per `[[category]]` rule.
+fn by_category(
+    report: &Report,
+    projects: Vec<model::ProjectLog>,
+    footer: Option<&str>,
+    heat: Option<&heatmap::Heatmap>,
+) -> Result<()> {
+    let (depth, show_origin) = (report.cli.depth, report.show_origin);
+    let rules: Vec<_> = report
+        .cfg
+        .category
+        .iter()
+        .flatten()
+        .map(|c| c.rule())
+        .collect();
+    if rules.is_empty() {
+        output::info("No [[category]] rules configured; all commits are listed as \"other\".");
+    }
+    let classifier =
+        category::Classifier::new(&rules).map_err(|e| coded(ErrorCode::InvalidArgument, e))?;
+    let mut groups = category::group(projects, &classifier);
+    report.limit(groups.iter_mut().flat_map(|g| &mut g.projects));
+    if report.machine {
+        report.emit(output::render_by_category_json(&groups, depth))?;
+    } else {
+        if !groups.is_empty() {
+            println!();
+        }
+        output::render_by_category(&groups, depth, show_origin);
+        output::render_heatmap(heat);
+        output::render_footer(footer);
+    }
+    if report.cli.copy {
+        copy_report(&clipboard::with_footer(
+            clipboard::render_plain_by_category(&groups, depth, show_origin),
+            footer,
+        ));
+    }
+    Ok(())
+}
+
+/// `--group-by day|week|month`: one section per calendar unit.
+fn by_time(
+    report: &Report,
+    projects: Vec<model::ProjectLog>,
+    by: bucket::TimeBucket,
+    footer: Option<&str>,
+    heat: Option<&heatmap::Heatmap>,
+) -> Result<()> {
+    let (depth, show_origin) = (report.cli.depth, report.show_origin);
+    let mut buckets = bucket::group(projects, by);
+    report.limit(buckets.iter_mut().flat_map(|b| &mut b.projects));
+    if report.machine {
+        report.emit(output::render_grouped_json(&buckets, depth))?;
+    } else {
+        if !buckets.is_empty() {
+            println!();
+        }
+        output::render_grouped(&buckets, depth, show_origin);
+        output::render_heatmap(heat);
+        output::render_footer(footer);
+    }
+    if report.cli.copy {
+        copy_report(&clipboard::with_footer(
+            clipboard::render_plain_grouped(&buckets, depth, show_origin),
+            footer,
+        ));
+    }
+    Ok(())
+}
+
+/// The terminal report in sections per `[projects]` group label.
+fn by_group(
+    report: &Report,
+    projects: Vec<model::ProjectLog>,
+    wip: &[wip::WorkInProgress],
+    tags: &[tags::RepoTags],
+    footer: Option<&str>,
+    heat: Option<&heatmap::Heatmap>,
+) {
+    let (depth, show_origin) = (report.cli.depth, report.show_origin);
+    let mut groups = groups::group(projects);
+    report.limit(groups.iter_mut().flat_map(|g| &mut g.projects));
+    println!();
+    output::render_by_group(&groups, depth, show_origin);
+    output::render_wip(wip);
+    output::render_tags(tags);
+    output::render_heatmap(heat);
+    output::render_footer(footer);
+    if report.cli.copy {
+        copy_report(&clipboard::with_footer(
+            clipboard::with_tags(
+                clipboard::with_wip(
+                    clipboard::render_plain_by_group(&groups, depth, show_origin),
+                    wip,
+                ),
+                tags,
+            ),
+            footer,
+        ));
+    }
+}
+
+/// `--interactive` and `--tui`: browse the report, rescanning on request.
+fn browse(report: &Report, scanner: &Scanner, projects: &[model::ProjectLog]) -> Result<()> {
+    let rescan = |range: &TimeRange| {
+        let mut projects = scanner.rescan(range);
+        report.limit(&mut projects);
+        projects
+    };
+    if report.tui {
+        tui::run(
+            projects,
+            &report.interactive_opts().keymap,
+            report.show_origin,
+        )
+    } else {
+        interactive::run(projects, &report.interactive_opts(), &rescan)
+    }
+}
+
+/// The flat commit report as a changelog, template, CSV, JSON or on the
+/// terminal.
+fn print_report(
+    report: &Report,
+    projects: &[model::ProjectLog],
+    wip: &[wip::WorkInProgress],
+    tags: &[tags::RepoTags],
+    footer: Option<&str>,
+    heat: Option<&heatmap::Heatmap>,
+) -> Result<()> {
+    let depth = report.cli.depth;
+    if report.changelog {
+        if projects.is_empty() {
+            output::info("No commits found for the given period.");
+        }
+        print!(
+            "{}",
+            changelog::with_footer(changelog::render(projects), footer)
+        );
+    } else if let Some(template) = &report.template {
+        print!(
+            "{}",
+            template.render(&report.template_context(projects, wip, tags))?
+        );
+    } else if report.csv {
+        print!("{}", csv::render(projects, report.csv_delimiter));
+    } else if report.machine && (report.show_wip || report.show_tags) {
+        report.emit(output::render_json_with_sections(
+            projects,
+            depth,
+            report.show_wip.then_some(wip),
+            report.show_tags.then_some(tags),
+        ))?;
+    } else if report.machine {
+        report.emit(output::render_json(projects, depth))?;
+    } else {
+        if !projects.is_empty() {
+            println!();
+        }
+        output::render_terminal(projects, depth, report.show_origin);
+        output::render_wip(wip);
+        output::render_tags(tags);
+        output::render_heatmap(heat);
+        output::render_footer(footer);
+    }
+    Ok(())
+}
+
+/// What `--copy` puts on the clipboard for the flat commit report.
+fn plain_report(
+    report: &Report,
+    projects: &[model::ProjectLog],
+    wip: &[wip::WorkInProgress],
+    tags: &[tags::RepoTags],
+    footer: Option<&str>,
+) -> Result<String> {
+    Ok(if report.changelog {
+        changelog::with_footer(changelog::render(projects), footer)
+    } else if let Some(template) = &report.template {
+        template.render(&report.template_context(projects, wip, tags))?
+    } else if report.csv {
+        csv::render(projects, report.csv_delimiter)
+    } else {
+        clipboard::with_footer(
+            clipboard::with_tags(
+                clipboard::with_wip(
+                    clipboard::render_plain(projects, report.cli.depth, report.show_origin),
+                    wip,
+                ),
+                tags,
+            ),
+            footer,
+        )
+    })
+}
+
+/// Fetch all repos, a few at a time so a large tree does not open dozens of
+/// connections to the same server. Returns the failures.
+fn fetch_repos(repos: &[PathBuf]) -> Vec<(&PathBuf, anyhow::Error)> {
+    let fetch = || {
+        repos
+            .par_iter()
+            .filter_map(|repo| git::fetch_all(repo, FETCH_TIMEOUT).err().map(|e| (repo, e)))
+            .collect()
+    };
+    match rayon::ThreadPoolBuilder::new()
+        .num_threads(FETCH_CONCURRENCY)
+        .build()
+    {
+        Ok(pool) => pool.install(fetch),
+        Err(_) => fetch(),
+    }
+}
+
+fn cache_policy(refresh: bool, ttl: Option<&str>) -> cache::CachePolicy {
+    let minutes = match ttl {
+        None => cache::DEFAULT_TTL_MINUTES,
+        Some(s) => devcap_core::duration::parse_minutes(s).unwrap_or_else(|| {
+            eprintln!("Warning: invalid cache_ttl in ~/.devcap.toml: \"{s}\"");
+            cache::DEFAULT_TTL_MINUTES
+        }),
+    };
+    cache::CachePolicy {
+        ttl: chrono::Duration::minutes(i64::from(minutes)),
+        refresh,
+    }
+}
+
+/// The `enrich` entries of the config file, warning about unknown ones.
+fn config_enrichments(entries: &[String]) -> Vec<cli::Enrichment> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let parsed = <cli::Enrichment as clap::ValueEnum>::from_str(entry, true);
+            if parsed.is_err() {
+                eprintln!("Warning: invalid enrich in ~/.devcap.toml: \"{entry}\"");
+            }
+            parsed.ok()
+        })
+        .collect()
+}
+
+/// The `group_by` entry of the config file, warning about an invalid one.
+fn config_group_by(value: Option<&str>) -> Option<GroupBy> {
+    let s = value?;
+    match s.parse::<GroupBy>() {
+        Ok(by) => Some(by),
+        Err(e) => {
+            eprintln!("Warning: invalid group_by in ~/.devcap.toml: \"{s}\" ({e})");
+            None
+        }
+    }
+}
+
+/// Apply `--max-commits` per branch, then `--max-lines` across the report.
+fn limit_report<'a>(
+    projects: impl IntoIterator<Item = &'a mut model::ProjectLog>,
+    max_commits: Option<usize>,
+    max_lines: Option<usize>,
+) {
+    let mut projects: Vec<&mut model::ProjectLog> = projects.into_iter().collect();
+    if let Some(max) = max_commits {
+        budget::limit_branch_commits(projects.iter_mut().map(|p| &mut **p), max);
+    }
+    if let Some(max) = max_lines {
+        budget::limit_commits(projects, max);
+    }
+}
+
+/// The spinner shown while nothing can be counted, e.g. during discovery.
+fn spinner_style() -> Option<ProgressStyle> {
+    ProgressStyle::default_spinner()
+        .tick_strings(&[
+            "\u{2802}", "\u{2816}", "\u{2834}", "\u{2830}", "\u{2860}", "\u{28e0}", "\u{28c0}",
+            "\u{2880}",
+        ])
+        .template("{spinner} {msg}")
+        .ok()
+}
+
+/// The scan bar: repositories done out of all, and the last one finished.
+fn scan_bar_style() -> Option<ProgressStyle> {
+    ProgressStyle::default_bar()
+        .template("Scanning {bar:24} {pos}/{len} repos  {wide_msg}")
+        .ok()
+        .map(|style| {
+            style
+                .progress_chars("\u{2588}\u{2589}\u{258a}\u{258b}\u{258c}\u{258d}\u{258e}\u{258f} ")
+        })
+}
+
+/// The `--summary` section and the `--footer` line, as enabled.
+fn report_footer(projects: &[model::ProjectLog], summary: bool, footer: bool) -> Option<String> {
+    if projects.is_empty() || !(summary || footer) {
+        return None;
+    }
+    let totals = totals::compute(projects);
+    let parts: Vec<String> = [
+        summary.then(|| output::summary_text(&totals)),
+        footer.then(|| output::footer_line(&totals)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    Some(parts.join("\n\n"))
+}
+
+fn copy_report(text: &str) {
+    match clipboard::copy_text(text) {
+        Ok(()) => output::info("Copied to clipboard."),
+        Err(e) => eprintln!("Warning: {e:#}"),
+    }
+}
+
+fn parse_config_date(value: Option<&str>, field: &str) -> Option<NaiveDate> {
+    let s = value?;
+    match s.parse::<NaiveDate>() {
+        Ok(d) => Some(d),
+        Err(e) => {
+            eprintln!("Warning: invalid {field} in ~/.devcap.toml: \"{s}\" ({e})");
+            None
+        }
+    }
+}
+
+fn resolve_period(cli_period: Option<Period>, cfg: &config::DevcapConfig) -> Period {
+    cli_period
+        .or_else(|| cfg.period.as_deref().and_then(|s| s.parse::<Period>().ok()))
+        .unwrap_or(Period::Today)
+}
+
+/// The range `stats` compares against: the previous calendar unit when the
+/// report comes from `--period` alone, else the span of equal length before.
+fn previous_time_range(
+    explicit_dates: bool,
+    cli_period: Option<Period>,
+    cfg: &config::DevcapConfig,
+    range: &TimeRange,
+    now: Time,
+) -> TimeRange {
+    if explicit_dates || cfg.since.is_some() || cfg.until.is_some() {
+        range.preceding(now)
+    } else {
+        resolve_period(cli_period, cfg).previous_range(now, range.zone)
+    }
+}
+
+fn resolve_time_range(
+    cli_since: Option<NaiveDate>,
+    cli_until: Option<NaiveDate>,
+    cli_period: Option<Period>,
+    zone: Zone,
+    cfg: &config::DevcapConfig,
+) -> Result<TimeRange> {
+    let since = cli_since.or_else(|| parse_config_date(cfg.since.as_deref(), "since"));
+    let until = cli_until.or_else(|| parse_config_date(cfg.until.as_deref(), "until"));
+
+    let resolve_period = || resolve_period(cli_period, cfg);
+
+    match (since, until) {
+        (Some(s), Some(u)) => {
+            TimeRange::from_dates(s, u, zone).map_err(|e| coded(ErrorCode::InvalidArgument, e))
+        }
+        (Some(s), None) => {
+            TimeRange::from_since_date(s, zone).map_err(|e| coded(ErrorCode::InvalidArgument, e))
+        }
+        (None, Some(u)) => {
+            let range = resolve_period().to_time_range(zone);
+            range
+                .with_until_date(u)
+                .map_err(|e| coded(ErrorCode::InvalidArgument, e))
+        }
+        (None, None) => Ok(resolve_period().to_time_range(zone)),
+    }
+}
@@ -0,0 +1,152 @@
+//! Guided first run: without a config file and with no repositories in the
+//! current directory, suggest common project roots and offer to save one.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::{Confirm, Input, Select};
+
+use devcap_core::discovery;
+
+/// Directories below `$HOME` where projects are commonly kept.
+const COMMON_ROOTS: [&str; 9] = [
+    "code",
+    "projects",
+    "Projects",
+    "src",
+    "dev",
+    "Developer",
+    "Sites",
+    "workspace",
+    "repos",
+];
+
+/// Ask for the directory to scan instead of the empty default one, offering
+/// to save it as `path` in the new config file at `config_path`. `None` when
+/// the user skipped.
+pub fn run(config_path: &Path) -> Result<Option<PathBuf>> {
+    let Some(home) = config_path.parent() else {
+        return Ok(None);
+    };
+    let suggestions = suggestions(home);
+    let theme = ColorfulTheme::default();
+
+    eprintln!("No git repositories found in the current directory.");
+    let mut items: Vec<String> = suggestions
+        .iter()
+        .map(|(root, count)| {
+            format!(
+                "{}  ({count} {})",
+                display(root, home),
+                if *count == 1 {
+                    "repository"
+                } else {
+                    "repositories"
+                }
+            )
+        })
+        .collect();
+    items.push("Another directory\u{2026}".to_string());
+    items.push("Skip".to_string());
+
+    let choice = Select::with_theme(&theme)
+        .with_prompt("Where do you keep your projects?")
+        .items(&items)
+        .default(0)
+        .interact_opt()?;
+    let root = match choice {
+        Some(i) if i < suggestions.len() => suggestions[i].0.clone(),
+        Some(i) if i == suggestions.len() => {
+            let input: String = Input::with_theme(&theme)
+                .with_prompt("Directory")
+                .validate_with(|s: &String| -> Result<(), &str> {
+                    if expand(s, home).is_dir() {
+                        Ok(())
+                    } else {
+                        Err("not a directory")
+                    }
+                })
+                .interact_text()?;
+            let dir = expand(&input, home);
+            std::path::absolute(&dir).unwrap_or(dir)
+        }
+        _ => return Ok(None),
+    };
+
+    let shown = display(&root, home);
+    let save = Confirm::with_theme(&theme)
+        .with_prompt(format!(
+            "Save path = \"{shown}\" to {}?",
+            display(config_path, home)
+        ))
+        .default(true)
+        .interact()?;
+    if save {
+        std::fs::write(config_path, config_contents(&shown))
+            .with_context(|| format!("could not write {}", config_path.display()))?;
+        eprintln!(
+            "Saved {}. See the README for more settings.",
+            display(config_path, home)
+        );
+    }
+    Ok(Some(root))
+}
+
+/// Common roots below `home` that contain repositories, with their counts.
+fn suggestions(home: &Path) -> Vec<(PathBuf, usize)> {
+    COMMON_ROOTS
+        .iter()
+        .map(|name| home.join(name))
+        .filter(|dir| dir.is_dir())
+        .filter_map(|dir| {
+            let count = discovery::find_repos(&dir).len();
+            (count > 0).then_some((dir, count))
+        })
+        .collect()
+}
+
+fn config_contents(path: &str) -> String {
+    format!(
+        "# Written by the devcap first-run setup.\npath = {}\n",
+        toml::Value::String(path.to_string())
+    )
+}
+
+/// `path` with the home directory written as `~`.
+fn display(path: &Path, home: &Path) -> String {
+    match path.strip_prefix(home) {
+        Ok(rest) => Path::new("~").join(rest).display().to_string(),
+        Err(_) => path.display().to_string(),
+    }
+}
+
+fn expand(input: &str, home: &Path) -> PathBuf {
+    let input = input.trim();
+    match input.strip_prefix("~/") {
+        Some(rest) => home.join(rest),
+        None if input == "~" => home.to_path_buf(),
+        None => PathBuf::from(input),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_below_home_use_tilde() {
+        let home = Path::new("/home/jane");
+        assert_eq!(display(Path::new("/home/jane/code"), home), "~/code");
+        assert_eq!(display(Path::new("/srv/git"), home), "/srv/git");
+        assert_eq!(expand("~/code", home), PathBuf::from("/home/jane/code"));
+        assert_eq!(expand("/srv/git", home), PathBuf::from("/srv/git"));
+    }
+
+    #[test]
+    fn written_config_parses() {
+        let cfg: crate::config::DevcapConfig =
+            toml::from_str(&config_contents("~/my \"code\"")).expect("valid toml");
+        assert_eq!(cfg.path, Some(PathBuf::from("~/my \"code\"")));
+    }
+}
@@ -1,11 +1,53 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
 use std::sync::OnceLock;
 
+use chrono::{DateTime, Local, NaiveDate};
 use colored::Colorize;
+use serde::Serialize;
 
 use crate::cli::Depth;
-use devcap_core::model::{BranchLog, Commit, DiffStat, ProjectLog};
+use crate::timesheet::Entry;
+use devcap_core::activity::{sparkline, DaySpan};
+use devcap_core::authors::AuthorGroup;
+use devcap_core::bucket::Bucket;
+use devcap_core::category::CategoryGroup;
+use devcap_core::conventional::ComplianceReport;
+use devcap_core::dco::DcoStatus;
+use devcap_core::export::Exported;
+use devcap_core::groups::ProjectGroup;
+use devcap_core::heatmap::Heatmap;
+use devcap_core::model::{
+    self, BranchLog, Commit, DiffStat, Omitted, PrState, ProjectLog, RepoOrigin, WorkTime,
+};
+use devcap_core::pairing::PairingReport;
+use devcap_core::stats::Stats;
+use devcap_core::tags::{RepoTags, Tag};
+use devcap_core::totals::Totals;
+use devcap_core::unpushed::UnpushedStatus;
+use devcap_core::wip::WorkInProgress;
+use devcap_core::zone::Time;
 
 static COLOR_ENABLED: OnceLock<bool> = OnceLock::new();
+static ORIGIN_BADGES: OnceLock<HashMap<String, String>> = OnceLock::new();
+static QUIET: OnceLock<bool> = OnceLock::new();
+static SHOW_AUTHORS: OnceLock<bool> = OnceLock::new();
+static BY_DAY: OnceLock<bool> = OnceLock::new();
+static TO_STDERR: OnceLock<bool> = OnceLock::new();
+
+/// `println!` for the terminal report, which goes to stderr after
+/// [`set_stderr`].
+macro_rules! outln {
+    ($($arg:tt)*) => {
+        if $crate::output::to_stderr() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+pub(crate) use outln;
 
 pub fn set_color_enabled(enabled: bool) {
     COLOR_ENABLED.set(enabled).ok();
@@ -16,15 +58,300 @@ pub(crate) fn color_enabled() -> bool {
     *COLOR_ENABLED.get().unwrap_or(&false)
 }
 
+/// Silence [`info`] messages (`--quiet`). Warnings and errors still print.
+pub fn set_quiet(quiet: bool) {
+    QUIET.set(quiet).ok();
+}
+
+pub(crate) fn quiet() -> bool {
+    *QUIET.get().unwrap_or(&false)
+}
+
+/// Name the author on every commit line, for reports over several authors.
+pub fn set_show_authors(show: bool) {
+    SHOW_AUTHORS.set(show).ok();
+}
+
+/// When the commit was made, prefixed by its author in team reports.
+pub(crate) fn commit_byline(commit: &Commit) -> String {
+    if *SHOW_AUTHORS.get().unwrap_or(&false) {
+        format!("{}, {}", commit.author, commit.relative_time)
+    } else {
+        commit.relative_time.clone()
+    }
+}
+
+/// List each branch's commits under date headers (`--by-day`).
+pub fn set_by_day(by_day: bool) {
+    BY_DAY.set(by_day).ok();
+}
+
+pub(crate) fn by_day() -> bool {
+    *BY_DAY.get().unwrap_or(&false)
+}
+
+/// `Mon 12 May`
+pub(crate) fn day_header(date: NaiveDate) -> String {
+    date.format("%a %-d %b").to_string()
+}
+
+/// Print the terminal report to stderr, keeping stdout for what a shell
+/// reads, like the `cd` line of the interactive mode.
+pub fn set_stderr(enabled: bool) {
+    TO_STDERR.set(enabled).ok();
+}
+
+pub(crate) fn to_stderr() -> bool {
+    *TO_STDERR.get().unwrap_or(&false)
+}
+
+/// Print an informational message to stderr unless `--quiet` is set.
+pub(crate) fn info(message: impl fmt::Display) {
+    if !quiet() {
+        eprintln!("{message}");
+    }
+}
+
+/// Badge overrides from `[origin_badges]`, keyed by [`RepoOrigin::key`].
+pub fn set_origin_badges(badges: HashMap<String, String>) {
+    ORIGIN_BADGES.set(badges).ok();
+}
+
+/// Short badge for an origin: `GH`, `GL`, `BB`, the host of a custom origin,
+/// or the configured override.
+pub(crate) fn origin_badge(origin: &RepoOrigin) -> String {
+    if let Some(badge) = ORIGIN_BADGES.get().and_then(|b| b.get(origin.key())) {
+        return badge.clone();
+    }
+    match origin {
+        RepoOrigin::GitHub => "GH".to_string(),
+        RepoOrigin::GitLab | RepoOrigin::GitLabSelfHosted => "GL".to_string(),
+        RepoOrigin::Bitbucket => "BB".to_string(),
+        RepoOrigin::Custom(host) => host.clone(),
+    }
+}
+
 pub fn render_terminal(projects: &[ProjectLog], depth: Depth, show_origin: bool) {
     if projects.is_empty() {
-        eprintln!("{}", "No commits found for the given period.".dimmed());
+        info("No commits found for the given period.".dimmed());
+        return;
+    }
+
+    render_projects(projects, depth, show_origin);
+    render_total_logged(model::total_time_spent(projects));
+}
+
+/// Render a report split into calendar buckets, each with its own subtotal.
+pub fn render_grouped(buckets: &[Bucket], depth: Depth, show_origin: bool) {
+    if buckets.is_empty() {
+        info("No commits found for the given period.".dimmed());
         return;
     }
 
+    for (i, bucket) in buckets.iter().enumerate() {
+        if i > 0 {
+            outln!();
+        }
+        let span = bucket
+            .span
+            .as_ref()
+            .map(|s| format!("  {}", span_line(s)))
+            .unwrap_or_default();
+        let summary = format!("({}){span}", bucket_summary(bucket)).dimmed();
+        if color_enabled() {
+            outln!(
+                "{} {}  {}",
+                "==".bold().magenta(),
+                bucket.label.bold(),
+                summary
+            );
+        } else {
+            outln!("{} {}  {}", "==".bold(), bucket.label.bold(), summary);
+        }
+        render_projects(&bucket.projects, depth, show_origin);
+    }
+
+    render_total_logged(
+        buckets
+            .iter()
+            .filter_map(|b| b.time_spent)
+            .reduce(|a, b| a + b),
+    );
+}
+
+/// Render a report split into one section per commit author.
+pub fn render_by_author(groups: &[AuthorGroup], depth: Depth, show_origin: bool) {
+    render_named_sections(
+        groups
+            .iter()
+            .map(|g| (g.author.as_str(), author_summary(g), g.projects.as_slice())),
+        depth,
+        show_origin,
+    );
+    render_total_logged(
+        groups
+            .iter()
+            .filter_map(|g| g.time_spent)
+            .reduce(|a, b| a + b),
+    );
+}
+
+/// Render a report split into one section per configured commit category.
+pub fn render_by_category(groups: &[CategoryGroup], depth: Depth, show_origin: bool) {
+    render_named_sections(
+        groups.iter().map(|g| {
+            (
+                g.category.as_str(),
+                category_summary(g),
+                g.projects.as_slice(),
+            )
+        }),
+        depth,
+        show_origin,
+    );
+    render_total_logged(
+        groups
+            .iter()
+            .filter_map(|g| g.time_spent)
+            .reduce(|a, b| a + b),
+    );
+}
+
+/// Render a report split into one section per `[projects]` group label.
+pub fn render_by_group(groups: &[ProjectGroup], depth: Depth, show_origin: bool) {
+    render_named_sections(
+        groups
+            .iter()
+            .map(|g| (g.group.as_str(), group_summary(g), g.projects.as_slice())),
+        depth,
+        show_origin,
+    );
+    render_total_logged(
+        groups
+            .iter()
+            .filter_map(|g| g.time_spent)
+            .reduce(|a, b| a + b),
+    );
+}
+
+/// `== name  (summary)` headings, each followed by its projects.
+fn render_named_sections<'a>(
+    sections: impl ExactSizeIterator<Item = (&'a str, String, &'a [ProjectLog])>,
+    depth: Depth,
+    show_origin: bool,
+) {
+    if sections.len() == 0 {
+        info("No commits found for the given period.".dimmed());
+        return;
+    }
+
+    for (i, (name, summary, projects)) in sections.enumerate() {
+        if i > 0 {
+            outln!();
+        }
+        let summary = format!("({summary})").dimmed();
+        if color_enabled() {
+            outln!("{} {}  {}", "==".bold().magenta(), name.bold(), summary);
+        } else {
+            outln!("{} {}  {}", "==".bold(), name.bold(), summary);
+        }
+        render_projects(projects, depth, show_origin);
+    }
+}
+
+pub(crate) fn bucket_summary(bucket: &Bucket) -> String {
+    section_summary(bucket.commits, bucket.projects.len(), bucket.time_spent)
+}
+
+pub(crate) fn author_summary(group: &AuthorGroup) -> String {
+    section_summary(group.commits, group.projects.len(), group.time_spent)
+}
+
+pub(crate) fn category_summary(group: &CategoryGroup) -> String {
+    section_summary(group.commits, group.projects.len(), group.time_spent)
+}
+
+pub(crate) fn group_summary(group: &ProjectGroup) -> String {
+    section_summary(group.commits, group.projects.len(), group.time_spent)
+}
+
+fn section_summary(commits: usize, projects: usize, time_spent: Option<WorkTime>) -> String {
+    let project_label = if projects == 1 { "project" } else { "projects" };
+    let commit_label = if commits == 1 { "commit" } else { "commits" };
+    format!(
+        "{commits} {commit_label}, {projects} {project_label}{}",
+        time_suffix_standalone(time_spent)
+    )
+}
+
+/// `active 09:12 – 18:47 (est. 7h10m)`
+pub(crate) fn span_line(span: &DaySpan) -> String {
+    format!(
+        "active {} \u{2013} {} (est. {})",
+        span.first.format("%H:%M"),
+        span.last.format("%H:%M"),
+        span.estimated
+    )
+}
+
+/// Single-day report: a header with first/last activity, then the usual tree.
+pub fn render_day(
+    date: NaiveDate,
+    span: Option<&DaySpan>,
+    projects: &[ProjectLog],
+    depth: Depth,
+    show_origin: bool,
+) {
+    let title = date.format("%a %Y-%m-%d").to_string();
+    let active = span
+        .map(span_line)
+        .unwrap_or_else(|| "no activity".to_string());
+    if color_enabled() {
+        outln!(
+            "{} {}  {}",
+            "==".bold().magenta(),
+            title.bold(),
+            active.dimmed()
+        );
+    } else {
+        outln!("{} {}  {}", "==".bold(), title.bold(), active.dimmed());
+    }
+    if !projects.is_empty() {
+        render_projects(projects, depth, show_origin);
+        render_total_logged(model::total_time_spent(projects));
+    }
+}
+
+#[derive(Serialize)]
+struct DayReport<'a> {
+    date: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    active: Option<&'a DaySpan>,
+    projects: &'a [ProjectLog],
+}
+
+pub fn render_day_json(
+    date: NaiveDate,
+    span: Option<&DaySpan>,
+    projects: &[ProjectLog],
+    depth: Depth,
+) -> String {
+    let report = DayReport {
+        date,
+        active: span,
+        projects,
+    };
+    let mut value = serde_json::to_value(&report).unwrap_or_default();
+    if let Some(projects) = value.get_mut("projects") {
+        trim_projects(projects, depth);
+    }
+    serde_json::to_string_pretty(&value).unwrap_or_else(|_| "{}".to_string())
+}
+
+fn render_projects(projects: &[ProjectLog], depth: Depth, show_origin: bool) {
     for (i, project) in projects.iter().enumerate() {
         if i > 0 && depth != Depth::Projects {
-            println!();
+            outln!();
         }
         match depth {
             Depth::Projects => render_project_summary(project, show_origin),
@@ -34,18 +361,112 @@ pub fn render_terminal(projects: &[ProjectLog], depth: Depth, show_origin: bool)
     }
 }
 
+fn render_total_logged(total: Option<WorkTime>) {
+    if let Some(total) = total {
+        outln!();
+        outln!("{}", format!("Total logged: {total}").bold());
+    }
+}
+
+/// `Total: 12 commits, 3 projects, 5 branches, est. 6h30m — busiest: web (7 commits)`
+pub(crate) fn footer_line(totals: &Totals) -> String {
+    let plural =
+        |n: usize, one: &str, many: &str| format!("{n} {}", if n == 1 { one } else { many });
+    let mut line = format!(
+        "Total: {}, {}, {}, est. {}",
+        plural(totals.commits, "commit", "commits"),
+        plural(totals.projects, "project", "projects"),
+        plural(totals.branches, "branch", "branches"),
+        totals.estimated
+    );
+    if let Some(busiest) = &totals.busiest {
+        line.push_str(&format!(
+            " \u{2014} busiest: {} ({})",
+            busiest.project,
+            plural(busiest.commits, "commit", "commits")
+        ));
+    }
+    line
+}
+
+/// The `--summary` section: a heading and one indented line per figure.
+pub(crate) fn summary_text(totals: &Totals) -> String {
+    let types = totals
+        .by_type
+        .iter()
+        .map(|t| format!("{} {}", t.commits, t.commit_type))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut lines = vec![
+        "Summary".to_string(),
+        format!("  Commits:   {}", totals.commits),
+        format!("  By type:   {types}"),
+        format!("  Projects:  {} active", totals.projects),
+    ];
+    if let Some(busiest) = &totals.busiest {
+        lines.push(format!(
+            "  Busiest:   {} ({} commit{})",
+            busiest.project,
+            busiest.commits,
+            if busiest.commits == 1 { "" } else { "s" }
+        ));
+    }
+    if let (Some(first), Some(last)) = (totals.first, totals.last) {
+        // The date only matters when the commits span several days.
+        let format = if first.date_naive() == last.date_naive() {
+            "%H:%M"
+        } else {
+            "%a %d %b %H:%M"
+        };
+        lines.push(format!("  First:     {}", first.format(format)));
+        lines.push(format!("  Last:      {}", last.format(format)));
+    }
+    lines.join("\n")
+}
+
+/// First line of each `--watch` redraw.
+pub fn render_watch_header(interval: crate::cli::WatchInterval, at: Time) {
+    outln!(
+        "{}",
+        format!(
+            "Every {interval} \u{00b7} updated {} \u{00b7} Ctrl-C to quit",
+            at.format("%H:%M:%S")
+        )
+        .dimmed()
+    );
+}
+
+pub fn render_footer(footer: Option<&str>) {
+    if let Some(text) = footer {
+        outln!();
+        for line in text.lines() {
+            // Headings are bold, the indented figures below them plain.
+            if line.starts_with(' ') {
+                outln!("{line}");
+            } else {
+                outln!("{}", line.bold());
+            }
+        }
+    }
+}
+
 pub(crate) fn origin_tag(project: &ProjectLog, show_origin: bool) -> String {
     if !show_origin {
         return String::new();
     }
     match &project.origin {
         Some(origin) => {
-            let label = format!("[{origin}]");
-            if color_enabled() {
-                format!(" {}", label.magenta())
-            } else {
-                format!(" {label}")
+            let label = format!("[{}]", origin_badge(origin));
+            if !color_enabled() {
+                return format!(" {label}");
             }
+            let label = match origin {
+                RepoOrigin::GitHub => label.bold(),
+                RepoOrigin::GitLab | RepoOrigin::GitLabSelfHosted => label.yellow(),
+                RepoOrigin::Bitbucket => label.blue(),
+                RepoOrigin::Custom(_) => label.magenta(),
+            };
+            format!(" {label}")
         }
         None => String::new(),
     }
@@ -77,92 +498,445 @@ pub(crate) fn stat_suffix_inline(stat: Option<&DiffStat>) -> String {
         .unwrap_or_default()
 }
 
+pub(crate) fn time_suffix_standalone(time: Option<WorkTime>) -> String {
+    time.map(|t| format!(", {t} logged")).unwrap_or_default()
+}
+
+/// `, ↑2 ↓1` against the upstream branch, or `, in sync`.
+pub(crate) fn sync_suffix(branch: &BranchLog) -> String {
+    let Some(upstream) = &branch.upstream else {
+        return String::new();
+    };
+    match (upstream.ahead, upstream.behind) {
+        (0, 0) => ", in sync".to_string(),
+        (ahead, 0) => format!(", \u{2191}{ahead}"),
+        (0, behind) => format!(", \u{2193}{behind}"),
+        (ahead, behind) => format!(", \u{2191}{ahead} \u{2193}{behind}"),
+    }
+}
+
+/// `, spans 2d 3h, started 12d ago` for the branches depth. The span is left
+/// out for single-commit branches, the start for the primary branch.
+pub(crate) fn branch_span_suffix(branch: &BranchLog, now: Time) -> String {
+    let mut out = String::new();
+    if let Some(span) = branch.period_span().filter(|d| d.num_minutes() > 0) {
+        out.push_str(&format!(", spans {}", format_elapsed(span)));
+    }
+    if let Some(started) = branch.started {
+        out.push_str(&format!(", started {} ago", format_elapsed(now - started)));
+    }
+    out
+}
+
+/// Wall-clock duration: `45m`, `5h`, `2d 3h`.
+fn format_elapsed(d: chrono::Duration) -> String {
+    let minutes = d.num_minutes().max(0);
+    let (days, hours) = (minutes / (24 * 60), minutes / 60 % 24);
+    match (days, hours) {
+        (0, 0) => format!("{minutes}m"),
+        (0, h) => format!("{h}h"),
+        (d, 0) => format!("{d}d"),
+        (d, h) => format!("{d}d {h}h"),
+    }
+}
+
+/// `  [unpushed]` for commits that exist only locally.
+pub(crate) fn unpushed_badge(commit: &Commit) -> &'static str {
+    if commit.unpushed {
+        "  [unpushed]"
+    } else {
+        ""
+    }
+}
+
+/// `  [co-authored]` for commits with `Co-authored-by` trailers.
+pub(crate) fn co_authored_badge(commit: &Commit) -> &'static str {
+    if commit.co_authors.is_empty() {
+        ""
+    } else {
+        "  [co-authored]"
+    }
+}
+
+pub(crate) fn time_suffix_inline(time: Option<WorkTime>) -> String {
+    time.map(|t| format!("  ({t} logged)")).unwrap_or_default()
+}
+
+/// Plain ` → "Add payment retries" (#12)` suffix for a branch with an open PR.
+/// ` ✓ merged` once a branch has landed in the primary branch, ` [worktree
+/// app-login]` while it is checked out in a linked worktree, then the title
+/// of its pull request if known.
+pub(crate) fn branch_suffix(branch: &BranchLog) -> String {
+    let merged = if branch.merged == Some(true) {
+        " \u{2713} merged"
+    } else {
+        ""
+    };
+    let worktree = branch
+        .worktree
+        .as_deref()
+        .map(|path| {
+            let name = Path::new(path).file_name().unwrap_or_default();
+            format!(" [worktree {}]", name.to_string_lossy())
+        })
+        .unwrap_or_default();
+    format!("{merged}{worktree}{}", pr_suffix(branch))
+}
+
+fn pr_suffix(branch: &BranchLog) -> String {
+    match &branch.pr {
+        Some(pr) => {
+            let state = match pr.state {
+                PrState::Open => "",
+                PrState::Merged => ", merged",
+                PrState::Closed => ", closed",
+            };
+            format!(" \u{2192} \"{}\" (#{}{state})", pr.title, pr.number)
+        }
+        None => String::new(),
+    }
+}
+
+/// Plain `Health: on main · 3 uncommitted · 1 stash · 2 unpushed`, or
+/// `Health: on main · clean`.
+pub(crate) fn health_line(project: &ProjectLog) -> Option<String> {
+    let health = project.health.as_ref()?;
+    let plural = |n: u32, one: &str, many: &str| format!("{n} {}", if n == 1 { one } else { many });
+    let mut parts = vec![match &health.branch {
+        Some(branch) => format!("on {branch}"),
+        None => "detached HEAD".to_string(),
+    }];
+    if health.is_clean() {
+        parts.push("clean".to_string());
+    }
+    if health.changes > 0 {
+        parts.push(format!("{} uncommitted", health.changes));
+    }
+    if health.stashes > 0 {
+        parts.push(plural(health.stashes, "stash", "stashes"));
+    }
+    if let Some(unpushed) = health.unpushed.filter(|n| *n > 0) {
+        parts.push(format!("{unpushed} unpushed"));
+    }
+    Some(format!("Health: {}", parts.join(" \u{b7} ")))
+}
+
+/// `4 files modified, not committed (1 staged), 2 untracked files, 1 stash`
+pub(crate) fn wip_summary(wip: &WorkInProgress) -> String {
+    let plural = |n: u32, one: &str, many: &str| format!("{n} {}", if n == 1 { one } else { many });
+    let mut parts = Vec::new();
+    if wip.changed > 0 {
+        let staged = match wip.staged {
+            0 => String::new(),
+            n if n == wip.changed => " (all staged)".to_string(),
+            n => format!(" ({n} staged)"),
+        };
+        parts.push(format!(
+            "{} modified, not committed{staged}",
+            plural(wip.changed, "file", "files")
+        ));
+    }
+    if wip.untracked > 0 {
+        parts.push(plural(wip.untracked, "untracked file", "untracked files"));
+    }
+    if !wip.stashes.is_empty() {
+        parts.push(plural(wip.stashes.len() as u32, "stash", "stashes"));
+    }
+    parts.join(", ")
+}
+
+/// `v2.3.0  a1b2c3d  Release 2.3.0` without the relative time.
+pub(crate) fn tag_summary(tag: &Tag) -> String {
+    match &tag.annotation {
+        Some(annotation) => format!("{}  {}  {annotation}", tag.name, tag.commit),
+        None => format!("{}  {}", tag.name, tag.commit),
+    }
+}
+
+const HEAT_CELLS: [&str; 5] = [
+    "\u{00b7} ",
+    "\u{2591}\u{2591}",
+    "\u{2592}\u{2592}",
+    "\u{2593}\u{2593}",
+    "\u{2588}\u{2588}",
+];
+
+/// The `--heatmap` grid: one row per weekday, one two-column cell per hour.
+pub(crate) fn heatmap_lines(heatmap: &Heatmap) -> Vec<String> {
+    let levels = HEAT_CELLS.len() - 1;
+    let hours: String = (0..24).step_by(3).map(|h| format!("{h:<6}")).collect();
+    let mut lines = vec![
+        "Activity by hour".to_string(),
+        format!("      {}", hours.trim_end()),
+    ];
+    for (day, name) in ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+        .iter()
+        .enumerate()
+    {
+        let cells: String = (0..24)
+            .map(|hour| {
+                let level = heatmap.level(day, hour, levels);
+                let cell = HEAT_CELLS[level];
+                if !color_enabled() {
+                    return cell.to_string();
+                }
+                match level {
+                    0 => cell.dimmed().to_string(),
+                    1 => cell.green().dimmed().to_string(),
+                    2 => cell.green().to_string(),
+                    3 => cell.bright_green().to_string(),
+                    _ => cell.bright_green().bold().to_string(),
+                }
+            })
+            .collect();
+        let total: usize = heatmap.counts[day].iter().sum();
+        lines.push(format!("  {name} {cells}  {total}"));
+    }
+    lines
+}
+
+pub fn render_heatmap(heatmap: Option<&Heatmap>) {
+    let Some(heatmap) = heatmap else {
+        return;
+    };
+    let mut lines = heatmap_lines(heatmap).into_iter();
+    outln!();
+    if let Some(title) = lines.next() {
+        outln!("{}", title.bold());
+    }
+    for line in lines {
+        outln!("{line}");
+    }
+}
+
+/// The "Work in progress" section of `--wip`; nothing when all is committed.
+pub fn render_wip(wip: &[WorkInProgress]) {
+    if wip.is_empty() {
+        return;
+    }
+    outln!();
+    outln!("{}", "Work in progress".bold());
+    for repo in wip {
+        let name = if color_enabled() {
+            repo.project.bold().white().to_string()
+        } else {
+            repo.project.clone()
+        };
+        let branch = repo
+            .branch
+            .as_ref()
+            .map(|b| format!(" ({b})").dimmed().to_string())
+            .unwrap_or_default();
+        outln!(
+            "{} {name}{branch}  {}",
+            "::".bold(),
+            wip_summary(repo).yellow()
+        );
+        for stash in &repo.stashes {
+            outln!(
+                "    {} {}  {}",
+                "stash".dimmed(),
+                stash.message,
+                stash.relative_time.dimmed()
+            );
+        }
+    }
+}
+
+/// The "Releases" section of `--tags`; nothing when no tag was created.
+pub fn render_tags(tags: &[RepoTags]) {
+    if tags.is_empty() {
+        return;
+    }
+    outln!();
+    outln!("{}", "Releases".bold());
+    for repo in tags {
+        let name = if color_enabled() {
+            repo.project.bold().white().to_string()
+        } else {
+            repo.project.clone()
+        };
+        outln!("{} {name}", "::".bold());
+        for tag in &repo.tags {
+            let summary = tag_summary(tag);
+            let summary = if color_enabled() {
+                summary.yellow().to_string()
+            } else {
+                summary
+            };
+            outln!("    {summary}  {}", tag.relative_time.dimmed());
+        }
+    }
+}
+
+fn render_health(project: &ProjectLog) {
+    let Some(line) = health_line(project) else {
+        return;
+    };
+    let clean = project.health.as_ref().is_some_and(|h| h.is_clean());
+    if !color_enabled() {
+        outln!("  {line}");
+    } else if clean {
+        outln!("  {}", line.green());
+    } else {
+        outln!("  {}", line.yellow());
+    }
+}
+
+/// Plain "Released v2.3.0  (2026-03-05)" lines, newest first.
+pub(crate) fn release_lines(project: &ProjectLog) -> Vec<String> {
+    project
+        .releases
+        .iter()
+        .flatten()
+        .map(|r| format!("Released {}  ({})", r.tag, r.time.format("%Y-%m-%d")))
+        .collect()
+}
+
+fn render_releases(project: &ProjectLog) {
+    for line in release_lines(project) {
+        if color_enabled() {
+            outln!("  {}", line.yellow().bold());
+        } else {
+            outln!("  {line}");
+        }
+    }
+}
+
+pub(crate) fn activity_suffix(project: &ProjectLog) -> String {
+    match &project.activity {
+        Some(counts) => {
+            let line = sparkline(counts);
+            if color_enabled() {
+                format!("  {}", line.cyan())
+            } else {
+                format!("  {line}")
+            }
+        }
+        None => String::new(),
+    }
+}
+
 fn render_project_summary(project: &ProjectLog, show_origin: bool) {
     let commits = project.total_commits();
     let branches = project.branches.len();
     let latest = project.latest_activity().unwrap_or("-");
     let origin = origin_tag(project, show_origin);
+    let activity = activity_suffix(project);
     let stat = stat_suffix_standalone(project.diff_stat.as_ref());
-    let summary = format!("({commits} commits, {branches} branches, {latest}{stat})").dimmed();
+    let logged = time_suffix_standalone(project.time_spent());
+    let summary =
+        format!("({commits} commits, {branches} branches, {latest}{stat}{logged})").dimmed();
     if color_enabled() {
-        println!(
-            "{} {}{}  {}",
+        outln!(
+            "{} {}{}{}  {}",
             "::".bold().cyan(),
             project.project.bold().white(),
             origin,
+            activity,
             summary
         );
     } else {
-        println!(
-            "{} {}{}  {}",
+        outln!(
+            "{} {}{}{}  {}",
             "::".bold(),
             project.project.bold(),
             origin,
+            activity,
             summary
         );
     }
+    render_health(project);
+    render_releases(project);
 }
 
 fn render_project_with_branches(project: &ProjectLog, show_origin: bool) {
     let latest = project.latest_activity().unwrap_or("-");
     let origin = origin_tag(project, show_origin);
+    let activity = activity_suffix(project);
     let stat = stat_suffix_standalone(project.diff_stat.as_ref());
-    let summary = format!("({latest}{stat})").dimmed();
+    let logged = time_suffix_standalone(project.time_spent());
+    let summary = format!("({latest}{stat}{logged})").dimmed();
     if color_enabled() {
-        println!(
-            "{} {}{}  {}",
+        outln!(
+            "{} {}{}{}  {}",
             "::".bold().cyan(),
             project.project.bold().white(),
             origin,
+            activity,
             summary
         );
     } else {
-        println!(
-            "{} {}{}  {}",
+        outln!(
+            "{} {}{}{}  {}",
             "::".bold(),
             project.project.bold(),
             origin,
+            activity,
             summary
         );
     }
+    render_health(project);
+    render_releases(project);
+    let now = Local::now().fixed_offset();
     for branch in &project.branches {
         let count = branch.commits.len();
         let branch_latest = branch.latest_activity().unwrap_or("-");
         let bstat = stat_suffix_standalone(branch.diff_stat.as_ref());
-        let branch_summary = format!("({count} commits, {branch_latest}{bstat})").dimmed();
+        let blogged = time_suffix_standalone(branch.time_spent());
+        let bsync = sync_suffix(branch);
+        let bspan = branch_span_suffix(branch, now);
+        let branch_summary =
+            format!("({count} commits, {branch_latest}{bstat}{blogged}{bsync}{bspan})").dimmed();
         if color_enabled() {
-            println!(
-                "  {} {}  {}",
+            outln!(
+                "  {} {}{}  {}",
                 ">>".green(),
                 branch.name.green(),
+                branch_suffix(branch),
                 branch_summary
             );
         } else {
-            println!("  >> {}  {}", branch.name, branch_summary);
+            outln!(
+                "  >> {}{}  {}",
+                branch.name,
+                branch_suffix(branch),
+                branch_summary
+            );
         }
     }
 }
 
 fn render_project_full(project: &ProjectLog, show_origin: bool) {
     let origin = origin_tag(project, show_origin);
-    let stat_str = stat_suffix_inline(project.diff_stat.as_ref()).dimmed();
+    let activity = activity_suffix(project);
+    let stat_str = format!(
+        "{}{}",
+        stat_suffix_inline(project.diff_stat.as_ref()),
+        time_suffix_inline(project.time_spent())
+    )
+    .dimmed();
     if color_enabled() {
-        println!(
-            "{} {}{}{}",
+        outln!(
+            "{} {}{}{}{}",
             "::".bold().cyan(),
             project.project.bold().white(),
             origin,
+            activity,
             stat_str
         );
     } else {
-        println!(
-            "{} {}{}{}",
+        outln!(
+            "{} {}{}{}{}",
             "::".bold(),
             project.project.bold(),
             origin,
+            activity,
             stat_str
         );
     }
+    render_health(project);
+    render_releases(project);
     for branch in &project.branches {
         render_branch(branch);
     }
@@ -173,38 +947,91 @@ pub(crate) fn render_project(project: &ProjectLog, show_origin: bool) {
 }
 
 pub(crate) fn render_branch(branch: &BranchLog) {
-    let stat = stat_suffix_inline(branch.diff_stat.as_ref()).dimmed();
+    let stat = format!(
+        "{}{}",
+        stat_suffix_inline(branch.diff_stat.as_ref()),
+        time_suffix_inline(branch.time_spent())
+    )
+    .dimmed();
     if color_enabled() {
-        println!("  {} {}{}", ">>".green(), branch.name.green(), stat);
+        outln!(
+            "  {} {}{}{}",
+            ">>".green(),
+            branch.name.green(),
+            branch_suffix(branch),
+            stat
+        );
     } else {
-        println!("  >> {}{}", branch.name, stat);
+        outln!("  >> {}{}{}", branch.name, branch_suffix(branch), stat);
     }
     render_commits(&branch.commits);
+    if let Some(omitted) = &branch.omitted {
+        outln!("    {}", omitted_line(omitted).dimmed());
+    }
+}
+
+/// `… 37 more commits (20 chore, 4 feat, 13 other)`, most frequent type first.
+pub(crate) fn omitted_line(omitted: &Omitted) -> String {
+    let mut types: Vec<(&String, &usize)> = omitted.types.iter().collect();
+    types.sort_by_key(|(_, count)| std::cmp::Reverse(**count));
+    let types: Vec<String> = types
+        .into_iter()
+        .map(|(kind, count)| format!("{count} {kind}"))
+        .collect();
+    let label = if omitted.commits == 1 {
+        "commit"
+    } else {
+        "commits"
+    };
+    format!(
+        "\u{2026} {} more {label} ({})",
+        omitted.commits,
+        types.join(", ")
+    )
 }
 
 fn render_commits(commits: &[Commit]) {
+    let by_day = by_day();
+    let indent = if by_day { "      " } else { "    " };
+    let mut day = None;
     for commit in commits {
+        if by_day && day != Some(commit.time.date_naive()) {
+            day = Some(commit.time.date_naive());
+            outln!("    {}", day_header(commit.time.date_naive()).cyan());
+        }
         let tag = commit_type_tag(commit);
         let msg = strip_type_prefix(&commit.message);
-        let stat = stat_suffix_inline(commit.diff_stat.as_ref()).dimmed();
+        let stat = format!(
+            "{}{}",
+            stat_suffix_inline(commit.diff_stat.as_ref()),
+            time_suffix_inline(commit.time_spent)
+        )
+        .dimmed();
+        let badge = format!(
+            "{}{}",
+            co_authored_badge(commit).cyan(),
+            unpushed_badge(commit).yellow()
+        );
         if tag.is_empty() {
-            println!(
-                "    {} {} - {}  {}{}",
+            outln!(
+                "{indent}{} {} - {}  {}{}{}",
                 "*".dimmed(),
                 commit.hash.dimmed(),
                 msg,
-                commit.relative_time.dimmed(),
+                commit_byline(commit).dimmed(),
                 stat,
+                badge,
             );
         } else {
-            println!(
-                "    {} {} {} - {}  {}{}",
+            outln!(
+                "{indent}{} {} {} - {}  {}{}{}",
                 "*".dimmed(),
                 commit.hash.dimmed(),
                 tag,
                 msg,
-                commit.relative_time.dimmed(),
+                commit_byline(commit).dimmed(),
                 stat,
+                badge,
             );
         }
     }
@@ -237,8 +1064,441 @@ pub(crate) fn strip_type_prefix(message: &str) -> &str {
     }
 }
 
-pub fn render_json(projects: &[ProjectLog]) -> String {
-    serde_json::to_string_pretty(projects).unwrap_or_else(|_| "[]".to_string())
+pub fn render_json(projects: &[ProjectLog], depth: Depth) -> String {
+    serde_json::to_string_pretty(&projects_json(projects, depth))
+        .unwrap_or_else(|_| "[]".to_string())
+}
+
+/// `projects` as `--json` prints them at `depth`.
+pub(crate) fn projects_json(projects: &[ProjectLog], depth: Depth) -> serde_json::Value {
+    let mut value = serde_json::to_value(projects).unwrap_or_default();
+    trim_projects(&mut value, depth);
+    value
+}
+
+/// `--wip` and `--tags` report: `{"projects": [...], "wip": [...], "tags":
+/// [...]}` with the sections that were asked for.
+pub fn render_json_with_sections(
+    projects: &[ProjectLog],
+    depth: Depth,
+    wip: Option<&[WorkInProgress]>,
+    tags: Option<&[RepoTags]>,
+) -> String {
+    let mut value = serde_json::json!({ "projects": projects_json(projects, depth) });
+    if let Some(wip) = wip {
+        value["wip"] = serde_json::json!(wip);
+    }
+    if let Some(tags) = tags {
+        value["tags"] = serde_json::json!(tags);
+    }
+    serde_json::to_string_pretty(&value).unwrap_or_else(|_| "{}".to_string())
+}
+
+pub fn render_grouped_json(buckets: &[Bucket], depth: Depth) -> String {
+    render_sections_json(buckets, depth)
+}
+
+pub fn render_by_author_json(groups: &[AuthorGroup], depth: Depth) -> String {
+    render_sections_json(groups, depth)
+}
+
+pub fn render_by_category_json(groups: &[CategoryGroup], depth: Depth) -> String {
+    render_sections_json(groups, depth)
+}
+
+/// Serialize sections that each carry a `projects` array.
+fn render_sections_json<T: Serialize>(sections: &[T], depth: Depth) -> String {
+    let mut value = serde_json::to_value(sections).unwrap_or_default();
+    for section in value.as_array_mut().into_iter().flatten() {
+        if let Some(projects) = section.get_mut("projects") {
+            trim_projects(projects, depth);
+        }
+    }
+    serde_json::to_string_pretty(&value).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Drop the levels below `depth` from serialized projects. Removed lists are
+/// replaced by `branch_count`/`commit_count` so aggregates stay available.
+/// Serialized commits as `[{"date": "2026-05-12", "commits": [...]}]`, in
+/// the order the commits come in.
+fn group_by_day(commits: serde_json::Value) -> serde_json::Value {
+    use serde_json::{json, Value};
+
+    let mut days: Vec<(String, Vec<Value>)> = Vec::new();
+    for commit in commits.as_array().into_iter().flatten() {
+        let date = commit
+            .get("timestamp")
+            .and_then(Value::as_str)
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.date_naive().to_string())
+            .unwrap_or_default();
+        match days.last_mut() {
+            Some((last, list)) if *last == date => list.push(commit.clone()),
+            _ => days.push((date, vec![commit.clone()])),
+        }
+    }
+    Value::Array(
+        days.into_iter()
+            .map(|(date, commits)| json!({ "date": date, "commits": commits }))
+            .collect(),
+    )
+}
+
+fn trim_projects(projects: &mut serde_json::Value, depth: Depth) {
+    use serde_json::{json, Value};
+
+    let count = |v: &Value, key: &str| v.get(key).and_then(Value::as_array).map_or(0, Vec::len);
+    for project in projects.as_array_mut().into_iter().flatten() {
+        let Some(project) = project.as_object_mut() else {
+            continue;
+        };
+        // Only `--max-commits` leaves commits out of machine output.
+        for branch in project
+            .get_mut("branches")
+            .and_then(Value::as_array_mut)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_object_mut)
+            .filter(|b| b.contains_key("omitted"))
+        {
+            branch.insert("truncated".to_string(), json!(true));
+        }
+        match depth {
+            Depth::Commits if by_day() => {
+                for branch in project
+                    .get_mut("branches")
+                    .and_then(Value::as_array_mut)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_object_mut)
+                {
+                    let commits = branch.remove("commits").unwrap_or_default();
+                    branch.insert("days".to_string(), group_by_day(commits));
+                }
+            }
+            Depth::Commits => {}
+            Depth::Branches => {
+                for branch in project
+                    .get_mut("branches")
+                    .and_then(Value::as_array_mut)
+                    .into_iter()
+                    .flatten()
+                {
+                    let commits = count(branch, "commits");
+                    if let Some(branch) = branch.as_object_mut() {
+                        branch.remove("commits");
+                        branch.insert("commit_count".to_string(), json!(commits));
+                    }
+                }
+            }
+            Depth::Projects => {
+                let branches = project.remove("branches").unwrap_or_default();
+                let list = branches.as_array().map(Vec::as_slice).unwrap_or_default();
+                let commits: usize = list.iter().map(|b| count(b, "commits")).sum();
+                project.insert("branch_count".to_string(), json!(list.len()));
+                project.insert("commit_count".to_string(), json!(commits));
+            }
+        }
+    }
+}
+
+pub fn render_stats(stats: &Stats) {
+    let row = |label: &str, value: String| {
+        outln!("  {:<19}{value}", label.dimmed());
+    };
+    let projects = if stats.projects == 1 {
+        "project"
+    } else {
+        "projects"
+    };
+    let trend = stats.trend.as_ref();
+    row(
+        "Commits",
+        format!(
+            "{} in {} {projects}{}",
+            stats.commits,
+            stats.projects,
+            trend_suffix(trend.map(|t| (t.commits_change, t.previous_commits)))
+        ),
+    );
+    row("Calendar days", stats.calendar_days.to_string());
+    let holidays = match stats.holidays {
+        0 => String::new(),
+        1 => " (1 holiday excluded)".to_string(),
+        n => format!(" ({n} holidays excluded)"),
+    };
+    row(
+        "Expected workdays",
+        format!("{}{holidays}", stats.expected_workdays),
+    );
+    row(
+        "Active days",
+        format!(
+            "{} ({} of {} workdays){}",
+            stats.active_days,
+            stats.active_workdays,
+            stats.expected_workdays,
+            trend_suffix(trend.map(|t| (t.active_days_change, t.previous_active_days)))
+        ),
+    );
+    if let Some(v) = &stats.verified {
+        row(
+            "Verified commits",
+            format!("{:.1}% ({} of {})", v.percent, v.verified, v.checked),
+        );
+    }
+    row(
+        "Off-day work",
+        match stats.off_days_worked.len() {
+            0 => "none".to_string(),
+            1 => "1 day".to_string(),
+            n => format!("{n} days"),
+        },
+    );
+    for day in &stats.off_days_worked {
+        let kind = day.holiday.as_deref().unwrap_or("weekend");
+        let commits = if day.commits == 1 {
+            "commit"
+        } else {
+            "commits"
+        };
+        let kind = if color_enabled() {
+            kind.yellow().to_string()
+        } else {
+            kind.to_string()
+        };
+        outln!(
+            "    {}  {kind}  {}",
+            day.date.format("%Y-%m-%d %a"),
+            format!("({} {commits})", day.commits).dimmed()
+        );
+    }
+}
+
+/// `  ↑ 20% (vs. 10)`: change against the previous period.
+fn trend_suffix(trend: Option<(Option<f64>, usize)>) -> String {
+    let Some((change, previous)) = trend else {
+        return String::new();
+    };
+    let Some(change) = change else {
+        return format!("  {}", "(none in previous period)".dimmed());
+    };
+    let arrow = if change > 0.0 {
+        "\u{2191}"
+    } else if change < 0.0 {
+        "\u{2193}"
+    } else {
+        "\u{2192}"
+    };
+    let text = format!("{arrow} {}%", change.abs());
+    let text = match (color_enabled(), change) {
+        (true, c) if c > 0.0 => text.green().to_string(),
+        (true, c) if c < 0.0 => text.red().to_string(),
+        _ => text,
+    };
+    format!("  {text} {}", format!("(vs. {previous})").dimmed())
+}
+
+pub fn render_stats_json(stats: &Stats) -> String {
+    serde_json::to_string_pretty(stats).unwrap_or_else(|_| "{}".to_string())
+}
+
+pub fn render_dco(statuses: &[DcoStatus]) {
+    if statuses.is_empty() {
+        info("No commits found for the given period.".dimmed());
+        return;
+    }
+    for status in statuses {
+        let name = if color_enabled() {
+            status.project.bold().white().to_string()
+        } else {
+            status.project.clone()
+        };
+        if status.missing.is_empty() {
+            let summary = format!("all {} commits signed off", status.commits);
+            outln!("{} {name}  {}", "::".bold(), summary.green());
+            continue;
+        }
+        let summary = format!(
+            "{} of {} commits missing sign-off",
+            status.missing.len(),
+            status.commits
+        );
+        outln!("{} {name}  {}", "::".bold(), summary.red());
+        for commit in &status.missing {
+            outln!(
+                "    {} {} {}  {}",
+                "*".dimmed(),
+                commit.hash.dimmed(),
+                commit.message,
+                format!("{}, {}", commit.author, commit.relative_time).dimmed()
+            );
+        }
+    }
+}
+
+pub fn render_dco_json(statuses: &[DcoStatus]) -> String {
+    serde_json::to_string_pretty(statuses).unwrap_or_else(|_| "[]".to_string())
+}
+
+pub fn render_unpushed(statuses: &[UnpushedStatus]) {
+    if statuses.is_empty() {
+        outln!("{}", "\u{2713} Everything pushed.".green());
+        return;
+    }
+    for status in statuses {
+        let name = if color_enabled() {
+            status.project.bold().white().to_string()
+        } else {
+            status.project.clone()
+        };
+        let summary = format!(
+            "{} unpushed {}",
+            status.commits.len(),
+            if status.commits.len() == 1 {
+                "commit"
+            } else {
+                "commits"
+            }
+        );
+        outln!("{} {name}  {}", "::".bold(), summary.yellow());
+        for commit in &status.commits {
+            outln!(
+                "    {} {} {}  {}",
+                "*".dimmed(),
+                commit.hash.dimmed(),
+                commit.message,
+                format!("{}, {}", commit.branches.join(", "), commit.relative_time).dimmed()
+            );
+        }
+    }
+}
+
+pub fn render_unpushed_json(statuses: &[UnpushedStatus]) -> String {
+    serde_json::to_string_pretty(statuses).unwrap_or_else(|_| "[]".to_string())
+}
+
+pub fn render_compliance(report: &ComplianceReport) {
+    if report.commits == 0 {
+        info("No commits found for the given period.".dimmed());
+        return;
+    }
+    outln!(
+        "  {}  {:.1}% {}",
+        "Conventional commits".dimmed(),
+        report.percent,
+        format!("({} of {})", report.conventional, report.commits).dimmed()
+    );
+    for (heading, rows) in [("Projects", &report.projects), ("Authors", &report.authors)] {
+        outln!();
+        outln!("  {}", heading.bold());
+        let width = rows
+            .iter()
+            .map(|r| r.name.chars().count())
+            .max()
+            .unwrap_or(0);
+        for row in rows {
+            outln!(
+                "    {:<width$}  {:>5.1}%  {}",
+                row.name,
+                row.percent,
+                format!("({} of {})", row.conventional, row.commits).dimmed()
+            );
+        }
+    }
+}
+
+pub fn render_compliance_json(report: &ComplianceReport) -> String {
+    serde_json::to_string_pretty(report).unwrap_or_else(|_| "{}".to_string())
+}
+
+pub fn render_pairing(report: &PairingReport) {
+    if report.commits == 0 {
+        info("No commits found for the given period.".dimmed());
+        return;
+    }
+    outln!(
+        "  {}  {:.1}% {}",
+        "Paired commits".dimmed(),
+        report.percent,
+        format!("({} of {})", report.paired, report.commits).dimmed()
+    );
+    if report.partners.is_empty() {
+        return;
+    }
+    outln!();
+    outln!("  {}", "Partners".bold());
+    let width = report
+        .partners
+        .iter()
+        .map(|p| p.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let now = Local::now().fixed_offset();
+    for partner in &report.partners {
+        let label = if partner.commits == 1 {
+            "commit"
+        } else {
+            "commits"
+        };
+        outln!(
+            "    {:<width$}  {:>3} {label:<7}  {}  {}",
+            partner.name,
+            partner.commits,
+            partner.projects.join(", "),
+            format!("last {} ago", format_elapsed(now - partner.last)).dimmed()
+        );
+    }
+}
+
+pub fn render_pairing_json(report: &PairingReport) -> String {
+    serde_json::to_string_pretty(report).unwrap_or_else(|_| "{}".to_string())
+}
+
+pub fn render_exported(exported: &Exported) {
+    let target = match exported.files.as_slice() {
+        [file] => file.display().to_string(),
+        files => match files.first().and_then(|f| f.parent()) {
+            Some(dir) => format!("{} files in {}", files.len(), dir.display()),
+            None => "nothing written".to_string(),
+        },
+    };
+    let name = if color_enabled() {
+        exported.project.bold().to_string()
+    } else {
+        exported.project.clone()
+    };
+    outln!("  {name}  {}", target.dimmed());
+}
+
+pub fn render_exported_json(exported: &[Exported]) -> String {
+    serde_json::to_string_pretty(exported).unwrap_or_else(|_| "[]".to_string())
+}
+
+pub fn render_timesheet(entries: &[Entry]) {
+    if entries.is_empty() {
+        info("No time to book for the given period.".dimmed());
+        return;
+    }
+    let width = entries.iter().map(|e| e.repo.len()).max().unwrap_or(0);
+    for entry in entries {
+        let target = match &entry.task {
+            Some(task) => format!("{}/{task}", entry.project),
+            None => entry.project.clone(),
+        };
+        outln!(
+            "  {}  {:<width$}  {:>6}  {}  {}",
+            entry.date.format("%a %Y-%m-%d").to_string().dimmed(),
+            entry.repo,
+            entry.time.to_string().cyan(),
+            target.dimmed(),
+            entry.notes
+        );
+    }
+}
+
+pub fn render_timesheet_json(entries: &[Entry]) -> String {
+    serde_json::to_string_pretty(entries).unwrap_or_else(|_| "[]".to_string())
 }
 
 pub fn summary_line(projects: &[ProjectLog]) -> String {
@@ -257,7 +1517,6 @@ pub fn summary_line(projects: &[ProjectLog]) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Local;
     use std::sync::atomic::{AtomicU32, Ordering};
 
     static COUNTER: AtomicU32 = AtomicU32::new(0);
@@ -268,13 +1527,30 @@ mod tests {
             hash: format!("{id:07x}"),
             message: message.to_string(),
             commit_type: commit_type.map(String::from),
-            time: Local::now(),
+            time: Local::now().fixed_offset(),
             relative_time: "1h ago".to_string(),
-            url: None,
-            diff_stat: None,
+            ..Default::default()
         }
     }
 
+    #[test]
+    fn json_commits_grouped_by_day() {
+        let commits = serde_json::json!([
+            { "hash": "c3", "timestamp": "2026-05-13T09:00:00+02:00" },
+            { "hash": "c2", "timestamp": "2026-05-12T23:30:00+02:00" },
+            { "hash": "c1", "timestamp": "2026-05-12T08:00:00+02:00" },
+        ]);
+        let days = group_by_day(commits);
+        assert_eq!(days[0]["date"], "2026-05-13");
+        assert_eq!(days[0]["commits"].as_array().map(Vec::len), Some(1));
+        assert_eq!(days[1]["date"], "2026-05-12");
+        assert_eq!(days[1]["commits"][1]["hash"], "c1");
+        assert_eq!(
+            day_header(NaiveDate::from_ymd_opt(2026, 5, 12).expect("valid date")),
+            "Tue 12 May"
+        );
+    }
+
     #[test]
     fn summary_no_commits() {
         assert_eq!(summary_line(&[]), "No commits found.");
@@ -285,15 +1561,12 @@ mod tests {
         let projects = vec![ProjectLog {
             project: "test".to_string(),
             path: "/test".to_string(),
-            origin: None,
-            remote_url: None,
             branches: vec![BranchLog {
                 name: "main".to_string(),
-                url: None,
                 commits: vec![make_commit("test", None)],
-                diff_stat: None,
+                ..Default::default()
             }],
-            diff_stat: None,
+            ..Default::default()
         }];
         assert_eq!(summary_line(&projects), "Found 1 commit in 1 project");
     }
@@ -304,33 +1577,164 @@ mod tests {
             ProjectLog {
                 project: "a".to_string(),
                 path: "/a".to_string(),
-                origin: None,
-                remote_url: None,
                 branches: vec![BranchLog {
                     name: "main".to_string(),
-                    url: None,
                     commits: vec![make_commit("1", None), make_commit("2", None)],
-                    diff_stat: None,
+                    ..Default::default()
                 }],
-                diff_stat: None,
+                ..Default::default()
             },
             ProjectLog {
                 project: "b".to_string(),
                 path: "/b".to_string(),
-                origin: None,
-                remote_url: None,
                 branches: vec![BranchLog {
                     name: "main".to_string(),
-                    url: None,
                     commits: vec![make_commit("3", None)],
-                    diff_stat: None,
+                    ..Default::default()
                 }],
-                diff_stat: None,
+                ..Default::default()
             },
         ];
         assert_eq!(summary_line(&projects), "Found 3 commits in 2 projects");
     }
 
+    #[test]
+    fn branch_span_suffix_shows_span_and_start() {
+        let now = Local::now().fixed_offset();
+        let mut first = make_commit("a", None);
+        first.time = now - chrono::Duration::hours(51);
+        let mut last = make_commit("b", None);
+        last.time = now - chrono::Duration::hours(2);
+        let mut branch = BranchLog {
+            name: "feature/x".to_string(),
+            commits: vec![last, first],
+            started: Some(now - chrono::Duration::days(12)),
+            ..Default::default()
+        };
+        assert_eq!(
+            branch_span_suffix(&branch, now),
+            ", spans 2d 1h, started 12d ago"
+        );
+
+        branch.commits.truncate(1);
+        branch.started = None;
+        assert_eq!(branch_span_suffix(&branch, now), "");
+    }
+
+    #[test]
+    fn sync_suffix_shows_nonzero_counts() {
+        let mut branch = BranchLog {
+            name: "feature/x".to_string(),
+            commits: vec![make_commit("a", None)],
+            ..Default::default()
+        };
+        assert_eq!(sync_suffix(&branch), "");
+        let mut upstream = model::Upstream {
+            name: "origin/feature/x".to_string(),
+            ahead: 2,
+            behind: 0,
+        };
+        branch.upstream = Some(upstream.clone());
+        assert_eq!(sync_suffix(&branch), ", \u{2191}2");
+        upstream.behind = 1;
+        branch.upstream = Some(upstream.clone());
+        assert_eq!(sync_suffix(&branch), ", \u{2191}2 \u{2193}1");
+        upstream.ahead = 0;
+        upstream.behind = 0;
+        branch.upstream = Some(upstream);
+        assert_eq!(sync_suffix(&branch), ", in sync");
+    }
+
+    #[test]
+    fn branch_suffix_marks_merged_before_pr() {
+        let mut branch = BranchLog {
+            name: "feature/x".to_string(),
+            commits: vec![make_commit("a", None)],
+            pr: Some(model::PullRequest {
+                number: 42,
+                title: "Add login".to_string(),
+                url: String::new(),
+                state: PrState::Open,
+            }),
+            merged: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(
+            branch_suffix(&branch),
+            " \u{2713} merged \u{2192} \"Add login\" (#42)"
+        );
+        if let Some(pr) = branch.pr.as_mut() {
+            pr.state = PrState::Merged;
+        }
+        assert_eq!(
+            branch_suffix(&branch),
+            " \u{2713} merged \u{2192} \"Add login\" (#42, merged)"
+        );
+        branch.merged = Some(false);
+        branch.pr = None;
+        assert_eq!(branch_suffix(&branch), "");
+        branch.worktree = Some("/src/app-login".to_string());
+        assert_eq!(branch_suffix(&branch), " [worktree app-login]");
+    }
+
+    #[test]
+    fn json_respects_depth() {
+        let projects = vec![ProjectLog {
+            project: "api".to_string(),
+            path: "/api".to_string(),
+            branches: vec![BranchLog {
+                name: "main".to_string(),
+                commits: vec![make_commit("a", None), make_commit("b", None)],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }];
+        let parse = |depth| -> serde_json::Value {
+            serde_json::from_str(&render_json(&projects, depth)).expect("valid json")
+        };
+
+        let full = parse(Depth::Commits);
+        assert_eq!(full[0]["branches"][0]["commits"][1]["message"], "b");
+
+        let branches = parse(Depth::Branches);
+        assert!(branches[0]["branches"][0].get("commits").is_none());
+        assert_eq!(branches[0]["branches"][0]["commit_count"], 2);
+
+        let summary = parse(Depth::Projects);
+        assert!(summary[0].get("branches").is_none());
+        assert_eq!(summary[0]["branch_count"], 1);
+        assert_eq!(summary[0]["commit_count"], 2);
+    }
+
+    #[test]
+    fn json_flags_truncated_branches() {
+        let mut projects = vec![ProjectLog {
+            project: "api".to_string(),
+            path: "/api".to_string(),
+            branches: vec![BranchLog {
+                name: "main".to_string(),
+                commits: vec![make_commit("a", None), make_commit("b", None)],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }];
+        assert!(projects_json(&projects, Depth::Commits)[0]["branches"][0]
+            .get("truncated")
+            .is_none());
+
+        devcap_core::budget::limit_branch_commits(&mut projects, 1);
+        let json = projects_json(&projects, Depth::Commits);
+        assert_eq!(json[0]["branches"][0]["truncated"], true);
+        assert_eq!(json[0]["branches"][0]["omitted"]["commits"], 1);
+    }
+
+    #[test]
+    fn format_elapsed_units() {
+        assert_eq!(format_elapsed(chrono::Duration::minutes(45)), "45m");
+        assert_eq!(format_elapsed(chrono::Duration::minutes(5 * 60 + 10)), "5h");
+        assert_eq!(format_elapsed(chrono::Duration::days(3)), "3d");
+    }
+
     #[test]
     fn tag_feat_is_not_empty() {
         let commit = make_commit("feat: add feature", Some("feat"));
@@ -353,4 +1757,96 @@ mod tests {
     fn strip_prefix_keeps_plain_message() {
         assert_eq!(strip_type_prefix("update readme"), "update readme");
     }
+
+    #[test]
+    fn footer_line_summarizes_totals() {
+        let totals = Totals {
+            commits: 12,
+            projects: 3,
+            branches: 1,
+            estimated: WorkTime::from_minutes(390),
+            busiest: Some(devcap_core::totals::Busiest {
+                project: "web".to_string(),
+                commits: 7,
+            }),
+            by_type: Vec::new(),
+            first: None,
+            last: None,
+        };
+        assert_eq!(
+            footer_line(&totals),
+            "Total: 12 commits, 3 projects, 1 branch, est. 6h30m \u{2014} busiest: web (7 commits)"
+        );
+    }
+
+    #[test]
+    fn heatmap_grid_has_a_row_per_weekday() {
+        let mut heatmap = Heatmap::default();
+        heatmap.counts[0][9] = 4;
+        heatmap.counts[0][10] = 1;
+        heatmap.counts[6][23] = 2;
+        let lines = heatmap_lines(&heatmap);
+        assert_eq!(lines.len(), 9);
+        assert_eq!(
+            lines[1],
+            "      0     3     6     9     12    15    18    21"
+        );
+        let monday = &lines[2];
+        assert!(monday.starts_with("  Mon \u{00b7} "));
+        assert!(monday.contains("\u{2588}\u{2588}\u{2591}\u{2591}"));
+        assert!(monday.ends_with("  5"));
+        assert!(lines[8].ends_with("\u{2592}\u{2592}  2"));
+    }
+
+    #[test]
+    fn summary_lists_types_and_commit_times() {
+        use chrono::TimeZone;
+        let at = |h: u32, m: u32| {
+            Local
+                .with_ymd_and_hms(2026, 3, 2, h, m, 0)
+                .single()
+                .expect("unambiguous time")
+                .fixed_offset()
+        };
+        let count = |commit_type: &str, commits: usize| devcap_core::totals::TypeCount {
+            commit_type: commit_type.to_string(),
+            commits,
+        };
+        let mut totals = Totals {
+            commits: 4,
+            projects: 2,
+            branches: 2,
+            estimated: WorkTime::from_minutes(90),
+            busiest: Some(devcap_core::totals::Busiest {
+                project: "web".to_string(),
+                commits: 3,
+            }),
+            by_type: vec![count("feat", 2), count("fix", 1), count("other", 1)],
+            first: Some(at(8, 12)),
+            last: Some(at(18, 40)),
+        };
+        assert_eq!(
+            summary_text(&totals),
+            "Summary\n  Commits:   4\n  By type:   2 feat, 1 fix, 1 other\n  \
+             Projects:  2 active\n  Busiest:   web (3 commits)\n  \
+             First:     08:12\n  Last:      18:40"
+        );
+
+        totals.last = Some(at(18, 40) + chrono::Duration::days(4));
+        let text = summary_text(&totals);
+        assert!(text.ends_with("First:     Mon 02 Mar 08:12\n  Last:      Fri 06 Mar 18:40"));
+    }
+
+    #[test]
+    fn trend_suffix_shows_direction() {
+        assert_eq!(
+            dialoguer::console::strip_ansi_codes(&trend_suffix(Some((Some(20.0), 10)))),
+            "  \u{2191} 20% (vs. 10)"
+        );
+        assert_eq!(
+            dialoguer::console::strip_ansi_codes(&trend_suffix(Some((Some(-12.5), 8)))),
+            "  \u{2193} 12.5% (vs. 8)"
+        );
+        assert_eq!(trend_suffix(None), "");
+    }
 }
@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+/// `$HOME`, or `%USERPROFILE%` on Windows where `HOME` is usually unset.
+pub fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .filter(|h| !h.is_empty())
+        .map(PathBuf::from)
+}
+
+/// `$XDG_CONFIG_HOME`, falling back to `%APPDATA%` on Windows and
+/// `~/.config` elsewhere.
+pub fn config_home() -> Option<PathBuf> {
+    xdg_base("XDG_CONFIG_HOME")
+        .or_else(|| {
+            std::env::var_os("APPDATA")
+                .map(PathBuf::from)
+                .filter(|_| cfg!(windows))
+        })
+        .or_else(|| home_dir().map(|h| h.join(".config")))
+}
+
+/// `$XDG_DATA_HOME/devcap`, falling back to `~/.local/share/devcap`.
+pub fn data_dir() -> Option<PathBuf> {
+    let base = xdg_base("XDG_DATA_HOME").or_else(|| home_dir().map(|h| h.join(".local/share")))?;
+    Some(base.join("devcap"))
+}
+
+/// `$XDG_CACHE_HOME/devcap`, falling back to `~/.cache/devcap`.
+pub fn cache_dir() -> Option<PathBuf> {
+    let base = xdg_base("XDG_CACHE_HOME").or_else(|| home_dir().map(|h| h.join(".cache")))?;
+    Some(base.join("devcap"))
+}
+
+/// An XDG base directory variable; relative paths are to be ignored.
+fn xdg_base(var: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+}
@@ -0,0 +1,277 @@
+use std::collections::BTreeSet;
+use std::io;
+
+use colored::Colorize;
+use dialoguer::console::{self, Key, Term};
+use dialoguer::theme::Theme;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+
+use crate::keys::{Action, Keymap};
+
+/// Result of a picker interaction. Indices refer to the original item list.
+pub enum Outcome {
+    Cancel,
+    Pick(usize),
+    Action(Action, Option<usize>),
+}
+
+/// Shows the cursor again when dropped, also when the prompt fails while it
+/// is hidden.
+struct CursorGuard<'a>(&'a Term);
+
+impl Drop for CursorGuard<'_> {
+    fn drop(&mut self) {
+        let _ = self.0.show_cursor();
+    }
+}
+
+/// Fuzzy-filtering list prompt whose keys are driven by a [`Keymap`].
+pub struct Picker<'a> {
+    theme: &'a dyn Theme,
+    keymap: &'a Keymap,
+    prompt: &'a str,
+    items: &'a [String],
+    marks: Option<&'a mut BTreeSet<usize>>,
+    first_markable: usize,
+    query: String,
+}
+
+impl<'a> Picker<'a> {
+    pub fn new(
+        theme: &'a dyn Theme,
+        keymap: &'a Keymap,
+        prompt: &'a str,
+        items: &'a [String],
+    ) -> Self {
+        Self {
+            theme,
+            keymap,
+            prompt,
+            items,
+            marks: None,
+            first_markable: 0,
+            query: String::new(),
+        }
+    }
+
+    /// Start with a pre-filled search filter.
+    pub fn with_query(mut self, query: &str) -> Self {
+        self.query = query.to_string();
+        self
+    }
+
+    /// The search filter as it was when the prompt was closed.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Enable marking items (from `first_markable` on) with the toggle key.
+    /// Marks are kept in `marks` as original item indices.
+    pub fn with_marks(mut self, marks: &'a mut BTreeSet<usize>, first_markable: usize) -> Self {
+        self.marks = Some(marks);
+        self.first_markable = first_markable;
+        self
+    }
+
+    pub fn interact(&mut self) -> io::Result<Outcome> {
+        let term = Term::stderr();
+        let matcher = SkimMatcherV2::default();
+        let mut query = std::mem::take(&mut self.query);
+        let mut sel = 0usize;
+        let mut offset = 0usize;
+        let mut drawn = 0usize;
+
+        term.hide_cursor()?;
+        let cursor = CursorGuard(&term);
+        let outcome = loop {
+            let filtered = filter_items(&matcher, self.items, &query);
+            let (rows, cols) = term.size();
+            let visible = (rows as usize).saturating_sub(3).max(1);
+            sel = sel.min(filtered.len().saturating_sub(1));
+            if sel < offset {
+                offset = sel;
+            } else if sel >= offset + visible {
+                offset = sel + 1 - visible;
+            }
+
+            term.clear_last_lines(drawn)?;
+            let lines = self.render_lines(&filtered, &query, sel, offset, visible);
+            for line in &lines {
+                let width = (cols as usize).saturating_sub(1);
+                term.write_line(&console::truncate_str(line, width, "\u{2026}"))?;
+            }
+            drawn = lines.len();
+
+            let key = term.read_key()?;
+            match self.keymap.action_for(&key, query.is_empty()) {
+                Some(Action::Up) => {
+                    sel = if sel == 0 {
+                        filtered.len().saturating_sub(1)
+                    } else {
+                        sel - 1
+                    };
+                }
+                Some(Action::Down) => {
+                    sel = if sel + 1 >= filtered.len() {
+                        0
+                    } else {
+                        sel + 1
+                    };
+                }
+                Some(Action::Select) => {
+                    if let Some(&idx) = filtered.get(sel) {
+                        break Outcome::Pick(idx);
+                    }
+                }
+                Some(Action::Back) => break Outcome::Cancel,
+                Some(Action::Toggle) => {
+                    if let (Some(marks), Some(&idx)) =
+                        (self.marks.as_deref_mut(), filtered.get(sel))
+                    {
+                        if idx >= self.first_markable && !marks.remove(&idx) {
+                            marks.insert(idx);
+                        }
+                        sel = if sel + 1 >= filtered.len() {
+                            0
+                        } else {
+                            sel + 1
+                        };
+                    }
+                }
+                Some(action) => break Outcome::Action(action, filtered.get(sel).copied()),
+                None => match key {
+                    Key::Backspace => {
+                        query.pop();
+                        sel = 0;
+                    }
+                    Key::Char(c) if !c.is_control() => {
+                        query.push(c);
+                        sel = 0;
+                    }
+                    _ => {}
+                },
+            }
+        };
+        term.clear_last_lines(drawn)?;
+        drop(cursor);
+        self.query = query;
+
+        if let Outcome::Pick(idx) = outcome {
+            let mut report = String::new();
+            let clean = console::strip_ansi_codes(&self.items[idx]);
+            if self
+                .theme
+                .format_input_prompt_selection(&mut report, self.prompt, &clean)
+                .is_ok()
+            {
+                term.write_line(&report)?;
+            }
+        }
+        Ok(outcome)
+    }
+
+    fn render_lines(
+        &self,
+        filtered: &[usize],
+        query: &str,
+        sel: usize,
+        offset: usize,
+        visible: usize,
+    ) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut prompt = String::new();
+        self.theme
+            .format_fuzzy_select_prompt(&mut prompt, self.prompt, query, query.len())
+            .ok();
+        lines.push(prompt);
+        for (pos, &idx) in filtered.iter().enumerate().skip(offset).take(visible) {
+            let text = match self.marks.as_deref() {
+                Some(marks) if idx >= self.first_markable => {
+                    let marker = if marks.contains(&idx) {
+                        "\u{25c9}".green().to_string()
+                    } else {
+                        "\u{25cb}".dimmed().to_string()
+                    };
+                    format!("{marker} {}", self.items[idx])
+                }
+                _ => self.items[idx].clone(),
+            };
+            let mut item = String::new();
+            self.theme
+                .format_select_prompt_item(&mut item, &text, pos == sel)
+                .ok();
+            lines.push(item);
+        }
+        let mut actions = vec![
+            Action::Back,
+            Action::ShowAll,
+            Action::Open,
+            Action::Copy,
+            Action::Edit,
+            Action::Cd,
+            Action::Run,
+        ];
+        if self.marks.is_some() {
+            actions.push(Action::Toggle);
+            actions.push(Action::CopyHash);
+        }
+        let mut hint = self.keymap.hint(&actions);
+        if let Some(marks) = self.marks.as_deref().filter(|m| !m.is_empty()) {
+            hint.push_str(&format!(" \u{b7} {} marked", marks.len()));
+        }
+        lines.push(format!("  {}", hint.dimmed()));
+        lines
+    }
+}
+
+/// Indices of items matching `query`, best match first. An empty query keeps
+/// the original order.
+fn filter_items(matcher: &SkimMatcherV2, items: &[String], query: &str) -> Vec<usize> {
+    if query.is_empty() {
+        return (0..items.len()).collect();
+    }
+    let mut scored: Vec<(usize, i64)> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(i, item)| {
+            let clean = console::strip_ansi_codes(item);
+            matcher.fuzzy_match(&clean, query).map(|score| (i, score))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    scored.into_iter().map(|(i, _)| i).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn empty_query_keeps_order() {
+        let matcher = SkimMatcherV2::default();
+        let list = items(&["b", "a", "c"]);
+        assert_eq!(filter_items(&matcher, &list, ""), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn query_filters_non_matches() {
+        let matcher = SkimMatcherV2::default();
+        let list = items(&["api-server", "web-client", "api-gateway"]);
+        let result = filter_items(&matcher, &list, "api");
+        assert_eq!(result.len(), 2);
+        assert!(!result.contains(&1));
+    }
+
+    #[test]
+    fn query_ignores_ansi_codes() {
+        let matcher = SkimMatcherV2::default();
+        let list = vec!["\x1b[32mmain\x1b[0m".to_string()];
+        assert_eq!(filter_items(&matcher, &list, "32m"), Vec::<usize>::new());
+        assert_eq!(filter_items(&matcher, &list, "main"), vec![0]);
+    }
+}
@@ -0,0 +1,122 @@
+//! Output format plugins. A plugin is a `<name>.wasm` module in the plugin
+//! directory, run as an external command: `plugin_command` (by default
+//! `wasmtime run`) with the module path appended. devcap does not host the
+//! module itself, so the command must be installed, and any sandboxing is
+//! up to it.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context, Result};
+
+use crate::paths;
+
+/// Command that runs plugin modules when `plugin_command` is not set.
+pub const DEFAULT_COMMAND: &str = "wasmtime run";
+
+/// `<data dir>/plugins`, where `<name>.wasm` modules are looked up.
+pub fn plugins_dir() -> Option<PathBuf> {
+    Some(paths::data_dir()?.join("plugins"))
+}
+
+/// Names of all installed plugins, sorted.
+pub fn list() -> Vec<String> {
+    let Some(dir) = plugins_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "wasm"))
+        .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Path of an installed plugin module, or an error naming the expected location.
+pub fn module_path(name: &str) -> Result<PathBuf> {
+    let dir = plugins_dir().context("cannot determine plugin directory")?;
+    let path = dir.join(format!("{name}.wasm"));
+    if !path.is_file() {
+        bail!(
+            "unknown format \"{name}\": no plugin at {} (built-in formats: text, json, changelog)",
+            path.display()
+        );
+    }
+    Ok(path)
+}
+
+/// Run the plugin `name` through `command`: the JSON model goes to its
+/// stdin, whatever it writes to stdout is the rendered output.
+pub fn render(name: &str, json: &str, command: &str) -> Result<String> {
+    let module = module_path(name)?;
+    let stdout = run(name, command, &module, json)?;
+    String::from_utf8(stdout).with_context(|| format!("plugin \"{name}\" returned invalid UTF-8"))
+}
+
+/// Run `command` (split on whitespace) with `module` appended, feeding it
+/// `input`, and collect its stdout.
+fn run(name: &str, command: &str, module: &Path, input: &str) -> Result<Vec<u8>> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next().context("plugin_command is empty")?;
+
+    let mut child = Command::new(program)
+        .args(parts)
+        .arg(module)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .with_context(|| {
+            format!(
+                "cannot start plugin command \"{program}\" (install it or set plugin_command in ~/.devcap.toml)"
+            )
+        })?;
+
+    // Written from another thread: a plugin that writes while it reads
+    // would otherwise block on a full stdout pipe, and devcap on its stdin.
+    let stdin = child.stdin.take();
+    let (written, output) = std::thread::scope(|scope| {
+        let writer = scope.spawn(move || match stdin {
+            Some(mut stdin) => stdin.write_all(input.as_bytes()),
+            None => Ok(()),
+        });
+        let output = child.wait_with_output();
+        (writer.join(), output)
+    });
+    let output = output.context("plugin did not finish")?;
+    if !output.status.success() {
+        bail!("plugin \"{name}\" failed with {}", output.status);
+    }
+    match written {
+        // A plugin may stop reading once it has seen enough.
+        Ok(Err(e)) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+            Err(e).context("cannot send data to plugin")
+        }
+        Err(_) => bail!("cannot send data to plugin"),
+        _ => Ok(output.stdout),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn plugin_output_is_read_while_input_is_written() {
+        let module =
+            std::env::temp_dir().join(format!("devcap-plugin-{}.wasm", std::process::id()));
+        std::fs::write(&module, "").expect("write module");
+        // Far more than both pipe buffers hold, echoed back as it is read.
+        let input = "x".repeat(4 * 1024 * 1024);
+        let output = run("echo", "cat -", &module, &input).expect("run plugin");
+        std::fs::remove_file(&module).ok();
+        assert_eq!(output.len(), input.len());
+    }
+}
@@ -0,0 +1,172 @@
+//! `--schema`: the JSON Schema of the `--json` commit report, for tools
+//! that validate their input.
+
+/// JSON Schema (draft 2020-12) of `devcap --json`.
+pub const REPORT: &str = include_str!("schema.json");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Depth;
+    use crate::output;
+    use devcap_core::model::ProjectLog;
+    use devcap_core::tags::{RepoTags, Tag};
+    use devcap_core::wip::WorkInProgress;
+    use serde_json::{json, Value};
+
+    /// The parts of JSON Schema the report schema uses.
+    fn validate(value: &Value, schema: &Value, root: &Value, at: &str) -> Result<(), String> {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = reference.trim_start_matches("#/$defs/");
+            return validate(value, &root["$defs"][name], root, at);
+        }
+        if let Some(options) = schema.get("oneOf").and_then(Value::as_array) {
+            let matching = options
+                .iter()
+                .filter(|o| validate(value, o, root, at).is_ok())
+                .count();
+            return (matching == 1)
+                .then_some(())
+                .ok_or(format!("{at}: matches {matching} alternatives"));
+        }
+        if let Some(types) = schema.get("type") {
+            let kind = match value {
+                Value::Null => "null",
+                Value::Bool(_) => "boolean",
+                Value::Number(n) if n.is_u64() || n.is_i64() => "integer",
+                Value::Number(_) => "number",
+                Value::String(_) => "string",
+                Value::Array(_) => "array",
+                Value::Object(_) => "object",
+            };
+            let allowed = match types {
+                Value::Array(list) => list.iter().any(|t| t == kind),
+                t => t == kind,
+            };
+            if !allowed {
+                return Err(format!("{at}: {kind} is not {types}"));
+            }
+        }
+        if let (Value::Array(items), Some(item)) = (value, schema.get("items")) {
+            for (i, v) in items.iter().enumerate() {
+                validate(v, item, root, &format!("{at}[{i}]"))?;
+            }
+        }
+        if let Value::Object(map) = value {
+            for key in schema["required"].as_array().into_iter().flatten() {
+                let key = key.as_str().unwrap_or_default();
+                if !map.contains_key(key) {
+                    return Err(format!("{at}: missing {key}"));
+                }
+            }
+            for (key, v) in map {
+                let at = format!("{at}.{key}");
+                match (
+                    schema["properties"].get(key),
+                    &schema["additionalProperties"],
+                ) {
+                    (Some(property), _) => validate(v, property, root, &at)?,
+                    (None, Value::Bool(false)) => return Err(format!("{at}: not in schema")),
+                    (None, Value::Object(_)) => {
+                        validate(v, &schema["additionalProperties"], root, &at)?
+                    }
+                    (None, _) => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn projects() -> Vec<ProjectLog> {
+        let stat = json!({ "files_changed": 2, "insertions": 10, "deletions": 3 });
+        serde_json::from_value(json!([{
+            "project": "api",
+            "path": "/src/api",
+            "origin": "github",
+            "remote_url": "https://github.com/acme/api",
+            "group": "Acme",
+            "diff_stat": stat,
+            "activity": [0, 2],
+            "releases": [{ "tag": "v1.2.0", "timestamp": "2026-03-02T10:00:00+01:00", "url": "https://github.com/acme/api/releases/tag/v1.2.0" }],
+            "health": { "branch": null, "changes": 1, "stashes": 0, "unpushed": null },
+            "branches": [{
+                "name": "feature/export",
+                "url": "https://github.com/acme/api/tree/feature/export",
+                "diff_stat": stat,
+                "pr": { "number": 7, "title": "Export", "url": "https://github.com/acme/api/pull/7", "state": "open" },
+                "started": "2026-03-01T09:00:00+01:00",
+                "merged": false,
+                "upstream": { "name": "origin/feature/export", "ahead": 1, "behind": 0 },
+                "omitted": { "commits": 2, "types": { "fix": 2 } },
+                "worktree": "/src/api-export",
+                "commits": [{
+                    "hash": "a1b2c3d",
+                    "message": "feat: add export",
+                    "author": "Jane Doe",
+                    "author_email": "jane@example.com",
+                    "commit_type": "feat",
+                    "timestamp": "2026-03-02T10:00:00+01:00",
+                    "relative_time": "2h ago",
+                    "url": "https://github.com/acme/api/commit/a1b2c3d",
+                    "diff_stat": stat,
+                    "time_spent": 90,
+                    "signed_off_by": ["Jane Doe <jane@example.com>"],
+                    "co_authors": [{ "name": "Max", "email": "max@example.com" }],
+                    "unpushed": true,
+                    "verified": true,
+                }],
+            }],
+        }]))
+        .expect("valid projects")
+    }
+
+    #[test]
+    fn report_matches_schema_at_every_depth() {
+        let schema: Value = serde_json::from_str(REPORT).expect("schema is JSON");
+        let projects = projects();
+        for depth in [Depth::Commits, Depth::Branches, Depth::Projects] {
+            let report = output::projects_json(&projects, depth);
+            assert_eq!(
+                validate(&report, &schema, &schema, "$"),
+                Ok(()),
+                "{depth:?}"
+            );
+        }
+
+        let wip = WorkInProgress {
+            project: "api".to_string(),
+            path: "/src/api".to_string(),
+            changed: 1,
+            ..WorkInProgress::default()
+        };
+        let tags = RepoTags {
+            project: "api".to_string(),
+            path: "/src/api".to_string(),
+            tags: vec![Tag {
+                name: "v1.2.0".to_string(),
+                commit: "a1b2c3d".to_string(),
+                annotation: Some("Release 1.2".to_string()),
+                time: chrono::Local::now().fixed_offset(),
+                relative_time: "2h ago".to_string(),
+            }],
+        };
+        for (wip, tags) in [
+            (Some(vec![wip.clone()]), None),
+            (None, Some(vec![tags.clone()])),
+            (Some(vec![wip]), Some(vec![tags])),
+        ] {
+            let report: Value = serde_json::from_str(&output::render_json_with_sections(
+                &projects,
+                Depth::Commits,
+                wip.as_deref(),
+                tags.as_deref(),
+            ))
+            .expect("valid JSON");
+            assert_eq!(validate(&report, &schema, &schema, "$"), Ok(()));
+        }
+
+        let mut unknown = output::projects_json(&projects, Depth::Commits);
+        unknown[0]["branches"][0]["commits"][0]["extra"] = json!(1);
+        assert!(validate(&unknown, &schema, &schema, "$").is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::HashSet;
+
+use colored::Colorize;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use serde::Serialize;
+
+use crate::output;
+use devcap_core::model::{BranchLog, Commit, ProjectLog};
+
+/// A commit together with the project and branch it was found on.
+#[derive(Clone, Copy)]
+pub struct Entry<'a> {
+    pub project: &'a ProjectLog,
+    pub branch: &'a BranchLog,
+    pub commit: &'a Commit,
+}
+
+#[derive(Serialize)]
+struct JsonHit<'a> {
+    project: &'a str,
+    path: &'a str,
+    branch: &'a str,
+    #[serde(flatten)]
+    commit: &'a Commit,
+}
+
+/// Every commit across all projects, listed once per project even when it is
+/// reachable from several branches.
+pub fn all_commits(projects: &[ProjectLog]) -> Vec<Entry<'_>> {
+    let mut entries = Vec::new();
+    for project in projects {
+        let mut seen = HashSet::new();
+        for branch in &project.branches {
+            for commit in &branch.commits {
+                if seen.insert(commit.hash.as_str()) {
+                    entries.push(Entry {
+                        project,
+                        branch,
+                        commit,
+                    });
+                }
+            }
+        }
+    }
+    entries
+}
+
+/// The text a search term is matched against.
+pub fn haystack(entry: &Entry) -> String {
+    format!(
+        "{} {} {} {}",
+        entry.project.project, entry.branch.name, entry.commit.hash, entry.commit.message
+    )
+}
+
+/// Fuzzy-match `term` against all commits, best match first.
+pub fn search<'a>(projects: &'a [ProjectLog], term: &str) -> Vec<Entry<'a>> {
+    let matcher = SkimMatcherV2::default();
+    let mut scored: Vec<(i64, Entry)> = all_commits(projects)
+        .into_iter()
+        .filter_map(|e| matcher.fuzzy_match(&haystack(&e), term).map(|s| (s, e)))
+        .collect();
+    scored.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| b.1.commit.time.cmp(&a.1.commit.time))
+    });
+    scored.into_iter().map(|(_, e)| e).collect()
+}
+
+/// One-line rendering used by the terminal output and the interactive picker.
+pub fn format_entry(entry: &Entry) -> String {
+    let tag = output::commit_type_tag(entry.commit);
+    let msg = output::strip_type_prefix(&entry.commit.message);
+    let location = format!("{} \u{203a} {}", entry.project.project, entry.branch.name);
+    let location = if output::color_enabled() {
+        location.cyan().to_string()
+    } else {
+        location
+    };
+    let tag = if tag.is_empty() {
+        String::new()
+    } else {
+        format!("{tag} ")
+    };
+    format!(
+        "{location}  {} {tag}- {msg}  {}",
+        entry.commit.hash.dimmed(),
+        entry.commit.relative_time.dimmed()
+    )
+}
+
+pub fn render_terminal(entries: &[Entry]) {
+    if entries.is_empty() {
+        output::info("No matching commits found.".dimmed());
+        return;
+    }
+    for entry in entries {
+        output::outln!("{}", format_entry(entry));
+    }
+}
+
+pub fn render_json(entries: &[Entry]) -> String {
+    let hits: Vec<JsonHit> = entries
+        .iter()
+        .map(|e| JsonHit {
+            project: &e.project.project,
+            path: &e.project.path,
+            branch: &e.branch.name,
+            commit: e.commit,
+        })
+        .collect();
+    serde_json::to_string_pretty(&hits).unwrap_or_else(|_| "[]".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Local;
+
+    fn make_commit(hash: &str, message: &str) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            message: message.to_string(),
+            time: Local::now().fixed_offset(),
+            relative_time: "1h ago".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn make_projects() -> Vec<ProjectLog> {
+        vec![
+            ProjectLog {
+                project: "billing-api".to_string(),
+                path: "/src/billing-api".to_string(),
+                branches: vec![
+                    BranchLog {
+                        name: "main".to_string(),
+                        commits: vec![make_commit("aaa1111", "fix: rounding of invoices")],
+                        ..Default::default()
+                    },
+                    BranchLog {
+                        name: "feature/retry".to_string(),
+                        commits: vec![
+                            make_commit("bbb2222", "feat: retry failed payments"),
+                            make_commit("aaa1111", "fix: rounding of invoices"),
+                        ],
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            ProjectLog {
+                project: "website".to_string(),
+                path: "/src/website".to_string(),
+                branches: vec![BranchLog {
+                    name: "main".to_string(),
+                    commits: vec![make_commit("ccc3333", "docs: update pricing page")],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn all_commits_dedupes_within_project() {
+        let projects = make_projects();
+        let entries = all_commits(&projects);
+        assert_eq!(entries.len(), 3);
+    }
+
+    #[test]
+    fn search_matches_message() {
+        let projects = make_projects();
+        let hits = search(&projects, "invoice");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].commit.hash, "aaa1111");
+    }
+
+    #[test]
+    fn search_matches_project_and_branch() {
+        let projects = make_projects();
+        let hits = search(&projects, "website");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].project.project, "website");
+
+        let hits = search(&projects, "feature/retry");
+        assert!(hits.iter().any(|h| h.commit.hash == "bbb2222"));
+    }
+
+    #[test]
+    fn search_without_match_is_empty() {
+        let projects = make_projects();
+        assert!(search(&projects, "zzzzqqq").is_empty());
+    }
+
+    #[test]
+    fn json_hits_include_location() {
+        let projects = make_projects();
+        let hits = search(&projects, "pricing");
+        let json = render_json(&hits);
+        let value: serde_json::Value = serde_json::from_str(&json).expect("valid json");
+        assert_eq!(value[0]["project"], "website");
+        assert_eq!(value[0]["branch"], "main");
+        assert_eq!(value[0]["hash"], "ccc3333");
+    }
+}
@@ -0,0 +1,341 @@
+//! `--post slack`: the report as Slack Block Kit message, sent to an incoming
+//! webhook or posted to a channel with a bot token.
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::cli::Depth;
+use crate::config::SlackConfig;
+use crate::curl::Curl;
+use crate::output;
+use devcap_core::model::{BranchLog, Commit, ProjectLog};
+use devcap_core::period::TimeRange;
+
+const POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
+/// Slack rejects messages with more blocks than this.
+const MAX_BLOCKS: usize = 50;
+/// Maximum length of a section's text.
+const MAX_SECTION_CHARS: usize = 3000;
+/// Maximum length of a header's text.
+const MAX_HEADER_CHARS: usize = 150;
+
+/// Where the message goes.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Destination {
+    Webhook(String),
+    Channel { token: String, channel: String },
+}
+
+/// The configured destination, falling back to `SLACK_WEBHOOK_URL` and
+/// `SLACK_BOT_TOKEN` for the secrets. A webhook wins over a bot token.
+pub fn destination(cfg: Option<&SlackConfig>) -> Result<Destination> {
+    resolve(cfg, |key| std::env::var(key).ok().filter(|v| !v.is_empty()))
+}
+
+fn resolve(cfg: Option<&SlackConfig>, env: impl Fn(&str) -> Option<String>) -> Result<Destination> {
+    let webhook = cfg
+        .and_then(|c| c.webhook_url.clone())
+        .or_else(|| env("SLACK_WEBHOOK_URL"));
+    if let Some(url) = webhook {
+        return Ok(Destination::Webhook(url));
+    }
+    let token = cfg
+        .and_then(|c| c.token.clone())
+        .or_else(|| env("SLACK_BOT_TOKEN"));
+    match (token, cfg.and_then(|c| c.channel.clone())) {
+        (Some(token), Some(channel)) => Ok(Destination::Channel { token, channel }),
+        (Some(_), None) => anyhow::bail!("a Slack bot token needs a `channel` in [slack]"),
+        (None, _) => anyhow::bail!(
+            "no Slack destination: set `webhook_url`, or `token` and `channel`, in [slack]"
+        ),
+    }
+}
+
+/// Message title naming the reported days, e.g. `Worklog Mon 2026-03-02`.
+pub fn title(range: &TimeRange) -> String {
+    let first = range.since.date_naive();
+    // Ranges end at midnight or at the last second of their final day.
+    let last = range
+        .until
+        .map(|until| (until - chrono::Duration::seconds(1)).date_naive())
+        .unwrap_or_else(|| range.zone.now().date_naive());
+    if last <= first {
+        format!("Worklog {}", first.format("%a %Y-%m-%d"))
+    } else {
+        format!(
+            "Worklog {} \u{2013} {}",
+            first.format("%a %Y-%m-%d"),
+            last.format("%a %Y-%m-%d")
+        )
+    }
+}
+
+/// The message payload: a header with `title`, one section per project and
+/// a plain-text fallback for notifications.
+pub fn message(projects: &[ProjectLog], depth: Depth, title: &str) -> Value {
+    let summary = output::summary_line(projects);
+    let mut blocks = vec![
+        json!({
+            "type": "header",
+            "text": { "type": "plain_text", "text": truncate(title, MAX_HEADER_CHARS) },
+        }),
+        json!({
+            "type": "context",
+            "elements": [{ "type": "mrkdwn", "text": escape(&summary) }],
+        }),
+    ];
+    // Keep one block for the note about projects that did not fit.
+    let room = MAX_BLOCKS - blocks.len() - 1;
+    let sections = if depth == Depth::Projects {
+        projects
+            .chunks(20)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .map(project_line)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>()
+    } else {
+        projects.iter().map(|p| project_section(p, depth)).collect()
+    };
+    let shown = sections.len().min(room);
+    for text in &sections[..shown] {
+        blocks.push(section(text));
+    }
+    if sections.len() > shown {
+        let more = sections.len() - shown;
+        blocks.push(json!({
+            "type": "context",
+            "elements": [{ "type": "mrkdwn", "text": format!("\u{2026}and {more} more") }],
+        }));
+    }
+    json!({ "text": format!("{title}: {summary}"), "blocks": blocks })
+}
+
+/// Send `message` and check Slack's answer.
+pub fn post(destination: &Destination, message: &Value) -> Result<()> {
+    let (curl, body) = match destination {
+        Destination::Webhook(url) => (Curl::new(url, 15), message.clone()),
+        Destination::Channel { token, channel } => {
+            let mut body = message.clone();
+            body["channel"] = json!(channel);
+            let curl = Curl::new(POST_MESSAGE_URL, 15)
+                .secret_header(&format!("Authorization: Bearer {token}"));
+            (curl, body)
+        }
+    };
+    let response = curl
+        .header("Content-Type: application/json; charset=utf-8")
+        .data(&body.to_string())
+        .send("posting to Slack")?;
+    check_response(&response.status.to_string(), &response.text())
+}
+
+/// Webhooks answer `ok`, the Web API `{"ok": true}` or `{"ok": false,
+/// "error": "..."}`, with status 200 in both cases.
+fn check_response(status: &str, body: &str) -> Result<()> {
+    if let Ok(value) = serde_json::from_str::<Value>(body) {
+        if value.get("ok") == Some(&Value::Bool(false)) {
+            let error = value
+                .get("error")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown");
+            anyhow::bail!("Slack rejected the message: {error}");
+        }
+    }
+    if status != "200" {
+        anyhow::bail!("Slack answered HTTP {status}: {body}");
+    }
+    Ok(())
+}
+
+fn section(text: &str) -> Value {
+    json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": truncate(text, MAX_SECTION_CHARS) },
+    })
+}
+
+fn project_line(project: &ProjectLog) -> String {
+    let commits = project.total_commits();
+    format!(
+        "*{}*  {commits} {}, {}",
+        escape(&project.project),
+        if commits == 1 { "commit" } else { "commits" },
+        project.latest_activity().unwrap_or("-")
+    )
+}
+
+fn project_section(project: &ProjectLog, depth: Depth) -> String {
+    let mut out = format!("*{}*", escape(&project.project));
+    for branch in &project.branches {
+        out.push_str(&format!("\n{}", branch_line(branch)));
+        if depth == Depth::Commits {
+            for commit in &branch.commits {
+                out.push_str(&format!("\n\u{2003}\u{2022} {}", commit_line(commit)));
+            }
+            if let Some(omitted) = &branch.omitted {
+                out.push_str(&format!("\n\u{2003}_{}_", output::omitted_line(omitted)));
+            }
+        }
+    }
+    out
+}
+
+fn branch_line(branch: &BranchLog) -> String {
+    let count = branch.commits.len();
+    let name = match &branch.url {
+        Some(url) => format!("<{url}|{}>", escape(&branch.name)),
+        None => format!("`{}`", escape(&branch.name)),
+    };
+    format!(
+        "{name}  _{count} {}, {}_",
+        if count == 1 { "commit" } else { "commits" },
+        branch.latest_activity().unwrap_or("-")
+    )
+}
+
+fn commit_line(commit: &Commit) -> String {
+    let hash = match &commit.url {
+        Some(url) => format!("<{url}|`{}`>", commit.hash),
+        None => format!("`{}`", commit.hash),
+    };
+    format!(
+        "{hash} {}  _{}_",
+        escape(&commit.message),
+        escape(&output::commit_byline(commit))
+    )
+}
+
+/// Slack's control characters in mrkdwn text.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// At most `max` characters, cut at a line break when possible.
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max - 1).collect();
+    match cut.rfind('\n') {
+        Some(i) if i > 0 => format!("{}\n\u{2026}", &cut[..i]),
+        _ => format!("{cut}\u{2026}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use devcap_core::zone::Zone;
+
+    fn project(name: &str, commits: usize) -> ProjectLog {
+        let commits: Vec<Value> = (0..commits)
+            .map(|i| {
+                json!({
+                    "hash": format!("abc{i:04}"),
+                    "message": "fix: handle <none> & empty",
+                    "author": "Jane",
+                    "timestamp": "2026-03-02T10:00:00+00:00",
+                    "relative_time": "2h ago",
+                })
+            })
+            .collect();
+        serde_json::from_value(json!({
+            "project": name,
+            "path": format!("/src/{name}"),
+            "branches": [{ "name": "main", "commits": commits }],
+        }))
+        .expect("valid project")
+    }
+
+    #[test]
+    fn message_has_header_and_escaped_sections() {
+        let msg = message(&[project("api", 2)], Depth::Commits, "Worklog yesterday");
+        let blocks = msg["blocks"].as_array().expect("blocks");
+        assert_eq!(blocks[0]["text"]["text"], "Worklog yesterday");
+        let text = blocks[2]["text"]["text"].as_str().expect("section text");
+        assert!(text.starts_with("*api*\n`main`  _2 commits, 2h ago_"));
+        assert!(text.contains("`abc0001` fix: handle &lt;none&gt; &amp; empty"));
+        assert!(msg["text"]
+            .as_str()
+            .expect("fallback")
+            .starts_with("Worklog yesterday: Found 2 commits"));
+    }
+
+    #[test]
+    fn message_stays_within_slack_limits() {
+        let projects: Vec<_> = (0..60).map(|i| project(&format!("p{i}"), 200)).collect();
+        let msg = message(&projects, Depth::Commits, "Worklog");
+        let blocks = msg["blocks"].as_array().expect("blocks");
+        assert_eq!(blocks.len(), MAX_BLOCKS);
+        assert!(blocks
+            .iter()
+            .filter_map(|b| b["text"]["text"].as_str())
+            .all(|t| t.chars().count() <= MAX_SECTION_CHARS));
+        assert_eq!(
+            blocks[MAX_BLOCKS - 1]["elements"][0]["text"],
+            "\u{2026}and 13 more"
+        );
+    }
+
+    #[test]
+    fn title_names_the_days() {
+        let day = |d: u32, h: u32| {
+            let date = chrono::NaiveDate::from_ymd_opt(2026, 3, d).expect("valid date");
+            let time = chrono::NaiveTime::from_hms_opt(h, 0, 0).expect("valid time");
+            Zone::Local.at(date, time).expect("local time")
+        };
+        let yesterday = TimeRange {
+            since: day(2, 0),
+            until: Some(day(3, 0)),
+            zone: Zone::Local,
+        };
+        assert_eq!(title(&yesterday), "Worklog Mon 2026-03-02");
+        let week = TimeRange {
+            since: day(2, 0),
+            until: Some(day(9, 0)),
+            zone: Zone::Local,
+        };
+        assert_eq!(
+            title(&week),
+            "Worklog Mon 2026-03-02 \u{2013} Sun 2026-03-08"
+        );
+    }
+
+    #[test]
+    fn webhook_wins_over_token() {
+        let cfg = SlackConfig {
+            webhook_url: None,
+            token: Some("xoxb-1".to_string()),
+            channel: Some("#standup".to_string()),
+        };
+        assert_eq!(
+            resolve(Some(&cfg), |_| None).expect("destination"),
+            Destination::Channel {
+                token: "xoxb-1".to_string(),
+                channel: "#standup".to_string()
+            }
+        );
+        let hook = |key: &str| (key == "SLACK_WEBHOOK_URL").then(|| "https://hook".to_string());
+        assert_eq!(
+            resolve(Some(&cfg), hook).expect("destination"),
+            Destination::Webhook("https://hook".to_string())
+        );
+        assert!(resolve(None, |_| None).is_err());
+    }
+
+    #[test]
+    fn slack_errors_are_reported() {
+        assert!(check_response("200", "ok").is_ok());
+        assert!(check_response("200", r#"{"ok":true}"#).is_ok());
+        let err = check_response("200", r#"{"ok":false,"error":"channel_not_found"}"#)
+            .expect_err("rejected");
+        assert!(err.to_string().contains("channel_not_found"));
+        assert!(check_response("404", "no_service").is_err());
+    }
+}
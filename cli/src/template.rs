@@ -0,0 +1,165 @@
+//! `--template`: render the report through a user-supplied
+//! [Handlebars](https://handlebarsjs.com/guide/) template, so new output
+//! formats need no code.
+//!
+//! Templates run in strict mode, so a misspelled field is an error naming
+//! it rather than an empty string, and output is not HTML-escaped.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use handlebars::Handlebars;
+use serde_json::{json, Value};
+
+use devcap_core::model::ProjectLog;
+use devcap_core::tags::RepoTags;
+use devcap_core::wip::WorkInProgress;
+use devcap_core::zone::Time;
+
+use crate::cli::Depth;
+use crate::errors::{coded, ErrorCode};
+use crate::output;
+
+#[derive(Debug)]
+pub struct Template {
+    registry: Handlebars<'static>,
+    /// The file name, which errors while rendering refer to.
+    name: String,
+}
+
+/// Read and parse the template at `path`.
+pub fn load(path: &Path) -> Result<Template> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("could not read template {}", path.display()))?;
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    Template::parse(&name, &source).map_err(|e| anyhow::anyhow!("{}: {e}", path.display()))
+}
+
+/// What a template sees: the report's `projects` as `--json` prints them,
+/// the period as `since`/`until` (RFC 3339, `until` empty for open ranges),
+/// the `author` filter, the total `commits`, and the `--wip` and `--tags`
+/// entries.
+pub fn context(
+    projects: &[ProjectLog],
+    depth: Depth,
+    since: Time,
+    until: Option<Time>,
+    author: Option<&str>,
+    wip: &[WorkInProgress],
+    tags: &[RepoTags],
+) -> Value {
+    json!({
+        "projects": output::projects_json(projects, depth),
+        "since": since.to_rfc3339(),
+        "until": until.map(|u| u.to_rfc3339()),
+        "author": author,
+        "commits": projects.iter().map(ProjectLog::total_commits).sum::<usize>(),
+        "wip": wip,
+        "tags": tags,
+    })
+}
+
+impl Template {
+    pub fn parse(name: &str, source: &str) -> Result<Self, String> {
+        let mut registry = Handlebars::new();
+        registry.set_strict_mode(true);
+        registry.register_escape_fn(handlebars::no_escape);
+        registry
+            .register_template_string(name, source)
+            .map_err(|e| e.to_string())?;
+        Ok(Template {
+            registry,
+            name: name.to_string(),
+        })
+    }
+
+    /// Render with `context`; a field the context does not have is an
+    /// error.
+    pub fn render(&self, context: &Value) -> Result<String> {
+        self.registry
+            .render(&self.name, context)
+            .map_err(|e| coded(ErrorCode::InvalidArgument, e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(source: &str) -> Template {
+        Template::parse("test.hbs", source).expect("valid template")
+    }
+
+    fn render(source: &str, context: Value) -> String {
+        template(source).render(&context).expect("renders")
+    }
+
+    fn error(source: &str) -> String {
+        Template::parse("test.hbs", source).expect_err("invalid template")
+    }
+
+    #[test]
+    fn renders_loops_conditions_and_parents() {
+        let context = json!({
+            "author": "Jane",
+            "projects": [
+                { "project": "api", "branches": [{ "name": "main", "commits": [
+                    { "hash": "a1b2c3d", "message": "feat: export" },
+                    { "hash": "e4f5a6b", "message": "fix: rounding" },
+                ]}]},
+                { "project": "web", "branches": [] },
+            ],
+        });
+        let source = "\
+{{! Jira comment }}
+h3. Work by {{author}}
+{{#each projects}}
+* *{{project}}*
+{{#each branches}}
+{{#each commits}}
+** {{../../project}}/{{../name}} {{hash}} {{message}}{{#unless @last}};{{/unless}}
+{{/each}}
+{{else}}
+** no branches
+{{/each}}
+{{/each}}
+";
+        assert_eq!(
+            render(source, context),
+            "h3. Work by Jane\n\
+             * *api*\n\
+             ** api/main a1b2c3d feat: export;\n\
+             ** api/main e4f5a6b fix: rounding\n\
+             * *web*\n\
+             ** no branches\n"
+        );
+    }
+
+    #[test]
+    fn values_are_not_escaped_and_optional_fields_are_tested_with_if() {
+        let context = json!({ "n": 0, "s": "<a & b>", "list": [1, 2] });
+        assert_eq!(
+            render(
+                "{{n}} {{s}} {{#if n}}x{{else}}zero{{/if}} {{#if time_spent}}x{{else}}none{{/if}} {{#each list}}{{@index}}={{this}} {{/each}}",
+                context
+            ),
+            "0 <a & b> zero none 0=1 1=2 "
+        );
+    }
+
+    #[test]
+    fn misspelled_fields_are_errors() {
+        let e = template("{{#each projects}}{{projcet}}{{/each}}")
+            .render(&json!({ "projects": [{ "project": "api" }] }))
+            .expect_err("unknown field");
+        assert!(e.to_string().contains("projcet"), "{e}");
+        assert!(e.to_string().contains("test.hbs"), "{e}");
+    }
+
+    #[test]
+    fn reports_unbalanced_blocks() {
+        assert!(error("{{#if a}}x{{/each}}").contains("\"if\" was opened, but \"each\" is closing"));
+        assert!(error("a\n{{#each projects}}\nb").contains(":3:"));
+        assert!(error("{{name").contains("invalid handlebars syntax"));
+    }
+}
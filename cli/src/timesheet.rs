@@ -0,0 +1,387 @@
+//! `devcap timesheet`: book the period's working time per repository and day
+//! in a time tracker. Each backend turns an [`Entry`] into one HTTP request;
+//! the repositories are mapped to tracker projects in `[timesheet.projects]`.
+
+use std::collections::{BTreeMap, HashSet};
+
+use anyhow::Result;
+use chrono::{Duration, NaiveDate, SecondsFormat, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::config::{self, TimesheetConfig, TimesheetTarget};
+use crate::curl::Curl;
+use devcap_core::activity;
+use devcap_core::model::{ProjectLog, WorkTime};
+use devcap_core::zone::Time;
+
+const CLOCKIFY_API: &str = "https://api.clockify.me/api/v1";
+const HARVEST_API: &str = "https://api.harvestapp.com/v2";
+
+/// Time spent on one repository on one day, and where to book it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Entry {
+    pub date: NaiveDate,
+    pub repo: String,
+    /// Tracker project ID.
+    pub project: String,
+    /// Tracker task ID.
+    pub task: Option<String>,
+    /// When the work began: the first commit of the day less the lead-in the
+    /// estimate credits for it.
+    pub start: Time,
+    #[serde(rename = "minutes")]
+    pub time: WorkTime,
+    /// The day's commit subjects.
+    pub notes: String,
+}
+
+/// The entries for `projects`, one per mapped repository and day with
+/// commits, oldest first. The time is the sum of the logged time trailers of
+/// the day when there are any, the session estimate of `devcap day`
+/// otherwise. Also returns the names of repositories without a mapping.
+pub fn entries(
+    projects: &[ProjectLog],
+    targets: &BTreeMap<String, TimesheetTarget>,
+) -> (Vec<Entry>, Vec<String>) {
+    let mut entries = Vec::new();
+    let mut unmapped = Vec::new();
+    for project in projects {
+        let Some(target) = targets
+            .iter()
+            .find(|(key, _)| config::matches_repo(key, project))
+            .map(|(_, target)| target)
+        else {
+            unmapped.push(project.project.clone());
+            continue;
+        };
+        for span in activity::daily_spans(std::slice::from_ref(project)) {
+            let mut seen = HashSet::new();
+            let mut commits: Vec<_> = project
+                .branches
+                .iter()
+                .flat_map(|b| &b.commits)
+                .filter(|c| c.time.date_naive() == span.date && seen.insert(&c.hash))
+                .collect();
+            commits.sort_by_key(|c| c.time);
+            let logged = commits
+                .iter()
+                .filter_map(|c| c.time_spent)
+                .reduce(|a, b| a + b);
+            let mut subjects: Vec<&str> = commits.iter().map(|c| c.message.as_str()).collect();
+            subjects.dedup();
+            entries.push(Entry {
+                date: span.date,
+                repo: project.project.clone(),
+                project: target.project.clone(),
+                task: target.task.clone(),
+                start: span.first - Duration::minutes(activity::SESSION_LEAD_MINUTES),
+                time: logged.unwrap_or(span.estimated),
+                notes: subjects.join("; "),
+            });
+        }
+    }
+    entries.sort_by(|a, b| (a.date, &a.repo).cmp(&(b.date, &b.repo)));
+    (entries, unmapped)
+}
+
+/// An HTTP request creating one time entry.
+#[derive(Debug, PartialEq)]
+pub struct Request {
+    pub url: String,
+    /// Credentials included; never put on curl's command line.
+    pub headers: Vec<String>,
+    pub body: Value,
+}
+
+/// A time tracker entries can be booked in.
+pub trait Backend {
+    /// Name used in messages, e.g. `Clockify`.
+    fn name(&self) -> &'static str;
+    /// The request booking `entry`; fails when the entry cannot be expressed.
+    fn request(&self, entry: &Entry) -> Result<Request>;
+}
+
+/// Clockify: entries are start and end times in a workspace.
+pub struct Clockify {
+    pub api_key: String,
+    pub workspace: String,
+}
+
+impl Backend for Clockify {
+    fn name(&self) -> &'static str {
+        "Clockify"
+    }
+
+    fn request(&self, entry: &Entry) -> Result<Request> {
+        let end = entry.start + Duration::minutes(i64::from(entry.time.minutes()));
+        let utc = |t: Time| {
+            t.with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::Secs, true)
+        };
+        let mut body = json!({
+            "start": utc(entry.start),
+            "end": utc(end),
+            "projectId": entry.project,
+            "description": entry.notes,
+        });
+        if let Some(task) = &entry.task {
+            body["taskId"] = json!(task);
+        }
+        Ok(Request {
+            url: format!("{CLOCKIFY_API}/workspaces/{}/time-entries", self.workspace),
+            headers: vec![format!("X-Api-Key: {}", self.api_key)],
+            body,
+        })
+    }
+}
+
+/// Harvest: entries are hours on a day, for a project and task.
+pub struct Harvest {
+    pub token: String,
+    pub account: String,
+}
+
+impl Backend for Harvest {
+    fn name(&self) -> &'static str {
+        "Harvest"
+    }
+
+    fn request(&self, entry: &Entry) -> Result<Request> {
+        let Some(task) = &entry.task else {
+            anyhow::bail!(
+                "Harvest needs a `task` for {} in [timesheet.projects]",
+                entry.repo
+            );
+        };
+        let hours = f64::from(entry.time.minutes()) / 60.0;
+        Ok(Request {
+            url: format!("{HARVEST_API}/time_entries"),
+            headers: vec![
+                format!("Authorization: Bearer {}", self.token),
+                format!("Harvest-Account-Id: {}", self.account),
+                format!("User-Agent: devcap/{}", env!("CARGO_PKG_VERSION")),
+            ],
+            body: json!({
+                "project_id": id(&entry.project),
+                "task_id": id(task),
+                "spent_date": entry.date.to_string(),
+                "hours": (hours * 100.0).round() / 100.0,
+                "notes": entry.notes,
+            }),
+        })
+    }
+}
+
+/// Harvest IDs are numbers; keep anything else as configured.
+fn id(value: &str) -> Value {
+    value
+        .parse::<u64>()
+        .map_or_else(|_| json!(value), |n| json!(n))
+}
+
+/// The configured backend, falling back to `CLOCKIFY_API_KEY`,
+/// `HARVEST_ACCESS_TOKEN` and `HARVEST_ACCOUNT_ID` for the credentials.
+pub fn backend(cfg: Option<&TimesheetConfig>) -> Result<Box<dyn Backend>> {
+    resolve(cfg, |key| std::env::var(key).ok().filter(|v| !v.is_empty()))
+}
+
+fn resolve(
+    cfg: Option<&TimesheetConfig>,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<Box<dyn Backend>> {
+    let Some(cfg) = cfg else {
+        anyhow::bail!("no [timesheet] section in ~/.devcap.toml");
+    };
+    match cfg.backend.as_deref() {
+        Some("clockify") => {
+            let api_key = cfg.token.clone().or_else(|| env("CLOCKIFY_API_KEY"));
+            match (api_key, cfg.workspace.clone()) {
+                (Some(api_key), Some(workspace)) => Ok(Box::new(Clockify { api_key, workspace })),
+                (None, _) => anyhow::bail!(
+                    "no Clockify API key: set `token` in [timesheet] or CLOCKIFY_API_KEY"
+                ),
+                (Some(_), None) => anyhow::bail!("Clockify needs a `workspace` ID in [timesheet]"),
+            }
+        }
+        Some("harvest") => {
+            let token = cfg.token.clone().or_else(|| env("HARVEST_ACCESS_TOKEN"));
+            let account = cfg.account.clone().or_else(|| env("HARVEST_ACCOUNT_ID"));
+            match (token, account) {
+                (Some(token), Some(account)) => Ok(Box::new(Harvest { token, account })),
+                (None, _) => anyhow::bail!(
+                    "no Harvest token: set `token` in [timesheet] or HARVEST_ACCESS_TOKEN"
+                ),
+                (Some(_), None) => anyhow::bail!(
+                    "Harvest needs an `account` ID in [timesheet] or HARVEST_ACCOUNT_ID"
+                ),
+            }
+        }
+        Some(other) => {
+            anyhow::bail!("unknown timesheet backend \"{other}\": use clockify or harvest")
+        }
+        None => anyhow::bail!("set `backend` in [timesheet]: clockify or harvest"),
+    }
+}
+
+/// Book `entry` with `backend`.
+pub fn push(backend: &dyn Backend, entry: &Entry) -> Result<()> {
+    let request = backend.request(entry)?;
+    let mut curl = Curl::new(&request.url, 15).header("Content-Type: application/json");
+    for header in &request.headers {
+        curl = curl.secret_header(header);
+    }
+    let response = curl
+        .data(&request.body.to_string())
+        .send(&format!("booking time in {}", backend.name()))?;
+    if !(200..300).contains(&response.status) {
+        anyhow::bail!(
+            "{} answered HTTP {} for {} on {}: {}",
+            backend.name(),
+            response.status,
+            entry.repo,
+            entry.date,
+            response.text()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn projects() -> Vec<ProjectLog> {
+        let commit = |hash: &str, message: &str, time: &str, spent: Option<u32>| {
+            json!({
+                "hash": hash,
+                "message": message,
+                "author": "Jane",
+                "timestamp": time,
+                "relative_time": "",
+                "time_spent": spent,
+            })
+        };
+        serde_json::from_value(json!([
+            {
+                "project": "api",
+                "path": "/src/api",
+                "branches": [
+                    { "name": "main", "commits": [
+                        commit("c", "fix: retry", "2026-03-03T12:00:00+01:00", None),
+                        commit("b", "feat: export", "2026-03-02T13:00:00+01:00", None),
+                        commit("a", "feat: import", "2026-03-02T12:00:00+01:00", None),
+                    ]},
+                    { "name": "feature", "commits": [
+                        commit("b", "feat: export", "2026-03-02T13:00:00+01:00", None),
+                    ]},
+                ],
+            },
+            {
+                "project": "web",
+                "path": "/src/web",
+                "branches": [{ "name": "main", "commits": [
+                    commit("d", "docs: readme", "2026-03-02T14:00:00+01:00", Some(45)),
+                ]}],
+            },
+            {
+                "project": "dotfiles",
+                "path": "/src/dotfiles",
+                "branches": [{ "name": "main", "commits": [
+                    commit("e", "chore: vim", "2026-03-02T20:00:00+01:00", None),
+                ]}],
+            },
+        ]))
+        .expect("valid projects")
+    }
+
+    fn targets() -> BTreeMap<String, TimesheetTarget> {
+        BTreeMap::from([
+            (
+                "api".to_string(),
+                TimesheetTarget {
+                    project: "101".to_string(),
+                    task: Some("7".to_string()),
+                },
+            ),
+            (
+                "/src/web".to_string(),
+                TimesheetTarget {
+                    project: "202".to_string(),
+                    task: None,
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn one_entry_per_mapped_repo_and_day() {
+        let (entries, unmapped) = entries(&projects(), &targets());
+        assert_eq!(unmapped, ["dotfiles"]);
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|e| (e.date.to_string(), e.repo.as_str(), e.time.minutes()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("2026-03-02".to_string(), "api", 30 + 60),
+                ("2026-03-02".to_string(), "web", 45),
+                ("2026-03-03".to_string(), "api", 30),
+            ]
+        );
+        assert_eq!(entries[0].notes, "feat: import; feat: export");
+        assert_eq!(entries[0].project, "101");
+    }
+
+    #[test]
+    fn backends_build_their_requests() {
+        let (entries, _) = entries(&projects(), &targets());
+        let clockify = Clockify {
+            api_key: "key".to_string(),
+            workspace: "ws1".to_string(),
+        };
+        let request = clockify.request(&entries[0]).expect("request");
+        assert_eq!(
+            request.url,
+            "https://api.clockify.me/api/v1/workspaces/ws1/time-entries"
+        );
+        assert_eq!(request.headers, ["X-Api-Key: key"]);
+        assert_eq!(request.body["start"], "2026-03-02T10:30:00Z");
+        assert_eq!(request.body["end"], "2026-03-02T12:00:00Z");
+        assert_eq!(request.body["taskId"], "7");
+
+        let harvest = Harvest {
+            token: "tok".to_string(),
+            account: "42".to_string(),
+        };
+        let request = harvest.request(&entries[0]).expect("request");
+        assert_eq!(request.body["project_id"], 101);
+        assert_eq!(request.body["task_id"], 7);
+        assert_eq!(request.body["spent_date"], "2026-03-02");
+        assert_eq!(request.body["hours"], 1.5);
+        assert!(harvest.request(&entries[1]).is_err(), "web has no task");
+    }
+
+    #[test]
+    fn backend_comes_from_config_and_env() {
+        let cfg = TimesheetConfig {
+            backend: Some("harvest".to_string()),
+            account: Some("42".to_string()),
+            ..TimesheetConfig::default()
+        };
+        let env = |key: &str| (key == "HARVEST_ACCESS_TOKEN").then(|| "tok".to_string());
+        assert_eq!(resolve(Some(&cfg), env).expect("backend").name(), "Harvest");
+        assert!(resolve(Some(&cfg), |_| None).is_err());
+
+        let cfg = TimesheetConfig {
+            backend: Some("toggl".to_string()),
+            ..TimesheetConfig::default()
+        };
+        let err = resolve(Some(&cfg), |_| None)
+            .err()
+            .expect("unknown backend");
+        assert!(err.to_string().contains("toggl"));
+        assert!(resolve(None, |_| None).is_err());
+    }
+}
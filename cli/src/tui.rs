@@ -0,0 +1,641 @@
+//! Full-screen browser (`--tui`): projects, branches and commits side by side,
+//! a live filter per pane, and a detail pane for the highlighted commit.
+
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::Result;
+use dialoguer::console::Key;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::cli::Depth;
+use crate::clipboard;
+use crate::interactive;
+use crate::keys::{self, Action, Keymap};
+use crate::output;
+use devcap_core::git;
+use devcap_core::model::{BranchLog, Commit, ProjectLog};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pane {
+    Projects,
+    Branches,
+    Commits,
+}
+
+impl Pane {
+    const ALL: [Pane; 3] = [Pane::Projects, Pane::Branches, Pane::Commits];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Pane::Projects => "Projects",
+            Pane::Branches => "Branches",
+            Pane::Commits => "Commits",
+        }
+    }
+
+    fn left(self) -> Self {
+        match self {
+            Pane::Commits => Pane::Branches,
+            _ => Pane::Projects,
+        }
+    }
+
+    fn right(self) -> Self {
+        match self {
+            Pane::Projects => Pane::Branches,
+            _ => Pane::Commits,
+        }
+    }
+}
+
+struct App<'a> {
+    projects: &'a [ProjectLog],
+    keymap: &'a Keymap,
+    show_origin: bool,
+    focus: Pane,
+    /// Filter text of each pane, applied while typing.
+    filters: [String; 3],
+    /// Keys go to the focused pane's filter.
+    editing: bool,
+    /// Highlighted row of each pane, as a position in its filtered list.
+    states: [ListState; 3],
+    /// Feedback for the last action, shown in the footer.
+    status: Option<String>,
+    /// `git show --stat` output per (project, commit hash).
+    details: HashMap<(usize, String), String>,
+    matcher: SkimMatcherV2,
+}
+
+/// Browse `projects` in a full-screen terminal UI until the user quits.
+pub fn run(projects: &[ProjectLog], keymap: &Keymap, show_origin: bool) -> Result<()> {
+    let mut terminal = ratatui::init();
+    let result = App::new(projects, keymap, show_origin).run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+impl<'a> App<'a> {
+    fn new(projects: &'a [ProjectLog], keymap: &'a Keymap, show_origin: bool) -> Self {
+        let mut app = Self {
+            projects,
+            keymap,
+            show_origin,
+            focus: Pane::Projects,
+            filters: Default::default(),
+            editing: false,
+            states: Default::default(),
+            status: None,
+            details: HashMap::new(),
+            matcher: SkimMatcherV2::default(),
+        };
+        app.select(Pane::Projects, 0);
+        app
+    }
+
+    fn run(mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        loop {
+            self.load_detail();
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && self.handle(key).is_break() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Original indices of the items of `pane` that match its filter.
+    fn visible(&self, pane: Pane) -> Vec<usize> {
+        let labels: Vec<String> = match pane {
+            Pane::Projects => self.projects.iter().map(|p| p.project.clone()).collect(),
+            Pane::Branches => self
+                .project()
+                .map(|p| p.branches.iter().map(|b| b.name.clone()).collect())
+                .unwrap_or_default(),
+            Pane::Commits => self
+                .branch()
+                .map(|b| {
+                    b.commits
+                        .iter()
+                        .map(|c| format!("{} {}", c.hash, c.message))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+        let filter = &self.filters[pane.index()];
+        labels
+            .iter()
+            .enumerate()
+            .filter(|(_, label)| {
+                filter.is_empty() || self.matcher.fuzzy_match(label, filter).is_some()
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Original index of the highlighted item of `pane`.
+    fn selected(&self, pane: Pane) -> Option<usize> {
+        let pos = self.states[pane.index()].selected()?;
+        self.visible(pane).get(pos).copied()
+    }
+
+    fn project(&self) -> Option<&'a ProjectLog> {
+        self.selected(Pane::Projects).map(|i| &self.projects[i])
+    }
+
+    fn branch(&self) -> Option<&'a BranchLog> {
+        let project = self.project()?;
+        self.selected(Pane::Branches).map(|i| &project.branches[i])
+    }
+
+    fn commit(&self) -> Option<&'a Commit> {
+        self.selected(Pane::Commits)
+            .and_then(|i| self.branch().map(|b| &b.commits[i]))
+    }
+
+    /// Highlight row `pos` of `pane` (clamped) and reset the panes to its right.
+    fn select(&mut self, pane: Pane, pos: usize) {
+        let len = self.visible(pane).len();
+        self.states[pane.index()].select((len > 0).then(|| pos.min(len - 1)));
+        if pane != Pane::Commits {
+            self.select(pane.right(), 0);
+        }
+    }
+
+    fn step(&mut self, delta: isize) {
+        let pane = self.focus;
+        let len = self.visible(pane).len();
+        if len == 0 {
+            return;
+        }
+        let pos = self.states[pane.index()].selected().unwrap_or(0);
+        self.select(
+            pane,
+            (pos as isize + delta).rem_euclid(len as isize) as usize,
+        );
+    }
+
+    fn handle(&mut self, event: KeyEvent) -> ControlFlow<()> {
+        self.status = None;
+        let ctrl = event.modifiers.contains(KeyModifiers::CONTROL);
+        if ctrl && event.code == KeyCode::Char('c') {
+            return ControlFlow::Break(());
+        }
+        let pane = self.focus;
+        if self.editing {
+            match event.code {
+                KeyCode::Char(c) if !ctrl => {
+                    self.filters[pane.index()].push(c);
+                    self.select(pane, 0);
+                    return ControlFlow::Continue(());
+                }
+                KeyCode::Backspace => {
+                    self.filters[pane.index()].pop();
+                    self.select(pane, 0);
+                    return ControlFlow::Continue(());
+                }
+                KeyCode::Enter => {
+                    self.editing = false;
+                    return ControlFlow::Continue(());
+                }
+                KeyCode::Esc => {
+                    self.editing = false;
+                    self.filters[pane.index()].clear();
+                    self.select(pane, 0);
+                    return ControlFlow::Continue(());
+                }
+                _ => {}
+            }
+        } else {
+            match event.code {
+                KeyCode::Char('/') => {
+                    self.editing = true;
+                    return ControlFlow::Continue(());
+                }
+                KeyCode::Char('q') => return ControlFlow::Break(()),
+                KeyCode::Left | KeyCode::Char('h') => {
+                    self.focus = pane.left();
+                    return ControlFlow::Continue(());
+                }
+                KeyCode::Right | KeyCode::Char('l') => {
+                    self.focus = pane.right();
+                    return ControlFlow::Continue(());
+                }
+                _ => {}
+            }
+        }
+
+        let Some(key) = console_key(&event) else {
+            return ControlFlow::Continue(());
+        };
+        match self.keymap.action_for(&key, !self.editing) {
+            Some(Action::Up) => self.step(-1),
+            Some(Action::Down) => self.step(1),
+            Some(Action::Select) => self.focus = pane.right(),
+            Some(Action::Back) if pane == Pane::Projects => return ControlFlow::Break(()),
+            Some(Action::Back) => self.focus = pane.left(),
+            Some(Action::Copy) => {
+                let text = match pane {
+                    Pane::Projects => self.project().map(|p| {
+                        clipboard::render_plain(
+                            std::slice::from_ref(p),
+                            Depth::Commits,
+                            self.show_origin,
+                        )
+                    }),
+                    Pane::Branches => self.branch().map(clipboard::render_branch),
+                    Pane::Commits => self.commit().map(clipboard::render_commit_line),
+                };
+                self.copy(text.as_deref(), "selection");
+            }
+            Some(Action::CopyHash) => {
+                let hash = self.commit().map(|c| c.hash.clone());
+                self.copy(hash.as_deref(), "hash");
+            }
+            Some(Action::CopyMessage) => {
+                let message = self.project().zip(self.commit()).map(|(p, c)| {
+                    git::commit_message(Path::new(&p.path), &c.hash)
+                        .unwrap_or_else(|| c.message.clone())
+                });
+                self.copy(message.as_deref(), "message");
+            }
+            Some(Action::CopyUrl) => {
+                let url = self.url().map(str::to_string);
+                self.copy(url.as_deref(), "URL");
+            }
+            Some(Action::Open) => match self.url() {
+                Some(url) => {
+                    let spawned = Command::new(interactive::default_opener())
+                        .arg(url)
+                        .stdout(Stdio::null())
+                        .stderr(Stdio::null())
+                        .spawn();
+                    if let Err(e) = spawned {
+                        self.status = Some(format!("Could not open {url}: {e}"));
+                    }
+                }
+                None => self.status = Some("No remote URL available.".to_string()),
+            },
+            _ => {}
+        }
+        ControlFlow::Continue(())
+    }
+
+    /// Forge URL of the highlighted item in the focused pane.
+    fn url(&self) -> Option<&'a str> {
+        match self.focus {
+            Pane::Projects => self.project()?.remote_url.as_deref(),
+            Pane::Branches => self.branch()?.url.as_deref(),
+            Pane::Commits => self.commit()?.url.as_deref(),
+        }
+    }
+
+    fn copy(&mut self, value: Option<&str>, what: &str) {
+        self.status = Some(match value {
+            None => format!("No {what} available."),
+            Some(value) => match clipboard::copy_text(value) {
+                Ok(()) => format!("Copied {what} to clipboard."),
+                Err(e) => format!("Warning: {e:#}"),
+            },
+        });
+    }
+
+    /// Read message and diff stat of the highlighted commit, once per commit.
+    fn load_detail(&mut self) {
+        let (Some(idx), Some(commit)) = (self.selected(Pane::Projects), self.commit()) else {
+            return;
+        };
+        let key = (idx, commit.hash.clone());
+        if self.details.contains_key(&key) {
+            return;
+        }
+        let output = git::command()
+            .args([
+                "-C",
+                &self.projects[idx].path,
+                "show",
+                "--stat",
+                "--format=%B",
+                &commit.hash,
+            ])
+            .output();
+        let detail = match output {
+            Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout).trim().to_string(),
+            _ => commit.message.clone(),
+        };
+        self.details.insert(key, detail);
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header, panes, detail, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Percentage(55),
+            Constraint::Min(5),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [left, middle, right] = Layout::horizontal([
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+        ])
+        .areas(panes);
+
+        frame.render_widget(
+            Line::from(vec![
+                Span::styled(" devcap ", Style::new().add_modifier(Modifier::BOLD)),
+                Span::styled(output::summary_line(self.projects), dim()),
+            ]),
+            header,
+        );
+        for (pane, area) in Pane::ALL.into_iter().zip([left, middle, right]) {
+            self.draw_pane(frame, pane, area);
+        }
+        self.draw_detail(frame, detail);
+        frame.render_widget(self.footer_line(), footer);
+    }
+
+    fn draw_pane(&mut self, frame: &mut Frame, pane: Pane, area: Rect) {
+        let items: Vec<ListItem> = self
+            .visible(pane)
+            .into_iter()
+            .filter_map(|i| match pane {
+                Pane::Projects => Some(project_item(&self.projects[i])),
+                Pane::Branches => self.project().map(|p| branch_item(&p.branches[i])),
+                Pane::Commits => self.branch().map(|b| commit_item(&b.commits[i])),
+            })
+            .collect();
+        let focused = pane == self.focus;
+        let filter = &self.filters[pane.index()];
+        let title = if filter.is_empty() {
+            format!(" {} ", pane.title())
+        } else {
+            format!(" {} /{filter} ", pane.title())
+        };
+        let border = if focused { paint(Color::Cyan) } else { dim() };
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(border)
+                    .title(title),
+            )
+            .highlight_style(if focused {
+                Style::new().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::new().add_modifier(Modifier::BOLD)
+            });
+        frame.render_stateful_widget(list, area, &mut self.states[pane.index()]);
+    }
+
+    fn draw_detail(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(dim())
+            .title(" Detail ");
+        let lines = match (self.selected(Pane::Projects), self.commit()) {
+            (Some(idx), Some(commit)) => {
+                let mut lines = vec![Line::from(vec![
+                    Span::styled(commit.hash.clone(), paint(Color::Yellow)),
+                    Span::raw(format!("  {}", commit.author)),
+                    Span::styled(
+                        format!(
+                            "  {} ({})",
+                            commit.time.format("%Y-%m-%d %H:%M"),
+                            commit.relative_time
+                        ),
+                        dim(),
+                    ),
+                ])];
+                let detail = self.details.get(&(idx, commit.hash.clone()));
+                lines.extend(
+                    detail
+                        .map(String::as_str)
+                        .unwrap_or(&commit.message)
+                        .lines()
+                        .map(|l| Line::raw(l.to_string())),
+                );
+                lines
+            }
+            _ => vec![Line::styled("No commit selected.", dim())],
+        };
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+    }
+
+    fn footer_line(&self) -> Line<'static> {
+        if self.editing {
+            return Line::from(vec![
+                Span::styled(" filter ", paint(Color::Cyan)),
+                Span::raw(format!("/{}", self.filters[self.focus.index()])),
+                Span::styled("  enter keep \u{b7} esc clear", dim()),
+            ]);
+        }
+        if let Some(status) = &self.status {
+            return Line::raw(format!(" {status}"));
+        }
+        let hint = self.keymap.hint(&[
+            Action::Back,
+            Action::Open,
+            Action::Copy,
+            Action::CopyHash,
+            Action::CopyMessage,
+            Action::CopyUrl,
+        ]);
+        Line::styled(
+            format!(" \u{2190}\u{2192} pane \u{b7} / filter \u{b7} q quit \u{b7} {hint}"),
+            dim(),
+        )
+    }
+}
+
+fn project_item(project: &ProjectLog) -> ListItem<'static> {
+    let commits = project.total_commits();
+    ListItem::new(Line::from(vec![
+        Span::styled(
+            project.project.clone(),
+            Style::new().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!(" {commits}"), dim()),
+    ]))
+}
+
+fn branch_item(branch: &BranchLog) -> ListItem<'static> {
+    ListItem::new(Line::from(vec![
+        Span::styled(branch.name.clone(), paint(Color::Green)),
+        Span::styled(
+            format!("{} {}", output::branch_suffix(branch), branch.commits.len()),
+            dim(),
+        ),
+    ]))
+}
+
+fn commit_item(commit: &Commit) -> ListItem<'static> {
+    let mut spans = vec![Span::styled(format!("{} ", commit.hash), dim())];
+    if let Some(kind) = &commit.commit_type {
+        spans.push(Span::styled(format!("{kind} "), type_style(kind)));
+    }
+    spans.push(Span::raw(
+        output::strip_type_prefix(&commit.message).to_string(),
+    ));
+    spans.push(Span::styled(
+        format!("  {}", output::commit_byline(commit)),
+        dim(),
+    ));
+    ListItem::new(Line::from(spans))
+}
+
+/// Same palette as the commit type tags of the terminal tree.
+fn type_style(kind: &str) -> Style {
+    let bold = Style::new().add_modifier(Modifier::BOLD);
+    match kind {
+        "feat" => bold.patch(paint(Color::Green)),
+        "fix" => bold.patch(paint(Color::Red)),
+        "refactor" => paint(Color::Cyan),
+        "docs" => paint(Color::Blue),
+        "test" | "style" => paint(Color::Yellow),
+        _ => dim(),
+    }
+}
+
+/// Foreground `color`, unless colors are turned off.
+fn paint(color: Color) -> Style {
+    if output::color_enabled() {
+        Style::new().fg(color)
+    } else {
+        Style::new()
+    }
+}
+
+fn dim() -> Style {
+    Style::new().add_modifier(Modifier::DIM)
+}
+
+/// The key as the picker-based mode sees it, so `[keys]` bindings apply here too.
+fn console_key(event: &KeyEvent) -> Option<Key> {
+    let key = match event.code {
+        KeyCode::Up => Key::ArrowUp,
+        KeyCode::Down => Key::ArrowDown,
+        KeyCode::Left => Key::ArrowLeft,
+        KeyCode::Right => Key::ArrowRight,
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Esc => Key::Escape,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::BackTab => Key::BackTab,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Delete => Key::Del,
+        KeyCode::Home => Key::Home,
+        KeyCode::End => Key::End,
+        KeyCode::PageUp => Key::PageUp,
+        KeyCode::PageDown => Key::PageDown,
+        KeyCode::Char(c) if event.modifiers.contains(KeyModifiers::CONTROL) => {
+            keys::ctrl_key(c.to_ascii_lowercase())
+        }
+        KeyCode::Char(c) => Key::Char(c),
+        _ => return None,
+    };
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn commit(hash: &str, message: &str) -> serde_json::Value {
+        json!({
+            "hash": hash,
+            "message": message,
+            "author": "Jane",
+            "timestamp": "2026-03-02T10:00:00+01:00",
+            "relative_time": "1h ago",
+        })
+    }
+
+    fn projects() -> Vec<ProjectLog> {
+        serde_json::from_value(json!([
+            {
+                "project": "app",
+                "path": "/tmp/app",
+                "branches": [
+                    { "name": "main", "commits": [commit("a1", "feat: export"), commit("b2", "fix: typo")] },
+                    { "name": "feature/billing", "commits": [commit("c3", "feat: invoices")] },
+                ],
+            },
+            { "project": "docs", "path": "/tmp/docs", "branches": [] },
+        ]))
+        .expect("projects")
+    }
+
+    fn press(app: &mut App, keys: &str) {
+        for c in keys.chars() {
+            let code = match c {
+                '\n' => KeyCode::Enter,
+                '>' => KeyCode::Right,
+                c => KeyCode::Char(c),
+            };
+            let _ = app.handle(KeyEvent::new(code, KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn filter_narrows_the_focused_pane() {
+        let projects = projects();
+        let keymap = Keymap::default();
+        let mut app = App::new(&projects, &keymap, false);
+        assert_eq!(app.commit().map(|c| c.hash.as_str()), Some("a1"));
+
+        press(&mut app, ">/bill\n");
+        assert_eq!(app.visible(Pane::Branches), vec![1]);
+        assert_eq!(
+            app.branch().map(|b| b.name.as_str()),
+            Some("feature/billing")
+        );
+        assert_eq!(app.commit().map(|c| c.hash.as_str()), Some("c3"));
+        assert!(!app.editing);
+    }
+
+    #[test]
+    fn moving_the_project_resets_the_panes_to_its_right() {
+        let projects = projects();
+        let keymap = Keymap::default();
+        let mut app = App::new(&projects, &keymap, false);
+        app.step(1);
+        assert_eq!(app.project().map(|p| p.project.as_str()), Some("docs"));
+        assert_eq!(app.branch().map(|b| b.name.as_str()), None);
+        assert!(app.commit().is_none());
+    }
+
+    #[test]
+    fn ctrl_keys_map_like_the_terminal() {
+        let event = KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL);
+        let key = console_key(&event).expect("mapped");
+        assert_eq!(Keymap::default().action_for(&key, true), Some(Action::Open));
+    }
+
+    #[test]
+    fn panes_move_left_and_right() {
+        assert_eq!(Pane::Projects.right(), Pane::Branches);
+        assert_eq!(Pane::Commits.right(), Pane::Commits);
+        assert_eq!(Pane::Commits.left(), Pane::Branches);
+        assert_eq!(Pane::Projects.left(), Pane::Projects);
+    }
+}
@@ -1,3 +1,7 @@
+// `clippy.toml` only allows `.expect()` inside `#[test]` functions; the
+// helpers here panic on setup failures the same way.
+#![allow(clippy::expect_used)]
+
 use std::process::Command;
 
 fn cargo_run(args: &[&str]) -> std::process::Output {
@@ -98,11 +102,198 @@ fn inverted_date_range_shows_error() {
 }
 
 #[test]
-fn nonexistent_path_shows_message() {
+fn nonexistent_path_shows_error() {
     let output = cargo_run(&["--path", "/tmp/nonexistent_devcap_test_dir"]);
     let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
     assert!(
-        stderr.contains("No git repositories found") || output.status.success(),
+        stderr.contains("path does not exist"),
         "Unexpected output: {stderr}"
     );
 }
+
+#[test]
+fn json_errors_are_reported_on_stdout() {
+    let output = cargo_run(&["--json", "--path", "/tmp/nonexistent_devcap_test_dir"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value: serde_json::Value = serde_json::from_str(stdout.trim()).expect("JSON error");
+    assert_eq!(value["error"]["code"], "path_not_found");
+
+    let output = cargo_run(&["--json", "-p", "foobar"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value: serde_json::Value = serde_json::from_str(stdout.trim()).expect("JSON error");
+    assert_eq!(value["error"]["code"], "invalid_argument");
+}
+
+#[test]
+fn parse_errors_are_json_with_format_json() {
+    for args in [
+        ["--format", "json", "-p", "foobar"].as_slice(),
+        ["--format=json", "-p", "foobar"].as_slice(),
+    ] {
+        let output = cargo_run(args);
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let value: serde_json::Value = serde_json::from_str(stdout.trim()).expect("JSON error");
+        assert_eq!(value["error"]["code"], "invalid_argument");
+    }
+}
+
+#[test]
+fn invalid_group_by_shows_error() {
+    let output = cargo_run(&["--group-by", "year", "--path", "/tmp"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("Unknown grouping"),
+        "Unexpected error: {stderr}"
+    );
+}
+
+#[test]
+fn day_subcommand_outputs_json() {
+    let output = cargo_run(&["day", "2026-03-02", "--json", "--path", "/tmp"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(serde_json::from_str::<serde_json::Value>(stdout.trim()).is_ok());
+}
+
+#[test]
+fn bundle_flag_rejects_non_bundle_file() {
+    let output = cargo_run(&[
+        "--bundle",
+        "Cargo.toml",
+        "--path",
+        "/tmp/nonexistent_devcap_test_dir",
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("not a git bundle"),
+        "Unexpected error: {stderr}"
+    );
+}
+
+#[test]
+fn export_requires_output_dir() {
+    let output = cargo_run(&["export", "patches", "--path", "/tmp"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("--output"), "Unexpected error: {stderr}");
+}
+
+#[test]
+fn invalid_exclude_grep_shows_error() {
+    let output = cargo_run(&["--exclude-grep", "(", "--path", ".."]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("invalid pattern"),
+        "Unexpected error: {stderr}"
+    );
+}
+
+#[test]
+fn offline_rejects_fetch() {
+    let output = cargo_run(&["--offline", "--fetch", "--path", "/tmp"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("offline mode"),
+        "Unexpected error: {stderr}"
+    );
+}
+
+#[test]
+fn offline_rejects_post() {
+    let output = cargo_run(&["--offline", "--post", "slack", "--path", "/tmp"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("offline mode"),
+        "Unexpected error: {stderr}"
+    );
+}
+
+#[test]
+fn schema_flag_prints_json_schema() {
+    let output = cargo_run(&["--schema"]);
+    assert!(output.status.success());
+    let schema: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("schema is valid JSON");
+    assert!(schema["$defs"]["commit"]["properties"]["hash"].is_object());
+}
+
+#[test]
+fn template_renders_empty_report() {
+    let dir = std::env::temp_dir().join("devcap-template-test");
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    let template = dir.join("report.hbs");
+    std::fs::write(
+        &template,
+        "{{#each projects}}\n- {{project}}\n{{else}}\nNothing in {{commits}} commits\n{{/each}}\n",
+    )
+    .expect("write template");
+    let output = cargo_run(&[
+        "--path",
+        &dir.to_string_lossy(),
+        "--template",
+        &template.to_string_lossy(),
+    ]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Nothing in 0 commits\n"
+    );
+}
+
+#[test]
+fn quiet_flag_silences_informational_stderr() {
+    let dir = std::env::temp_dir().join("devcap-quiet-test");
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    let path = dir.to_string_lossy();
+    let output = cargo_run(&["--quiet", "--path", &path]);
+    assert!(output.status.success());
+    assert!(
+        output.stderr.is_empty(),
+        "Unexpected stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn no_cache_flag_accepted() {
+    let output = cargo_run(&["--no-cache", "--path", "/tmp"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn author_flag_repeatable() {
+    let output = cargo_run(&["--author", "Jane", "--author", "Max", "--path", "/tmp"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn select_conflicts_with_interactive() {
+    let output = cargo_run(&["--select", "project", "--interactive", "--path", "/tmp"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("cannot be used with"),
+        "Unexpected error: {stderr}"
+    );
+}
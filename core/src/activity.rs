@@ -0,0 +1,279 @@
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{Duration, NaiveDate};
+use serde::Serialize;
+
+use crate::model::{BranchLog, ProjectLog, WorkTime};
+use crate::period::TimeRange;
+use crate::zone::Time;
+
+/// Commits further apart than this start a new work session.
+const SESSION_GAP_MINUTES: i64 = 120;
+/// Time credited before the first commit of each session.
+pub const SESSION_LEAD_MINUTES: i64 = 30;
+
+/// First and last commit of one calendar day across all projects.
+#[derive(Debug, Clone, Serialize)]
+pub struct DaySpan {
+    pub date: NaiveDate,
+    pub first: Time,
+    pub last: Time,
+    /// Estimated working time: commit sessions plus a lead-in per session.
+    #[serde(rename = "estimated_minutes")]
+    pub estimated: WorkTime,
+}
+
+const BARS: [char; 8] = [
+    '\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}', '\u{2588}',
+];
+
+/// Calendar days covered by a range, oldest first. Open-ended ranges end today.
+pub fn period_days(range: &TimeRange, now: Time) -> Vec<NaiveDate> {
+    let first = range.since.date_naive();
+    // Exclusive bounds at midnight (e.g. `yesterday`) must not add the next day.
+    let last = range
+        .until
+        .map(|u| (u - Duration::nanoseconds(1)).date_naive())
+        .unwrap_or_else(|| now.date_naive());
+    first.iter_days().take_while(|d| *d <= last).collect()
+}
+
+/// Unique commits per day across all branches, aligned with `days`.
+pub fn daily_counts(branches: &[BranchLog], days: &[NaiveDate]) -> Vec<u32> {
+    let mut counts = vec![0u32; days.len()];
+    let mut seen = HashSet::new();
+    for commit in branches.iter().flat_map(|b| &b.commits) {
+        if !seen.insert(&commit.hash) {
+            continue;
+        }
+        if let Ok(idx) = days.binary_search(&commit.time.date_naive()) {
+            counts[idx] += 1;
+        }
+    }
+    counts
+}
+
+/// One span per day that has commits, oldest first. Commits reachable from
+/// several branches of a project are counted once.
+pub fn daily_spans(projects: &[ProjectLog]) -> Vec<DaySpan> {
+    let mut per_day: BTreeMap<NaiveDate, Vec<Time>> = BTreeMap::new();
+    for project in projects {
+        let mut seen = HashSet::new();
+        for commit in project.branches.iter().flat_map(|b| &b.commits) {
+            if seen.insert(&commit.hash) {
+                per_day
+                    .entry(commit.time.date_naive())
+                    .or_default()
+                    .push(commit.time);
+            }
+        }
+    }
+    per_day
+        .into_iter()
+        .filter_map(|(date, mut times)| {
+            times.sort();
+            Some(DaySpan {
+                date,
+                first: *times.first()?,
+                last: *times.last()?,
+                estimated: WorkTime::from_minutes(estimate_minutes(&times)),
+            })
+        })
+        .collect()
+}
+
+/// Estimate working time from sorted commit timestamps: gaps up to
+/// two hours count as work, and each session gets a 30 minute lead-in.
+pub fn estimate_minutes(sorted: &[Time]) -> u32 {
+    let Some(first) = sorted.first() else {
+        return 0;
+    };
+    let mut total = SESSION_LEAD_MINUTES;
+    let mut prev = *first;
+    for &t in &sorted[1..] {
+        let gap = (t - prev).num_minutes();
+        total += if gap > SESSION_GAP_MINUTES {
+            SESSION_LEAD_MINUTES
+        } else {
+            gap
+        };
+        prev = t;
+    }
+    total.max(0) as u32
+}
+
+/// Most bars a sparkline is drawn with, one per day up to a month.
+pub const MAX_BARS: usize = 31;
+
+/// Render daily counts as a block sparkline scaled to the busiest bar. Days
+/// without commits use the lowest bar so the shape stays aligned with the
+/// calendar. Beyond [`MAX_BARS`] days, each bar sums a week, or as many
+/// whole weeks as it takes to stay within that width.
+pub fn sparkline(counts: &[u32]) -> String {
+    let counts: Vec<u32> = if counts.len() > MAX_BARS {
+        let weeks = counts.len().div_ceil(7).div_ceil(MAX_BARS);
+        counts
+            .chunks(7 * weeks)
+            .map(|chunk| chunk.iter().sum())
+            .collect()
+    } else {
+        counts.to_vec()
+    };
+    let max = counts.iter().copied().max().unwrap_or(0);
+    counts
+        .iter()
+        .map(|&c| {
+            if max == 0 || c == 0 {
+                BARS[0]
+            } else {
+                let level = (c as usize * (BARS.len() - 1)).div_ceil(max as usize);
+                BARS[level.min(BARS.len() - 1)]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Commit;
+    use crate::zone::Zone;
+    use chrono::{Local, TimeZone};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).expect("valid date")
+    }
+
+    fn at(d: NaiveDate, hour: u32) -> Time {
+        Local
+            .from_local_datetime(&d.and_hms_opt(hour, 0, 0).expect("valid time"))
+            .single()
+            .expect("unambiguous time")
+            .fixed_offset()
+    }
+
+    fn commit(hash: &str, time: Time) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            message: "msg".to_string(),
+            time,
+            relative_time: "1d ago".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn period_days_inclusive_range() {
+        let range =
+            TimeRange::from_dates(date(2026, 3, 1), date(2026, 3, 3), Zone::Local).expect("range");
+        let days = period_days(&range, at(date(2026, 3, 10), 12));
+        assert_eq!(
+            days,
+            vec![date(2026, 3, 1), date(2026, 3, 2), date(2026, 3, 3)]
+        );
+    }
+
+    #[test]
+    fn period_days_exclusive_midnight_end() {
+        let range = TimeRange {
+            since: at(date(2026, 3, 1), 0),
+            until: Some(at(date(2026, 3, 2), 0)),
+            zone: Zone::Local,
+        };
+        let days = period_days(&range, at(date(2026, 3, 10), 12));
+        assert_eq!(days, vec![date(2026, 3, 1)]);
+    }
+
+    #[test]
+    fn period_days_open_ended_until_now() {
+        let range = TimeRange::from_since_date(date(2026, 3, 1), Zone::Local).expect("range");
+        let days = period_days(&range, at(date(2026, 3, 2), 9));
+        assert_eq!(days.len(), 2);
+    }
+
+    #[test]
+    fn daily_counts_dedupes_across_branches() {
+        let d1 = date(2026, 3, 1);
+        let d2 = date(2026, 3, 2);
+        let branches = vec![
+            BranchLog {
+                name: "main".to_string(),
+                commits: vec![commit("a", at(d1, 10)), commit("b", at(d2, 11))],
+                ..Default::default()
+            },
+            BranchLog {
+                name: "feature".to_string(),
+                commits: vec![commit("a", at(d1, 10)), commit("c", at(d2, 15))],
+                ..Default::default()
+            },
+        ];
+        assert_eq!(daily_counts(&branches, &[d1, d2]), vec![1, 2]);
+    }
+
+    #[test]
+    fn estimate_splits_sessions_on_long_gaps() {
+        let d = date(2026, 3, 2);
+        let t = |h: u32, m: u32| {
+            Local
+                .from_local_datetime(&d.and_hms_opt(h, m, 0).expect("valid time"))
+                .single()
+                .expect("unambiguous time")
+                .fixed_offset()
+        };
+        // 09:00-11:00 session, lunch, 14:00-15:30 session
+        let times = [t(9, 0), t(10, 0), t(11, 0), t(14, 0), t(15, 30)];
+        assert_eq!(estimate_minutes(&times), 30 + 120 + 30 + 90);
+        assert_eq!(estimate_minutes(&times[..1]), 30);
+        assert_eq!(estimate_minutes(&[]), 0);
+    }
+
+    #[test]
+    fn daily_spans_per_day_across_projects() {
+        let d1 = date(2026, 3, 1);
+        let d2 = date(2026, 3, 2);
+        let project = |name: &str, commits: Vec<Commit>| ProjectLog {
+            project: name.to_string(),
+            path: format!("/src/{name}"),
+            branches: vec![BranchLog {
+                name: "main".to_string(),
+                commits,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let projects = vec![
+            project(
+                "api",
+                vec![commit("b", at(d2, 17)), commit("a", at(d1, 10))],
+            ),
+            project("web", vec![commit("c", at(d2, 9))]),
+        ];
+        let spans = daily_spans(&projects);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].date, d1);
+        assert_eq!(spans[1].first, at(d2, 9));
+        assert_eq!(spans[1].last, at(d2, 17));
+        assert_eq!(spans[1].estimated, WorkTime::from_minutes(60));
+    }
+
+    #[test]
+    fn sparkline_scales_to_max() {
+        assert_eq!(sparkline(&[0, 1, 2, 4]), "\u{2581}\u{2583}\u{2585}\u{2588}");
+        assert_eq!(sparkline(&[0, 0]), "\u{2581}\u{2581}");
+        assert_eq!(sparkline(&[]), "");
+    }
+
+    #[test]
+    fn sparkline_sums_weeks_of_long_periods() {
+        assert_eq!(sparkline(&[1; 31]).chars().count(), 31);
+        // A quarter: one bar per week, the partial last week included.
+        let quarter = sparkline(&[1; 91]);
+        assert_eq!(quarter.chars().count(), 13);
+        let mut year = vec![0; 365];
+        year[0] = 5;
+        let bars: Vec<char> = sparkline(&year).chars().collect();
+        assert!(bars.len() <= MAX_BARS);
+        assert_eq!(bars[0], '\u{2588}');
+        assert!(bars[1..].iter().all(|&b| b == '\u{2581}'));
+    }
+}
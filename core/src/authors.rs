@@ -0,0 +1,223 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::git;
+use crate::model::{BranchLog, ProjectLog, WorkTime};
+
+/// Replace each commit's author and co-authors with the display name
+/// configured for their email or name. Keys are matched case-insensitively,
+/// the email first, so several identities of one person collapse into a
+/// single name.
+pub fn apply_display_names(projects: &mut [ProjectLog], names: &HashMap<String, String>) {
+    if names.is_empty() {
+        return;
+    }
+    let names: HashMap<String, &String> = names
+        .iter()
+        .map(|(key, name)| (key.trim().to_lowercase(), name))
+        .collect();
+    for commit in projects
+        .iter_mut()
+        .flat_map(|p| &mut p.branches)
+        .flat_map(|b| &mut b.commits)
+    {
+        if let Some(display) = lookup(&names, &commit.author_email, &commit.author) {
+            commit.author = display.to_string();
+        }
+        for co in &mut commit.co_authors {
+            if let Some(display) = lookup(&names, &co.email, &co.name) {
+                co.name = display.to_string();
+            }
+        }
+    }
+}
+
+fn lookup<'a>(names: &HashMap<String, &'a String>, email: &str, name: &str) -> Option<&'a String> {
+    names
+        .get(&email.to_lowercase())
+        .or_else(|| names.get(&name.to_lowercase()))
+        .copied()
+}
+
+/// Author filters widened by `aliases`, which map a person to the other
+/// names and emails they commit under. A filter equal to a key or to one of
+/// its aliases (ignoring case) pulls in the key and all its aliases, so one
+/// `--author` matches every identity. The added names are matched literally;
+/// the filters themselves are kept as they are.
+pub fn expand_aliases(filters: &[String], aliases: &HashMap<String, Vec<String>>) -> Vec<String> {
+    let mut expanded: Vec<String> = Vec::new();
+    let mut push = |filter: &String| {
+        if !expanded.iter().any(|f| f.eq_ignore_ascii_case(filter)) {
+            expanded.push(filter.clone());
+        }
+    };
+    for filter in filters {
+        push(filter);
+        for (key, names) in aliases {
+            if std::iter::once(key)
+                .chain(names)
+                .any(|n| n.trim().eq_ignore_ascii_case(filter.trim()))
+            {
+                std::iter::once(key)
+                    .chain(names)
+                    .map(|n| git::literal_pattern(n))
+                    .for_each(|n| push(&n));
+            }
+        }
+    }
+    expanded
+}
+
+/// One author's share of the report.
+#[derive(Debug, Serialize)]
+pub struct AuthorGroup {
+    pub author: String,
+    pub commits: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_spent: Option<WorkTime>,
+    pub projects: Vec<ProjectLog>,
+}
+
+/// Split projects by commit author, most active author first. Project order
+/// inside a group follows the input order; aggregate diff stats, activity and
+/// releases describe the whole repository and are dropped from the copies.
+pub fn group(projects: Vec<ProjectLog>) -> Vec<AuthorGroup> {
+    let mut groups: BTreeMap<String, Vec<ProjectLog>> = BTreeMap::new();
+
+    for project in projects {
+        let mut per_author: BTreeMap<String, Vec<BranchLog>> = BTreeMap::new();
+        for branch in project.branches {
+            let mut split: BTreeMap<String, Vec<_>> = BTreeMap::new();
+            for commit in branch.commits {
+                split.entry(commit.author.clone()).or_default().push(commit);
+            }
+            for (author, commits) in split {
+                per_author.entry(author).or_default().push(BranchLog {
+                    name: branch.name.clone(),
+                    url: branch.url.clone(),
+                    commits,
+                    diff_stat: None,
+                    pr: branch.pr.clone(),
+                    started: branch.started,
+                    omitted: None,
+                    worktree: branch.worktree.clone(),
+                    remote_name: branch.remote_name.clone(),
+                    merged: branch.merged,
+                    upstream: branch.upstream.clone(),
+                });
+            }
+        }
+        for (author, branches) in per_author {
+            groups.entry(author).or_default().push(ProjectLog {
+                project: project.project.clone(),
+                path: project.path.clone(),
+                origin: project.origin.clone(),
+                remote_url: project.remote_url.clone(),
+                branches,
+                diff_stat: None,
+                activity: None,
+                releases: None,
+                health: project.health.clone(),
+                group: project.group.clone(),
+            });
+        }
+    }
+
+    let mut groups: Vec<AuthorGroup> = groups
+        .into_iter()
+        .map(|(author, projects)| AuthorGroup {
+            author,
+            commits: projects.iter().map(ProjectLog::total_commits).sum(),
+            time_spent: crate::model::total_time_spent(&projects),
+            projects,
+        })
+        .collect();
+    groups.sort_by_key(|g| std::cmp::Reverse(g.commits));
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Commit;
+    use chrono::Local;
+
+    fn commit(hash: &str, author: &str, email: &str) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            message: format!("commit {hash}"),
+            author: author.to_string(),
+            author_email: email.to_string(),
+            time: Local::now().fixed_offset(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn aliases_widen_matching_filters() {
+        let aliases = HashMap::from([(
+            "Jane Doe".to_string(),
+            vec!["jane@work.example".to_string(), "jdoe".to_string()],
+        )]);
+        assert_eq!(
+            expand_aliases(&["JDOE".to_string(), "bob".to_string()], &aliases),
+            ["JDOE", "Jane Doe", "jane@work\\.example", "bob"]
+        );
+        assert_eq!(
+            expand_aliases(&["Jane Doe".to_string()], &HashMap::new()),
+            ["Jane Doe"]
+        );
+    }
+
+    fn project(name: &str, commits: Vec<Commit>) -> ProjectLog {
+        ProjectLog {
+            project: name.to_string(),
+            path: format!("/src/{name}"),
+            branches: vec![BranchLog {
+                name: "main".to_string(),
+                commits,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn display_names_match_email_then_name() {
+        let mut projects = vec![project(
+            "web",
+            vec![
+                commit("a", "jdoe", "Jane@Work.example"),
+                commit("b", "Jane D.", "jane@home.example"),
+                commit("c", "Bob", "bob@work.example"),
+            ],
+        )];
+        let names = HashMap::from([
+            ("jane@work.example".to_string(), "Jane Doe".to_string()),
+            ("Jane D.".to_string(), "Jane Doe".to_string()),
+        ]);
+        apply_display_names(&mut projects, &names);
+        let authors: Vec<&str> = projects[0].branches[0]
+            .commits
+            .iter()
+            .map(|c| c.author.as_str())
+            .collect();
+        assert_eq!(authors, ["Jane Doe", "Jane Doe", "Bob"]);
+    }
+
+    #[test]
+    fn group_orders_authors_by_commits() {
+        let projects = vec![
+            project("web", vec![commit("a", "Ann", ""), commit("b", "Bob", "")]),
+            project("api", vec![commit("c", "Bob", ""), commit("d", "Bob", "")]),
+        ];
+        let groups = group(projects);
+        let summary: Vec<(&str, usize, usize)> = groups
+            .iter()
+            .map(|g| (g.author.as_str(), g.commits, g.projects.len()))
+            .collect();
+        assert_eq!(summary, [("Bob", 3, 2), ("Ann", 1, 1)]);
+        assert_eq!(groups[0].projects[0].project, "web");
+    }
+}
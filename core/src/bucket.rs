@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+
+use crate::activity::{self, DaySpan};
+use crate::model::{BranchLog, ProjectLog, WorkTime};
+
+/// Calendar unit used to split a long period into sections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeBucket {
+    Day,
+    Week,
+    Month,
+}
+
+impl FromStr for TimeBucket {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(TimeBucket::Day),
+            "week" => Ok(TimeBucket::Week),
+            "month" => Ok(TimeBucket::Month),
+            other => Err(format!("Unknown grouping: {other}. Use: day, week, month")),
+        }
+    }
+}
+
+impl fmt::Display for TimeBucket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeBucket::Day => write!(f, "day"),
+            TimeBucket::Week => write!(f, "week"),
+            TimeBucket::Month => write!(f, "month"),
+        }
+    }
+}
+
+impl TimeBucket {
+    /// First day of the bucket containing `date` (weeks start on Monday).
+    pub fn start_of(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            TimeBucket::Day => date,
+            TimeBucket::Week => {
+                date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+            }
+            TimeBucket::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    /// Human-readable label for the bucket starting at `start`.
+    pub fn label(self, start: NaiveDate) -> String {
+        match self {
+            TimeBucket::Day => start.format("%Y-%m-%d %a").to_string(),
+            TimeBucket::Week => {
+                let iso = start.iso_week();
+                format!("{}-W{:02}", iso.year(), iso.week())
+            }
+            TimeBucket::Month => start.format("%B %Y").to_string(),
+        }
+    }
+}
+
+/// How a report is sectioned: the plain per-project tree, or calendar buckets
+/// that each hold their own project trees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    Project,
+    Time(TimeBucket),
+    /// Configured commit categories, see [`crate::category`].
+    Category,
+}
+
+impl FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "project" => Ok(GroupBy::Project),
+            "category" => Ok(GroupBy::Category),
+            other => other.parse().map(GroupBy::Time).map_err(|_| {
+                format!("Unknown grouping: {other}. Use: project, category, day, week, month")
+            }),
+        }
+    }
+}
+
+impl fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GroupBy::Project => write!(f, "project"),
+            GroupBy::Category => write!(f, "category"),
+            GroupBy::Time(by) => by.fmt(f),
+        }
+    }
+}
+
+impl GroupBy {
+    /// The calendar unit, unless the report stays grouped by project.
+    pub fn time_bucket(self) -> Option<TimeBucket> {
+        match self {
+            GroupBy::Project | GroupBy::Category => None,
+            GroupBy::Time(by) => Some(by),
+        }
+    }
+}
+
+/// One section of a grouped report with its per-bucket subtotals.
+#[derive(Debug, Serialize)]
+pub struct Bucket {
+    #[serde(rename = "bucket")]
+    pub label: String,
+    pub start: NaiveDate,
+    pub commits: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_spent: Option<WorkTime>,
+    /// First/last activity, for day buckets only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<DaySpan>,
+    pub projects: Vec<ProjectLog>,
+}
+
+/// Split projects into calendar buckets, newest first. Project order inside a
+/// bucket follows the input order. Aggregate diff stats and activity cover the
+/// whole period, so they are dropped from the per-bucket copies; releases go
+/// to the bucket they were tagged in.
+pub fn group(projects: Vec<ProjectLog>, by: TimeBucket) -> Vec<Bucket> {
+    let mut buckets: BTreeMap<NaiveDate, Vec<ProjectLog>> = BTreeMap::new();
+
+    for project in projects {
+        let mut per_bucket: BTreeMap<NaiveDate, Vec<BranchLog>> = BTreeMap::new();
+        for branch in project.branches {
+            let mut split: BTreeMap<NaiveDate, Vec<_>> = BTreeMap::new();
+            for commit in branch.commits {
+                let start = by.start_of(commit.time.date_naive());
+                split.entry(start).or_default().push(commit);
+            }
+            for (start, commits) in split {
+                per_bucket.entry(start).or_default().push(BranchLog {
+                    name: branch.name.clone(),
+                    url: branch.url.clone(),
+                    commits,
+                    diff_stat: None,
+                    pr: branch.pr.clone(),
+                    started: branch.started,
+                    omitted: None,
+                    worktree: branch.worktree.clone(),
+                    remote_name: branch.remote_name.clone(),
+                    merged: branch.merged,
+                    upstream: branch.upstream.clone(),
+                });
+            }
+        }
+        for (start, branches) in per_bucket {
+            let releases: Vec<_> = project
+                .releases
+                .iter()
+                .flatten()
+                .filter(|r| by.start_of(r.time.date_naive()) == start)
+                .cloned()
+                .collect();
+            buckets.entry(start).or_default().push(ProjectLog {
+                project: project.project.clone(),
+                path: project.path.clone(),
+                origin: project.origin.clone(),
+                remote_url: project.remote_url.clone(),
+                branches,
+                diff_stat: None,
+                activity: None,
+                releases: (!releases.is_empty()).then_some(releases),
+                health: project.health.clone(),
+                group: project.group.clone(),
+            });
+        }
+    }
+
+    buckets
+        .into_iter()
+        .rev()
+        .map(|(start, projects)| Bucket {
+            label: by.label(start),
+            start,
+            commits: projects.iter().map(ProjectLog::total_commits).sum(),
+            time_spent: crate::model::total_time_spent(&projects),
+            span: match by {
+                TimeBucket::Day => activity::daily_spans(&projects).into_iter().next(),
+                _ => None,
+            },
+            projects,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Commit;
+    use chrono::{Local, TimeZone};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).expect("valid date")
+    }
+
+    fn commit(hash: &str, day: NaiveDate) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            message: "msg".to_string(),
+            time: Local
+                .from_local_datetime(&day.and_hms_opt(12, 0, 0).expect("valid time"))
+                .single()
+                .expect("unambiguous time")
+                .fixed_offset(),
+            relative_time: "1d ago".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn project(name: &str, commits: Vec<Commit>) -> ProjectLog {
+        ProjectLog {
+            project: name.to_string(),
+            path: format!("/src/{name}"),
+            branches: vec![BranchLog {
+                name: "main".to_string(),
+                commits,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_bucket_units() {
+        assert_eq!("day".parse::<TimeBucket>(), Ok(TimeBucket::Day));
+        assert_eq!("week".parse::<TimeBucket>(), Ok(TimeBucket::Week));
+        assert_eq!("month".parse::<TimeBucket>(), Ok(TimeBucket::Month));
+        assert!("year".parse::<TimeBucket>().is_err());
+        assert_eq!("project".parse::<GroupBy>(), Ok(GroupBy::Project));
+        assert_eq!("category".parse::<GroupBy>(), Ok(GroupBy::Category));
+        assert_eq!("day".parse::<GroupBy>(), Ok(GroupBy::Time(TimeBucket::Day)));
+        assert!("year".parse::<GroupBy>().is_err());
+    }
+
+    #[test]
+    fn start_of_week_is_monday() {
+        // 2026-03-05 is a Thursday
+        assert_eq!(
+            TimeBucket::Week.start_of(date(2026, 3, 5)),
+            date(2026, 3, 2)
+        );
+        assert_eq!(
+            TimeBucket::Month.start_of(date(2026, 3, 5)),
+            date(2026, 3, 1)
+        );
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(TimeBucket::Day.label(date(2026, 3, 5)), "2026-03-05 Thu");
+        assert_eq!(TimeBucket::Week.label(date(2026, 3, 2)), "2026-W10");
+        assert_eq!(TimeBucket::Month.label(date(2026, 3, 1)), "March 2026");
+    }
+
+    #[test]
+    fn group_by_month_splits_projects_newest_first() {
+        let projects = vec![
+            project(
+                "api",
+                vec![
+                    commit("c", date(2026, 4, 2)),
+                    commit("b", date(2026, 3, 20)),
+                    commit("a", date(2026, 3, 1)),
+                ],
+            ),
+            project("web", vec![commit("d", date(2026, 3, 15))]),
+        ];
+        let buckets = group(projects, TimeBucket::Month);
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].label, "April 2026");
+        assert_eq!(buckets[0].commits, 1);
+        assert_eq!(buckets[1].label, "March 2026");
+        assert_eq!(buckets[1].commits, 3);
+        let names: Vec<_> = buckets[1].projects.iter().map(|p| &p.project).collect();
+        assert_eq!(names, ["api", "web"]);
+        assert!(buckets[1].span.is_none());
+    }
+
+    #[test]
+    fn buckets_keep_branch_worktree() {
+        let mut api = project(
+            "api",
+            vec![commit("b", date(2026, 4, 2)), commit("a", date(2026, 3, 1))],
+        );
+        api.branches[0].worktree = Some("/src/api-login".to_string());
+        let buckets = group(vec![api], TimeBucket::Month);
+        for bucket in &buckets {
+            assert_eq!(
+                bucket.projects[0].branches[0].worktree.as_deref(),
+                Some("/src/api-login")
+            );
+        }
+    }
+
+    #[test]
+    fn day_buckets_carry_activity_span() {
+        let projects = vec![project(
+            "api",
+            vec![commit("b", date(2026, 3, 2)), commit("a", date(2026, 3, 1))],
+        )];
+        let buckets = group(projects, TimeBucket::Day);
+        let span = buckets[0].span.as_ref().expect("day span");
+        assert_eq!(span.date, date(2026, 3, 2));
+    }
+}
@@ -0,0 +1,161 @@
+use std::cmp::Reverse;
+
+use crate::model::{BranchLog, Commit, Omitted, ProjectLog};
+
+/// Keep only the `max` most recent commit lines across all `projects` and
+/// record the rest per branch in [`BranchLog::omitted`](crate::model::BranchLog::omitted).
+/// A commit listed on two branches uses two lines.
+pub fn limit_commits<'a>(projects: impl IntoIterator<Item = &'a mut ProjectLog>, max: usize) {
+    let mut projects: Vec<&mut ProjectLog> = projects.into_iter().collect();
+
+    let mut times: Vec<_> = projects
+        .iter()
+        .flat_map(|p| &p.branches)
+        .flat_map(|b| &b.commits)
+        .map(|c| c.time)
+        .collect();
+    if times.len() <= max {
+        return;
+    }
+    times.sort_by_key(|t| Reverse(*t));
+    let cutoff = times[max.saturating_sub(1)];
+    // Commits at the cutoff time share the remaining lines in listing order.
+    let mut at_cutoff = times[..max].iter().filter(|t| **t == cutoff).count();
+
+    for branch in projects.iter_mut().flat_map(|p| &mut p.branches) {
+        let (kept, dropped): (Vec<_>, Vec<_>) = std::mem::take(&mut branch.commits)
+            .into_iter()
+            .partition(|c| {
+                if max == 0 || c.time < cutoff {
+                    false
+                } else if c.time > cutoff {
+                    true
+                } else if at_cutoff > 0 {
+                    at_cutoff -= 1;
+                    true
+                } else {
+                    false
+                }
+            });
+        branch.commits = kept;
+        record_omitted(branch, dropped);
+    }
+}
+
+/// Keep only the `max` most recent commits of each branch, recording the
+/// rest in [`BranchLog::omitted`].
+pub fn limit_branch_commits<'a>(
+    projects: impl IntoIterator<Item = &'a mut ProjectLog>,
+    max: usize,
+) {
+    for branch in projects.into_iter().flat_map(|p| &mut p.branches) {
+        if branch.commits.len() > max {
+            let dropped = branch.commits.split_off(max);
+            record_omitted(branch, dropped);
+        }
+    }
+}
+
+fn record_omitted(branch: &mut BranchLog, dropped: Vec<Commit>) {
+    if dropped.is_empty() {
+        return;
+    }
+    let omitted = branch.omitted.get_or_insert_with(|| Omitted {
+        commits: 0,
+        types: Default::default(),
+    });
+    omitted.commits += dropped.len();
+    for commit in dropped {
+        let kind = commit.commit_type.unwrap_or_else(|| "other".to_string());
+        *omitted.types.entry(kind).or_default() += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BranchLog, Commit};
+    use chrono::{Duration, Local};
+
+    fn commit(hours_ago: i64, commit_type: Option<&str>) -> Commit {
+        Commit {
+            hash: format!("h{hours_ago}"),
+            message: "msg".to_string(),
+            commit_type: commit_type.map(String::from),
+            time: Local::now().fixed_offset() - Duration::hours(hours_ago),
+            ..Default::default()
+        }
+    }
+
+    fn project(name: &str, commits: Vec<Commit>) -> ProjectLog {
+        ProjectLog {
+            project: name.to_string(),
+            path: format!("/src/{name}"),
+            branches: vec![BranchLog {
+                name: "main".to_string(),
+                commits,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn keeps_most_recent_commits_across_projects() {
+        let mut projects = vec![
+            project(
+                "web",
+                vec![
+                    commit(1, Some("feat")),
+                    commit(5, Some("chore")),
+                    commit(6, None),
+                ],
+            ),
+            project(
+                "api",
+                vec![commit(2, Some("fix")), commit(7, Some("chore"))],
+            ),
+        ];
+        limit_commits(&mut projects, 2);
+
+        let web = &projects[0].branches[0];
+        assert_eq!(web.commits.len(), 1);
+        let omitted = web.omitted.as_ref().expect("omitted");
+        assert_eq!(omitted.commits, 2);
+        assert_eq!(omitted.types.get("chore"), Some(&1));
+        assert_eq!(omitted.types.get("other"), Some(&1));
+
+        let api = &projects[1].branches[0];
+        assert_eq!(api.commits[0].hash, "h2");
+        assert_eq!(api.omitted.as_ref().map(|o| o.commits), Some(1));
+    }
+
+    #[test]
+    fn limits_each_branch_on_its_own() {
+        let mut projects = vec![
+            project(
+                "web",
+                vec![commit(1, None), commit(2, Some("fix")), commit(3, None)],
+            ),
+            project("api", vec![commit(4, None), commit(5, None)]),
+        ];
+        limit_branch_commits(&mut projects, 2);
+        limit_commits(&mut projects, 3);
+
+        let web = &projects[0].branches[0];
+        assert_eq!(web.commits.len(), 2);
+        let omitted = web.omitted.as_ref().expect("omitted");
+        assert_eq!(omitted.commits, 1);
+        let api = &projects[1].branches[0];
+        assert_eq!(api.commits.len(), 1);
+        assert_eq!(api.omitted.as_ref().map(|o| o.commits), Some(1));
+    }
+
+    #[test]
+    fn within_budget_is_untouched() {
+        let mut projects = vec![project("web", vec![commit(1, None), commit(2, None)])];
+        limit_commits(&mut projects, 2);
+        assert_eq!(projects[0].branches[0].commits.len(), 2);
+        assert!(projects[0].branches[0].omitted.is_none());
+    }
+}
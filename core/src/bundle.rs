@@ -0,0 +1,93 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::git::{self, LogOptions};
+use crate::model::ProjectLog;
+use crate::period::TimeRange;
+
+static CLONE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether `path` starts with a git bundle header (`# v2 git bundle`).
+pub fn is_bundle(path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    File::open(path)
+        .and_then(|mut f| f.read_exact(&mut header))
+        .is_ok()
+        && header.starts_with(b"# v")
+        && header[4..].starts_with(b" git bundle")
+}
+
+/// Collect the log of a bundle by mirroring it into a temporary bare
+/// repository. The project is named after the bundle file.
+pub fn collect_bundle_log(
+    bundle: &Path,
+    range: &TimeRange,
+    opts: &LogOptions,
+) -> Option<ProjectLog> {
+    let scratch = TempClone::mirror(bundle)?;
+    let mut log = git::collect_project_log(&scratch.0, range, opts)?;
+    log.project = bundle.file_stem()?.to_string_lossy().to_string();
+    log.path = bundle.to_string_lossy().to_string();
+    log.origin = None;
+    log.remote_url = None;
+    for branch in &mut log.branches {
+        branch.url = None;
+        for commit in &mut branch.commits {
+            commit.url = None;
+            commit.unpushed = false;
+        }
+    }
+    Some(log)
+}
+
+/// A temporary bare mirror, removed on drop.
+struct TempClone(PathBuf);
+
+impl TempClone {
+    fn mirror(bundle: &Path) -> Option<Self> {
+        let n = CLONE_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let dir = std::env::temp_dir().join(format!("devcap-bundle-{}-{n}", std::process::id()));
+        let clone = TempClone(dir);
+        let status = git::command()
+            .args(["clone", "--quiet", "--mirror"])
+            .arg(bundle)
+            .arg(&clone.0)
+            .status()
+            .ok()?;
+        status.success().then_some(clone)
+    }
+}
+
+impl Drop for TempClone {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn detects_bundle_header() {
+        let dir = std::env::temp_dir().join(format!("devcap-bundle-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create dir");
+
+        let bundle = dir.join("work.bundle");
+        let mut f = File::create(&bundle).expect("create bundle");
+        f.write_all(b"# v2 git bundle\n6b35 refs/heads/main\n")
+            .expect("write bundle");
+
+        let other = dir.join("gems.bundle");
+        std::fs::write(&other, "not a bundle at all").expect("write file");
+
+        assert!(is_bundle(&bundle));
+        assert!(!is_bundle(&other));
+        assert!(!is_bundle(&dir.join("missing.bundle")));
+
+        std::fs::remove_dir_all(&dir).expect("cleanup");
+    }
+}
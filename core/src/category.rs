@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::Serialize;
+
+use crate::git;
+use crate::model::{BranchLog, Commit, ProjectLog, WorkTime};
+
+/// Category of commits that no rule matches.
+pub const UNCATEGORIZED: &str = "other";
+
+/// Assigns commits to a named category, e.g. "maintenance" for `chore` and
+/// `fix` commits touching `legacy/**`. Empty lists match everything.
+#[derive(Debug, Clone, Default)]
+pub struct CategoryRule {
+    pub name: String,
+    /// Conventional commit types.
+    pub types: Vec<String>,
+    /// Globs over the changed paths; one matching file is enough.
+    pub paths: Vec<String>,
+}
+
+struct CompiledRule<'a> {
+    rule: &'a CategoryRule,
+    paths: Option<GlobSet>,
+}
+
+/// Rules ready to classify commits. The first matching rule wins.
+pub struct Classifier<'a> {
+    rules: Vec<CompiledRule<'a>>,
+}
+
+impl<'a> Classifier<'a> {
+    pub fn new(rules: &'a [CategoryRule]) -> Result<Self, String> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                let paths = if rule.paths.is_empty() {
+                    None
+                } else {
+                    let mut set = GlobSetBuilder::new();
+                    for pattern in &rule.paths {
+                        let glob = Glob::new(pattern).map_err(|e| {
+                            format!("invalid path glob in category \"{}\": {e}", rule.name)
+                        })?;
+                        set.add(glob);
+                    }
+                    Some(set.build().map_err(|e| e.to_string())?)
+                };
+                Ok(CompiledRule { rule, paths })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Classifier { rules })
+    }
+
+    /// Category of `commit`; `files` lists its changed paths and is only
+    /// called when a rule needs them.
+    fn classify(&self, commit: &Commit, files: &mut dyn FnMut() -> Vec<String>) -> &'a str {
+        let mut changed: Option<Vec<String>> = None;
+        for compiled in &self.rules {
+            let rule = compiled.rule;
+            let type_matches = rule.types.is_empty()
+                || commit
+                    .commit_type
+                    .as_deref()
+                    .is_some_and(|t| rule.types.iter().any(|r| r == t));
+            if !type_matches {
+                continue;
+            }
+            let Some(paths) = &compiled.paths else {
+                return &rule.name;
+            };
+            let changed = changed.get_or_insert_with(&mut *files);
+            if changed.iter().any(|f| paths.is_match(f)) {
+                return &rule.name;
+            }
+        }
+        UNCATEGORIZED
+    }
+}
+
+/// One category's share of the report.
+#[derive(Debug, Serialize)]
+pub struct CategoryGroup {
+    pub category: String,
+    pub commits: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_spent: Option<WorkTime>,
+    pub projects: Vec<ProjectLog>,
+}
+
+/// Split projects by commit category, in rule order with uncategorized
+/// commits last. Changed paths are read from each repository only for
+/// rules that have `paths`.
+pub fn group(projects: Vec<ProjectLog>, classifier: &Classifier) -> Vec<CategoryGroup> {
+    let mut order: Vec<&str> = classifier
+        .rules
+        .iter()
+        .map(|r| r.rule.name.as_str())
+        .collect();
+    order.push(UNCATEGORIZED);
+    let mut groups: Vec<Vec<ProjectLog>> = order.iter().map(|_| Vec::new()).collect();
+
+    for project in projects {
+        let repo = Path::new(&project.path);
+        let mut cache: HashMap<String, &str> = HashMap::new();
+        let mut per_category: Vec<Vec<BranchLog>> = order.iter().map(|_| Vec::new()).collect();
+        for branch in project.branches {
+            let mut split: Vec<Vec<Commit>> = order.iter().map(|_| Vec::new()).collect();
+            for commit in branch.commits {
+                let category = *cache.entry(commit.hash.clone()).or_insert_with(|| {
+                    classifier.classify(&commit, &mut || git::changed_files(repo, &commit.hash))
+                });
+                let idx = order
+                    .iter()
+                    .position(|c| *c == category)
+                    .unwrap_or(order.len() - 1);
+                split[idx].push(commit);
+            }
+            for (idx, commits) in split.into_iter().enumerate() {
+                if commits.is_empty() {
+                    continue;
+                }
+                per_category[idx].push(BranchLog {
+                    name: branch.name.clone(),
+                    url: branch.url.clone(),
+                    commits,
+                    diff_stat: None,
+                    pr: branch.pr.clone(),
+                    started: branch.started,
+                    omitted: None,
+                    worktree: branch.worktree.clone(),
+                    remote_name: branch.remote_name.clone(),
+                    merged: branch.merged,
+                    upstream: branch.upstream.clone(),
+                });
+            }
+        }
+        for (idx, branches) in per_category.into_iter().enumerate() {
+            if branches.is_empty() {
+                continue;
+            }
+            groups[idx].push(ProjectLog {
+                project: project.project.clone(),
+                path: project.path.clone(),
+                origin: project.origin.clone(),
+                remote_url: project.remote_url.clone(),
+                branches,
+                diff_stat: None,
+                activity: None,
+                releases: None,
+                health: project.health.clone(),
+                group: project.group.clone(),
+            });
+        }
+    }
+
+    order
+        .into_iter()
+        .zip(groups)
+        .filter(|(_, projects)| !projects.is_empty())
+        .map(|(category, projects)| CategoryGroup {
+            category: category.to_string(),
+            commits: projects.iter().map(ProjectLog::total_commits).sum(),
+            time_spent: crate::model::total_time_spent(&projects),
+            projects,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Local;
+
+    fn commit(hash: &str, commit_type: Option<&str>) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            message: format!("commit {hash}"),
+            commit_type: commit_type.map(String::from),
+            time: Local::now().fixed_offset(),
+            ..Default::default()
+        }
+    }
+
+    fn rule(name: &str, types: &[&str], paths: &[&str]) -> CategoryRule {
+        CategoryRule {
+            name: name.to_string(),
+            types: types.iter().map(|s| s.to_string()).collect(),
+            paths: paths.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let rules = vec![
+            rule("maintenance", &["chore", "fix"], &["legacy/**"]),
+            rule("capitalizable", &["feat"], &[]),
+        ];
+        let classifier = Classifier::new(&rules).expect("valid rules");
+        let legacy = || vec!["legacy/billing/invoice.php".to_string()];
+        let modern = || vec!["src/api.rs".to_string()];
+
+        let fix = commit("a", Some("fix"));
+        assert_eq!(
+            classifier.classify(&fix, &mut legacy.clone()),
+            "maintenance"
+        );
+        assert_eq!(
+            classifier.classify(&fix, &mut modern.clone()),
+            UNCATEGORIZED
+        );
+        let feat = commit("b", Some("feat"));
+        let mut never = || -> Vec<String> { panic!("paths not needed") };
+        assert_eq!(classifier.classify(&feat, &mut never), "capitalizable");
+        assert_eq!(
+            classifier.classify(&commit("c", None), &mut never),
+            UNCATEGORIZED
+        );
+    }
+
+    #[test]
+    fn invalid_glob_is_reported() {
+        let rules = vec![rule("broken", &[], &["legacy/[*"])];
+        let err = Classifier::new(&rules).err().expect("invalid glob");
+        assert!(err.contains("broken"));
+    }
+
+    #[test]
+    fn groups_follow_rule_order() {
+        let rules = vec![rule("capitalizable", &["feat"], &[])];
+        let classifier = Classifier::new(&rules).expect("valid rules");
+        let projects = vec![ProjectLog {
+            project: "api".to_string(),
+            path: "/src/api".to_string(),
+            branches: vec![BranchLog {
+                name: "main".to_string(),
+                commits: vec![commit("a", Some("chore")), commit("b", Some("feat"))],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }];
+        let groups = group(projects, &classifier);
+        let names: Vec<&str> = groups.iter().map(|g| g.category.as_str()).collect();
+        assert_eq!(names, ["capitalizable", UNCATEGORIZED]);
+        assert_eq!(groups[0].commits, 1);
+    }
+}
@@ -0,0 +1,97 @@
+use std::ffi::OsString;
+
+/// Whether to color output, for every devcap frontend alike.
+///
+/// In order of precedence: `disabled` (`--no-color`, machine-readable
+/// output), the `configured` setting, the `NO_COLOR`, `CLICOLOR_FORCE` and
+/// `CLICOLOR` environment variables, and finally whether the output goes to
+/// a terminal.
+pub fn should_color(disabled: bool, configured: Option<bool>, is_terminal: bool) -> bool {
+    decide(disabled, configured, is_terminal, |key| {
+        std::env::var_os(key)
+    })
+}
+
+fn decide(
+    disabled: bool,
+    configured: Option<bool>,
+    is_terminal: bool,
+    env: impl Fn(&str) -> Option<OsString>,
+) -> bool {
+    if disabled {
+        return false;
+    }
+    if let Some(configured) = configured {
+        return configured;
+    }
+    // https://no-color.org: any non-empty value disables colors.
+    if env("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        return false;
+    }
+    // https://bixense.com/clicolors: colors even when piped unless "0",
+    // none at all with `CLICOLOR=0`.
+    if env("CLICOLOR_FORCE").is_some_and(|v| !v.is_empty() && v != "0") {
+        return true;
+    }
+    if env("CLICOLOR").is_some_and(|v| v == "0") {
+        return false;
+    }
+    is_terminal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<OsString> {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| {
+            vars.iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| OsString::from(v))
+        }
+    }
+
+    #[test]
+    fn flags_and_config_win_over_environment() {
+        let force = with_env(&[("CLICOLOR_FORCE", "1")]);
+        assert!(!decide(true, Some(true), true, &force));
+        assert!(!decide(false, Some(false), false, &force));
+        assert!(decide(
+            false,
+            Some(true),
+            false,
+            with_env(&[("NO_COLOR", "1")])
+        ));
+    }
+
+    #[test]
+    fn environment_conventions() {
+        assert!(!decide(false, None, true, with_env(&[("NO_COLOR", "1")])));
+        assert!(decide(false, None, true, with_env(&[("NO_COLOR", "")])));
+        assert!(decide(
+            false,
+            None,
+            false,
+            with_env(&[("CLICOLOR_FORCE", "1")])
+        ));
+        assert!(!decide(
+            false,
+            None,
+            false,
+            with_env(&[("CLICOLOR_FORCE", "0")])
+        ));
+        assert!(!decide(
+            false,
+            None,
+            false,
+            with_env(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")])
+        ));
+        assert!(!decide(false, None, true, with_env(&[("CLICOLOR", "0")])));
+        assert!(decide(false, None, true, with_env(&[("CLICOLOR", "1")])));
+        assert!(!decide(false, None, false, with_env(&[])));
+    }
+}
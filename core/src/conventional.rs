@@ -0,0 +1,181 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+
+use crate::model::ProjectLog;
+
+/// Whether `message` (the subject line) follows the Conventional Commits
+/// format `type(scope)!: description`, with scope and `!` optional.
+pub fn is_conventional(message: &str) -> bool {
+    let Some((prefix, description)) = message.split_once(": ") else {
+        return false;
+    };
+    if description.trim().is_empty() {
+        return false;
+    }
+    let prefix = prefix.strip_suffix('!').unwrap_or(prefix);
+    let (kind, scope) = match prefix.split_once('(') {
+        Some((kind, rest)) => match rest.strip_suffix(')') {
+            Some(scope) => (kind, Some(scope)),
+            None => return false,
+        },
+        None => (prefix, None),
+    };
+    let valid_kind = !kind.is_empty() && kind.chars().all(|c| c.is_ascii_alphabetic());
+    let valid_scope = scope.is_none_or(|s| !s.trim().is_empty() && !s.contains(['(', ')']));
+    valid_kind && valid_scope
+}
+
+/// Share of conventional commits for one project or author.
+#[derive(Debug, Serialize)]
+pub struct ComplianceRow {
+    pub name: String,
+    pub commits: usize,
+    pub conventional: usize,
+    pub percent: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComplianceReport {
+    pub commits: usize,
+    pub conventional: usize,
+    pub percent: f64,
+    pub projects: Vec<ComplianceRow>,
+    pub authors: Vec<ComplianceRow>,
+}
+
+/// Count each commit once per project (commits shared between branches are
+/// not double counted). Rows are sorted by name.
+pub fn compliance(projects: &[ProjectLog]) -> ComplianceReport {
+    let mut per_project: Vec<ComplianceRow> = Vec::new();
+    let mut per_author: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+
+    for project in projects {
+        let mut seen = HashSet::new();
+        let (mut total, mut ok) = (0, 0);
+        for commit in project.branches.iter().flat_map(|b| &b.commits) {
+            if !seen.insert(&commit.hash) {
+                continue;
+            }
+            let conventional = is_conventional(&commit.message);
+            total += 1;
+            ok += usize::from(conventional);
+            let entry = per_author.entry(commit.author.clone()).or_default();
+            entry.0 += 1;
+            entry.1 += usize::from(conventional);
+        }
+        per_project.push(row(project.project.clone(), total, ok));
+    }
+    per_project.sort_by_key(|r| r.name.to_lowercase());
+
+    let commits = per_project.iter().map(|r| r.commits).sum();
+    let conventional = per_project.iter().map(|r| r.conventional).sum();
+    ComplianceReport {
+        commits,
+        conventional,
+        percent: percent(conventional, commits),
+        projects: per_project,
+        authors: per_author
+            .into_iter()
+            .map(|(name, (total, ok))| row(name, total, ok))
+            .collect(),
+    }
+}
+
+fn row(name: String, commits: usize, conventional: usize) -> ComplianceRow {
+    ComplianceRow {
+        name,
+        commits,
+        conventional,
+        percent: percent(conventional, commits),
+    }
+}
+
+pub(crate) fn percent(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        (part as f64 * 1000.0 / total as f64).round() / 10.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BranchLog, Commit};
+    use chrono::Local;
+
+    #[test]
+    fn conventional_messages() {
+        assert!(is_conventional("feat: add login"));
+        assert!(is_conventional("fix(api): handle timeout"));
+        assert!(is_conventional("refactor!: drop old config"));
+        assert!(is_conventional("feat(ui)!: new layout"));
+        assert!(!is_conventional("Update README"));
+        assert!(!is_conventional("fix"));
+        assert!(!is_conventional("fix:"));
+        assert!(!is_conventional("fix:missing space"));
+        assert!(!is_conventional("feat(): empty scope"));
+        assert!(!is_conventional("feat(api: unclosed"));
+        assert!(!is_conventional("WIP 2: stuff"));
+    }
+
+    fn commit(hash: &str, message: &str, author: &str) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            message: message.to_string(),
+            author: author.to_string(),
+            time: Local::now().fixed_offset(),
+            ..Default::default()
+        }
+    }
+
+    fn project(name: &str, branches: Vec<Vec<Commit>>) -> ProjectLog {
+        ProjectLog {
+            project: name.to_string(),
+            path: format!("/tmp/{name}"),
+            branches: branches
+                .into_iter()
+                .map(|commits| BranchLog {
+                    name: "main".to_string(),
+                    commits,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn compliance_per_project_and_author() {
+        let projects = vec![
+            project(
+                "web",
+                vec![
+                    vec![commit("a", "feat: one", "Ann"), commit("b", "oops", "Bob")],
+                    vec![commit("a", "feat: one", "Ann")],
+                ],
+            ),
+            project(
+                "api",
+                vec![vec![
+                    commit("c", "fix: two", "Bob"),
+                    commit("d", "chore: three", "Bob"),
+                ]],
+            ),
+        ];
+        let report = compliance(&projects);
+        assert_eq!(report.commits, 4);
+        assert_eq!(report.conventional, 3);
+        assert_eq!(report.percent, 75.0);
+
+        let names: Vec<&str> = report.projects.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["api", "web"]);
+        assert_eq!(report.projects[1].percent, 50.0);
+
+        let bob = &report.authors[1];
+        assert_eq!(bob.name, "Bob");
+        assert_eq!((bob.commits, bob.conventional), (3, 2));
+        assert_eq!(bob.percent, 66.7);
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::model::ProjectLog;
+use crate::zone::Time;
+
+/// A commit without a `Signed-off-by` trailer.
+#[derive(Debug, Serialize)]
+pub struct Unsigned {
+    pub hash: String,
+    pub message: String,
+    pub author: String,
+    #[serde(rename = "timestamp")]
+    pub time: Time,
+    pub relative_time: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Developer Certificate of Origin status of one project.
+#[derive(Debug, Serialize)]
+pub struct DcoStatus {
+    pub project: String,
+    pub path: String,
+    pub commits: usize,
+    pub missing: Vec<Unsigned>,
+}
+
+/// Collect the commits of `project` that carry no sign-off, newest first.
+pub fn check(project: &ProjectLog) -> DcoStatus {
+    let mut seen = HashSet::new();
+    let mut commits = 0;
+    let mut missing: Vec<Unsigned> = Vec::new();
+    for commit in project.branches.iter().flat_map(|b| &b.commits) {
+        if !seen.insert(&commit.hash) {
+            continue;
+        }
+        commits += 1;
+        if commit.signed_off_by.is_empty() {
+            missing.push(Unsigned {
+                hash: commit.hash.clone(),
+                message: commit.message.clone(),
+                author: commit.author.clone(),
+                time: commit.time,
+                relative_time: commit.relative_time.clone(),
+                url: commit.url.clone(),
+            });
+        }
+    }
+    missing.sort_by_key(|u| std::cmp::Reverse(u.time));
+    DcoStatus {
+        project: project.project.clone(),
+        path: project.path.clone(),
+        commits,
+        missing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BranchLog, Commit};
+    use chrono::Local;
+
+    fn commit(hash: &str, signed: bool) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            message: format!("commit {hash}"),
+            author: "Jane Doe".to_string(),
+            time: Local::now().fixed_offset(),
+            signed_off_by: if signed {
+                vec!["Jane Doe <jane@example.com>".to_string()]
+            } else {
+                Vec::new()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn flags_each_unsigned_commit_once() {
+        let branch = |commits| BranchLog {
+            name: "main".to_string(),
+            commits,
+            ..Default::default()
+        };
+        let project = ProjectLog {
+            project: "kernel-mod".to_string(),
+            path: "/src/kernel-mod".to_string(),
+            branches: vec![
+                branch(vec![commit("a", true), commit("b", false)]),
+                branch(vec![commit("b", false), commit("c", false)]),
+            ],
+            ..Default::default()
+        };
+        let status = check(&project);
+        assert_eq!(status.commits, 3);
+        let hashes: Vec<&str> = status.missing.iter().map(|u| u.hash.as_str()).collect();
+        assert_eq!(hashes.len(), 2);
+        assert!(hashes.contains(&"b") && hashes.contains(&"c"));
+    }
+}
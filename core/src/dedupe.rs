@@ -0,0 +1,130 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::git::is_primary_branch;
+use crate::model::{BranchLog, DiffStat};
+
+/// List each commit under exactly one of `branches`.
+///
+/// Commits on the first-parent history of the primary branch
+/// (`primary_line`) were made there and stay on it. Any other commit goes to
+/// a non-primary branch containing it, the one with the fewest commits in
+/// the period: a branch forked off another feature branch also contains
+/// that branch's commits, so the smaller one is where they were made.
+/// Branches left without commits are dropped, and the diff stats of the
+/// others are recomputed from their remaining commits.
+pub(crate) fn dedupe(branches: &mut Vec<BranchLog>, primary_line: &HashSet<String>) {
+    let rank = |b: usize, hash: &str| {
+        let branch = &branches[b];
+        let primary = is_primary_branch(&branch.name);
+        // Lower is preferred.
+        (primary != primary_line.contains(hash), branch.commits.len())
+    };
+    let mut owner: HashMap<&str, usize> = HashMap::new();
+    for (i, branch) in branches.iter().enumerate() {
+        for commit in &branch.commits {
+            let hash = commit.hash.as_str();
+            owner
+                .entry(hash)
+                .and_modify(|current| {
+                    if rank(i, hash) < rank(*current, hash) {
+                        *current = i;
+                    }
+                })
+                .or_insert(i);
+        }
+    }
+    let owner: HashMap<String, usize> = owner
+        .into_iter()
+        .map(|(hash, i)| (hash.to_string(), i))
+        .collect();
+
+    for (i, branch) in branches.iter_mut().enumerate() {
+        let before = branch.commits.len();
+        branch.commits.retain(|c| owner.get(&c.hash) == Some(&i));
+        let Some(stat) = branch
+            .diff_stat
+            .as_mut()
+            .filter(|_| branch.commits.len() < before)
+        else {
+            continue;
+        };
+        let kept = branch
+            .commits
+            .iter()
+            .filter_map(|c| c.diff_stat.as_ref())
+            .fold(DiffStat::default(), |sum, s| DiffStat {
+                files_changed: sum.files_changed + s.files_changed,
+                insertions: sum.insertions + s.insertions,
+                deletions: sum.deletions + s.deletions,
+            });
+        // Files are only known per commit, so those touched by several
+        // remaining commits are counted again, up to the branch's former count.
+        *stat = DiffStat {
+            files_changed: kept.files_changed.min(stat.files_changed),
+            ..kept
+        };
+    }
+    branches.retain(|b| !b.commits.is_empty());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Commit;
+    use chrono::Local;
+
+    fn commit(hash: &str) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            message: format!("commit {hash}"),
+            time: Local::now().fixed_offset(),
+            ..Default::default()
+        }
+    }
+
+    fn branch(name: &str, hashes: &[&str]) -> BranchLog {
+        BranchLog {
+            name: name.to_string(),
+            commits: hashes.iter().map(|h| commit(h)).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn names_and_hashes(branches: &[BranchLog]) -> Vec<(String, Vec<String>)> {
+        branches
+            .iter()
+            .map(|b| {
+                (
+                    b.name.clone(),
+                    b.commits.iter().map(|c| c.hash.clone()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn each_commit_lands_on_one_branch() {
+        let mut branches = vec![
+            // m1 and m2 were committed on main, a1 came in with a merge.
+            branch("main", &["m2", "a1", "m1"]),
+            // feature/a was forked after m1; feature/b off feature/a.
+            branch("feature/a", &["a2", "a1", "m1"]),
+            branch("feature/b", &["b1", "a2", "a1", "m1"]),
+        ];
+        let primary_line = HashSet::from(["m2".to_string(), "m1".to_string()]);
+        dedupe(&mut branches, &primary_line);
+        assert_eq!(
+            names_and_hashes(&branches),
+            [
+                ("main".to_string(), vec!["m2".to_string(), "m1".to_string()]),
+                (
+                    "feature/a".to_string(),
+                    vec!["a2".to_string(), "a1".to_string()]
+                ),
+                ("feature/b".to_string(), vec!["b1".to_string()]),
+            ]
+        );
+        let total: usize = branches.iter().map(|b| b.commits.len()).sum();
+        assert_eq!(total, 5);
+    }
+}
@@ -1,5 +1,11 @@
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use walkdir::{DirEntry, WalkDir};
+
+use crate::progress::{Progress, ProgressEvent};
+use crate::{bundle, git, netfs};
 
 const SKIP_DIRS: &[&str] = &[
     "node_modules",
@@ -14,18 +20,530 @@ const SKIP_DIRS: &[&str] = &[
     ".cache",
 ];
 
+/// Ignore rules in gitignore syntax, read in every directory of the walk
+/// and applied to everything below it.
+pub const IGNORE_FILE: &str = ".worklogignore";
+
+#[derive(Debug, Clone, Default)]
+pub struct DiscoveryOptions {
+    /// Do not descend into network file systems mounted below the root.
+    pub skip_network: bool,
+    /// Directories not to descend into: globs over the name, or over the
+    /// path when the entry contains a `/`. Invalid globs are ignored; see
+    /// [`check_exclude`].
+    pub exclude: Vec<String>,
+    /// How many directory levels below the root to look for repositories;
+    /// `0` only considers the root itself. Unlimited when `None`.
+    pub max_depth: Option<usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct Discovered {
+    pub repos: Vec<PathBuf>,
+    /// `.bundle` files with a git bundle header.
+    pub bundles: Vec<PathBuf>,
+    /// Mount points that were not scanned because they are network mounts.
+    pub skipped_mounts: Vec<PathBuf>,
+    /// Entries of an explicit list that are neither in a repository nor a
+    /// bundle.
+    pub not_found: Vec<PathBuf>,
+}
+
 pub fn find_repos(root: &Path) -> Vec<PathBuf> {
-    WalkDir::new(root)
+    discover(root, &DiscoveryOptions::default()).repos
+}
+
+pub fn discover(root: &Path, opts: &DiscoveryOptions) -> Discovered {
+    walk(root, opts, &mut |_| true)
+}
+
+/// Discover below several roots. Repositories and bundles reachable from
+/// more than one root are listed once.
+pub fn discover_all(roots: &[PathBuf], opts: &DiscoveryOptions) -> Discovered {
+    discover_all_with_progress(roots, opts, &())
+}
+
+/// [`discover_all`], reporting each repository and bundle to `progress` as
+/// [`ProgressEvent::RepoDiscovered`] as soon as it is found.
+pub fn discover_all_with_progress(
+    roots: &[PathBuf],
+    opts: &DiscoveryOptions,
+    progress: &dyn Progress,
+) -> Discovered {
+    let mut seen = HashSet::new();
+    let mut all = Discovered::default();
+    for root in roots {
+        let found = walk(root, opts, &mut |path| {
+            let new = seen.insert(path.to_path_buf());
+            if new {
+                progress.event(ProgressEvent::RepoDiscovered {
+                    path: path.to_path_buf(),
+                });
+            }
+            new
+        });
+        all.repos.extend(found.repos);
+        all.bundles.extend(found.bundles);
+        all.skipped_mounts.extend(found.skipped_mounts);
+    }
+    all
+}
+
+/// Use a curated list of repositories and bundles instead of walking a
+/// directory tree. Paths inside a working tree stand for its repository,
+/// linked worktrees for their main one; entries listed twice are kept once.
+pub fn from_list(paths: &[PathBuf], progress: &dyn Progress) -> Discovered {
+    let mut seen = HashSet::new();
+    let mut all = Discovered::default();
+    for path in paths {
+        let (found, list) = if path.is_file() && bundle::is_bundle(path) {
+            (Some(path.clone()), &mut all.bundles)
+        } else {
+            (
+                path.is_dir()
+                    .then(|| git::toplevel(path))
+                    .flatten()
+                    .map(|repo| git::worktree_main(&repo).unwrap_or(repo)),
+                &mut all.repos,
+            )
+        };
+        match found {
+            Some(found) if seen.insert(found.clone()) => {
+                progress.event(ProgressEvent::RepoDiscovered {
+                    path: found.clone(),
+                });
+                list.push(found);
+            }
+            Some(_) => {}
+            None => all.not_found.push(path.clone()),
+        }
+    }
+    all
+}
+
+/// Walk `root`, listing each repository and bundle that `keep` accepts.
+fn walk(root: &Path, opts: &DiscoveryOptions, keep: &mut dyn FnMut(&Path) -> bool) -> Discovered {
+    let mut mounts = MountCheck::new(root);
+    let exclude = Exclude::new(&opts.exclude);
+    let mut ignore_files = IgnoreFiles::default();
+    let mut skipped_mounts = Vec::new();
+
+    let mut repos = Vec::new();
+    let mut bundles = Vec::new();
+    let mut found_any = false;
+
+    let mut walker = WalkDir::new(root);
+    if let Some(depth) = opts.max_depth {
+        // One more level, to see the `.git` of repositories at `depth`.
+        walker = walker.max_depth(depth + 1);
+    }
+    let entries = walker
         .into_iter()
         .filter_entry(|entry| {
-            if !entry.file_type().is_dir() {
+            let is_dir = entry.file_type().is_dir();
+            if ignore_files.is_ignored(root, entry.path(), is_dir) {
+                return false;
+            }
+            if !is_dir {
                 return true;
             }
+            // A bare repository is listed, but its objects are not walked.
+            if entry.path().parent().is_some_and(is_bare_repo) {
+                return false;
+            }
             let name = entry.file_name().to_string_lossy();
-            !SKIP_DIRS.contains(&name.as_ref())
+            if SKIP_DIRS.contains(&name.as_ref()) || exclude.matches(entry.path()) {
+                return false;
+            }
+            if opts.skip_network && mounts.is_network_mount(entry) {
+                skipped_mounts.push(entry.path().to_path_buf());
+                return false;
+            }
+            true
+        })
+        .filter_map(Result::ok);
+
+    // Canonical paths of the repositories found, so a linked worktree and
+    // its main working tree are listed once.
+    let mut listed = HashSet::new();
+    for entry in entries {
+        let is_dir = entry.file_type().is_dir();
+        let repo = if entry.file_name() == ".git" {
+            // A `.git` file links a worktree to the repository it belongs to.
+            let parent = entry.path().parent();
+            match is_dir {
+                true => parent.map(Path::to_path_buf),
+                false => parent.and_then(git::worktree_main),
+            }
+        } else if is_dir
+            && opts.max_depth.is_none_or(|depth| entry.depth() <= depth)
+            && is_bare_repo(entry.path())
+        {
+            Some(entry.path().to_path_buf())
+        } else {
+            None
+        };
+        if let Some(repo) = repo {
+            found_any = true;
+            let canonical = repo.canonicalize().unwrap_or_else(|_| repo.clone());
+            if listed.insert(canonical) && keep(&repo) {
+                repos.push(repo);
+            }
+        } else if !is_dir
+            && entry.path().extension().is_some_and(|ext| ext == "bundle")
+            && bundle::is_bundle(entry.path())
+        {
+            found_any = true;
+            if keep(entry.path()) {
+                bundles.push(entry.path().to_path_buf());
+            }
+        }
+    }
+
+    // A root inside a working tree has no `.git` below it: report on the
+    // enclosing repository instead of finding nothing.
+    if !found_any {
+        repos.extend(
+            git::toplevel(root)
+                .map(|repo| git::worktree_main(&repo).unwrap_or(repo))
+                .filter(|repo| keep(repo)),
+        );
+    }
+
+    Discovered {
+        repos,
+        bundles,
+        skipped_mounts,
+        not_found: Vec::new(),
+    }
+}
+
+/// A `*.git` directory holding a bare repository, e.g. a mirror or a `git
+/// clone --bare` checkout.
+fn is_bare_repo(dir: &Path) -> bool {
+    dir.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.len() > 4 && name.ends_with(".git"))
+        && git::is_bare(dir)
+}
+
+/// The [`IGNORE_FILE`]s of the directories seen so far, `None` for
+/// directories without one.
+#[derive(Default)]
+struct IgnoreFiles {
+    dirs: HashMap<PathBuf, Option<Gitignore>>,
+}
+
+impl IgnoreFiles {
+    /// Whether an ignore file between `root` and the parent of `path`
+    /// excludes it. The closest file decides, so a nested `!pattern`
+    /// re-includes what a file further up ignores. Unreadable files and
+    /// invalid lines are skipped.
+    fn is_ignored(&mut self, root: &Path, path: &Path, is_dir: bool) -> bool {
+        for dir in path.ancestors().skip(1) {
+            if !dir.starts_with(root) {
+                break;
+            }
+            let rules = self.dirs.entry(dir.to_path_buf()).or_insert_with(|| {
+                let file = dir.join(IGNORE_FILE);
+                file.is_file().then(|| {
+                    let mut builder = GitignoreBuilder::new(dir);
+                    builder.add(file);
+                    builder.build().unwrap_or_else(|_| Gitignore::empty())
+                })
+            });
+            let matched = rules.as_ref().map(|r| r.matched(path, is_dir));
+            match matched {
+                Some(m) if m.is_ignore() => return true,
+                Some(m) if m.is_whitelist() => return false,
+                _ => {}
+            }
+        }
+        false
+    }
+}
+
+/// Compiled [`DiscoveryOptions::exclude`] entries.
+struct Exclude {
+    /// Entries without a `/`, matched against the directory name.
+    names: GlobSet,
+    /// Entries with a `/`, matched against the whole path.
+    paths: GlobSet,
+}
+
+impl Exclude {
+    /// Invalid entries are left out.
+    fn new(entries: &[String]) -> Self {
+        let mut names = GlobSetBuilder::new();
+        let mut paths = GlobSetBuilder::new();
+        for entry in entries {
+            let Ok(globs) = globs(entry) else {
+                continue;
+            };
+            let set = if entry.trim_end_matches('/').contains('/') {
+                &mut paths
+            } else {
+                &mut names
+            };
+            for glob in globs {
+                set.add(glob);
+            }
+        }
+        Self {
+            names: names.build().unwrap_or_else(|_| GlobSet::empty()),
+            paths: paths.build().unwrap_or_else(|_| GlobSet::empty()),
+        }
+    }
+
+    fn matches(&self, dir: &Path) -> bool {
+        if dir
+            .file_name()
+            .is_some_and(|name| self.names.is_match(name))
+        {
+            return true;
+        }
+        match dir.is_relative() && !self.paths.is_empty() {
+            true => std::path::absolute(dir).is_ok_and(|dir| self.paths.is_match(dir)),
+            false => self.paths.is_match(dir),
+        }
+    }
+}
+
+fn globs(entry: &str) -> Result<Vec<Glob>, String> {
+    let pattern = entry.trim_end_matches('/');
+    let patterns = if !pattern.contains('/') {
+        vec![pattern.to_string()]
+    } else {
+        // Relative paths match at any depth; either way the directory and
+        // everything below it is skipped.
+        let pattern = if pattern.starts_with('/') || pattern.starts_with("**") {
+            pattern.to_string()
+        } else {
+            format!("**/{pattern}")
+        };
+        vec![format!("{pattern}/**"), pattern]
+    };
+    patterns
+        .iter()
+        .map(|p| {
+            GlobBuilder::new(p)
+                .literal_separator(true)
+                .build()
+                .map_err(|e| format!("invalid exclude pattern \"{entry}\": {e}"))
         })
-        .filter_map(Result::ok)
-        .filter(|entry| entry.file_type().is_dir() && entry.file_name() == ".git")
-        .filter_map(|entry| entry.path().parent().map(Path::to_path_buf))
         .collect()
 }
+
+/// Check that every exclude entry is a valid glob.
+pub fn check_exclude(entries: &[String]) -> Result<(), String> {
+    entries
+        .iter()
+        .try_for_each(|entry| globs(entry).map(|_| ()))
+}
+
+/// Checks directories for network mounts, only calling `statfs` when the
+/// device differs from the root's (i.e. at mount boundaries).
+struct MountCheck {
+    root_dev: Option<u64>,
+    cache: HashMap<u64, bool>,
+}
+
+impl MountCheck {
+    fn new(root: &Path) -> Self {
+        Self {
+            root_dev: std::fs::metadata(root).ok().and_then(|m| device_of(&m)),
+            cache: HashMap::new(),
+        }
+    }
+
+    fn is_network_mount(&mut self, entry: &DirEntry) -> bool {
+        if entry.depth() == 0 {
+            return false;
+        }
+        let Some(dev) = entry.metadata().ok().and_then(|m| device_of(&m)) else {
+            return false;
+        };
+        if Some(dev) == self.root_dev {
+            return false;
+        }
+        *self
+            .cache
+            .entry(dev)
+            .or_insert_with(|| netfs::is_network_fs(entry.path()))
+    }
+}
+
+#[cfg(unix)]
+fn device_of(meta: &std::fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(meta.dev())
+}
+
+#[cfg(not(unix))]
+fn device_of(_meta: &std::fs::Metadata) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    #[test]
+    fn exclude_globs_match_names_and_paths() {
+        let exclude = Exclude::new(&[
+            "*.archive".to_string(),
+            "mirror-*".to_string(),
+            "go/pkg".to_string(),
+            "/srv/old/".to_string(),
+        ]);
+        assert!(exclude.matches(Path::new("/src/shop.archive")));
+        assert!(exclude.matches(Path::new("/src/mirror-linux")));
+        assert!(!exclude.matches(Path::new("/src/shop")));
+        assert!(exclude.matches(Path::new("/home/jane/go/pkg")));
+        assert!(exclude.matches(Path::new("/home/jane/go/pkg/mod")));
+        assert!(!exclude.matches(Path::new("/home/jane/go/src")));
+        assert!(exclude.matches(Path::new("/srv/old")));
+        assert!(!exclude.matches(Path::new("/srv/older")));
+        assert!(check_exclude(&["[oops".to_string()]).is_err());
+    }
+
+    #[test]
+    fn ignore_files_skip_matching_subtrees() {
+        let dir = std::env::temp_dir().join(format!("devcap-ignore-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        for repo in [
+            "app",
+            "mono/packages/legacy",
+            "mono/packages/keep",
+            "mono/tools",
+        ] {
+            std::fs::create_dir_all(dir.join(repo).join(".git")).expect("create dir");
+        }
+        std::fs::write(dir.join(IGNORE_FILE), "tools/\n").expect("write ignore file");
+        std::fs::write(dir.join("mono").join(IGNORE_FILE), "packages/*\n!keep\n")
+            .expect("write ignore file");
+
+        let mut repos = find_repos(&dir);
+        repos.sort();
+        assert_eq!(repos, vec![dir.join("app"), dir.join("mono/packages/keep")]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn finds_bare_repositories() {
+        let dir = std::env::temp_dir().join(format!("devcap-bare-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("app").join(".git")).expect("create dir");
+        let status = Command::new("git")
+            .args(["init", "-q", "--bare"])
+            .arg(dir.join("mirrors/shop.git"))
+            .status()
+            .expect("run git");
+        assert!(status.success());
+
+        let mut repos = find_repos(&dir);
+        repos.sort();
+        assert_eq!(repos, vec![dir.join("app"), dir.join("mirrors/shop.git")]);
+        assert_eq!(
+            git::project_name(&dir.join("mirrors/shop.git")).as_deref(),
+            Some("shop")
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn worktrees_are_listed_as_their_repository() {
+        let dir = std::env::temp_dir().join(format!("devcap-worktree-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("create dir");
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(&dir)
+                .args(["-c", "user.name=T", "-c", "user.email=t@example.com"])
+                .args(args)
+                .status()
+                .expect("run git");
+            assert!(status.success());
+        };
+        git(&["init", "-q", "app"]);
+        git(&["-C", "app", "commit", "-q", "--allow-empty", "-m", "init"]);
+        git(&[
+            "-C",
+            "app",
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            "login",
+            "../app-login",
+        ]);
+        let app = dir.join("app").canonicalize().expect("canonical path");
+
+        let repos = find_repos(&dir);
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].canonicalize().expect("canonical path"), app);
+        assert_eq!(
+            git::worktree_main(&dir.join("app-login")),
+            Some(app.clone())
+        );
+        assert_eq!(git::worktree_main(&app), None);
+        assert_eq!(from_list(&[dir.join("app-login")], &()).repos, vec![app]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn max_depth_limits_the_walk() {
+        let dir = std::env::temp_dir().join(format!("devcap-depth-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        for repo in ["shallow", "deep/er/repo"] {
+            std::fs::create_dir_all(dir.join(repo).join(".git")).expect("create dir");
+        }
+        let found = |max_depth| {
+            let opts = DiscoveryOptions {
+                max_depth,
+                ..DiscoveryOptions::default()
+            };
+            let mut repos = discover(&dir, &opts).repos;
+            repos.sort();
+            repos
+        };
+        assert_eq!(found(Some(1)), vec![dir.join("shallow")]);
+        assert_eq!(
+            found(Some(3)),
+            vec![dir.join("deep/er/repo"), dir.join("shallow")]
+        );
+        assert_eq!(found(None).len(), 2);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn listed_paths_resolve_to_repositories() {
+        let dir = std::env::temp_dir().join(format!("devcap-list-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let repo = dir.join("app");
+        std::fs::create_dir_all(repo.join("src")).expect("create dir");
+        let status = Command::new("git")
+            .args(["init", "-q"])
+            .arg(&repo)
+            .status()
+            .expect("run git");
+        assert!(status.success());
+        let repo = repo.canonicalize().expect("canonical path");
+
+        let found = from_list(
+            &[
+                repo.clone(),
+                repo.join("src"),
+                dir.join("missing"),
+                dir.clone(),
+            ],
+            &(),
+        );
+        assert_eq!(found.repos, vec![repo]);
+        assert!(found.bundles.is_empty());
+        assert_eq!(found.not_found, vec![dir.join("missing"), dir.clone()]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,112 @@
+/// Parse a logged-time value into minutes.
+///
+/// Accepts unit notation (`1h30m`, `2h`, `45m`, `1.5h`, `1d` = 8h), clock
+/// notation (`1:30`) and bare numbers, which are taken as minutes.
+pub fn parse_minutes(value: &str) -> Option<u32> {
+    let s: String = value
+        .trim()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase();
+    if s.is_empty() {
+        return None;
+    }
+
+    if let Some((h, m)) = s.split_once(':') {
+        let hours: u32 = h.parse().ok()?;
+        let mins: u32 = m.parse().ok()?;
+        if mins >= 60 {
+            return None;
+        }
+        return hours.checked_mul(60)?.checked_add(mins);
+    }
+
+    if let Ok(mins) = s.parse::<u32>() {
+        return Some(mins);
+    }
+
+    let mut total = 0.0_f64;
+    let mut number = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            continue;
+        }
+        let factor = match c {
+            'd' => 8.0 * 60.0,
+            'h' => 60.0,
+            'm' => 1.0,
+            _ => return None,
+        };
+        let n: f64 = number.parse().ok()?;
+        total += n * factor;
+        number.clear();
+    }
+    if !number.is_empty() {
+        return None;
+    }
+    let total = total.round();
+    if !(0.0..=f64::from(u32::MAX)).contains(&total) {
+        return None;
+    }
+    Some(total as u32)
+}
+
+/// Format minutes compactly, e.g. `1h30m`, `2h`, `45m`.
+pub fn format_minutes(minutes: u32) -> String {
+    let (h, m) = (minutes / 60, minutes % 60);
+    match (h, m) {
+        (0, m) => format!("{m}m"),
+        (h, 0) => format!("{h}h"),
+        (h, m) => format!("{h}h{m}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_unit_notation() {
+        assert_eq!(parse_minutes("1h30m"), Some(90));
+        assert_eq!(parse_minutes("2h"), Some(120));
+        assert_eq!(parse_minutes("45m"), Some(45));
+        assert_eq!(parse_minutes("1.5h"), Some(90));
+        assert_eq!(parse_minutes("1d"), Some(480));
+        assert_eq!(parse_minutes(" 1h 15m "), Some(75));
+        assert_eq!(parse_minutes("2H"), Some(120));
+    }
+
+    #[test]
+    fn parse_clock_and_plain_minutes() {
+        assert_eq!(parse_minutes("1:30"), Some(90));
+        assert_eq!(parse_minutes("0:05"), Some(5));
+        assert_eq!(parse_minutes("40"), Some(40));
+    }
+
+    #[test]
+    fn parse_rejects_garbage() {
+        assert_eq!(parse_minutes(""), None);
+        assert_eq!(parse_minutes("soon"), None);
+        assert_eq!(parse_minutes("1h30"), None);
+        assert_eq!(parse_minutes("1:75"), None);
+        assert_eq!(parse_minutes("h"), None);
+    }
+
+    #[test]
+    fn parse_rejects_overflow() {
+        assert_eq!(parse_minutes("71582789:00"), None);
+        assert_eq!(parse_minutes("4294967295:59"), None);
+        assert_eq!(parse_minutes("9999999999d"), None);
+        assert_eq!(parse_minutes("71582788:15"), Some(4_294_967_295));
+    }
+
+    #[test]
+    fn format_compact() {
+        assert_eq!(format_minutes(0), "0m");
+        assert_eq!(format_minutes(45), "45m");
+        assert_eq!(format_minutes(120), "2h");
+        assert_eq!(format_minutes(90), "1h30m");
+    }
+}
@@ -0,0 +1,454 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+
+use crate::git;
+use crate::model::{Commit, ProjectLog};
+use crate::period::TimeRange;
+
+/// Files written for one project.
+#[derive(Debug, Serialize)]
+pub struct Exported {
+    pub project: String,
+    pub files: Vec<PathBuf>,
+}
+
+/// File names for exporting `projects`, one per project, taken from the
+/// repository directory rather than the display name: characters other than
+/// ASCII letters, digits, `-`, `_` and `.` become `_`, leading dots are
+/// dropped, and a name already given to an earlier project (ignoring case)
+/// gets a `-2`, `-3`, ... suffix.
+pub fn file_names(projects: &[ProjectLog]) -> Vec<String> {
+    let mut taken: HashSet<String> = HashSet::new();
+    projects
+        .iter()
+        .map(|project| {
+            let dir = Path::new(&project.path)
+                .file_name()
+                .map(|n| n.to_string_lossy())
+                .unwrap_or_default();
+            let base: String = dir
+                .chars()
+                .map(|c| match c {
+                    'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' => c,
+                    _ => '_',
+                })
+                .collect::<String>()
+                .trim_start_matches('.')
+                .to_string();
+            let base = if base.is_empty() {
+                "repo".to_string()
+            } else {
+                base
+            };
+            let mut name = base.clone();
+            let mut n = 1;
+            while !taken.insert(name.to_lowercase()) {
+                n += 1;
+                name = format!("{base}-{n}");
+            }
+            name
+        })
+        .collect()
+}
+
+/// Refuse a file name that would leave the output directory.
+fn check_name(name: &str) -> Result<()> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        bail!("invalid export file name: {name:?}");
+    }
+    Ok(())
+}
+
+/// Write one `git format-patch` file per commit of `project` into
+/// `<out_dir>/<name>/`, oldest first. Merge commits produce no patch.
+pub fn write_patches(project: &ProjectLog, out_dir: &Path, name: &str) -> Result<Exported> {
+    check_name(name)?;
+    let dir = out_dir.join(name);
+    std::fs::create_dir_all(&dir).with_context(|| format!("cannot create {}", dir.display()))?;
+
+    let mut files = Vec::new();
+    for commit in commits_oldest_first(project) {
+        let output = git::command()
+            .args(["-C", &project.path, "format-patch", "-1"])
+            .arg(format!("--start-number={}", files.len() + 1))
+            .arg("-o")
+            .arg(&dir)
+            .arg(&commit.hash)
+            .output()
+            .context("Failed to run git format-patch")?;
+        if !output.status.success() {
+            bail!(
+                "git format-patch failed for {} in {}: {}",
+                commit.hash,
+                project.project,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        files.extend(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .map(PathBuf::from),
+        );
+    }
+
+    Ok(Exported {
+        project: project.project.clone(),
+        files,
+    })
+}
+
+/// Write `<out_dir>/<name>.bundle` holding the period's history of every
+/// branch that has commits in `project`. Commits before the period become
+/// prerequisites, so the bundle applies on top of an older clone.
+pub fn write_bundle(
+    project: &ProjectLog,
+    range: &TimeRange,
+    out_dir: &Path,
+    name: &str,
+) -> Result<Exported> {
+    check_name(name)?;
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("cannot create {}", out_dir.display()))?;
+    let file = out_dir.join(format!("{name}.bundle"));
+
+    let mut cmd = git::command();
+    cmd.args(["-C", &project.path, "bundle", "create", "--quiet"])
+        .arg(&file)
+        .arg(format!("--since={}", range.since.to_rfc3339()));
+    if let Some(until) = range.until {
+        cmd.arg(format!("--until={}", until.to_rfc3339()));
+    }
+    cmd.args(project.branches.iter().map(|b| git::branch_rev(&b.name)));
+
+    let output = cmd.output().context("Failed to run git bundle")?;
+    if !output.status.success() {
+        bail!(
+            "git bundle failed in {}: {}",
+            project.project,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(Exported {
+        project: project.project.clone(),
+        files: vec![file],
+    })
+}
+
+/// One commit on one branch, flattened for tabular export. A commit reachable
+/// from several branches yields one row per branch, so counting distinct
+/// `hash` values gives the number of commits.
+#[derive(Debug)]
+pub struct CommitRow<'a> {
+    pub project: &'a str,
+    pub branch: &'a str,
+    pub commit: &'a Commit,
+}
+
+/// Rows of `project`, in report order.
+pub fn commit_rows(project: &ProjectLog) -> Vec<CommitRow<'_>> {
+    project
+        .branches
+        .iter()
+        .flat_map(|b| {
+            b.commits.iter().map(|commit| CommitRow {
+                project: &project.project,
+                branch: &b.name,
+                commit,
+            })
+        })
+        .collect()
+}
+
+/// Write `<out_dir>/<name>.parquet` with one row per commit and branch
+/// (see [`CommitRow`]). Every file shares the same schema, so the directory
+/// can be read as one dataset.
+#[cfg(feature = "parquet")]
+pub fn write_parquet(project: &ProjectLog, out_dir: &Path, name: &str) -> Result<Exported> {
+    use std::sync::Arc;
+
+    use arrow_array::{
+        ArrayRef, BooleanArray, RecordBatch, StringArray, TimestampMillisecondArray, UInt32Array,
+    };
+    use arrow_schema::{DataType, Field, Schema, TimeUnit};
+    use parquet::arrow::ArrowWriter;
+    use parquet::basic::Compression;
+    use parquet::file::properties::WriterProperties;
+
+    check_name(name)?;
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("cannot create {}", out_dir.display()))?;
+    let file = out_dir.join(format!("{name}.parquet"));
+
+    let rows = commit_rows(project);
+    let text = |f: for<'r> fn(&'r CommitRow<'r>) -> &'r str| -> ArrayRef {
+        Arc::new(StringArray::from_iter_values(rows.iter().map(f)))
+    };
+    let optional_text = |f: for<'r> fn(&'r CommitRow<'r>) -> Option<&'r str>| -> ArrayRef {
+        Arc::new(rows.iter().map(f).collect::<StringArray>())
+    };
+    let number = |f: fn(&CommitRow) -> Option<u32>| -> ArrayRef {
+        Arc::new(rows.iter().map(f).collect::<UInt32Array>())
+    };
+    let utc = Some("UTC".into());
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("project", DataType::Utf8, false),
+        Field::new("path", DataType::Utf8, false),
+        Field::new("branch", DataType::Utf8, false),
+        Field::new("hash", DataType::Utf8, false),
+        Field::new(
+            "time",
+            DataType::Timestamp(TimeUnit::Millisecond, utc.clone()),
+            false,
+        ),
+        Field::new("author", DataType::Utf8, false),
+        Field::new("author_email", DataType::Utf8, false),
+        Field::new("commit_type", DataType::Utf8, true),
+        Field::new("message", DataType::Utf8, false),
+        Field::new("files_changed", DataType::UInt32, true),
+        Field::new("insertions", DataType::UInt32, true),
+        Field::new("deletions", DataType::UInt32, true),
+        Field::new("time_spent_minutes", DataType::UInt32, true),
+        Field::new("unpushed", DataType::Boolean, false),
+        Field::new("url", DataType::Utf8, true),
+    ]));
+    let columns: Vec<ArrayRef> = vec![
+        text(|r| r.project),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|_| project.path.as_str()),
+        )),
+        text(|r| r.branch),
+        text(|r| &r.commit.hash),
+        Arc::new(
+            TimestampMillisecondArray::from_iter_values(
+                rows.iter().map(|r| r.commit.time.timestamp_millis()),
+            )
+            .with_timezone_opt(utc),
+        ),
+        text(|r| &r.commit.author),
+        text(|r| &r.commit.author_email),
+        optional_text(|r| r.commit.commit_type.as_deref()),
+        text(|r| &r.commit.message),
+        number(|r| r.commit.diff_stat.as_ref().map(|d| d.files_changed)),
+        number(|r| r.commit.diff_stat.as_ref().map(|d| d.insertions)),
+        number(|r| r.commit.diff_stat.as_ref().map(|d| d.deletions)),
+        number(|r| r.commit.time_spent.map(|t| t.minutes())),
+        Arc::new(BooleanArray::from_iter(
+            rows.iter().map(|r| Some(r.commit.unpushed)),
+        )),
+        optional_text(|r| r.commit.url.as_deref()),
+    ];
+    let batch =
+        RecordBatch::try_new(schema.clone(), columns).context("cannot build commit table")?;
+
+    let out = std::fs::File::create(&file)
+        .with_context(|| format!("cannot create {}", file.display()))?;
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer = ArrowWriter::try_new(out, schema, Some(props))
+        .with_context(|| format!("cannot write {}", file.display()))?;
+    writer
+        .write(&batch)
+        .and_then(|_| writer.close().map(|_| ()))
+        .with_context(|| format!("cannot write {}", file.display()))?;
+
+    Ok(Exported {
+        project: project.project.clone(),
+        files: vec![file],
+    })
+}
+
+/// Unique commits across all branches, oldest first. Branch logs are newest
+/// first, so they are reversed before the stable sort to keep commits made
+/// within the same second in order.
+fn commits_oldest_first(project: &ProjectLog) -> Vec<&Commit> {
+    let mut seen = HashSet::new();
+    let mut commits: Vec<&Commit> = project
+        .branches
+        .iter()
+        .flat_map(|b| &b.commits)
+        .filter(|c| seen.insert(c.hash.as_str()))
+        .collect();
+    commits.reverse();
+    commits.sort_by_key(|c| c.time);
+    commits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::BranchLog;
+    use chrono::{Local, TimeZone};
+
+    fn commit(hash: &str, hour: u32) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            message: format!("commit {hash}"),
+            time: Local
+                .with_ymd_and_hms(2026, 3, 2, hour, 0, 0)
+                .single()
+                .expect("valid time")
+                .fixed_offset(),
+            ..Default::default()
+        }
+    }
+
+    fn branch(name: &str, commits: Vec<Commit>) -> BranchLog {
+        BranchLog {
+            name: name.to_string(),
+            commits,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn commits_are_unique_and_oldest_first() {
+        let project = ProjectLog {
+            project: "app".to_string(),
+            path: "/tmp/app".to_string(),
+            branches: vec![
+                branch("main", vec![commit("ccc", 12), commit("aaa", 9)]),
+                branch("feature", vec![commit("bbb", 10), commit("aaa", 9)]),
+            ],
+            ..Default::default()
+        };
+        let hashes: Vec<&str> = commits_oldest_first(&project)
+            .iter()
+            .map(|c| c.hash.as_str())
+            .collect();
+        assert_eq!(hashes, ["aaa", "bbb", "ccc"]);
+    }
+
+    fn sample_project() -> ProjectLog {
+        ProjectLog {
+            project: "app".to_string(),
+            path: "/tmp/app".to_string(),
+            branches: vec![
+                branch("main", vec![commit("ccc", 12), commit("aaa", 9)]),
+                branch("feature", vec![commit("aaa", 9)]),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rows_are_one_per_branch_and_commit() {
+        let project = sample_project();
+        let rows: Vec<(&str, &str)> = commit_rows(&project)
+            .iter()
+            .map(|r| (r.branch, r.commit.hash.as_str()))
+            .collect();
+        assert_eq!(rows, [("main", "ccc"), ("main", "aaa"), ("feature", "aaa")]);
+    }
+
+    #[test]
+    fn file_names_come_from_the_directory_and_are_unique() {
+        let project = |name: &str, path: &str| ProjectLog {
+            project: name.to_string(),
+            path: path.to_string(),
+            ..Default::default()
+        };
+        let projects = [
+            project("../../etc", "/src/client/api"),
+            project("API", "/src/other/API"),
+            project("web", "/src/web app"),
+            project("hidden", "/src/..."),
+            project("api", "/src/more/api"),
+        ];
+        assert_eq!(
+            file_names(&projects),
+            ["api", "API-2", "web_app", "repo", "api-3"]
+        );
+    }
+
+    #[test]
+    fn names_with_separators_are_rejected() {
+        let out = std::env::temp_dir().join(format!("devcap-names-{}", std::process::id()));
+        let project = sample_project();
+        for name in ["../escape", "a/b", "a\\b", "..", ""] {
+            assert!(write_patches(&project, &out, name).is_err(), "{name}");
+        }
+        assert!(!out.exists());
+    }
+
+    #[test]
+    fn bundle_includes_detached_head() {
+        use crate::zone::Zone;
+        use chrono::Duration;
+
+        let dir = std::env::temp_dir().join(format!("devcap-export-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let repo = dir.join("app");
+        std::fs::create_dir_all(&repo).expect("create dir");
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .arg("-C")
+                .arg(&repo)
+                .args([
+                    "-c",
+                    "user.name=Jane Doe",
+                    "-c",
+                    "user.email=jane@example.com",
+                ])
+                .args(args)
+                .status()
+                .expect("run git");
+            assert!(status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "feat: on main"]);
+        git(&["checkout", "-q", "--detach"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "test: detached"]);
+
+        let now = Local::now().fixed_offset();
+        let range = TimeRange {
+            since: now - Duration::days(1),
+            until: None,
+            zone: Zone::Local,
+        };
+        let project = git::collect_project_log(&repo, &range, &git::LogOptions::default())
+            .expect("has commits");
+        assert!(project
+            .branches
+            .iter()
+            .any(|b| b.name == git::DETACHED_BRANCH));
+        let exported =
+            write_bundle(&project, &range, &dir.join("out"), "app").expect("writes bundle");
+        let heads = std::process::Command::new("git")
+            .args(["bundle", "list-heads"])
+            .arg(&exported.files[0])
+            .output()
+            .expect("run git bundle");
+        let heads = String::from_utf8_lossy(&heads.stdout);
+        assert!(heads.contains(" HEAD"), "{heads}");
+        assert!(heads.contains("refs/heads/main"), "{heads}");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_file_holds_every_row() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let dir = std::env::temp_dir().join(format!("devcap-parquet-{}", std::process::id()));
+        let exported = write_parquet(&sample_project(), &dir, "app").expect("writes parquet");
+        let file = std::fs::File::open(&exported.files[0]).expect("file exists");
+        let reader = SerializedFileReader::new(file).expect("valid parquet");
+        let metadata = reader.metadata().file_metadata();
+        assert_eq!(metadata.num_rows(), 3);
+        let columns: Vec<&str> = metadata
+            .schema_descr()
+            .columns()
+            .iter()
+            .map(|c| c.name())
+            .collect();
+        assert_eq!(&columns[..4], ["project", "path", "branch", "hash"]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
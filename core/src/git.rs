@@ -1,70 +1,411 @@
-use std::collections::HashSet;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::{Context, Result};
-use chrono::{DateTime, Local};
+use chrono::DateTime;
 
-use crate::model::{BranchLog, Commit, DiffStat, ProjectLog, RepoOrigin};
+use crate::activity;
+use crate::dedupe;
+use crate::model::{BranchLog, CoAuthor, Commit, DiffStat, ProjectLog, RepoOrigin, WorkTime};
+use crate::offline;
 use crate::period::TimeRange;
+use crate::progress::{Progress, ProgressEvent};
+use crate::refs;
+use crate::relative::RelativeTime;
+use crate::revwalk;
+use crate::zone::{Time, Zone};
+
+/// Environment variables kept from the caller; every other `GIT_*` variable
+/// is removed so a `GIT_DIR` or `GIT_INDEX_FILE` leaking in from a hook or
+/// an IDE cannot redirect the scan.
+const KEPT_GIT_VARS: &[&str] = &[
+    "GIT_EXEC_PATH",
+    "GIT_SSH",
+    "GIT_SSH_COMMAND",
+    "GIT_SSL_CAINFO",
+    "GIT_SSL_CAPATH",
+    "GIT_PROXY_COMMAND",
+];
+
+/// A `git` invocation that is safe to run in any repository:
+///
+/// - repositories owned by other users are readable (`safe.directory=*`);
+/// - no optional locks are taken, so scanning never blocks a concurrent git;
+/// - hooks and fsmonitor daemons configured in the repo are not run;
+/// - credential prompts fail instead of waiting for input;
+/// - in offline mode, missing objects of partial clones are not fetched.
+pub fn command() -> Command {
+    #[cfg(test)]
+    COMMANDS.with(|n| n.set(n.get() + 1));
+    let mut cmd = Command::new("git");
+    for (key, _) in std::env::vars_os() {
+        let Some(key) = key.to_str() else {
+            continue;
+        };
+        if key.starts_with("GIT_") && !KEPT_GIT_VARS.contains(&key) {
+            cmd.env_remove(key);
+        }
+    }
+    cmd.env("GIT_TERMINAL_PROMPT", "0");
+    if offline::is_enabled() {
+        cmd.env("GIT_NO_LAZY_FETCH", "1");
+    }
+    cmd.args([
+        "--no-optional-locks",
+        "-c",
+        "safe.directory=*",
+        "-c",
+        "core.hooksPath=/dev/null",
+        "-c",
+        "core.fsmonitor=false",
+        "-c",
+        "credential.interactive=never",
+    ]);
+    cmd.stdin(std::process::Stdio::null());
+    cmd
+}
 
-pub fn default_author() -> Option<String> {
-    Command::new("git")
-        .args(["config", "--global", "user.name"])
+#[cfg(test)]
+thread_local! {
+    /// Git commands built by [`command`] on this thread, so tests can check
+    /// that a scan stays in-process.
+    static COMMANDS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Top-level directory of the working tree containing `dir`, or the
+/// repository itself when `dir` is in a bare repository.
+pub fn toplevel(dir: &Path) -> Option<PathBuf> {
+    let output = command()
+        .args(["-C", &dir.to_string_lossy(), "rev-parse", "--show-toplevel"])
         .output()
-        .ok()
-        .and_then(|out| {
-            if out.status.success() {
-                String::from_utf8(out.stdout)
-                    .ok()
-                    .map(|s| s.trim().to_string())
-                    .filter(|s| !s.is_empty())
-            } else {
-                None
-            }
-        })
+        .ok()?;
+    if !output.status.success() {
+        return bare_git_dir(dir);
+    }
+    let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!path.is_empty()).then(|| PathBuf::from(path))
+}
+
+/// The bare repository containing `dir`.
+fn bare_git_dir(dir: &Path) -> Option<PathBuf> {
+    let output = command()
+        .args([
+            "-C",
+            &dir.to_string_lossy(),
+            "rev-parse",
+            "--is-bare-repository",
+            "--absolute-git-dir",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    if lines.next()? != "true" {
+        return None;
+    }
+    lines.next().map(PathBuf::from)
+}
+
+/// The repository a linked worktree (`git worktree add`) belongs to: its
+/// main working tree, or the bare repository. `None` for any other
+/// directory, including the main working tree itself.
+pub fn worktree_main(dir: &Path) -> Option<PathBuf> {
+    let link = std::fs::read_to_string(dir.join(".git")).ok()?;
+    let gitdir = dir.join(link.trim().strip_prefix("gitdir:")?.trim());
+    let common = std::fs::read_to_string(gitdir.join("commondir")).ok()?;
+    let common = gitdir.join(common.trim()).canonicalize().ok()?;
+    if common.file_name().is_some_and(|name| name == ".git") {
+        common.parent().map(Path::to_path_buf)
+    } else {
+        Some(common)
+    }
 }
 
-fn list_branches(repo: &Path) -> Result<Vec<String>> {
-    let output = Command::new("git")
+/// Whether `repo` is a bare repository, such as a mirror or a `git clone
+/// --bare`, rather than a working tree.
+pub fn is_bare(repo: &Path) -> bool {
+    !repo.join(".git").exists()
+        && repo.join("HEAD").is_file()
+        && repo.join("objects").is_dir()
+        && repo.join("refs").is_dir()
+}
+
+/// Name a repository is reported under: its directory name, without the
+/// `.git` suffix of bare repositories.
+pub fn project_name(repo: &Path) -> Option<String> {
+    let name = repo.file_name()?.to_string_lossy();
+    let name = match name.strip_suffix(".git") {
+        Some(stem) if !stem.is_empty() && is_bare(repo) => stem,
+        _ => &name,
+    };
+    Some(name.to_string())
+}
+
+/// Full message (subject and body) of commit `hash`.
+pub fn commit_message(repo: &Path, hash: &str) -> Option<String> {
+    let output = command()
         .args([
             "-C",
             &repo.to_string_lossy(),
-            "branch",
-            "--format=%(refname:short)",
+            "show",
+            "-s",
+            "--format=%B",
+            hash,
         ])
         .output()
-        .context("Failed to run git branch")?;
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .trim_end()
+            .to_string(),
+    )
+}
+
+/// `HEAD` and every ref (branches, remote-tracking branches, tags) with
+/// what it points to. Equal output means a scan of the repository yields
+/// the same commits.
+pub fn refs_state(repo: &Path) -> Option<String> {
+    refs::state(&refs::open(repo).ok()?)
+}
+
+/// Paths changed by commit `hash`, relative to the repository root. Empty
+/// when the commit cannot be read.
+pub fn changed_files(repo: &Path, hash: &str) -> Vec<String> {
+    let output = command()
+        .args([
+            "-C",
+            &repo.to_string_lossy(),
+            "show",
+            "--no-renames",
+            "--name-only",
+            "--format=",
+            hash,
+        ])
+        .output();
+    match output {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(String::from)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Whether `git` can be run at all.
+pub fn is_available() -> bool {
+    command()
+        .arg("--version")
+        .output()
+        .is_ok_and(|o| o.status.success())
+}
+
+/// `text` with the metacharacters of extended regexes escaped, so that an
+/// identity such as `1234+jane@users.noreply.github.com` matches itself when
+/// used as an author pattern.
+pub fn literal_pattern(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.[](){}*+?^$|".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Check that `key` can name a trailer: ASCII letters, digits and dashes,
+/// as in `Time-Spent`. Other characters would end up inside `git log`'s
+/// `%(trailers:key=…)` placeholder.
+pub fn check_trailer_key(key: &str) -> Result<(), String> {
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(format!(
+            "invalid trailer key: {key:?} (use letters, digits and dashes, e.g. Time-Spent)"
+        ));
+    }
+    Ok(())
+}
+
+pub fn default_author() -> Option<String> {
+    env_author().or_else(|| {
+        command()
+            .args(["config", "--global", "user.name"])
+            .output()
+            .ok()
+            .and_then(|out| {
+                if out.status.success() {
+                    String::from_utf8(out.stdout)
+                        .ok()
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                } else {
+                    None
+                }
+            })
+    })
+}
+
+/// The identity git would record for a new commit in `repo`:
+/// `GIT_AUTHOR_NAME`/`GIT_AUTHOR_EMAIL` from the environment, otherwise
+/// `user.name` (or `user.email`) with repo-local config taking precedence
+/// over the global one.
+pub fn repo_author(repo: &Path) -> Option<String> {
+    env_author()
+        .or_else(|| config_value(repo, "user.name"))
+        .or_else(|| config_value(repo, "user.email"))
+}
+
+fn env_author() -> Option<String> {
+    ["GIT_AUTHOR_NAME", "GIT_AUTHOR_EMAIL"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+}
 
+fn config_value(repo: &Path, key: &str) -> Option<String> {
+    let output = command()
+        .arg("-C")
+        .arg(repo)
+        .args(["config", key])
+        .output()
+        .ok()?;
     if !output.status.success() {
-        return Ok(vec![]);
+        return None;
     }
+    let value = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
 
-    Ok(String::from_utf8_lossy(&output.stdout)
-        .lines()
-        .map(|l| l.trim().to_string())
-        .filter(|l| !l.is_empty())
-        .collect())
+/// Remote-tracking branches that have no local branch of the same name,
+/// from `(ref, name on the remote)` pairs, e.g. `("origin/feature",
+/// "feature")`. A name on several remotes is taken from `origin`, else from
+/// the first remote.
+fn remote_only_branches(
+    mut refs: Vec<(String, String)>,
+    local: &[String],
+) -> Vec<(String, String)> {
+    refs.sort_by_key(|(short, _)| !short.starts_with("origin/"));
+    let mut seen: HashSet<String> = local.iter().cloned().collect();
+    refs.into_iter()
+        .filter(|(_, name)| seen.insert(name.clone()))
+        .collect()
+}
+
+/// Name of the synthetic branch holding commits made on a detached HEAD
+/// that no local branch contains.
+pub const DETACHED_BRANCH: &str = "(detached)";
+
+/// The revision git understands for a branch of the report: `HEAD` for
+/// [`DETACHED_BRANCH`], the branch name otherwise.
+pub fn branch_rev(name: &str) -> &str {
+    if name == DETACHED_BRANCH {
+        "HEAD"
+    } else {
+        name
+    }
 }
 
-fn log_branch(
+/// Filters and extras applied to every `git log` call.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogOptions<'a> {
+    /// Keep commits whose `Name <email>` (of the author or a co-author)
+    /// matches any of these extended regexes; empty keeps every commit.
+    pub authors: &'a [String],
+    pub with_stat: bool,
+    /// Trailer key holding the time spent on a commit.
+    pub time_trailer: Option<&'a str>,
+    /// Keep only commits whose message matches any of these extended
+    /// regexes, like `git log --grep`; empty keeps every commit.
+    pub grep: &'a [String],
+    /// Drop commits whose message matches any of these extended regexes.
+    pub exclude_grep: &'a [String],
+    /// Keep merge commits, which are left out by default.
+    pub include_merges: bool,
+    /// Also report remote-tracking branches that have no local branch of
+    /// the same name, e.g. work pushed from another machine.
+    pub remote_branches: bool,
+    /// List each commit under one branch only instead of every branch that
+    /// contains it.
+    pub dedupe: bool,
+    /// How `relative_time` is written for each commit.
+    pub relative: RelativeTime,
+}
+
+/// Check that `patterns` are valid extended regexes for `git log --grep`,
+/// using `repo` to run git in. Returns git's error message otherwise.
+pub fn check_grep_patterns(repo: &Path, patterns: &[String]) -> Result<(), String> {
+    for pattern in patterns {
+        let output = command()
+            .args([
+                "-C",
+                &repo.to_string_lossy(),
+                "log",
+                "-n0",
+                "--extended-regexp",
+            ])
+            .arg(format!("--grep={pattern}"))
+            .output()
+            .map_err(|e| format!("Failed to run git: {e}"))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = stderr
+                .trim()
+                .rsplit(": ")
+                .next()
+                .unwrap_or("invalid pattern");
+            return Err(format!("invalid pattern \"{pattern}\": {reason}"));
+        }
+    }
+    Ok(())
+}
+
+/// Commits of `revs` (a branch, or a revision range in `git log` syntax)
+/// within `range`.
+pub(crate) fn log_branch(
     repo: &Path,
-    branch: &str,
+    revs: &[&str],
     range: &TimeRange,
-    author: Option<&str>,
-    with_stat: bool,
+    opts: &LogOptions,
 ) -> Result<(Vec<Commit>, Option<DiffStat>, HashSet<String>)> {
+    let LogOptions {
+        authors,
+        with_stat,
+        time_trailer,
+        grep,
+        exclude_grep,
+        include_merges,
+        relative,
+        ..
+    } = *opts;
     let since_str = range.since.to_rfc3339();
+    let mut format =
+        "--format=%h%x00%s%x00%aI%x00%aN%x00%aE%x00%(trailers:key=Signed-off-by,valueonly,separator=%x1f)%x00%(trailers:key=Co-authored-by,valueonly,separator=%x1f)"
+            .to_string();
+    if let Some(key) = time_trailer.filter(|k| check_trailer_key(k).is_ok()) {
+        format.push_str(&format!(
+            "%x00%(trailers:key={key},valueonly,separator=%x1f)"
+        ));
+    }
 
     let mut args = vec![
         "-C".to_string(),
         repo.to_string_lossy().to_string(),
         "log".to_string(),
-        branch.to_string(),
-        format!("--after={since_str}"),
-        "--format=%h%x00%s%x00%aI".to_string(),
-        "--no-merges".to_string(),
     ];
+    args.extend(revs.iter().map(|r| r.to_string()));
+    args.extend([format!("--after={since_str}"), format]);
+
+    if !include_merges {
+        args.push("--no-merges".to_string());
+    }
 
     if with_stat {
         args.push("--numstat".to_string());
@@ -74,11 +415,27 @@ fn log_branch(
         args.push(format!("--before={}", until.to_rfc3339()));
     }
 
-    if let Some(author) = author {
-        args.push(format!("--author={author}"));
+    // Author and message patterns are both extended regexes, as in the
+    // in-process walk; `--extended-regexp` applies to `--author` too.
+    args.push("--extended-regexp".to_string());
+
+    // `--author` cannot see `Co-authored-by` trailers, so the authors are
+    // matched below unless a pattern only git understands.
+    let author_filter = revwalk::AuthorFilter::new(authors);
+    if author_filter.is_none() {
+        args.extend(authors.iter().map(|a| format!("--author={a}")));
+    }
+
+    // `--invert-grep` applies to every `--grep`, so exclusions next to
+    // wanted patterns are looked up separately below.
+    if !grep.is_empty() {
+        args.extend(grep.iter().map(|p| format!("--grep={p}")));
+    } else if !exclude_grep.is_empty() {
+        args.push("--invert-grep".to_string());
+        args.extend(exclude_grep.iter().map(|p| format!("--grep={p}")));
     }
 
-    let output = Command::new("git")
+    let output = command()
         .args(&args)
         .output()
         .context("Failed to run git log")?;
@@ -87,9 +444,20 @@ fn log_branch(
         return Ok((vec![], None, HashSet::new()));
     }
 
-    let now = Local::now();
+    let now = range.zone.now();
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let (commits, branch_files) = parse_log_output(&stdout, now, with_stat);
+    let (mut commits, branch_files) = parse_log_output(
+        &stdout,
+        now,
+        range.zone,
+        &relative,
+        with_stat,
+        author_filter.as_ref(),
+    );
+    if !grep.is_empty() && !exclude_grep.is_empty() && !commits.is_empty() {
+        let excluded = grep_matches(repo, revs, range, exclude_grep)?;
+        commits.retain(|c| !excluded.contains(&c.hash));
+    }
 
     let branch_stat = if with_stat && !commits.is_empty() {
         let insertions: u32 = commits
@@ -114,13 +482,51 @@ fn log_branch(
     Ok((commits, branch_stat, branch_files))
 }
 
+/// Abbreviated hashes of the commits of `revs` within `range` whose message
+/// matches any of `patterns`.
+fn grep_matches(
+    repo: &Path,
+    revs: &[&str],
+    range: &TimeRange,
+    patterns: &[String],
+) -> Result<HashSet<String>> {
+    let mut args = vec![
+        "-C".to_string(),
+        repo.to_string_lossy().to_string(),
+        "log".to_string(),
+    ];
+    args.extend(revs.iter().map(|r| r.to_string()));
+    args.extend([
+        format!("--after={}", range.since.to_rfc3339()),
+        "--format=%h".to_string(),
+        "--extended-regexp".to_string(),
+    ]);
+    if let Some(until) = &range.until {
+        args.push(format!("--before={}", until.to_rfc3339()));
+    }
+    args.extend(patterns.iter().map(|p| format!("--grep={p}")));
+    let output = command()
+        .args(&args)
+        .output()
+        .context("Failed to run git log")?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
 fn parse_log_output(
     stdout: &str,
-    now: DateTime<Local>,
+    now: Time,
+    zone: Zone,
+    relative: &RelativeTime,
     with_stat: bool,
+    authors: Option<&revwalk::AuthorFilter>,
 ) -> (Vec<Commit>, HashSet<String>) {
     let mut commits = Vec::new();
     let mut branch_files = HashSet::new();
+    // The numstat lines of a commit dropped by `authors` are skipped too.
+    let mut skipping = false;
     let mut current_insertions: u32 = 0;
     let mut current_deletions: u32 = 0;
     let mut current_files: u32 = 0;
@@ -147,10 +553,14 @@ fn parse_log_output(
             current_deletions = 0;
             current_files = 0;
 
-            if let Some(commit) = parse_commit_line(line, now) {
+            let commit = parse_commit_line(line, now, zone, relative);
+            skipping = commit.as_ref().is_some_and(|c| {
+                authors.is_some_and(|a| !a.matches(&c.author, &c.author_email, &c.co_authors))
+            });
+            if let Some(commit) = commit.filter(|_| !skipping) {
                 commits.push(commit);
             }
-        } else if with_stat {
+        } else if with_stat && !skipping {
             if let Some((ins, del, path)) = parse_numstat_line(line) {
                 current_insertions += ins;
                 current_deletions += del;
@@ -186,28 +596,54 @@ fn parse_numstat_line(line: &str) -> Option<(u32, u32, String)> {
     Some((ins, del, parts[2].to_string()))
 }
 
-fn parse_commit_line(line: &str, now: DateTime<Local>) -> Option<Commit> {
-    let parts: Vec<&str> = line.splitn(3, '\0').collect();
-    if parts.len() != 3 {
+fn parse_commit_line(line: &str, now: Time, zone: Zone, relative: &RelativeTime) -> Option<Commit> {
+    let parts: Vec<&str> = line.splitn(8, '\0').collect();
+    if parts.len() < 3 {
         return None;
     }
 
-    let time = DateTime::parse_from_rfc3339(parts[2])
-        .ok()?
-        .with_timezone(&Local);
+    let time = zone.convert(DateTime::parse_from_rfc3339(parts[2]).ok()?);
 
     Some(Commit {
         hash: parts[0].to_string(),
         message: parts[1].to_string(),
+        author: parts.get(3).unwrap_or(&"").to_string(),
+        author_email: parts.get(4).unwrap_or(&"").to_string(),
         commit_type: detect_commit_type(parts[1]),
-        relative_time: format_relative(now, time),
+        relative_time: relative.format(now, time),
         time,
         url: None,
         diff_stat: None,
+        time_spent: parts.get(7).and_then(|t| parse_time_trailer(t)),
+        signed_off_by: parts
+            .get(5)
+            .map(|v| {
+                v.split('\x1f')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        co_authors: parts
+            .get(6)
+            .map(|v| v.split('\x1f').filter_map(CoAuthor::parse).collect())
+            .unwrap_or_default(),
+        unpushed: false,
+        verified: None,
     })
 }
 
-fn detect_commit_type(message: &str) -> Option<String> {
+/// Sum the `\x1f`-separated trailer values of one commit; unparseable values
+/// are ignored.
+fn parse_time_trailer(values: &str) -> Option<WorkTime> {
+    values
+        .split('\x1f')
+        .filter_map(WorkTime::parse)
+        .reduce(|a, b| a + b)
+}
+
+pub(crate) fn detect_commit_type(message: &str) -> Option<String> {
     let prefix = message.split([':', '(']).next()?;
     let trimmed = prefix.trim();
     let is_kebab_case = !trimmed.is_empty()
@@ -221,37 +657,120 @@ fn detect_commit_type(message: &str) -> Option<String> {
     }
 }
 
-fn format_relative(now: DateTime<Local>, then: DateTime<Local>) -> String {
-    let duration = now.signed_duration_since(then);
-    let mins = duration.num_minutes();
+/// `MAJOR.MINOR.PATCH` with an optional `v` prefix and pre-release/build suffix.
+fn is_semver_tag(tag: &str) -> bool {
+    let version = tag.strip_prefix('v').unwrap_or(tag);
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let parts: Vec<&str> = core.split('.').collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+        && (core.len() == version.len() || version.len() > core.len() + 1)
+}
 
-    if mins < 1 {
-        "just now".to_string()
-    } else if mins < 60 {
-        format!("{mins}m ago")
-    } else if mins < 1440 {
-        format!("{}h ago", duration.num_hours())
+/// For a shallow clone whose history does not reach back to `since`, the
+/// commit date of its newest shallow boundary: commits of the period older
+/// than that are missing from the clone.
+pub fn shallow_history_start(repo: &Path, range: &TimeRange) -> Option<Time> {
+    let shallow_file = if repo.join(".git").is_dir() {
+        repo.join(".git").join("shallow")
     } else {
-        format!("{}d ago", duration.num_days())
+        let output = command()
+            .args([
+                "-C",
+                &repo.to_string_lossy(),
+                "rev-parse",
+                "--git-path",
+                "shallow",
+            ])
+            .output()
+            .ok()?;
+        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        repo.join(path)
+    };
+    let boundaries = std::fs::read_to_string(shallow_file).ok()?;
+    let hashes: Vec<&str> = boundaries.split_whitespace().collect();
+    if hashes.is_empty() {
+        return None;
     }
-}
-
-fn get_remote_url(repo: &Path) -> Option<String> {
-    let output = Command::new("git")
-        .args(["-C", &repo.to_string_lossy(), "remote", "get-url", "origin"])
+    let output = command()
+        .args([
+            "-C",
+            &repo.to_string_lossy(),
+            "log",
+            "--no-walk",
+            "--format=%cI",
+        ])
+        .args(&hashes)
         .output()
         .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| DateTime::parse_from_rfc3339(line.trim()).ok())
+        .map(|t| range.zone.convert(t))
+        .max()
+        .filter(|start| *start > range.since)
+}
 
-    if !output.status.success() {
-        return None;
+/// Run `git fetch --all` in `repo`, killing it after `timeout`. Credential
+/// prompts are disabled so a remote needing a password fails instead of
+/// blocking the scan.
+pub fn fetch_all(repo: &Path, timeout: std::time::Duration) -> Result<()> {
+    offline::ensure_online("git fetch")?;
+    let mut child = command()
+        .args(["-C", &repo.to_string_lossy(), "fetch", "--all", "--quiet"])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .context("failed to run git fetch")?;
+    let started = std::time::Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            if status.success() {
+                return Ok(());
+            }
+            let mut stderr = String::new();
+            if let Some(mut pipe) = child.stderr.take() {
+                use std::io::Read;
+                let _ = pipe.read_to_string(&mut stderr);
+            }
+            let reason = stderr.lines().next().unwrap_or_default().trim();
+            anyhow::bail!("git fetch failed: {reason}");
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            anyhow::bail!("git fetch timed out after {}s", timeout.as_secs());
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
     }
+}
 
-    let url = String::from_utf8(output.stdout).ok()?.trim().to_string();
-    if url.is_empty() {
-        None
-    } else {
-        Some(url)
+/// Deepen a shallow clone so its history covers everything since `since`.
+pub fn deepen_since(repo: &Path, since: Time) -> Result<()> {
+    offline::ensure_online("git fetch")?;
+    let output = command()
+        .args([
+            "-C",
+            &repo.to_string_lossy(),
+            "fetch",
+            "--quiet",
+            &format!("--shallow-since={}", since.to_rfc3339()),
+        ])
+        .output()
+        .context("failed to run git fetch")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git fetch failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
     }
+    Ok(())
+}
+
+fn get_remote_url(repo: &Path) -> Option<String> {
+    refs::origin_url(&refs::open(repo).ok()?)
 }
 
 fn extract_hostname(url: &str) -> Option<&str> {
@@ -335,9 +854,11 @@ pub fn browser_url(repo: &Path) -> Option<String> {
 }
 
 pub fn detect_origin(repo: &Path) -> Option<RepoOrigin> {
-    let url = get_remote_url(repo)?;
-    let hostname = extract_hostname(&url)?;
-    Some(classify_host(hostname))
+    origin_of(&get_remote_url(repo)?)
+}
+
+fn origin_of(url: &str) -> Option<RepoOrigin> {
+    extract_hostname(url).map(classify_host)
 }
 
 /// Build a browser URL for a branch, respecting platform-specific URL patterns.
@@ -372,6 +893,22 @@ pub fn commit_url(remote_url: &str, origin: Option<&RepoOrigin>, hash: &str) ->
     }
 }
 
+/// Build a browser URL for a release tag, respecting platform-specific URL patterns.
+pub fn release_url(remote_url: &str, origin: Option<&RepoOrigin>, tag: &str) -> String {
+    let encoded = urlencoded(tag);
+    match origin {
+        Some(RepoOrigin::GitLab | RepoOrigin::GitLabSelfHosted) => {
+            format!("{remote_url}/-/tags/{encoded}")
+        }
+        Some(RepoOrigin::Bitbucket) => {
+            format!("{remote_url}/src/{encoded}")
+        }
+        _ => {
+            format!("{remote_url}/releases/tag/{encoded}")
+        }
+    }
+}
+
 /// Minimal percent-encoding for branch names in URLs (spaces, special chars).
 fn urlencoded(s: &str) -> String {
     s.replace('%', "%25")
@@ -383,13 +920,69 @@ fn urlencoded(s: &str) -> String {
 pub fn collect_project_log(
     repo: &Path,
     range: &TimeRange,
-    author: Option<&str>,
-    with_stat: bool,
+    opts: &LogOptions,
 ) -> Option<ProjectLog> {
-    let project_name = repo.file_name()?.to_string_lossy().to_string();
-    let branches = list_branches(repo).ok()?;
-    let origin = detect_origin(repo);
-    let remote = browser_url(repo);
+    collect_project_log_with_progress(repo, range, opts, &())
+}
+
+/// [`collect_project_log`], reporting the outcome to `progress` as
+/// [`ProgressEvent::RepoScanned`] or [`ProgressEvent::RepoFailed`].
+pub fn collect_project_log_with_progress(
+    repo: &Path,
+    range: &TimeRange,
+    opts: &LogOptions,
+    progress: &dyn Progress,
+) -> Option<ProjectLog> {
+    let path = repo.to_path_buf();
+    match try_collect_project_log(repo, range, opts) {
+        Ok(project) => {
+            let commits = project.as_ref().map_or(0, ProjectLog::total_commits);
+            progress.event(ProgressEvent::RepoScanned { path, commits });
+            project
+        }
+        Err(e) => {
+            progress.event(ProgressEvent::RepoFailed {
+                path,
+                error: format!("{e:#}"),
+            });
+            None
+        }
+    }
+}
+
+/// `Ok(None)` when the repository has no matching commits in the period.
+fn try_collect_project_log(
+    repo: &Path,
+    range: &TimeRange,
+    opts: &LogOptions,
+) -> Result<Option<ProjectLog>> {
+    let with_stat = opts.with_stat;
+    let Some(project_name) = project_name(repo) else {
+        return Ok(None);
+    };
+    let git = refs::open(repo)?;
+    let mut branches = refs::local_branches(&git)?;
+    let primary = branches.iter().find(|b| is_primary_branch(b)).cloned();
+    let primary_tip = primary.as_deref().and_then(|p| refs::branch_tip(&git, p));
+    // Remote-tracking branches and their names on the remote, for URLs.
+    let remote_names: HashMap<String, String> = if opts.remote_branches {
+        remote_only_branches(refs::remote_branches(&git), &branches)
+            .into_iter()
+            .collect()
+    } else {
+        HashMap::new()
+    };
+    let upstreams = refs::upstream_names(&git, &branches);
+    branches.extend(remote_names.keys().cloned());
+    let remote_url = refs::origin_url(&git);
+    let origin = remote_url.as_deref().and_then(origin_of);
+    let remote = remote_url.as_deref().and_then(remote_to_browser_url);
+    let unpushed = refs::unpushed_commits(&git, range);
+    let mut worktrees = refs::worktree_paths(&git, repo);
+    if refs::is_detached(&git) {
+        branches.push(DETACHED_BRANCH.to_string());
+    }
+    let reader = revwalk::Reader::new(git.clone(), opts);
 
     let mut project_files: HashSet<String> = HashSet::new();
     let mut project_insertions: u32 = 0;
@@ -398,8 +991,19 @@ pub fn collect_project_log(
     let mut branch_logs: Vec<BranchLog> = branches
         .into_iter()
         .filter_map(|branch_name| {
-            let (mut commits, branch_stat, branch_file_set) =
-                log_branch(repo, &branch_name, range, author, with_stat).ok()?;
+            let detached = branch_name == DETACHED_BRANCH;
+            let revs: &[&str] = if detached {
+                &["HEAD", "--not", "--branches"]
+            } else {
+                &[&branch_name]
+            };
+            let read = reader
+                .as_ref()
+                .and_then(|r| r.log(&branch_name, range, opts));
+            let (mut commits, branch_stat, branch_file_set) = match read {
+                Some(commits) => (commits, None, HashSet::new()),
+                None => log_branch(repo, revs, range, opts).ok()?,
+            };
             if commits.is_empty() {
                 None
             } else {
@@ -408,9 +1012,15 @@ pub fn collect_project_log(
                         c.url = Some(commit_url(base, origin.as_ref(), &c.hash));
                     }
                 }
-                let b_url = remote
-                    .as_deref()
-                    .map(|base| branch_url(base, origin.as_ref(), &branch_name));
+                if let Some(unpushed) = &unpushed {
+                    for c in &mut commits {
+                        c.unpushed = unpushed.contains(&c.hash);
+                    }
+                }
+                let b_url = remote.as_deref().filter(|_| !detached).map(|base| {
+                    let name = remote_names.get(&branch_name).unwrap_or(&branch_name);
+                    branch_url(base, origin.as_ref(), name)
+                });
 
                 if let Some(stat) = &branch_stat {
                     project_insertions += stat.insertions;
@@ -418,18 +1028,61 @@ pub fn collect_project_log(
                     project_files.extend(branch_file_set);
                 }
 
+                let divergence = primary_tip
+                    .filter(|_| primary.as_deref() != Some(&branch_name) && !detached)
+                    .and_then(|p| {
+                        let tip = refs::branch_tip(&git, &branch_name)?;
+                        refs::divergence(&git, p, tip, range.zone)
+                    });
+                let started = divergence.as_ref().and_then(|d| d.started);
+                let upstream = upstreams
+                    .get(&branch_name)
+                    .and_then(|u| refs::upstream_status(&git, &branch_name, u));
+                let is_merged = primary
+                    .as_deref()
+                    .filter(|p| *p != branch_name && !detached)
+                    .map(|_| divergence.as_ref().is_some_and(|d| d.merged));
+                let worktree = worktrees.remove(&branch_name);
+                let remote_name = remote_names.get(&branch_name).cloned();
+
                 Some(BranchLog {
                     name: branch_name,
                     url: b_url,
                     commits,
                     diff_stat: branch_stat,
+                    pr: None,
+                    started,
+                    merged: is_merged,
+                    omitted: None,
+                    worktree,
+                    remote_name,
+                    upstream,
                 })
             }
         })
         .collect();
 
     if branch_logs.is_empty() {
-        return None;
+        return Ok(None);
+    }
+    if opts.dedupe {
+        let primary_line = primary_tip
+            .map(|tip| refs::first_parent_commits(&git, tip, range))
+            .unwrap_or_default();
+        dedupe::dedupe(&mut branch_logs, &primary_line);
+        // Shared commits were counted once per branch.
+        if with_stat {
+            let commits = branch_logs.iter().flat_map(|b| &b.commits);
+            project_insertions = commits
+                .clone()
+                .filter_map(|c| c.diff_stat.as_ref())
+                .map(|s| s.insertions)
+                .sum();
+            project_deletions = commits
+                .filter_map(|c| c.diff_stat.as_ref())
+                .map(|s| s.deletions)
+                .sum();
+        }
     }
 
     branch_logs.sort_by(|a, b| {
@@ -448,50 +1101,84 @@ pub fn collect_project_log(
         None
     };
 
-    Some(ProjectLog {
+    let days = activity::period_days(range, range.zone.now());
+    let activity = if days.len() > 1 {
+        Some(activity::daily_counts(&branch_logs, &days))
+    } else {
+        None
+    };
+
+    let mut releases = refs::releases(&git, range, is_semver_tag);
+    if let Some(base) = &remote {
+        for r in &mut releases {
+            r.url = Some(release_url(base, origin.as_ref(), &r.tag));
+        }
+    }
+
+    Ok(Some(ProjectLog {
         project: project_name,
         path: repo.to_string_lossy().to_string(),
         origin,
         remote_url: remote,
         branches: branch_logs,
         diff_stat: project_stat,
-    })
+        activity,
+        releases: (!releases.is_empty()).then_some(releases),
+        health: None,
+        group: None,
+    }))
 }
 
-fn is_primary_branch(name: &str) -> bool {
+pub fn is_primary_branch(name: &str) -> bool {
     matches!(name, "main" | "master")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Duration;
+    use chrono::Local;
 
     #[test]
-    fn format_relative_just_now() {
-        let now = Local::now();
-        assert_eq!(format_relative(now, now), "just now");
-    }
-
-    #[test]
-    fn format_relative_minutes() {
-        let now = Local::now();
-        let then = now - Duration::minutes(5);
-        assert_eq!(format_relative(now, then), "5m ago");
+    fn trailer_keys_are_letters_digits_and_dashes() {
+        assert!(check_trailer_key("Time-Spent").is_ok());
+        assert!(check_trailer_key("X-Time2").is_ok());
+        for key in [
+            "",
+            "Time Spent",
+            "Time,valueonly",
+            "Time)%x00",
+            "Zeit-\u{e4}",
+        ] {
+            assert!(check_trailer_key(key).is_err(), "{key:?}");
+        }
     }
 
     #[test]
-    fn format_relative_hours() {
-        let now = Local::now();
-        let then = now - Duration::hours(3);
-        assert_eq!(format_relative(now, then), "3h ago");
+    fn literal_pattern_escapes_regex_metacharacters() {
+        assert_eq!(
+            literal_pattern("1234+jane@users.noreply.github.com"),
+            "1234\\+jane@users\\.noreply\\.github\\.com"
+        );
+        assert_eq!(literal_pattern("Jane Doe (Work)"), "Jane Doe \\(Work\\)");
     }
 
     #[test]
-    fn format_relative_days() {
-        let now = Local::now();
-        let then = now - Duration::days(2);
-        assert_eq!(format_relative(now, then), "2d ago");
+    fn remote_branches_skip_local_names() {
+        let refs = [
+            ("fork/feature/x", "feature/x"),
+            ("origin/feature/x", "feature/x"),
+            ("origin/main", "main"),
+            ("origin/spike", "spike"),
+        ]
+        .map(|(short, name)| (short.to_string(), name.to_string()))
+        .to_vec();
+        assert_eq!(
+            remote_only_branches(refs, &["main".to_string()]),
+            [
+                ("origin/feature/x".to_string(), "feature/x".to_string()),
+                ("origin/spike".to_string(), "spike".to_string()),
+            ]
+        );
     }
 
     #[test]
@@ -548,21 +1235,146 @@ mod tests {
 
     #[test]
     fn parse_commit_line_valid() {
-        let now = Local::now();
+        let now = Local::now().fixed_offset();
         let time_str = now.to_rfc3339();
-        let line = format!("abc1234\x00feat: add feature\x00{time_str}");
-        let commit = parse_commit_line(&line, now);
+        let line = format!("abc1234\x00feat: add feature\x00{time_str}\x00Jane Doe");
+        let commit = parse_commit_line(&line, now, Zone::Local, &RelativeTime::default());
         assert!(commit.is_some());
         let c = commit.unwrap_or_else(|| panic!("Expected Some"));
         assert_eq!(c.hash, "abc1234");
         assert_eq!(c.message, "feat: add feature");
         assert_eq!(c.commit_type, Some("feat".to_string()));
+        assert_eq!(c.author, "Jane Doe");
+        assert_eq!(c.author_email, "");
+    }
+
+    #[test]
+    fn commit_near_midnight_lands_on_the_zones_day() {
+        // 23:30 UTC on a Thursday is already Friday in Berlin.
+        let line = "abc1234\x00fix: late\x002026-03-05T23:30:00+00:00\x00Jane Doe";
+        let parse = |zone: &str| {
+            let zone: Zone = zone.parse().expect("known zone");
+            parse_commit_line(line, zone.now(), zone, &RelativeTime::default())
+                .expect("valid line")
+                .time
+        };
+        let berlin = parse("Europe/Berlin");
+        assert_eq!(berlin.to_rfc3339(), "2026-03-06T00:30:00+01:00");
+        assert_eq!(berlin.date_naive().to_string(), "2026-03-06");
+        let utc = parse("UTC");
+        assert_eq!(utc.date_naive().to_string(), "2026-03-05");
+
+        let range = TimeRange::from_dates(
+            utc.date_naive().succ_opt().expect("next day"),
+            utc.date_naive().succ_opt().expect("next day"),
+            "Europe/Berlin".parse().expect("known zone"),
+        )
+        .expect("range");
+        assert!(berlin >= range.since && range.until.is_some_and(|u| berlin < u));
+        let range = TimeRange::from_dates(
+            utc.date_naive(),
+            utc.date_naive(),
+            "UTC".parse().expect("known zone"),
+        )
+        .expect("range");
+        assert!(utc >= range.since && range.until.is_some_and(|u| utc < u));
+    }
+
+    #[test]
+    fn parse_commit_line_with_time_trailer() {
+        let now = Local::now().fixed_offset();
+        let time_str = now.to_rfc3339();
+        let line = format!("abc1234\x00fix: thing\x00{time_str}\x00Jane Doe\x00jane@example.com\x00\x00\x001h30m\x1f15m");
+        let c = parse_commit_line(&line, now, Zone::Local, &RelativeTime::default())
+            .unwrap_or_else(|| panic!("Expected Some"));
+        assert_eq!(c.time_spent, Some(WorkTime::from_minutes(105)));
+
+        let line = format!(
+            "abc1234\x00fix: thing\x00{time_str}\x00Jane Doe\x00jane@example.com\x00\x00\x00"
+        );
+        let c = parse_commit_line(&line, now, Zone::Local, &RelativeTime::default())
+            .unwrap_or_else(|| panic!("Expected Some"));
+        assert_eq!(c.time_spent, None);
+    }
+
+    #[test]
+    fn parse_commit_line_with_sign_off() {
+        let now = Local::now().fixed_offset();
+        let time_str = now.to_rfc3339();
+        let line = format!(
+            "abc1234\x00fix: thing\x00{time_str}\x00Jane Doe\x00jane@example.com\x00Jane Doe <jane@example.com>\x1fBot <bot@example.com>"
+        );
+        let c = parse_commit_line(&line, now, Zone::Local, &RelativeTime::default())
+            .unwrap_or_else(|| panic!("Expected Some"));
+        assert_eq!(
+            c.signed_off_by,
+            ["Jane Doe <jane@example.com>", "Bot <bot@example.com>"]
+        );
+        assert_eq!(c.time_spent, None);
+    }
+
+    #[test]
+    fn parse_commit_line_with_co_authors() {
+        let now = Local::now().fixed_offset();
+        let time_str = now.to_rfc3339();
+        let line = format!(
+            "abc1234\x00fix: thing\x00{time_str}\x00Jane Doe\x00jane@example.com\x00\x00Max Muster <max@example.com>\x1f bob "
+        );
+        let c = parse_commit_line(&line, now, Zone::Local, &RelativeTime::default())
+            .unwrap_or_else(|| panic!("Expected Some"));
+        let names: Vec<(&str, &str)> = c
+            .co_authors
+            .iter()
+            .map(|a| (a.name.as_str(), a.email.as_str()))
+            .collect();
+        assert_eq!(names, [("Max Muster", "max@example.com"), ("bob", "")]);
+    }
+
+    #[test]
+    fn parse_time_trailer_skips_invalid_values() {
+        assert_eq!(
+            parse_time_trailer("2h\x1fsoon"),
+            Some(WorkTime::from_minutes(120))
+        );
+        assert_eq!(parse_time_trailer("n/a"), None);
     }
 
     #[test]
     fn parse_commit_line_invalid() {
-        let now = Local::now();
-        assert!(parse_commit_line("incomplete line", now).is_none());
+        let now = Local::now().fixed_offset();
+        assert!(parse_commit_line(
+            "incomplete line",
+            now,
+            Zone::Local,
+            &RelativeTime::default()
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn semver_tags_detected() {
+        assert!(is_semver_tag("v2.3.0"));
+        assert!(is_semver_tag("1.0.0"));
+        assert!(is_semver_tag("v1.0.0-rc.1"));
+        assert!(is_semver_tag("1.2.3+build.5"));
+        assert!(!is_semver_tag("v1.2"));
+        assert!(!is_semver_tag("release-2026"));
+        assert!(!is_semver_tag("v1.2.3-"));
+        assert!(!is_semver_tag("v1.2.x"));
+    }
+
+    #[test]
+    fn release_url_per_platform() {
+        let gh = Some(&RepoOrigin::GitHub);
+        assert_eq!(
+            release_url("https://github.com/o/r", gh, "v1.0.0"),
+            "https://github.com/o/r/releases/tag/v1.0.0"
+        );
+        let gl = Some(&RepoOrigin::GitLab);
+        assert_eq!(
+            release_url("https://gitlab.com/o/r", gl, "v1.0.0"),
+            "https://gitlab.com/o/r/-/tags/v1.0.0"
+        );
     }
 
     #[test]
@@ -790,7 +1602,7 @@ mod tests {
 
     #[test]
     fn parse_log_output_with_stat() {
-        let now = Local::now();
+        let now = Local::now().fixed_offset();
         let ts = now.to_rfc3339();
         let input = format!(
             "abc1234\x00feat: add feature\x00{ts}\n\
@@ -800,7 +1612,14 @@ mod tests {
              def5678\x00fix: bug\x00{ts}\n\
              2\t5\tsrc/main.rs\n"
         );
-        let (commits, files) = parse_log_output(&input, now, true);
+        let (commits, files) = parse_log_output(
+            &input,
+            now,
+            Zone::Local,
+            &RelativeTime::default(),
+            true,
+            None,
+        );
         assert_eq!(commits.len(), 2);
 
         let s0 = commits[0]
@@ -825,14 +1644,138 @@ mod tests {
         assert!(files.contains("src/lib.rs"));
     }
 
+    #[test]
+    fn author_filter_keeps_co_authored_commits() {
+        let now = Local::now().fixed_offset();
+        let ts = now.to_rfc3339();
+        let input = format!(
+            "abc1234\x00feat: pair on export\x00{ts}\x00Max Muster\x00max@example.com\x00\x00Jane Doe <jane@example.com>\n\
+             3\t1\tsrc/export.rs\n\
+             \n\
+             def5678\x00fix: solo\x00{ts}\x00Max Muster\x00max@example.com\x00\x00\n\
+             2\t5\tsrc/main.rs\n"
+        );
+        let filter = revwalk::AuthorFilter::new(&["jane@".to_string()]).expect("valid pattern");
+        let (commits, files) = parse_log_output(
+            &input,
+            now,
+            Zone::Local,
+            &RelativeTime::default(),
+            true,
+            Some(&filter),
+        );
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].hash, "abc1234");
+        assert_eq!(files, HashSet::from(["src/export.rs".to_string()]));
+    }
+
     #[test]
     fn parse_log_output_without_stat() {
-        let now = Local::now();
+        let now = Local::now().fixed_offset();
         let ts = now.to_rfc3339();
         let input = format!("abc1234\x00feat: add feature\x00{ts}\n");
-        let (commits, files) = parse_log_output(&input, now, false);
+        let (commits, files) = parse_log_output(
+            &input,
+            now,
+            Zone::Local,
+            &RelativeTime::default(),
+            false,
+            None,
+        );
         assert_eq!(commits.len(), 1);
         assert!(commits[0].diff_stat.is_none());
         assert!(files.is_empty());
     }
+
+    #[test]
+    fn scan_reads_branch_metadata_without_git_commands() {
+        let dir = std::env::temp_dir().join(format!("devcap-scan-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let repo = dir.join("app");
+        std::fs::create_dir_all(&repo).expect("create dir");
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(&repo)
+                .args([
+                    "-c",
+                    "user.name=Jane Doe",
+                    "-c",
+                    "user.email=jane@example.com",
+                ])
+                .args(args)
+                .status()
+                .expect("run git");
+            assert!(status.success(), "git {args:?} failed");
+        };
+        let origin = dir.join("origin.git");
+        let worktree = dir.join("wip");
+        git(&["init", "-q", "--bare", &origin.to_string_lossy()]);
+        git(&["init", "-q", "-b", "main"]);
+        git(&["remote", "add", "origin", &origin.to_string_lossy()]);
+        git(&["commit", "-q", "--allow-empty", "-m", "feat: on main"]);
+        git(&["tag", "-a", "v1.0.0", "-m", "release"]);
+        git(&["push", "-q", "-u", "origin", "main"]);
+        git(&["checkout", "-q", "-b", "feature"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "feat: pushed"]);
+        git(&["push", "-q", "-u", "origin", "feature"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "feat: not pushed"]);
+        git(&["checkout", "-q", "-b", "done", "main"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "fix: merged"]);
+        git(&["checkout", "-q", "main"]);
+        git(&["merge", "-q", "--ff-only", "done"]);
+        git(&[
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            "wip",
+            &worktree.to_string_lossy(),
+        ]);
+        git(&["checkout", "-q", "--detach"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "test: detached"]);
+
+        let range = TimeRange {
+            since: Local::now().fixed_offset() - chrono::Duration::days(1),
+            until: None,
+            zone: Zone::Local,
+        };
+        COMMANDS.with(|n| n.set(0));
+        let project =
+            collect_project_log(&repo, &range, &LogOptions::default()).expect("has commits");
+        assert_eq!(COMMANDS.with(|n| n.get()), 0);
+
+        let branch = |name: &str| {
+            project
+                .branches
+                .iter()
+                .find(|b| b.name == name)
+                .unwrap_or_else(|| panic!("no branch {name}"))
+        };
+        let feature = branch("feature");
+        assert_eq!(feature.merged, Some(false));
+        assert!(feature.started.is_some());
+        let upstream = feature.upstream.as_ref().expect("has upstream");
+        assert_eq!(
+            (upstream.name.as_str(), upstream.ahead, upstream.behind),
+            ("origin/feature", 1, 0)
+        );
+        let unpushed: Vec<&str> = feature
+            .commits
+            .iter()
+            .filter(|c| c.unpushed)
+            .map(|c| c.message.as_str())
+            .collect();
+        assert_eq!(unpushed, ["feat: not pushed"]);
+        assert_eq!(branch("done").merged, Some(true));
+        assert_eq!(
+            branch("wip").worktree.as_deref(),
+            Some(worktree.to_string_lossy().as_ref())
+        );
+        assert!(branch(DETACHED_BRANCH).merged.is_none());
+        let releases = project.releases.as_deref().unwrap_or_default();
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].tag, "v1.0.0");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
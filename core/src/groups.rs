@@ -0,0 +1,87 @@
+use serde::Serialize;
+
+use crate::model::{total_time_spent, ProjectLog, WorkTime};
+
+/// Section for projects without a [`ProjectLog::group`] label.
+pub const UNGROUPED: &str = "Other";
+
+/// The projects listed under one label, e.g. a client.
+#[derive(Debug, Serialize)]
+pub struct ProjectGroup {
+    pub group: String,
+    pub commits: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_spent: Option<WorkTime>,
+    pub projects: Vec<ProjectLog>,
+}
+
+/// Whether any project carries a group label.
+pub fn has_groups(projects: &[ProjectLog]) -> bool {
+    projects.iter().any(|p| p.group.is_some())
+}
+
+/// Split projects by their group label, in the order the labels first
+/// appear, so the report keeps its sort order inside and across groups.
+/// Projects without a label come last, under [`UNGROUPED`].
+pub fn group(projects: Vec<ProjectLog>) -> Vec<ProjectGroup> {
+    let mut groups: Vec<(Option<String>, Vec<ProjectLog>)> = Vec::new();
+    for project in projects {
+        match groups.iter_mut().find(|(label, _)| *label == project.group) {
+            Some((_, list)) => list.push(project),
+            None => groups.push((project.group.clone(), vec![project])),
+        }
+    }
+    groups.sort_by_key(|(label, _)| label.is_none());
+    groups
+        .into_iter()
+        .map(|(label, projects)| ProjectGroup {
+            group: label.unwrap_or_else(|| UNGROUPED.to_string()),
+            commits: projects.iter().map(ProjectLog::total_commits).sum(),
+            time_spent: total_time_spent(&projects),
+            projects,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str, group: Option<&str>) -> ProjectLog {
+        ProjectLog {
+            project: name.to_string(),
+            path: format!("/src/{name}"),
+            group: group.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn groups_keep_order_with_ungrouped_last() {
+        let projects = vec![
+            project("notes", None),
+            project("shop", Some("Client A")),
+            project("crm", Some("Client B")),
+            project("api", Some("Client A")),
+        ];
+        assert!(has_groups(&projects));
+        let groups = group(projects);
+        let summary: Vec<(&str, Vec<&str>)> = groups
+            .iter()
+            .map(|g| {
+                (
+                    g.group.as_str(),
+                    g.projects.iter().map(|p| p.project.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("Client A", vec!["shop", "api"]),
+                ("Client B", vec!["crm"]),
+                ("Other", vec!["notes"]),
+            ]
+        );
+    }
+}
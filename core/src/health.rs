@@ -0,0 +1,113 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::git;
+
+/// Working-copy state of a repository at the time of the report.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoHealth {
+    /// Checked-out branch; `None` on a detached HEAD.
+    pub branch: Option<String>,
+    /// Uncommitted changes: modified, staged and untracked files.
+    pub changes: u32,
+    pub stashes: u32,
+    /// Commits on local branches that no remote-tracking branch contains;
+    /// `None` for repositories without a remote.
+    pub unpushed: Option<u32>,
+}
+
+impl RepoHealth {
+    /// Nothing uncommitted, stashed or unpushed.
+    pub fn is_clean(&self) -> bool {
+        self.changes == 0 && self.stashes == 0 && self.unpushed.unwrap_or(0) == 0
+    }
+}
+
+/// Health of the working copy at `repo`; `None` for bare repositories,
+/// bundles and paths git cannot read.
+pub fn check(repo: &Path) -> Option<RepoHealth> {
+    let repo_str = repo.to_string_lossy();
+    let output = git::command()
+        .args([
+            "-C",
+            &repo_str,
+            "status",
+            "--porcelain=v2",
+            "--branch",
+            "--show-stash",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let mut health = parse_status(&String::from_utf8_lossy(&output.stdout));
+    health.unpushed = unpushed_count(&repo_str);
+    Some(health)
+}
+
+/// Branch, change and stash counts from `git status --porcelain=v2 --branch
+/// --show-stash`.
+fn parse_status(status: &str) -> RepoHealth {
+    let mut health = RepoHealth::default();
+    for line in status.lines() {
+        match line.strip_prefix("# ") {
+            Some(header) => {
+                if let Some(head) = header.strip_prefix("branch.head ") {
+                    health.branch = (head != "(detached)").then(|| head.to_string());
+                } else if let Some(count) = header.strip_prefix("stash ") {
+                    health.stashes = count.trim().parse().unwrap_or(0);
+                }
+            }
+            None if !line.is_empty() => health.changes += 1,
+            None => {}
+        }
+    }
+    health
+}
+
+fn unpushed_count(repo: &str) -> Option<u32> {
+    let remotes = git::command().args(["-C", repo, "remote"]).output().ok()?;
+    if !remotes.status.success() || remotes.stdout.iter().all(u8::is_ascii_whitespace) {
+        return None;
+    }
+    let output = git::command()
+        .args([
+            "-C",
+            repo,
+            "rev-list",
+            "--count",
+            "--branches",
+            "--not",
+            "--remotes",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_branch_changes_and_stashes() {
+        let status = "# branch.oid 194816b\n# branch.head main\n# branch.upstream origin/main\n\
+                      # stash 2\n1 .M N... 100644 100644 100644 a b src/lib.rs\n? notes.txt\n";
+        let health = parse_status(status);
+        assert_eq!(health.branch.as_deref(), Some("main"));
+        assert_eq!(health.changes, 2);
+        assert_eq!(health.stashes, 2);
+    }
+
+    #[test]
+    fn detached_head_has_no_branch() {
+        let health = parse_status("# branch.oid 194816b\n# branch.head (detached)\n");
+        assert_eq!(health.branch, None);
+        assert!(health.is_clean());
+    }
+}
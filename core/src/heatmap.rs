@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+
+use chrono::{Datelike, Timelike};
+use serde::Serialize;
+
+use crate::model::ProjectLog;
+
+/// Commits per hour of day and day of week, for `--heatmap`.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Heatmap {
+    /// `counts[day][hour]`, days from Monday, hours in local time.
+    pub counts: [[usize; 24]; 7],
+}
+
+impl Heatmap {
+    /// The largest cell; 0 when there are no commits.
+    pub fn max(&self) -> usize {
+        self.counts.iter().flatten().copied().max().unwrap_or(0)
+    }
+
+    /// Intensity of a cell from 0 (no commits) to `levels`, relative to the
+    /// busiest cell: every non-empty cell gets at least level 1.
+    pub fn level(&self, day: usize, hour: usize, levels: usize) -> usize {
+        let count = self.counts[day][hour];
+        match self.max() {
+            0 => 0,
+            max => (count * levels).div_ceil(max),
+        }
+    }
+}
+
+/// Count the commits of `projects` by the local weekday and hour they were
+/// made. A commit on several branches of a project counts once.
+pub fn compute(projects: &[ProjectLog]) -> Heatmap {
+    let mut heatmap = Heatmap::default();
+    for project in projects {
+        let mut seen = HashSet::new();
+        for commit in project.branches.iter().flat_map(|b| &b.commits) {
+            if !seen.insert(&commit.hash) {
+                continue;
+            }
+            let day = commit.time.weekday().num_days_from_monday() as usize;
+            heatmap.counts[day][commit.time.hour() as usize] += 1;
+        }
+    }
+    heatmap
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BranchLog, Commit};
+    use chrono::{Local, TimeZone};
+
+    fn commit(hash: &str, day: u32, hour: u32) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            message: "msg".to_string(),
+            // March 2026 starts on a Sunday, so the 2nd is a Monday.
+            time: Local
+                .with_ymd_and_hms(2026, 3, day, hour, 30, 0)
+                .single()
+                .expect("unambiguous time")
+                .fixed_offset(),
+            ..Default::default()
+        }
+    }
+
+    fn branch(name: &str, commits: Vec<Commit>) -> BranchLog {
+        BranchLog {
+            name: name.to_string(),
+            commits,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn counts_commits_by_weekday_and_hour() {
+        let project = ProjectLog {
+            project: "api".to_string(),
+            path: "/src/api".to_string(),
+            branches: vec![
+                branch(
+                    "main",
+                    vec![commit("a", 2, 9), commit("b", 2, 9), commit("c", 2, 9)],
+                ),
+                branch("feature", vec![commit("d", 6, 17), commit("a", 2, 9)]),
+            ],
+            ..Default::default()
+        };
+        let heatmap = compute(&[project]);
+        assert_eq!(heatmap.counts[0][9], 3);
+        assert_eq!(heatmap.counts[4][17], 1);
+        assert_eq!(heatmap.counts.iter().flatten().sum::<usize>(), 4);
+        assert_eq!(heatmap.level(0, 9, 4), 4);
+        assert_eq!(heatmap.level(4, 17, 4), 2);
+        assert_eq!(heatmap.level(1, 9, 4), 0);
+    }
+}
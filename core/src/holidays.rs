@@ -0,0 +1,473 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// How a calendar day counts towards expected working time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DayKind {
+    Workday,
+    Weekend,
+    Holiday(String),
+}
+
+/// Public holidays and other days off, keyed by date.
+#[derive(Debug, Default, Clone)]
+pub struct HolidayCalendar {
+    days: BTreeMap<NaiveDate, String>,
+    yearly: Vec<Yearly>,
+    regions: Vec<Region>,
+}
+
+impl HolidayCalendar {
+    pub fn add(&mut self, date: NaiveDate, name: &str) {
+        self.days.insert(date, name.to_string());
+    }
+
+    /// Add every all-day event of an iCalendar file. Multi-day events cover
+    /// each day up to (excluding) `DTEND`; events with a yearly `RRULE`
+    /// repeat on the same date. Returns how many events repeat by other
+    /// rules, which only count on their first date.
+    pub fn add_ics(&mut self, content: &str) -> usize {
+        let mut unsupported = 0;
+        for event in parse_ics_events(content) {
+            let end = event.end.unwrap_or(event.start + Duration::days(1));
+            match event
+                .rrule
+                .as_deref()
+                .map(|rule| Yearly::parse(rule, &event, end))
+            {
+                Some(Some(yearly)) => {
+                    self.yearly.push(yearly);
+                    continue;
+                }
+                Some(None) => unsupported += 1,
+                None => {}
+            }
+            let mut day = event.start;
+            while day < end {
+                self.add(day, &event.summary);
+                day += Duration::days(1);
+            }
+        }
+        unsupported
+    }
+
+    /// Count the national public holidays of `region` as well.
+    pub fn add_region(&mut self, region: Region) {
+        if !self.regions.contains(&region) {
+            self.regions.push(region);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty() && self.yearly.is_empty() && self.regions.is_empty()
+    }
+
+    pub fn holiday(&self, date: NaiveDate) -> Option<&str> {
+        self.days
+            .get(&date)
+            .map(String::as_str)
+            .or_else(|| {
+                self.yearly
+                    .iter()
+                    .find(|y| y.covers(date))
+                    .map(|y| y.summary.as_str())
+            })
+            .or_else(|| self.regions.iter().find_map(|r| r.holiday(date)))
+    }
+
+    pub fn classify(&self, date: NaiveDate) -> DayKind {
+        if let Some(name) = self.holiday(date) {
+            DayKind::Holiday(name.to_string())
+        } else if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            DayKind::Weekend
+        } else {
+            DayKind::Workday
+        }
+    }
+}
+
+/// A country whose national public holidays are built in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    /// Austria.
+    At,
+    /// Germany; holidays of single states need an `.ics` or explicit dates.
+    De,
+    /// France.
+    Fr,
+}
+
+/// A holiday on a fixed date, or a number of days after Easter Sunday.
+enum Rule {
+    Fixed(u32, u32, &'static str),
+    Easter(i64, &'static str),
+}
+
+const AT: &[Rule] = &[
+    Rule::Fixed(1, 1, "Neujahr"),
+    Rule::Fixed(1, 6, "Heilige Drei K\u{f6}nige"),
+    Rule::Easter(1, "Ostermontag"),
+    Rule::Fixed(5, 1, "Staatsfeiertag"),
+    Rule::Easter(39, "Christi Himmelfahrt"),
+    Rule::Easter(50, "Pfingstmontag"),
+    Rule::Easter(60, "Fronleichnam"),
+    Rule::Fixed(8, 15, "Mari\u{e4} Himmelfahrt"),
+    Rule::Fixed(10, 26, "Nationalfeiertag"),
+    Rule::Fixed(11, 1, "Allerheiligen"),
+    Rule::Fixed(12, 8, "Mari\u{e4} Empf\u{e4}ngnis"),
+    Rule::Fixed(12, 25, "Christtag"),
+    Rule::Fixed(12, 26, "Stefanitag"),
+];
+
+const DE: &[Rule] = &[
+    Rule::Fixed(1, 1, "Neujahr"),
+    Rule::Easter(-2, "Karfreitag"),
+    Rule::Easter(1, "Ostermontag"),
+    Rule::Fixed(5, 1, "Tag der Arbeit"),
+    Rule::Easter(39, "Christi Himmelfahrt"),
+    Rule::Easter(50, "Pfingstmontag"),
+    Rule::Fixed(10, 3, "Tag der Deutschen Einheit"),
+    Rule::Fixed(12, 25, "1. Weihnachtstag"),
+    Rule::Fixed(12, 26, "2. Weihnachtstag"),
+];
+
+const FR: &[Rule] = &[
+    Rule::Fixed(1, 1, "Jour de l'an"),
+    Rule::Easter(1, "Lundi de P\u{e2}ques"),
+    Rule::Fixed(5, 1, "F\u{ea}te du Travail"),
+    Rule::Fixed(5, 8, "Victoire 1945"),
+    Rule::Easter(39, "Ascension"),
+    Rule::Easter(50, "Lundi de Pentec\u{f4}te"),
+    Rule::Fixed(7, 14, "F\u{ea}te nationale"),
+    Rule::Fixed(8, 15, "Assomption"),
+    Rule::Fixed(11, 1, "Toussaint"),
+    Rule::Fixed(11, 11, "Armistice 1918"),
+    Rule::Fixed(12, 25, "No\u{eb}l"),
+];
+
+impl Region {
+    fn rules(self) -> &'static [Rule] {
+        match self {
+            Region::At => AT,
+            Region::De => DE,
+            Region::Fr => FR,
+        }
+    }
+
+    pub fn holiday(self, date: NaiveDate) -> Option<&'static str> {
+        let easter = easter_sunday(date.year());
+        self.rules().iter().find_map(|rule| match *rule {
+            Rule::Fixed(month, day, name) => {
+                (date.month() == month && date.day() == day).then_some(name)
+            }
+            Rule::Easter(offset, name) => {
+                (easter.map(|e| e + Duration::days(offset)) == Some(date)).then_some(name)
+            }
+        })
+    }
+}
+
+impl FromStr for Region {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "AT" => Ok(Region::At),
+            "DE" => Ok(Region::De),
+            "FR" => Ok(Region::Fr),
+            _ => Err(format!(
+                "unknown holiday region: {s} (expected: AT, DE, FR)"
+            )),
+        }
+    }
+}
+
+/// Easter Sunday of the Gregorian calendar (anonymous Gregorian algorithm).
+fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let (a, b, c) = (year % 19, year / 100, year % 100);
+    let (d, e) = (b / 4, b % 4);
+    let g = (8 * b + 13) / 25;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let (i, k) = (c / 4, c % 4);
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, u32::try_from(month).ok()?, u32::try_from(day).ok()?)
+}
+
+/// An event repeating on its date every `interval` years
+/// (`RRULE:FREQ=YEARLY`).
+#[derive(Debug, Clone)]
+struct Yearly {
+    start: NaiveDate,
+    days: i64,
+    interval: i32,
+    count: Option<i32>,
+    until: Option<NaiveDate>,
+    summary: String,
+}
+
+impl Yearly {
+    /// The recurrence of `event` ending before `end`, `None` unless the rule
+    /// only repeats the same date, possibly limited by `COUNT` or `UNTIL`.
+    fn parse(rule: &str, event: &IcsEvent, end: NaiveDate) -> Option<Self> {
+        let mut yearly = Yearly {
+            start: event.start,
+            days: (end - event.start).num_days(),
+            interval: 1,
+            count: None,
+            until: None,
+            summary: event.summary.clone(),
+        };
+        let mut freq = None;
+        for part in rule.split(';') {
+            let (key, value) = part.split_once('=')?;
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => freq = Some(value.to_ascii_uppercase()),
+                "INTERVAL" => yearly.interval = value.parse().ok().filter(|i| *i > 0)?,
+                "COUNT" => yearly.count = Some(value.parse().ok()?),
+                "UNTIL" => yearly.until = Some(parse_ics_date(value)?),
+                "BYMONTH" if value.parse() == Ok(event.start.month()) => {}
+                "BYMONTHDAY" if value.parse() == Ok(event.start.day()) => {}
+                "WKST" => {}
+                _ => return None,
+            }
+        }
+        (freq.as_deref() == Some("YEARLY")).then_some(yearly)
+    }
+
+    fn covers(&self, date: NaiveDate) -> bool {
+        // An occurrence may begin in the year before, e.g. over New Year.
+        [date.year() - 1, date.year()].into_iter().any(|year| {
+            let n = year - self.start.year();
+            if n < 0 || n % self.interval != 0 {
+                return false;
+            }
+            if self.count.is_some_and(|count| n / self.interval >= count) {
+                return false;
+            }
+            // A February 29 only recurs in leap years.
+            let Some(first) = self.start.with_year(year) else {
+                return false;
+            };
+            self.until.is_none_or(|until| first <= until)
+                && first <= date
+                && date < first + Duration::days(self.days)
+        })
+    }
+}
+
+struct IcsEvent {
+    start: NaiveDate,
+    end: Option<NaiveDate>,
+    summary: String,
+    rrule: Option<String>,
+}
+
+/// The properties of a `VEVENT` read so far.
+#[derive(Default)]
+struct PendingEvent {
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    summary: String,
+    rrule: Option<String>,
+}
+
+/// Unfold continuation lines (RFC 5545 §3.1) and collect `VEVENT` dates.
+fn parse_ics_events(content: &str) -> Vec<IcsEvent> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in content.lines() {
+        let raw = raw.trim_end_matches('\r');
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(cont), Some(last)) => last.push_str(cont),
+            _ => lines.push(raw.to_string()),
+        }
+    }
+
+    let mut events = Vec::new();
+    let mut current: Option<PendingEvent> = None;
+    for line in &lines {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let name = key.split(';').next().unwrap_or(key).to_ascii_uppercase();
+        match (name.as_str(), value) {
+            ("BEGIN", "VEVENT") => current = Some(PendingEvent::default()),
+            ("END", "VEVENT") => {
+                if let Some(PendingEvent {
+                    start: Some(start),
+                    end,
+                    summary,
+                    rrule,
+                }) = current.take()
+                {
+                    events.push(IcsEvent {
+                        start,
+                        end: end.filter(|e| *e > start),
+                        summary: if summary.is_empty() {
+                            "Holiday".to_string()
+                        } else {
+                            summary
+                        },
+                        rrule,
+                    });
+                }
+            }
+            ("DTSTART", v) => {
+                if let Some(ev) = current.as_mut() {
+                    ev.start = parse_ics_date(v);
+                }
+            }
+            ("DTEND", v) => {
+                if let Some(ev) = current.as_mut() {
+                    ev.end = parse_ics_date(v);
+                }
+            }
+            ("RRULE", v) => {
+                if let Some(ev) = current.as_mut() {
+                    ev.rrule = Some(v.to_string());
+                }
+            }
+            ("SUMMARY", v) => {
+                if let Some(ev) = current.as_mut() {
+                    ev.summary = v.replace("\\,", ",").replace("\\;", ";");
+                }
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+fn parse_ics_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).expect("valid date")
+    }
+
+    const ICS: &str = concat!(
+        "BEGIN:VCALENDAR\r\n",
+        "BEGIN:VEVENT\r\n",
+        "DTSTART;VALUE=DATE:20261225\r\n",
+        "DTEND;VALUE=DATE:20261227\r\n",
+        "SUMMARY:Christmas\r\n",
+        "  Holidays\r\n",
+        "END:VEVENT\r\n",
+        "BEGIN:VEVENT\r\n",
+        "DTSTART;VALUE=DATE:20261003\r\n",
+        "SUMMARY:Tag der Deutschen Einheit\r\n",
+        "END:VEVENT\r\n",
+        "END:VCALENDAR\r\n",
+    );
+
+    #[test]
+    fn ics_all_day_and_multi_day_events() {
+        let mut cal = HolidayCalendar::default();
+        cal.add_ics(ICS);
+        assert_eq!(cal.holiday(date(2026, 12, 25)), Some("Christmas Holidays"));
+        assert_eq!(cal.holiday(date(2026, 12, 26)), Some("Christmas Holidays"));
+        assert_eq!(cal.holiday(date(2026, 12, 27)), None);
+        assert_eq!(
+            cal.holiday(date(2026, 10, 3)),
+            Some("Tag der Deutschen Einheit")
+        );
+    }
+
+    #[test]
+    fn ics_yearly_events_repeat() {
+        let mut cal = HolidayCalendar::default();
+        let unsupported = cal.add_ics(concat!(
+            "BEGIN:VEVENT\n",
+            "DTSTART;VALUE=DATE:20201231\n",
+            "DTEND;VALUE=DATE:20210102\n",
+            "RRULE:FREQ=YEARLY\n",
+            "SUMMARY:New Year\n",
+            "END:VEVENT\n",
+            "BEGIN:VEVENT\n",
+            "DTSTART;VALUE=DATE:20240815\n",
+            "RRULE:FREQ=YEARLY;INTERVAL=2;COUNT=3\n",
+            "SUMMARY:Company day\n",
+            "END:VEVENT\n",
+            "BEGIN:VEVENT\n",
+            "DTSTART;VALUE=DATE:20250301\n",
+            "RRULE:FREQ=YEARLY;UNTIL=20260301\n",
+            "SUMMARY:Founding day\n",
+            "END:VEVENT\n",
+            "BEGIN:VEVENT\n",
+            "DTSTART;VALUE=DATE:20250526\n",
+            "RRULE:FREQ=YEARLY;BYMONTH=5;BYDAY=-1MO\n",
+            "SUMMARY:Memorial Day\n",
+            "END:VEVENT\n",
+        ));
+        assert_eq!(unsupported, 1);
+        assert_eq!(cal.holiday(date(2026, 12, 31)), Some("New Year"));
+        assert_eq!(cal.holiday(date(2027, 1, 1)), Some("New Year"));
+        assert_eq!(cal.holiday(date(2027, 1, 2)), None);
+        assert_eq!(cal.holiday(date(2020, 1, 1)), None);
+        assert_eq!(cal.holiday(date(2026, 8, 15)), Some("Company day"));
+        assert_eq!(cal.holiday(date(2027, 8, 15)), None);
+        assert_eq!(cal.holiday(date(2028, 8, 15)), Some("Company day"));
+        assert_eq!(cal.holiday(date(2030, 8, 15)), None);
+        assert_eq!(cal.holiday(date(2026, 3, 1)), Some("Founding day"));
+        assert_eq!(cal.holiday(date(2027, 3, 1)), None);
+        assert_eq!(cal.holiday(date(2025, 5, 26)), Some("Memorial Day"));
+        assert_eq!(cal.holiday(date(2026, 5, 25)), None);
+    }
+
+    #[test]
+    fn easter_dates() {
+        assert_eq!(easter_sunday(2024), Some(date(2024, 3, 31)));
+        assert_eq!(easter_sunday(2025), Some(date(2025, 4, 20)));
+        assert_eq!(easter_sunday(2026), Some(date(2026, 4, 5)));
+        assert_eq!(easter_sunday(2038), Some(date(2038, 4, 25)));
+    }
+
+    #[test]
+    fn region_holidays() {
+        let mut cal = HolidayCalendar::default();
+        cal.add_region("de".parse().expect("known region"));
+        assert_eq!(cal.holiday(date(2026, 4, 3)), Some("Karfreitag"));
+        assert_eq!(cal.holiday(date(2026, 5, 14)), Some("Christi Himmelfahrt"));
+        assert_eq!(cal.holiday(date(2026, 5, 25)), Some("Pfingstmontag"));
+        assert_eq!(
+            cal.holiday(date(2026, 10, 3)),
+            Some("Tag der Deutschen Einheit")
+        );
+        assert_eq!(cal.holiday(date(2026, 6, 4)), None);
+        assert_eq!(Region::At.holiday(date(2026, 6, 4)), Some("Fronleichnam"));
+        assert_eq!(
+            Region::Fr.holiday(date(2026, 7, 14)),
+            Some("F\u{ea}te nationale")
+        );
+        assert!("XX".parse::<Region>().is_err());
+    }
+
+    #[test]
+    fn classify_days() {
+        let mut cal = HolidayCalendar::default();
+        cal.add(date(2026, 12, 25), "Christmas Day");
+        // 2026-12-25 is a Friday, 2026-12-26 a Saturday
+        assert_eq!(
+            cal.classify(date(2026, 12, 25)),
+            DayKind::Holiday("Christmas Day".to_string())
+        );
+        assert_eq!(cal.classify(date(2026, 12, 26)), DayKind::Weekend);
+        assert_eq!(cal.classify(date(2026, 12, 28)), DayKind::Workday);
+    }
+
+    #[test]
+    fn ics_without_events_is_empty() {
+        let mut cal = HolidayCalendar::default();
+        cal.add_ics("BEGIN:VCALENDAR\nEND:VCALENDAR\n");
+        assert!(cal.is_empty());
+    }
+}
@@ -1,4 +1,61 @@
+//! Aggregate git commits across many repositories into a
+//! `Project -> Branch -> Commits` log, the engine behind the `devcap` CLI.
+//!
+//! [`Worklog::builder`] is the entry point for most uses:
+//!
+//! ```no_run
+//! use devcap_core::period::Period;
+//! use devcap_core::Worklog;
+//!
+//! let worklog = Worklog::builder()
+//!     .path("/home/jane/code")
+//!     .period(Period::Week)
+//!     .author("Jane Doe")
+//!     .collect()?;
+//! for project in &worklog.projects {
+//!     println!("{}: {} commits", project.project, project.total_commits());
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! The modules below expose the individual steps, e.g. [`discovery`] and
+//! [`git::collect_project_log`], and the analyses built on the result
+//! ([`stats`], [`authors`], [`bucket`], ...).
+
+pub mod activity;
+pub mod authors;
+pub mod bucket;
+pub mod budget;
+pub mod bundle;
+pub mod category;
+pub mod color;
+pub mod conventional;
+pub mod dco;
+mod dedupe;
 pub mod discovery;
+pub mod duration;
+pub mod export;
 pub mod git;
+pub mod groups;
+pub mod health;
+pub mod heatmap;
+pub mod holidays;
 pub mod model;
+pub mod netfs;
+pub mod offline;
+pub mod pairing;
 pub mod period;
+pub mod progress;
+mod refs;
+pub mod relative;
+mod revwalk;
+pub mod sort;
+pub mod stats;
+pub mod tags;
+pub mod totals;
+pub mod unpushed;
+pub mod wip;
+pub mod worklog;
+pub mod zone;
+
+pub use worklog::{Worklog, WorklogBuilder};
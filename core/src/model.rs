@@ -1,17 +1,79 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt;
 
-use chrono::{DateTime, Local};
-use serde::Serialize;
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default, Serialize)]
+use crate::duration;
+use crate::health::RepoHealth;
+use crate::zone::Time;
+
+/// An amount of working time in whole minutes, as logged in commit trailers
+/// or estimated from commit times. Serialized as a number of minutes and
+/// displayed compactly (`1h45m`).
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct WorkTime(u32);
+
+impl WorkTime {
+    pub const fn from_minutes(minutes: u32) -> Self {
+        Self(minutes)
+    }
+
+    pub const fn minutes(self) -> u32 {
+        self.0
+    }
+
+    /// Parse `1h30m`, `1.5h`, `1d` (8h), `1:30` or plain minutes.
+    pub fn parse(value: &str) -> Option<Self> {
+        duration::parse_minutes(value).map(Self)
+    }
+}
+
+impl std::ops::Add for WorkTime {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0))
+    }
+}
+
+impl std::ops::AddAssign for WorkTime {
+    fn add_assign(&mut self, other: Self) {
+        self.0 = self.0.saturating_add(other.0);
+    }
+}
+
+impl std::iter::Sum for WorkTime {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |a, b| a + b)
+    }
+}
+
+impl std::str::FromStr for WorkTime {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| format!("invalid duration: {s}"))
+    }
+}
+
+impl fmt::Display for WorkTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&duration::format_minutes(self.0))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DiffStat {
     pub files_changed: u32,
     pub insertions: u32,
     pub deletions: u32,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RepoOrigin {
     #[serde(rename = "github")]
     GitHub,
@@ -25,6 +87,20 @@ pub enum RepoOrigin {
     Custom(String),
 }
 
+impl RepoOrigin {
+    /// Identifier as serialized: `github`, `gitlab`, `bitbucket`,
+    /// `gitlab-self-hosted`, or the host of a custom origin.
+    pub fn key(&self) -> &str {
+        match self {
+            RepoOrigin::GitHub => "github",
+            RepoOrigin::GitLab => "gitlab",
+            RepoOrigin::Bitbucket => "bitbucket",
+            RepoOrigin::GitLabSelfHosted => "gitlab-self-hosted",
+            RepoOrigin::Custom(host) => host,
+        }
+    }
+}
+
 impl fmt::Display for RepoOrigin {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -37,22 +113,66 @@ impl fmt::Display for RepoOrigin {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Commit {
     pub hash: String,
     pub message: String,
+    pub author: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub author_email: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub commit_type: Option<String>,
     #[serde(rename = "timestamp")]
-    pub time: DateTime<Local>,
+    pub time: Time,
     pub relative_time: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub diff_stat: Option<DiffStat>,
+    /// Time logged through the configured time trailer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_spent: Option<WorkTime>,
+    /// Values of `Signed-off-by` trailers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signed_off_by: Vec<String>,
+    /// People credited through `Co-authored-by` trailers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub co_authors: Vec<CoAuthor>,
+    /// Not contained in any remote-tracking branch. Always `false` in
+    /// repositories without a remote.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unpushed: bool,
+    /// Whether the forge shows the commit as verified; `None` unless looked up.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verified: Option<bool>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoAuthor {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub email: String,
+}
+
+impl CoAuthor {
+    /// Parse a `Name <email>` trailer value; the email part is optional.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (name, email) = match value.split_once('<') {
+            Some((name, rest)) => (name.trim(), rest.trim_end_matches('>').trim()),
+            None => (value, ""),
+        };
+        if name.is_empty() && email.is_empty() {
+            return None;
+        }
+        Some(Self {
+            name: if name.is_empty() { email } else { name }.to_string(),
+            email: email.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct BranchLog {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -60,9 +180,71 @@ pub struct BranchLog {
     pub commits: Vec<Commit>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub diff_stat: Option<DiffStat>,
+    /// Open pull/merge request whose source is this branch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr: Option<PullRequest>,
+    /// First commit ever made on this branch that is not on the primary
+    /// branch; `None` for the primary branch itself.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started: Option<Time>,
+    /// Whether the branch has landed in the primary branch; `None` for the
+    /// primary branch itself or when the repository has none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merged: Option<bool>,
+    /// Sync status against the configured upstream branch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upstream: Option<Upstream>,
+    /// Commits dropped from `commits` to stay within a line budget.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub omitted: Option<Omitted>,
+    /// Linked worktree the branch is checked out in, if not the main one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<String>,
+    /// Name on the remote of a remote-tracking branch (`feature` for
+    /// `origin/feature`); only set with `--remote-branches`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_name: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+/// How far a branch and its upstream have diverged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Upstream {
+    pub name: String,
+    /// Commits on the branch that the upstream lacks.
+    pub ahead: u32,
+    /// Commits on the upstream that the branch lacks.
+    pub behind: u32,
+}
+
+/// Summary of commits left out of a branch listing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Omitted {
+    pub commits: usize,
+    /// Count per commit type, `other` for untyped commits.
+    pub types: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+    #[serde(default)]
+    pub state: PrState,
+}
+
+/// Whether a pull/merge request is still under review.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrState {
+    #[default]
+    Open,
+    Merged,
+    /// Closed without being merged.
+    Closed,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ProjectLog {
     pub project: String,
     pub path: String,
@@ -73,12 +255,46 @@ pub struct ProjectLog {
     pub branches: Vec<BranchLog>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub diff_stat: Option<DiffStat>,
+    /// Unique commits per calendar day of the period (multi-day periods only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity: Option<Vec<u32>>,
+    /// Semver tags created during the period, newest first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub releases: Option<Vec<Release>>,
+    /// Working-copy state, when requested with `--health`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<RepoHealth>,
+    /// Label the project is listed under, e.g. a client, from the
+    /// `[projects]` config.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Release {
+    pub tag: String,
+    #[serde(rename = "timestamp")]
+    pub time: Time,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
 }
 
 impl BranchLog {
     pub fn latest_activity(&self) -> Option<&str> {
         self.commits.first().map(|c| c.relative_time.as_str())
     }
+
+    /// Time between the first and the last commit of the period.
+    pub fn period_span(&self) -> Option<Duration> {
+        let first = self.commits.iter().map(|c| c.time).min()?;
+        let last = self.commits.iter().map(|c| c.time).max()?;
+        Some(last - first)
+    }
+
+    /// Sum of logged time, `None` if no commit carries a time trailer.
+    pub fn time_spent(&self) -> Option<WorkTime> {
+        sum_time_spent(self.commits.iter())
+    }
 }
 
 impl ProjectLog {
@@ -98,4 +314,27 @@ impl ProjectLog {
             .max_by_key(|c| c.time)
             .map(|c| c.relative_time.as_str())
     }
+
+    /// Sum of logged time across branches, counting shared commits once.
+    pub fn time_spent(&self) -> Option<WorkTime> {
+        let mut seen = HashSet::new();
+        sum_time_spent(
+            self.branches
+                .iter()
+                .flat_map(|b| &b.commits)
+                .filter(|c| seen.insert(&c.hash)),
+        )
+    }
+}
+
+/// Total logged time across projects, `None` if nothing was logged.
+pub fn total_time_spent(projects: &[ProjectLog]) -> Option<WorkTime> {
+    projects
+        .iter()
+        .filter_map(ProjectLog::time_spent)
+        .reduce(|a, b| a + b)
+}
+
+fn sum_time_spent<'a>(commits: impl Iterator<Item = &'a Commit>) -> Option<WorkTime> {
+    commits.filter_map(|c| c.time_spent).reduce(|a, b| a + b)
 }
@@ -0,0 +1,165 @@
+//! Heuristic detection of network file systems (NFS, SMB, sshfs, ...).
+
+use std::path::Path;
+
+/// File system type names (as reported by BSD `statfs` or Linux
+/// `/proc/self/mounts`) that are backed by a remote host.
+const NETWORK_FS_TYPES: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "smbfs",
+    "smb3",
+    "cifs",
+    "afpfs",
+    "webdav",
+    "davfs",
+    "9p",
+    "afs",
+    "ceph",
+    "glusterfs",
+    "fuse.sshfs",
+    "fuse.rclone",
+    "fuse.s3fs",
+    "fuse.gcsfuse",
+    "macfuse",
+    "osxfuse",
+];
+
+/// Whether `path` lives on a network file system. Returns `false` when the
+/// type cannot be determined.
+pub fn is_network_fs(path: &Path) -> bool {
+    fs_type(path).is_some_and(|t| is_network_type(&t))
+}
+
+fn is_network_type(fs_type: &str) -> bool {
+    NETWORK_FS_TYPES.contains(&fs_type)
+}
+
+#[cfg(target_os = "linux")]
+fn fs_type(path: &Path) -> Option<String> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    const NFS: u32 = 0x6969;
+    const SMB: u32 = 0x517B;
+    const SMB2: u32 = 0xFE53_4D42;
+    const CIFS: u32 = 0xFF53_4D42;
+    const CODA: u32 = 0x7375_7245;
+    const AFS: u32 = 0x5346_414F;
+    const V9FS: u32 = 0x0102_1997;
+    const CEPH: u32 = 0x00C3_6400;
+    const FUSE: u32 = 0x6573_5546;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat = std::mem::MaybeUninit::<libc::statfs>::uninit();
+    // SAFETY: `c_path` is a valid NUL-terminated string and `stat` points to
+    // writable memory of the right size; it is only read after success.
+    let stat = unsafe {
+        if libc::statfs(c_path.as_ptr(), stat.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stat.assume_init()
+    };
+
+    // `f_type` is signed on some targets; the magic numbers are 32 bit.
+    match stat.f_type as u32 {
+        NFS => Some("nfs".to_string()),
+        SMB => Some("smbfs".to_string()),
+        SMB2 => Some("smb3".to_string()),
+        CIFS => Some("cifs".to_string()),
+        CODA | AFS => Some("afs".to_string()),
+        V9FS => Some("9p".to_string()),
+        CEPH => Some("ceph".to_string()),
+        // FUSE hosts both local and remote file systems; the subtype is only
+        // visible in the mount table.
+        FUSE => {
+            let mounts = std::fs::read_to_string("/proc/self/mounts").ok()?;
+            mount_type_for(&mounts, path)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+fn fs_type(path: &Path) -> Option<String> {
+    use std::ffi::{CStr, CString};
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat = std::mem::MaybeUninit::<libc::statfs>::uninit();
+    // SAFETY: `c_path` is a valid NUL-terminated string and `stat` points to
+    // writable memory of the right size; it is only read after success.
+    let stat = unsafe {
+        if libc::statfs(c_path.as_ptr(), stat.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stat.assume_init()
+    };
+    // SAFETY: the kernel NUL-terminates `f_fstypename`.
+    let name = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) };
+    Some(name.to_string_lossy().to_string())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "freebsd")))]
+fn fs_type(_path: &Path) -> Option<String> {
+    None
+}
+
+/// File system type of the mount (longest matching mount point) containing
+/// `path`, from `/proc/self/mounts` content.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn mount_type_for(mounts: &str, path: &Path) -> Option<String> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _source = fields.next()?;
+            let mount_point = fields.next()?.replace("\\040", " ");
+            let fs_type = fields.next()?;
+            path.starts_with(&mount_point)
+                .then(|| (mount_point.len(), fs_type.to_string()))
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, fs_type)| fs_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_type_names() {
+        assert!(is_network_type("nfs"));
+        assert!(is_network_type("fuse.sshfs"));
+        assert!(is_network_type("smbfs"));
+        assert!(!is_network_type("ext4"));
+        assert!(!is_network_type("apfs"));
+        assert!(!is_network_type("fuse.gocryptfs"));
+    }
+
+    #[test]
+    fn mount_type_uses_longest_prefix() {
+        let mounts = "\
+/dev/sda1 / ext4 rw 0 0
+me@host:/srv /home/me/remote fuse.sshfs rw 0 0
+/dev/sdb1 /home/me/remote\\040disk ext4 rw 0 0
+";
+        assert_eq!(
+            mount_type_for(mounts, Path::new("/home/me/remote/repo")).as_deref(),
+            Some("fuse.sshfs")
+        );
+        assert_eq!(
+            mount_type_for(mounts, Path::new("/home/me/remote disk/repo")).as_deref(),
+            Some("ext4")
+        );
+        assert_eq!(
+            mount_type_for(mounts, Path::new("/home/me/src")).as_deref(),
+            Some("ext4")
+        );
+    }
+
+    #[test]
+    fn local_temp_dir_is_not_network() {
+        assert!(!is_network_fs(&std::env::temp_dir()));
+    }
+}
@@ -0,0 +1,26 @@
+//! Process-wide switch forbidding network access (`--offline`).
+//!
+//! Every code path that can reach a remote checks it, so an offline run makes
+//! no requests even when a network feature is enabled in the config file.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+pub fn enable() {
+    OFFLINE.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Fail if offline mode is on; `action` names what was attempted.
+pub fn ensure_online(action: &str) -> Result<()> {
+    if is_enabled() {
+        anyhow::bail!("{action} is not allowed in offline mode");
+    }
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use serde::Serialize;
+
+use crate::conventional::percent;
+use crate::model::ProjectLog;
+use crate::zone::Time;
+
+/// Someone credited as co-author on the reported commits.
+#[derive(Debug, Serialize)]
+pub struct Partner {
+    pub name: String,
+    pub commits: usize,
+    pub projects: Vec<String>,
+    #[serde(rename = "last_paired")]
+    pub last: Time,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PairingReport {
+    pub commits: usize,
+    /// Commits with at least one co-author.
+    pub paired: usize,
+    pub percent: f64,
+    pub partners: Vec<Partner>,
+}
+
+/// Aggregate `Co-authored-by` trailers per partner, most frequent first.
+/// Commits shared between branches count once per project, and a commit
+/// crediting its own author does not count as paired.
+pub fn pairing(projects: &[ProjectLog]) -> PairingReport {
+    let mut partners: BTreeMap<String, Partner> = BTreeMap::new();
+    let (mut commits, mut paired) = (0, 0);
+
+    for project in projects {
+        let mut seen = HashSet::new();
+        for commit in project.branches.iter().flat_map(|b| &b.commits) {
+            if !seen.insert(&commit.hash) {
+                continue;
+            }
+            commits += 1;
+            let names: BTreeSet<&str> = commit
+                .co_authors
+                .iter()
+                .map(|c| c.name.as_str())
+                .filter(|name| !name.eq_ignore_ascii_case(&commit.author))
+                .collect();
+            if names.is_empty() {
+                continue;
+            }
+            paired += 1;
+            for name in names {
+                let partner = partners.entry(name.to_string()).or_insert_with(|| Partner {
+                    name: name.to_string(),
+                    commits: 0,
+                    projects: Vec::new(),
+                    last: commit.time,
+                });
+                partner.commits += 1;
+                partner.last = partner.last.max(commit.time);
+                if !partner.projects.contains(&project.project) {
+                    partner.projects.push(project.project.clone());
+                }
+            }
+        }
+    }
+
+    let mut partners: Vec<Partner> = partners.into_values().collect();
+    for partner in &mut partners {
+        partner.projects.sort_by_key(|p| p.to_lowercase());
+    }
+    partners.sort_by_key(|p| std::cmp::Reverse(p.commits));
+
+    PairingReport {
+        commits,
+        paired,
+        percent: percent(paired, commits),
+        partners,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BranchLog, CoAuthor, Commit};
+    use chrono::Local;
+
+    fn commit(hash: &str, co_authors: &[&str]) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            message: format!("commit {hash}"),
+            author: "Jane Doe".to_string(),
+            time: Local::now().fixed_offset(),
+            co_authors: co_authors
+                .iter()
+                .filter_map(|c| CoAuthor::parse(c))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn project(name: &str, branches: Vec<Vec<Commit>>) -> ProjectLog {
+        ProjectLog {
+            project: name.to_string(),
+            path: format!("/src/{name}"),
+            branches: branches
+                .into_iter()
+                .map(|commits| BranchLog {
+                    name: "main".to_string(),
+                    commits,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn partners_counted_per_commit_and_project() {
+        let projects = vec![
+            project(
+                "web",
+                vec![
+                    vec![commit("a", &["Max <max@example.com>"]), commit("b", &[])],
+                    vec![commit("a", &["Max <max@example.com>"])],
+                ],
+            ),
+            project(
+                "api",
+                vec![vec![
+                    commit("c", &["Max <max@example.com>", "Ann <ann@example.com>"]),
+                    commit("d", &["Jane Doe <jane@example.com>"]),
+                ]],
+            ),
+        ];
+        let report = pairing(&projects);
+        assert_eq!((report.commits, report.paired), (4, 2));
+        assert_eq!(report.percent, 50.0);
+
+        let summary: Vec<(&str, usize)> = report
+            .partners
+            .iter()
+            .map(|p| (p.name.as_str(), p.commits))
+            .collect();
+        assert_eq!(summary, [("Max", 2), ("Ann", 1)]);
+        assert_eq!(report.partners[0].projects, ["api", "web"]);
+    }
+
+    #[test]
+    fn co_author_without_email() {
+        let co = CoAuthor::parse("  bob ").expect("parsed");
+        assert_eq!((co.name.as_str(), co.email.as_str()), ("bob", ""));
+        assert!(CoAuthor::parse(" ").is_none());
+    }
+}
@@ -1,6 +1,8 @@
-use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime};
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
 use std::str::FromStr;
 
+use crate::zone::{Time, Zone};
+
 #[derive(Debug, Clone)]
 pub enum Period {
     Today,
@@ -8,12 +10,25 @@ pub enum Period {
     Hours(u32),
     Days(u32),
     Week,
+    /// Previous Monday through Sunday.
+    LastWeek,
+    Month,
+    /// The whole previous calendar month.
+    LastMonth,
+    /// Current calendar quarter (Jan, Apr, Jul, Oct).
+    Quarter,
+    /// Two inclusive dates, written `2024-05-01..2024-05-15`.
+    Range(NaiveDate, NaiveDate),
+    /// ISO 8601 calendar week, written `cw21` (current year) or `2024-W21`.
+    CalendarWeek(Option<i32>, u32),
 }
 
 #[derive(Debug)]
 pub struct TimeRange {
-    pub since: DateTime<Local>,
-    pub until: Option<DateTime<Local>>,
+    pub since: Time,
+    pub until: Option<Time>,
+    /// The zone days are counted in, for bounds and commit times alike.
+    pub zone: Zone,
 }
 
 impl FromStr for Period {
@@ -24,8 +39,50 @@ impl FromStr for Period {
             "today" => Ok(Period::Today),
             "yesterday" => Ok(Period::Yesterday),
             "week" => Ok(Period::Week),
+            "last-week" => Ok(Period::LastWeek),
+            "month" => Ok(Period::Month),
+            "last-month" => Ok(Period::LastMonth),
+            "quarter" => Ok(Period::Quarter),
             other => {
-                if let Some(h) = other.strip_suffix('h') {
+                if let Some((since, until)) = other.split_once("..") {
+                    let date = |s: &str| {
+                        s.parse::<NaiveDate>()
+                            .map_err(|_| format!("Invalid date in range: {other}"))
+                    };
+                    let (since, until) = (date(since)?, date(until)?);
+                    if since > until {
+                        return Err(format!(
+                            "Range start ({since}) must be on or before its end ({until})"
+                        ));
+                    }
+                    Ok(Period::Range(since, until))
+                } else if let Some(week) = other
+                    .strip_prefix("cw")
+                    .or_else(|| other.strip_prefix("CW"))
+                {
+                    let year = Zone::Local.now().date_naive().iso_week().year();
+                    week.parse::<u32>()
+                        .ok()
+                        .filter(|w| NaiveDate::from_isoywd_opt(year, *w, Weekday::Mon).is_some())
+                        .map(|w| Period::CalendarWeek(None, w))
+                        .ok_or_else(|| {
+                            format!(
+                                "Invalid calendar week: {other} ({year} has {} ISO weeks)",
+                                iso_weeks(year)
+                            )
+                        })
+                } else if let Some((year, week)) =
+                    other.split_once("-W").or_else(|| other.split_once("-w"))
+                {
+                    match (year.parse::<i32>(), week.parse::<u32>()) {
+                        (Ok(y), Ok(w))
+                            if NaiveDate::from_isoywd_opt(y, w, Weekday::Mon).is_some() =>
+                        {
+                            Ok(Period::CalendarWeek(Some(y), w))
+                        }
+                        _ => Err(format!("Invalid ISO week: {other}")),
+                    }
+                } else if let Some(h) = other.strip_suffix('h') {
                     h.parse::<u32>()
                         .map(Period::Hours)
                         .map_err(|_| format!("Invalid hours: {other}"))
@@ -35,7 +92,7 @@ impl FromStr for Period {
                         .map_err(|_| format!("Invalid days: {other}"))
                 } else {
                     Err(format!(
-                        "Unknown period: {other}. Use: today, yesterday, 24h, 3d, 7d, week"
+                        "Unknown period: {other}. Use: today, yesterday, 24h, 3d, 7d, week, last-week, month, last-month, quarter, cw21, 2024-W21, YYYY-MM-DD..YYYY-MM-DD"
                     ))
                 }
             }
@@ -44,93 +101,146 @@ impl FromStr for Period {
 }
 
 impl Period {
-    pub fn to_time_range(&self) -> TimeRange {
-        let now = Local::now();
-        let start_of_today = now
-            .date_naive()
-            .and_time(NaiveTime::MIN)
-            .and_local_timezone(Local)
-            .single()
-            .unwrap_or(now);
+    /// The range this period covers right now, with days counted in `zone`.
+    pub fn to_time_range(&self, zone: Zone) -> TimeRange {
+        self.range_at(zone.now(), zone)
+    }
 
+    /// The equivalent earlier range, for trends: the same part of the
+    /// previous day, week, month or quarter for calendar periods, and the
+    /// directly preceding span of equal length otherwise.
+    pub fn previous_range(&self, now: Time, zone: Zone) -> TimeRange {
+        let current = self.range_at(now, zone);
+        // Shifted on the zone's clocks, so that a day is a day even when
+        // daylight saving time starts or ends in between.
+        let back = |shift: &dyn Fn(NaiveDateTime) -> Option<NaiveDateTime>| {
+            let shift = |t: Time| {
+                shift(t.naive_local())
+                    .and_then(|n| zone.at(n.date(), n.time()))
+                    .unwrap_or(t)
+            };
+            TimeRange {
+                since: shift(current.since),
+                until: Some(shift(current.until.unwrap_or(now))),
+                zone,
+            }
+        };
+        let back_days = |days: i64| back(&|t| t.checked_sub_signed(Duration::days(days)));
+        let back_months = |months: u32| back(&|t| t.checked_sub_months(Months::new(months)));
         match self {
-            Period::Today => TimeRange {
-                since: start_of_today,
-                until: None,
-            },
-            Period::Yesterday => {
-                let yesterday_start = start_of_today - Duration::days(1);
-                TimeRange {
-                    since: yesterday_start,
-                    until: Some(start_of_today),
-                }
+            Period::Today | Period::Yesterday => back_days(1),
+            Period::Week | Period::LastWeek | Period::CalendarWeek(..) => back_days(7),
+            Period::Month | Period::LastMonth => back_months(1),
+            Period::Quarter => back_months(3),
+            Period::Hours(_) | Period::Days(_) | Period::Range(..) => current.preceding(now),
+        }
+    }
+
+    fn range_at(&self, now: Time, zone: Zone) -> TimeRange {
+        let now = zone.convert(now);
+        let midnight = |date: NaiveDate| zone.midnight(date).unwrap_or(now);
+        let today = now.date_naive();
+        let monday = today - Duration::days(i64::from(today.weekday().num_days_from_monday()));
+        let first_of_month = today.with_day(1).unwrap_or(today);
+        let range = |since: Time, until: Option<Time>| TimeRange { since, until, zone };
+
+        match self {
+            Period::Today => range(midnight(today), None),
+            Period::Yesterday => range(midnight(today - Duration::days(1)), Some(midnight(today))),
+            Period::Hours(h) => range(now - Duration::hours(i64::from(*h)), None),
+            Period::Days(d) => range(now - Duration::days(i64::from(*d)), None),
+            Period::Week => range(midnight(monday), None),
+            Period::LastWeek => range(
+                midnight(monday - Duration::weeks(1)),
+                Some(midnight(monday)),
+            ),
+            Period::Month => range(midnight(first_of_month), None),
+            Period::LastMonth => {
+                let previous = (first_of_month - Duration::days(1))
+                    .with_day(1)
+                    .unwrap_or(first_of_month);
+                range(midnight(previous), Some(midnight(first_of_month)))
             }
-            Period::Hours(h) => TimeRange {
-                since: now - Duration::hours(i64::from(*h)),
-                until: None,
-            },
-            Period::Days(d) => TimeRange {
-                since: now - Duration::days(i64::from(*d)),
-                until: None,
-            },
-            Period::Week => {
-                let days_since_monday = now.weekday().num_days_from_monday() as i64;
-                let monday = start_of_today - Duration::days(days_since_monday);
-                TimeRange {
-                    since: monday,
-                    until: None,
-                }
+            Period::Quarter => {
+                let first_month = (today.month0() / 3) * 3 + 1;
+                let start = first_of_month
+                    .with_month(first_month)
+                    .unwrap_or(first_of_month);
+                range(midnight(start), None)
+            }
+            Period::Range(since, until) => range(
+                midnight(*since),
+                Some(end_of_day(*until, zone).unwrap_or(now)),
+            ),
+            Period::CalendarWeek(year, week) => {
+                let year = year.unwrap_or_else(|| today.iso_week().year());
+                let start = NaiveDate::from_isoywd_opt(year, *week, Weekday::Mon).unwrap_or(monday);
+                range(midnight(start), Some(midnight(start + Duration::weeks(1))))
             }
         }
     }
 }
 
-fn end_of_day(date: NaiveDate) -> Result<DateTime<Local>, String> {
+/// Number of ISO weeks in `year`: 53 if December 28 falls in week 53.
+fn iso_weeks(year: i32) -> u32 {
+    NaiveDate::from_ymd_opt(year, 12, 28).map_or(52, |d| d.iso_week().week())
+}
+
+fn end_of_day(date: NaiveDate, zone: Zone) -> Result<Time, String> {
     let time = NaiveTime::from_hms_opt(23, 59, 59).unwrap_or(NaiveTime::MIN);
-    date.and_time(time)
-        .and_local_timezone(Local)
-        .single()
-        .ok_or_else(|| format!("Cannot convert {date} to local time"))
+    zone.at(date, time)
+        .ok_or_else(|| format!("Cannot convert {date} to time zone {zone}"))
 }
 
-fn start_of_day(date: NaiveDate) -> Result<DateTime<Local>, String> {
-    date.and_time(NaiveTime::MIN)
-        .and_local_timezone(Local)
-        .single()
-        .ok_or_else(|| format!("Cannot convert {date} to local time"))
+fn start_of_day(date: NaiveDate, zone: Zone) -> Result<Time, String> {
+    zone.midnight(date)
+        .ok_or_else(|| format!("Cannot convert {date} to time zone {zone}"))
 }
 
 impl TimeRange {
     /// Build a range from two explicit dates (both inclusive).
-    pub fn from_dates(since: NaiveDate, until: NaiveDate) -> Result<Self, String> {
+    pub fn from_dates(since: NaiveDate, until: NaiveDate, zone: Zone) -> Result<Self, String> {
         if since > until {
             return Err(format!(
                 "--since ({since}) must be on or before --until ({until})"
             ));
         }
         Ok(TimeRange {
-            since: start_of_day(since)?,
-            until: Some(end_of_day(until)?),
+            since: start_of_day(since, zone)?,
+            until: Some(end_of_day(until, zone)?),
+            zone,
         })
     }
 
     /// Build a range from a start date to now.
-    pub fn from_since_date(since: NaiveDate) -> Result<Self, String> {
+    pub fn from_since_date(since: NaiveDate, zone: Zone) -> Result<Self, String> {
         Ok(TimeRange {
-            since: start_of_day(since)?,
+            since: start_of_day(since, zone)?,
             until: None,
+            zone,
         })
     }
 
+    /// The range of equal length that ends where this one starts.
+    pub fn preceding(&self, now: Time) -> TimeRange {
+        let length = self.until.unwrap_or(now) - self.since;
+        TimeRange {
+            since: self.since - length,
+            until: Some(self.since),
+            zone: self.zone,
+        }
+    }
+
     /// Override the upper bound of an existing range.
     pub fn with_until_date(self, until: NaiveDate) -> Result<Self, String> {
-        let until_dt = end_of_day(until)?;
+        let until_dt = end_of_day(until, self.zone)?;
         if self.since >= until_dt {
             return Err(format!("--since must be before --until ({until})"));
         }
         Ok(TimeRange {
             since: self.since,
             until: Some(until_dt),
+            zone: self.zone,
         })
     }
 }
@@ -188,7 +298,7 @@ mod tests {
 
     #[test]
     fn today_range_starts_at_midnight() {
-        let range = Period::Today.to_time_range();
+        let range = Period::Today.to_time_range(Zone::Local);
         assert_eq!(range.since.time().hour(), 0);
         assert_eq!(range.since.time().minute(), 0);
         assert!(range.until.is_none());
@@ -196,7 +306,7 @@ mod tests {
 
     #[test]
     fn yesterday_range_has_both_bounds() {
-        let range = Period::Yesterday.to_time_range();
+        let range = Period::Yesterday.to_time_range(Zone::Local);
         assert!(range.until.is_some());
         let until = range.until.as_ref().unwrap_or(&range.since);
         assert!(range.since < *until);
@@ -204,22 +314,202 @@ mod tests {
 
     #[test]
     fn hours_range_is_in_past() {
-        let range = Period::Hours(24).to_time_range();
-        assert!(range.since < Local::now());
+        let range = Period::Hours(24).to_time_range(Zone::Local);
+        assert!(range.since < Zone::Local.now());
         assert!(range.until.is_none());
     }
 
     #[test]
     fn week_range_starts_on_monday() {
-        let range = Period::Week.to_time_range();
+        let range = Period::Week.to_time_range(Zone::Local);
         assert_eq!(range.since.weekday(), Weekday::Mon);
     }
 
+    fn at(y: i32, m: u32, d: u32) -> Time {
+        start_of_day(
+            NaiveDate::from_ymd_opt(y, m, d).expect("valid date"),
+            Zone::Local,
+        )
+        .expect("local time")
+            + Duration::hours(15)
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).expect("valid date")
+    }
+
+    #[test]
+    fn parse_calendar_periods() {
+        assert!(matches!(
+            Period::from_str("last-week"),
+            Ok(Period::LastWeek)
+        ));
+        assert!(matches!(Period::from_str("month"), Ok(Period::Month)));
+        assert!(matches!(
+            Period::from_str("last-month"),
+            Ok(Period::LastMonth)
+        ));
+        assert!(matches!(Period::from_str("quarter"), Ok(Period::Quarter)));
+    }
+
+    #[test]
+    fn parse_date_range() {
+        assert!(matches!(
+            Period::from_str("2024-05-01..2024-05-15"),
+            Ok(Period::Range(s, u)) if s == date(2024, 5, 1) && u == date(2024, 5, 15)
+        ));
+        assert!(Period::from_str("2024-05-15..2024-05-01").is_err());
+        assert!(Period::from_str("2024-05-01..soon").is_err());
+    }
+
+    #[test]
+    fn parse_calendar_weeks() {
+        assert!(matches!(
+            Period::from_str("cw21"),
+            Ok(Period::CalendarWeek(None, 21))
+        ));
+        assert!(matches!(
+            Period::from_str("2024-W21"),
+            Ok(Period::CalendarWeek(Some(2024), 21))
+        ));
+        assert!(Period::from_str("cw0").is_err());
+        assert!(Period::from_str("cw54").is_err());
+        // `cwNN` is checked against the current ISO year.
+        let year = Zone::Local.now().date_naive().iso_week().year();
+        assert_eq!(Period::from_str("cw53").is_ok(), iso_weeks(year) == 53);
+        assert_eq!(iso_weeks(2024), 52);
+        assert_eq!(iso_weeks(2026), 53);
+        // 2024 has 52 ISO weeks, 2026 has 53.
+        assert!(Period::from_str("2024-W53").is_err());
+        assert!(Period::from_str("2026-W53").is_ok());
+    }
+
+    #[test]
+    fn calendar_week_runs_monday_to_monday() {
+        let range = Period::CalendarWeek(Some(2024), 21).range_at(at(2026, 3, 5), Zone::Local);
+        assert_eq!(range.since.date_naive(), date(2024, 5, 20));
+        assert_eq!(
+            range.until.expect("bounded").date_naive(),
+            date(2024, 5, 27)
+        );
+
+        // Week 1 of 2026 starts in December 2025.
+        let range = Period::CalendarWeek(None, 1).range_at(at(2026, 3, 5), Zone::Local);
+        assert_eq!(range.since.date_naive(), date(2025, 12, 29));
+    }
+
+    #[test]
+    fn range_covers_both_dates() {
+        let range = Period::Range(date(2024, 5, 1), date(2024, 5, 15)).to_time_range(Zone::Local);
+        assert_eq!(range.since.date_naive(), date(2024, 5, 1));
+        let until = range.until.expect("bounded");
+        assert_eq!(until.date_naive(), date(2024, 5, 15));
+        assert_eq!(until.time().hour(), 23);
+    }
+
+    #[test]
+    fn last_week_is_previous_monday_to_monday() {
+        // Thursday 2026-03-05
+        let range = Period::LastWeek.range_at(at(2026, 3, 5), Zone::Local);
+        assert_eq!(range.since.date_naive(), date(2026, 2, 23));
+        assert_eq!(range.since.time().hour(), 0);
+        let until = range.until.expect("bounded");
+        assert_eq!(until.date_naive(), date(2026, 3, 2));
+    }
+
+    #[test]
+    fn month_ranges_follow_calendar_months() {
+        let range = Period::Month.range_at(at(2026, 3, 5), Zone::Local);
+        assert_eq!(range.since.date_naive(), date(2026, 3, 1));
+        assert!(range.until.is_none());
+
+        let range = Period::LastMonth.range_at(at(2026, 1, 15), Zone::Local);
+        assert_eq!(range.since.date_naive(), date(2025, 12, 1));
+        assert_eq!(range.until.expect("bounded").date_naive(), date(2026, 1, 1));
+    }
+
+    #[test]
+    fn quarter_starts_on_first_month_of_quarter() {
+        let range = Period::Quarter.range_at(at(2026, 8, 20), Zone::Local);
+        assert_eq!(range.since.date_naive(), date(2026, 7, 1));
+        let range = Period::Quarter.range_at(at(2026, 3, 31), Zone::Local);
+        assert_eq!(range.since.date_naive(), date(2026, 1, 1));
+    }
+
+    #[test]
+    fn days_start_at_midnight_of_the_zone() {
+        let zone: Zone = "Europe/Berlin".parse().expect("known zone");
+        // Thursday 23:30 UTC, Friday 00:30 in Berlin.
+        let now = Zone::Named(chrono_tz::UTC)
+            .at(
+                date(2026, 3, 5),
+                NaiveTime::from_hms_opt(23, 30, 0).expect("time"),
+            )
+            .expect("utc time");
+        let today = Period::Today.range_at(now, zone);
+        assert_eq!(today.since.to_rfc3339(), "2026-03-06T00:00:00+01:00");
+        let yesterday = Period::Yesterday.range_at(now, zone);
+        assert_eq!(yesterday.since.to_rfc3339(), "2026-03-05T00:00:00+01:00");
+        assert_eq!(yesterday.until, Some(today.since));
+
+        let utc = Period::Today.range_at(now, Zone::Named(chrono_tz::UTC));
+        assert_eq!(utc.since.to_rfc3339(), "2026-03-05T00:00:00+00:00");
+    }
+
+    #[test]
+    fn previous_day_keeps_midnight_across_daylight_saving_time() {
+        let zone: Zone = "Europe/Berlin".parse().expect("known zone");
+        // Clocks went forward on Sunday 2026-03-29.
+        let now = zone
+            .at(
+                date(2026, 3, 30),
+                NaiveTime::from_hms_opt(9, 0, 0).expect("time"),
+            )
+            .expect("local time");
+        let previous = Period::Today.previous_range(now, zone);
+        assert_eq!(previous.since.to_rfc3339(), "2026-03-29T00:00:00+01:00");
+        assert_eq!(
+            previous.until.expect("bounded").to_rfc3339(),
+            "2026-03-29T09:00:00+02:00"
+        );
+    }
+
+    #[test]
+    fn previous_week_covers_same_weekdays() {
+        // Thursday 2026-03-05 15:00
+        let now = at(2026, 3, 5);
+        let previous = Period::Week.previous_range(now, Zone::Local);
+        assert_eq!(previous.since.date_naive(), date(2026, 2, 23));
+        assert_eq!(previous.until, Some(now - Duration::weeks(1)));
+    }
+
+    #[test]
+    fn previous_month_shifts_calendar_months() {
+        let previous = Period::LastMonth.previous_range(at(2026, 3, 5), Zone::Local);
+        assert_eq!(previous.since.date_naive(), date(2026, 1, 1));
+        assert_eq!(
+            previous.until.expect("bounded").date_naive(),
+            date(2026, 2, 1)
+        );
+    }
+
+    #[test]
+    fn preceding_has_equal_length() {
+        let range =
+            TimeRange::from_dates(date(2026, 3, 1), date(2026, 3, 10), Zone::Local).expect("range");
+        let previous = range.preceding(Zone::Local.now());
+        assert_eq!(previous.until, Some(range.since));
+        assert_eq!(
+            previous.until.expect("bounded") - previous.since,
+            range.until.expect("bounded") - range.since
+        );
+    }
+
     #[test]
     fn from_dates_valid_range() {
         let since = NaiveDate::from_ymd_opt(2026, 3, 1).expect("valid date");
         let until = NaiveDate::from_ymd_opt(2026, 3, 10).expect("valid date");
-        let range = TimeRange::from_dates(since, until).expect("valid range");
+        let range = TimeRange::from_dates(since, until, Zone::Local).expect("valid range");
         assert_eq!(range.since.date_naive(), since);
         assert_eq!(range.since.time().hour(), 0);
         let until_dt = range.until.expect("should have until");
@@ -231,7 +521,7 @@ mod tests {
     #[test]
     fn from_dates_same_day_is_valid() {
         let date = NaiveDate::from_ymd_opt(2026, 3, 5).expect("valid date");
-        let range = TimeRange::from_dates(date, date).expect("same-day range");
+        let range = TimeRange::from_dates(date, date, Zone::Local).expect("same-day range");
         assert_eq!(range.since.date_naive(), date);
         let until_dt = range.until.expect("should have until");
         assert_eq!(until_dt.date_naive(), date);
@@ -239,24 +529,25 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::unwrap_used)]
     fn from_dates_inverted_errors() {
         let since = NaiveDate::from_ymd_opt(2026, 3, 10).expect("valid date");
         let until = NaiveDate::from_ymd_opt(2026, 3, 1).expect("valid date");
-        let err = TimeRange::from_dates(since, until).unwrap_err();
+        let err = TimeRange::from_dates(since, until, Zone::Local).unwrap_err();
         assert!(err.contains("must be on or before"));
     }
 
     #[test]
     fn from_since_date_open_ended() {
         let since = NaiveDate::from_ymd_opt(2026, 3, 1).expect("valid date");
-        let range = TimeRange::from_since_date(since).expect("valid range");
+        let range = TimeRange::from_since_date(since, Zone::Local).expect("valid range");
         assert_eq!(range.since.date_naive(), since);
         assert!(range.until.is_none());
     }
 
     #[test]
     fn with_until_date_overrides_end() {
-        let range = Period::Week.to_time_range();
+        let range = Period::Week.to_time_range(Zone::Local);
         let until = NaiveDate::from_ymd_opt(2030, 12, 31).expect("valid date");
         let capped = range.with_until_date(until).expect("valid range");
         let until_dt = capped.until.expect("should have until");
@@ -265,7 +556,7 @@ mod tests {
 
     #[test]
     fn with_until_date_before_since_errors() {
-        let range = Period::Today.to_time_range();
+        let range = Period::Today.to_time_range(Zone::Local);
         let until = NaiveDate::from_ymd_opt(2020, 1, 1).expect("valid date");
         assert!(range.with_until_date(until).is_err());
     }
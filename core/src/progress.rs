@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+/// Something that happened while collecting a report, so frontends can show
+/// accurate progress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgressEvent {
+    /// A repository or bundle was found below a scan root.
+    RepoDiscovered { path: PathBuf },
+    /// A repository was read; `commits` is 0 when none matched the period
+    /// and filters.
+    RepoScanned { path: PathBuf, commits: usize },
+    /// A repository could not be read.
+    RepoFailed { path: PathBuf, error: String },
+}
+
+/// Receiver of [`ProgressEvent`]s. Repositories are scanned in parallel, so
+/// events arrive from several threads.
+///
+/// Implemented for closures, for the sending half of a channel, and as a
+/// no-op for `()`.
+pub trait Progress: Sync {
+    fn event(&self, event: ProgressEvent);
+}
+
+impl Progress for () {
+    fn event(&self, _event: ProgressEvent) {}
+}
+
+impl<F: Fn(ProgressEvent) + Sync> Progress for F {
+    fn event(&self, event: ProgressEvent) {
+        self(event)
+    }
+}
+
+impl Progress for Sender<ProgressEvent> {
+    fn event(&self, event: ProgressEvent) {
+        // A receiver that went away is no longer interested.
+        let _ = self.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn channel_receives_events() {
+        let (tx, rx) = mpsc::channel();
+        let progress: &dyn Progress = &tx;
+        progress.event(ProgressEvent::RepoDiscovered {
+            path: PathBuf::from("/src/app"),
+        });
+        drop(tx);
+        assert_eq!(
+            rx.iter().collect::<Vec<_>>(),
+            vec![ProgressEvent::RepoDiscovered {
+                path: PathBuf::from("/src/app")
+            }]
+        );
+    }
+}
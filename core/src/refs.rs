@@ -0,0 +1,337 @@
+//! Branch, tag and remote metadata read through gitoxide from the
+//! repository a scan already has open, so reporting on a branch (merged,
+//! started, upstream, worktree, unpushed) does not cost a git subprocess.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use gix::refs::TargetRef;
+use gix::revision::walk::Sorting;
+use gix::traverse::commit::simple::CommitTimeOrder;
+use gix::ObjectId;
+
+use crate::model::{Release, Upstream};
+use crate::period::TimeRange;
+use crate::zone::{Time, Zone};
+
+/// Open `path` for in-process reads. Environment variables and global git
+/// config are ignored, like the `GIT_*` scrubbing of
+/// [`crate::git::command`], and repositories owned by other users are
+/// readable.
+pub(crate) fn open(path: &Path) -> Result<gix::Repository> {
+    gix::open_opts(path, gix::open::Options::isolated())
+        .with_context(|| format!("cannot open {}", path.display()))
+}
+
+/// Local branches by short name, sorted like `git branch` lists them.
+pub(crate) fn local_branches(repo: &gix::Repository) -> Result<Vec<String>> {
+    let references = repo.references()?;
+    let mut names = references
+        .local_branches()?
+        .map(|r| r.map(|r| r.name().shorten().to_string()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("cannot read branches: {e}"))?;
+    names.sort();
+    Ok(names)
+}
+
+/// Remote-tracking branches as `(ref, name on the remote)`, e.g.
+/// `("origin/feature", "feature")`. Symbolic refs such as `origin/HEAD`
+/// are left out.
+pub(crate) fn remote_branches(repo: &gix::Repository) -> Vec<(String, String)> {
+    let Ok(references) = repo.references() else {
+        return Vec::new();
+    };
+    let Ok(iter) = references.remote_branches() else {
+        return Vec::new();
+    };
+    iter.filter_map(Result::ok)
+        .filter(|r| !matches!(r.target(), TargetRef::Symbolic(_)))
+        .filter_map(|r| {
+            let short = r.name().shorten().to_string();
+            let name = short.split_once('/')?.1.to_string();
+            (!name.is_empty()).then_some((short, name))
+        })
+        .collect()
+}
+
+/// Whether `HEAD` points at a commit directly rather than at a branch.
+pub(crate) fn is_detached(repo: &gix::Repository) -> bool {
+    repo.head().is_ok_and(|head| head.is_detached())
+}
+
+/// The commit a branch of the report points to: a local branch, else a
+/// remote-tracking one such as `origin/feature`.
+pub(crate) fn branch_tip(repo: &gix::Repository, name: &str) -> Option<ObjectId> {
+    let mut reference = repo
+        .find_reference(&format!("refs/heads/{name}"))
+        .or_else(|_| repo.find_reference(&format!("refs/remotes/{name}")))
+        .ok()?;
+    Some(reference.peel_to_id().ok()?.detach())
+}
+
+fn tips(repo: &gix::Repository, remote: bool) -> Vec<ObjectId> {
+    let Ok(references) = repo.references() else {
+        return Vec::new();
+    };
+    let iter = if remote {
+        references.remote_branches()
+    } else {
+        references.local_branches()
+    };
+    let Ok(iter) = iter else {
+        return Vec::new();
+    };
+    iter.filter_map(Result::ok)
+        .filter_map(|mut r| r.peel_to_id().ok().map(|id| id.detach()))
+        .collect()
+}
+
+/// Local branch tips, which [`crate::revwalk`] hides for the detached
+/// `HEAD` like `git log HEAD --not --branches`.
+pub(crate) fn local_tips(repo: &gix::Repository) -> Vec<ObjectId> {
+    tips(repo, false)
+}
+
+/// How a branch relates to the primary branch.
+pub(crate) struct Divergence {
+    /// The branch tip is reachable from the primary branch.
+    pub merged: bool,
+    /// Author date of the oldest non-merge commit not on the primary
+    /// branch, as `git log --reverse --no-merges primary..branch` lists it.
+    pub started: Option<Time>,
+}
+
+/// One walk of `branch` with `primary` hidden; `None` when either cannot
+/// be read.
+pub(crate) fn divergence(
+    repo: &gix::Repository,
+    primary: ObjectId,
+    branch: ObjectId,
+    zone: Zone,
+) -> Option<Divergence> {
+    let walk = repo
+        .rev_walk([branch])
+        .sorting(Sorting::ByCommitTime(CommitTimeOrder::NewestFirst))
+        .with_hidden([primary])
+        .all()
+        .ok()?;
+    let mut merged = true;
+    let mut oldest = None;
+    for info in walk {
+        let info = info.ok()?;
+        merged = false;
+        if info.parent_ids.len() <= 1 {
+            oldest = Some(info.id);
+        }
+    }
+    let started = match oldest {
+        Some(id) => {
+            let commit = repo.find_commit(id).ok()?;
+            zone.timestamp(commit.author().ok()?.time().ok()?.seconds)
+        }
+        None => None,
+    };
+    Some(Divergence { merged, started })
+}
+
+/// Upstream branch of each local branch that has one configured, by short
+/// name, e.g. `origin/main`.
+pub(crate) fn upstream_names(repo: &gix::Repository, local: &[String]) -> HashMap<String, String> {
+    local
+        .iter()
+        .filter_map(|branch| {
+            let full: gix::refs::FullName = format!("refs/heads/{branch}").try_into().ok()?;
+            let upstream = repo
+                .branch_remote_tracking_ref_name(full.as_ref(), gix::remote::Direction::Fetch)?
+                .ok()?;
+            Some((branch.clone(), upstream.shorten().to_string()))
+        })
+        .collect()
+}
+
+/// Ahead/behind counts of `branch` against `upstream`; `None` when the
+/// upstream ref is gone.
+pub(crate) fn upstream_status(
+    repo: &gix::Repository,
+    branch: &str,
+    upstream: &str,
+) -> Option<Upstream> {
+    let local = branch_tip(repo, branch)?;
+    let mut remote = repo
+        .find_reference(&format!("refs/remotes/{upstream}"))
+        .or_else(|_| repo.find_reference(&format!("refs/heads/{upstream}")))
+        .ok()?;
+    let remote = remote.peel_to_id().ok()?.detach();
+    let count = |tip: ObjectId, hidden: ObjectId| -> Option<u32> {
+        let walk = repo.rev_walk([tip]).with_hidden([hidden]).all().ok()?;
+        let mut n = 0;
+        for info in walk {
+            info.ok()?;
+            n += 1;
+        }
+        Some(n)
+    };
+    Some(Upstream {
+        name: upstream.to_string(),
+        ahead: count(local, remote)?,
+        behind: count(remote, local)?,
+    })
+}
+
+/// Branches checked out in a linked worktree of `repo`, mapped to the
+/// worktree's path. Branches of the main working tree are left out.
+pub(crate) fn worktree_paths(repo: &gix::Repository, path: &Path) -> HashMap<String, String> {
+    let Ok(worktrees) = repo.worktrees() else {
+        return HashMap::new();
+    };
+    let main = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    worktrees
+        .iter()
+        .filter_map(|worktree| {
+            let head = std::fs::read_to_string(worktree.git_dir().join("HEAD")).ok()?;
+            let branch = head.trim().strip_prefix("ref: refs/heads/")?.to_string();
+            let base = worktree.base().ok()?;
+            let canonical = base.canonicalize().unwrap_or_else(|_| base.clone());
+            (canonical != main).then(|| (branch, base.to_string_lossy().into_owned()))
+        })
+        .collect()
+}
+
+/// Abbreviated hashes of commits in `range` reachable from a local branch
+/// but from no remote-tracking branch; `None` if the repository has no
+/// remote or is bare.
+pub(crate) fn unpushed_commits(
+    repo: &gix::Repository,
+    range: &TimeRange,
+) -> Option<HashSet<String>> {
+    if repo.is_bare() || repo.remote_names().is_empty() {
+        return None;
+    }
+    let since = range.since.timestamp();
+    let walk = repo
+        .rev_walk(local_tips(repo))
+        .sorting(Sorting::ByCommitTimeCutoff {
+            order: CommitTimeOrder::NewestFirst,
+            seconds: since,
+        })
+        .with_hidden(tips(repo, true))
+        .all()
+        .ok()?;
+    let mut hashes = HashSet::new();
+    for info in walk {
+        let info = info.ok()?;
+        if info.commit_time.is_some_and(|t| t >= since) {
+            hashes.insert(info.id().shorten_or_id().to_string());
+        }
+    }
+    Some(hashes)
+}
+
+/// Hashes on the first-parent history of `tip` in the period: the commits
+/// made on it rather than merged in from other branches.
+pub(crate) fn first_parent_commits(
+    repo: &gix::Repository,
+    tip: ObjectId,
+    range: &TimeRange,
+) -> HashSet<String> {
+    let since = range.since.timestamp();
+    let until = range.until.map(|u| u.timestamp());
+    let Ok(walk) = repo
+        .rev_walk([tip])
+        .first_parent_only()
+        .sorting(Sorting::ByCommitTimeCutoff {
+            order: CommitTimeOrder::NewestFirst,
+            seconds: since,
+        })
+        .all()
+    else {
+        return HashSet::new();
+    };
+    walk.filter_map(Result::ok)
+        .filter(|info| {
+            info.commit_time
+                .is_some_and(|t| t >= since && until.is_none_or(|u| t <= u))
+        })
+        .map(|info| info.id().shorten_or_id().to_string())
+        .collect()
+}
+
+/// Fetch URL of the `origin` remote, as `git remote get-url origin` prints
+/// it.
+pub(crate) fn origin_url(repo: &gix::Repository) -> Option<String> {
+    let remote = repo.find_remote("origin").ok()?;
+    let url = remote.url(gix::remote::Direction::Fetch)?;
+    let url = url.to_bstring().to_string();
+    (!url.is_empty()).then_some(url)
+}
+
+/// Tags whose name passes `is_release` created in `range`, newest first.
+/// Uses the tagger date for annotated tags and the commit date for
+/// lightweight ones.
+pub(crate) fn releases(
+    repo: &gix::Repository,
+    range: &TimeRange,
+    is_release: impl Fn(&str) -> bool,
+) -> Vec<Release> {
+    let Ok(references) = repo.references() else {
+        return Vec::new();
+    };
+    let Ok(tags) = references.tags() else {
+        return Vec::new();
+    };
+    let mut releases: Vec<Release> = tags
+        .filter_map(Result::ok)
+        .filter_map(|r| {
+            let tag = r.name().shorten().to_string();
+            if !is_release(&tag) {
+                return None;
+            }
+            let object = repo.find_object(r.target().try_id()?).ok()?;
+            let seconds = match object.kind {
+                gix::object::Kind::Tag => object.try_into_tag().ok()?.tagger().ok()??.seconds(),
+                gix::object::Kind::Commit => object.try_into_commit().ok()?.time().ok()?.seconds,
+                _ => return None,
+            };
+            let time = range.zone.timestamp(seconds)?;
+            let in_range = time >= range.since && range.until.is_none_or(|u| time < u);
+            in_range.then_some(Release {
+                tag,
+                time,
+                url: None,
+            })
+        })
+        .collect();
+    releases.sort_by_key(|r| std::cmp::Reverse(r.time));
+    releases
+}
+
+/// `HEAD` and every ref (branches, remote-tracking branches, tags) with
+/// what it points to. Equal output means a scan of the repository yields
+/// the same commits.
+pub(crate) fn state(repo: &gix::Repository) -> Option<String> {
+    let target = |target: TargetRef<'_>| match target {
+        TargetRef::Object(id) => id.to_string(),
+        TargetRef::Symbolic(name) => format!("ref: {}", name.as_bstr()),
+    };
+    let mut state = match repo.find_reference("HEAD") {
+        Ok(head) => format!("{} HEAD\n", target(head.target())),
+        Err(_) => String::new(),
+    };
+    let references = repo.references().ok()?;
+    for r in references.all().ok()? {
+        let r = r.ok()?;
+        state.push_str(&format!("{} {}\n", target(r.target()), r.name().as_bstr()));
+    }
+    Some(state)
+}
+
+/// Whether a bare `repo` has a `.mailmap` in its `HEAD` tree, which git
+/// reads there in place of the working tree's.
+pub(crate) fn bare_mailmap(repo: &gix::Repository) -> bool {
+    repo.is_bare()
+        && repo
+            .head_tree()
+            .is_ok_and(|tree| tree.find_entry(".mailmap").is_some())
+}
@@ -0,0 +1,109 @@
+use chrono::Duration;
+
+use crate::zone::Time;
+
+/// How commit times are shown relative to now (`5m ago`, `3h ago`, `2d ago`).
+/// Every renderer shows the `relative_time` produced here, so the options
+/// apply to all output formats alike.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RelativeTime {
+    /// Count whole weeks from seven days on: `2w ago` instead of `15d ago`.
+    pub weeks: bool,
+    /// Show commits from the previous calendar day as `yesterday 14:30`.
+    pub yesterday: bool,
+    /// Show the date (`2024-03-05`) for commits older than this many days.
+    pub absolute_after: Option<u32>,
+}
+
+impl RelativeTime {
+    pub fn format(&self, now: Time, then: Time) -> String {
+        let duration = now.signed_duration_since(then);
+        let mins = duration.num_minutes();
+
+        if let Some(days) = self.absolute_after {
+            if duration > Duration::days(i64::from(days)) {
+                return then.format("%Y-%m-%d").to_string();
+            }
+        }
+        if mins < 1 {
+            return "just now".to_string();
+        }
+        if mins < 60 {
+            return format!("{mins}m ago");
+        }
+        if self.yesterday && now.date_naive().pred_opt() == Some(then.date_naive()) {
+            return format!("yesterday {}", then.format("%H:%M"));
+        }
+        if mins < 1440 {
+            format!("{}h ago", duration.num_hours())
+        } else if self.weeks && duration.num_days() >= 7 {
+            format!("{}w ago", duration.num_weeks())
+        } else {
+            format!("{}d ago", duration.num_days())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Local, TimeZone};
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> Time {
+        Local
+            .with_ymd_and_hms(y, m, d, h, min, 0)
+            .single()
+            .expect("unambiguous local time")
+            .fixed_offset()
+    }
+
+    #[test]
+    fn default_matches_compact_units() {
+        let fmt = RelativeTime::default();
+        let now = at(2024, 3, 20, 12, 0);
+        assert_eq!(fmt.format(now, now), "just now");
+        assert_eq!(fmt.format(now, at(2024, 3, 20, 11, 55)), "5m ago");
+        assert_eq!(fmt.format(now, at(2024, 3, 20, 9, 0)), "3h ago");
+        assert_eq!(fmt.format(now, at(2024, 3, 19, 9, 0)), "1d ago");
+        assert_eq!(fmt.format(now, at(2024, 3, 5, 12, 0)), "15d ago");
+    }
+
+    #[test]
+    fn weeks_from_seven_days() {
+        let fmt = RelativeTime {
+            weeks: true,
+            ..RelativeTime::default()
+        };
+        let now = at(2024, 3, 20, 12, 0);
+        assert_eq!(fmt.format(now, at(2024, 3, 14, 12, 0)), "6d ago");
+        assert_eq!(fmt.format(now, at(2024, 3, 13, 12, 0)), "1w ago");
+        assert_eq!(fmt.format(now, at(2024, 3, 5, 12, 0)), "2w ago");
+    }
+
+    #[test]
+    fn yesterday_with_clock_time() {
+        let fmt = RelativeTime {
+            yesterday: true,
+            ..RelativeTime::default()
+        };
+        let now = at(2024, 3, 20, 9, 0);
+        assert_eq!(fmt.format(now, at(2024, 3, 19, 14, 30)), "yesterday 14:30");
+        assert_eq!(fmt.format(now, at(2024, 3, 20, 1, 0)), "8h ago");
+        assert_eq!(fmt.format(now, at(2024, 3, 18, 14, 30)), "1d ago");
+        // Within the hour stays relative even across midnight.
+        let now = at(2024, 3, 20, 0, 10);
+        assert_eq!(fmt.format(now, at(2024, 3, 19, 23, 50)), "20m ago");
+    }
+
+    #[test]
+    fn absolute_date_after_cutoff() {
+        let fmt = RelativeTime {
+            weeks: true,
+            absolute_after: Some(30),
+            ..RelativeTime::default()
+        };
+        let now = at(2024, 3, 20, 12, 0);
+        assert_eq!(fmt.format(now, at(2024, 2, 25, 12, 0)), "3w ago");
+        assert_eq!(fmt.format(now, at(2024, 2, 5, 8, 0)), "2024-02-05");
+    }
+}
@@ -0,0 +1,442 @@
+//! Commit reading through gitoxide, so a scan does not spawn one `git log`
+//! per branch. Anything this walk cannot reproduce exactly (diff stats,
+//! patterns outside the common regex syntax, missing objects, a `.mailmap`)
+//! makes it return `None`, and the caller falls back to `git log`.
+
+use regex::Regex;
+
+use crate::git::{self, LogOptions, DETACHED_BRANCH};
+use crate::model::{CoAuthor, Commit, WorkTime};
+use crate::period::TimeRange;
+use crate::refs;
+use crate::zone::{Time, Zone};
+
+/// A repository read in-process, with the filters of a [`LogOptions`].
+pub(crate) struct Reader {
+    repo: gix::Repository,
+    authors: AuthorFilter,
+    grep: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl Reader {
+    /// `None` when a filter of `opts` has no exact equivalent here, or the
+    /// repository has a `.mailmap` that `git log` would apply to author
+    /// names and the author filter.
+    pub(crate) fn new(repo: gix::Repository, opts: &LogOptions) -> Option<Self> {
+        let mailmap = repo
+            .workdir()
+            .is_some_and(|dir| dir.join(".mailmap").is_file())
+            || refs::bare_mailmap(&repo);
+        if opts.with_stat || mailmap {
+            return None;
+        }
+        let authors = AuthorFilter::new(opts.authors)?;
+        let message_patterns = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|p| regex::RegexBuilder::new(p).multi_line(true).build())
+                .collect::<Result<Vec<_>, _>>()
+                .ok()
+        };
+        let grep = message_patterns(opts.grep)?;
+        let exclude = message_patterns(opts.exclude_grep)?;
+        Some(Reader {
+            repo,
+            authors,
+            grep,
+            exclude,
+        })
+    }
+
+    /// Commits of `branch` within `range`, newest first, as `git log <branch>
+    /// --no-merges --after --before --author --grep` lists them; merges are
+    /// kept with `opts.include_merges`. [`DETACHED_BRANCH`] stands for `HEAD --not --branches`.
+    pub(crate) fn log(
+        &self,
+        branch: &str,
+        range: &TimeRange,
+        opts: &LogOptions,
+    ) -> Option<Vec<Commit>> {
+        let (tip, hidden) = if branch == DETACHED_BRANCH {
+            (
+                self.repo.head_id().ok()?.detach(),
+                refs::local_tips(&self.repo),
+            )
+        } else {
+            (refs::branch_tip(&self.repo, branch)?, Vec::new())
+        };
+        let since = range.since.timestamp();
+        let until = range.until.map(|u| u.timestamp());
+        let walk = self
+            .repo
+            .rev_walk([tip])
+            .sorting(gix::revision::walk::Sorting::ByCommitTimeCutoff {
+                order: gix::traverse::commit::simple::CommitTimeOrder::NewestFirst,
+                seconds: since,
+            })
+            .with_hidden(hidden)
+            .all()
+            .ok()?;
+
+        let now = range.zone.now();
+        let mut commits = Vec::new();
+        for info in walk {
+            let info = info.ok()?;
+            if info.parent_ids.len() > 1 && !opts.include_merges {
+                continue;
+            }
+            let commit = info.object().ok()?;
+            let committed = commit.time().ok()?.seconds;
+            if committed < since || until.is_some_and(|u| committed > u) {
+                continue;
+            }
+            if let Some(c) = self.to_commit(&commit, now, range.zone, opts)? {
+                commits.push(c);
+            }
+        }
+        Some(commits)
+    }
+
+    /// `Some(None)` for a commit dropped by the author or message filters,
+    /// `None` when the commit cannot be decoded.
+    fn to_commit(
+        &self,
+        commit: &gix::Commit<'_>,
+        now: Time,
+        zone: Zone,
+        opts: &LogOptions,
+    ) -> Option<Option<Commit>> {
+        let author = commit.author().ok()?;
+        let (name, email) = (author.name.to_string(), author.email.to_string());
+        let raw = commit.message_raw().ok()?.to_string();
+        if !self.grep.is_empty() && !self.grep.iter().any(|p| p.is_match(&raw)) {
+            return Some(None);
+        }
+        if self.exclude.iter().any(|p| p.is_match(&raw)) {
+            return Some(None);
+        }
+        let time = zone.timestamp(author.time().ok()?.seconds)?;
+        let subject = commit.message().ok()?.summary().to_string();
+        let block = trailer_block(&raw);
+        let trailers = |key: &str| -> Vec<String> {
+            block
+                .iter()
+                .filter(|(token, _)| token.eq_ignore_ascii_case(key))
+                .map(|(_, value)| value.to_string())
+                .filter(|v| !v.is_empty())
+                .collect()
+        };
+        let co_authors: Vec<CoAuthor> = trailers("Co-authored-by")
+            .iter()
+            .filter_map(|v| CoAuthor::parse(v))
+            .collect();
+        if !self.authors.matches(&name, &email, &co_authors) {
+            return Some(None);
+        }
+
+        Some(Some(Commit {
+            hash: commit.id().shorten_or_id().to_string(),
+            commit_type: git::detect_commit_type(&subject),
+            message: subject,
+            author: name,
+            author_email: email,
+            relative_time: opts.relative.format(now, time),
+            time,
+            url: None,
+            diff_stat: None,
+            time_spent: opts
+                .time_trailer
+                .filter(|k| git::check_trailer_key(k).is_ok())
+                .and_then(|key| {
+                    trailers(key)
+                        .iter()
+                        .filter_map(|v| WorkTime::parse(v))
+                        .reduce(|a, b| a + b)
+                }),
+            signed_off_by: trailers("Signed-off-by"),
+            co_authors,
+            unpushed: false,
+            verified: None,
+        }))
+    }
+}
+
+/// `(token, value)` pairs of the trailer block, found the way `git
+/// interpret-trailers` does: the last paragraph after the subject, when it
+/// consists only of trailers, or of at least 25% trailers including a
+/// `Signed-off-by` that git itself adds.
+fn trailer_block(message: &str) -> Vec<(&str, &str)> {
+    let mut paragraphs = message
+        .split("\n\n")
+        .map(|p| p.trim_matches('\n'))
+        .filter(|p| !p.is_empty());
+    paragraphs.next();
+    let Some(last) = paragraphs.last() else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = last
+        .lines()
+        .filter(|l| !l.starts_with(char::is_whitespace))
+        .collect();
+    let trailers: Vec<(&str, &str)> = lines
+        .iter()
+        .filter_map(|l| l.split_once(':'))
+        .filter(|(token, _)| {
+            !token.is_empty() && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+        .map(|(token, value)| (token, value.trim()))
+        .collect();
+    let git_generated = trailers
+        .iter()
+        .any(|(token, _)| token.eq_ignore_ascii_case("Signed-off-by"));
+    let valid =
+        trailers.len() == lines.len() || (git_generated && trailers.len() * 4 >= lines.len());
+    if valid {
+        trailers
+    } else {
+        Vec::new()
+    }
+}
+
+/// The author filter of [`LogOptions::authors`], matched against `Name
+/// <email>` of the author and of each `Co-authored-by` trailer, so paired
+/// commits count for everyone who worked on them.
+pub(crate) struct AuthorFilter(Vec<Regex>);
+
+impl AuthorFilter {
+    /// `None` when a pattern has no exact equivalent in the `regex` crate.
+    /// Patterns are extended regexes, like the message patterns.
+    pub(crate) fn new(patterns: &[String]) -> Option<Self> {
+        patterns
+            .iter()
+            .map(|p| Regex::new(p))
+            .collect::<Result<_, _>>()
+            .ok()
+            .map(Self)
+    }
+
+    /// Whether the commit is kept; every commit is without patterns.
+    pub(crate) fn matches(&self, name: &str, email: &str, co_authors: &[CoAuthor]) -> bool {
+        if self.0.is_empty() {
+            return true;
+        }
+        std::iter::once(format!("{name} <{email}>"))
+            .chain(
+                co_authors
+                    .iter()
+                    .map(|c| format!("{} <{}>", c.name, c.email)),
+            )
+            .any(|ident| self.0.iter().any(|p| p.is_match(&ident)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Local};
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    /// A scratch repository, removed on drop.
+    struct Scratch(PathBuf);
+
+    impl Scratch {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("devcap-{name}-{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).expect("create dir");
+            let scratch = Scratch(dir);
+            scratch.git(&["init", "-q", "-b", "main"], "");
+            scratch
+        }
+
+        fn git(&self, args: &[&str], date: &str) {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(&self.0)
+                .args([
+                    "-c",
+                    "user.name=Jane Doe",
+                    "-c",
+                    "user.email=jane@example.com",
+                ])
+                .args(args)
+                .env("GIT_AUTHOR_DATE", date)
+                .env("GIT_COMMITTER_DATE", date)
+                .status()
+                .expect("run git");
+            assert!(status.success(), "git {args:?} failed");
+        }
+
+        fn commit(&self, message: &str, date: &str) {
+            self.git(&["commit", "-q", "--allow-empty", "-m", message], date);
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn walk_matches_git_log() {
+        let repo = Scratch::new("revwalk");
+        let now = Local::now().fixed_offset();
+        let day = |n: i64| (now - Duration::days(n)).to_rfc3339();
+        repo.commit("chore: too old", &day(10));
+        repo.commit(
+            "feat: add export\n\nTime-Spent: 1h\nSigned-off-by: Jane Doe <jane@example.com>",
+            &day(3),
+        );
+        repo.git(&["checkout", "-q", "-b", "feature"], "");
+        repo.commit(
+            "fix(api): handle empty list\n\nCo-authored-by: Max <max@example.com>",
+            &day(2),
+        );
+        repo.git(&["checkout", "-q", "main"], "");
+        repo.commit("docs: readme", &day(2));
+        repo.git(
+            &["merge", "-q", "--no-ff", "-m", "Merge feature", "feature"],
+            &day(1),
+        );
+        repo.commit("wip (draft)", &day(1));
+        repo.git(&["checkout", "-q", "--detach"], "");
+        repo.commit("test: detached", &day(1));
+
+        let range = TimeRange {
+            since: now - Duration::days(5),
+            until: None,
+            zone: Zone::Local,
+        };
+        let exclude = vec!["^wip".to_string()];
+        let author_filters: [&[&str]; 4] = [&[], &["Jane Doe"], &["Max"], &["Jane", "Max"]];
+        let grep_filters: [&[&str]; 2] = [&[], &["export", "^(wip|test)"]];
+        for (author, grep, include_merges) in author_filters
+            .into_iter()
+            .flat_map(|a| grep_filters.map(|g| (a, g)))
+            .flat_map(|(a, g)| [(a, g, false), (a, g, true)])
+        {
+            let authors: Vec<String> = author.iter().map(|a| a.to_string()).collect();
+            let grep: Vec<String> = grep.iter().map(|g| g.to_string()).collect();
+            let opts = LogOptions {
+                authors: &authors,
+                time_trailer: Some("Time-Spent"),
+                grep: &grep,
+                exclude_grep: &exclude,
+                include_merges,
+                ..LogOptions::default()
+            };
+            let reader = Reader::new(refs::open(&repo.0).expect("opens"), &opts).expect("opens");
+            for branch in ["main", "feature", DETACHED_BRANCH] {
+                let walked = reader.log(branch, &range, &opts).expect("walks");
+                let revs: &[&str] = if branch == DETACHED_BRANCH {
+                    &["HEAD", "--not", "--branches"]
+                } else {
+                    &[branch]
+                };
+                let (logged, _, _) =
+                    git::log_branch(&repo.0, revs, &range, &opts).expect("git log");
+                let summary = |commits: &[Commit]| -> Vec<String> {
+                    commits
+                        .iter()
+                        .map(|c| {
+                            format!(
+                                "{} {} {:?} {} {} {:?} {:?} {:?}",
+                                c.hash,
+                                c.message,
+                                c.commit_type,
+                                c.author,
+                                c.time,
+                                c.time_spent,
+                                c.signed_off_by,
+                                c.co_authors.len()
+                            )
+                        })
+                        .collect()
+                };
+                assert_eq!(
+                    summary(&walked),
+                    summary(&logged),
+                    "{branch} {author:?} {grep:?} merges: {include_merges}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn patterns_are_extended_regexes_in_both_backends() {
+        let repo = Scratch::new("dialect");
+        let now = Local::now().fixed_offset();
+        let date = (now - Duration::hours(1)).to_rfc3339();
+        let by = |author: &str, message: &str| {
+            let author = format!("--author={author}");
+            repo.git(
+                &["commit", "-q", "--allow-empty", &author, "-m", message],
+                &date,
+            );
+        };
+        by("Jane Doe <jane@example.com>", "feat(api): add export");
+        by("Max Power <1234+max@example.com>", "fix: a+b overflow");
+        by("Jane Doe (Work) <jane@work.example>", "docs: readme");
+
+        let range = TimeRange {
+            since: now - Duration::days(1),
+            until: None,
+            zone: Zone::Local,
+        };
+        let cases: [(&[&str], &[&str]); 6] = [
+            (&["Jane|Max"], &[]),
+            (&["Jane Doe \\(Work\\)"], &[]),
+            (&[&git::literal_pattern("1234+max@example.com")], &[]),
+            (&["^(Max|Jane Doe) <"], &["^(feat|fix)"]),
+            (&[], &["feat\\(api\\)"]),
+            (&[], &["a\\+b", "re+adme"]),
+        ];
+        for (authors, grep) in cases {
+            let authors: Vec<String> = authors.iter().map(|a| a.to_string()).collect();
+            let grep: Vec<String> = grep.iter().map(|g| g.to_string()).collect();
+            let opts = LogOptions {
+                authors: &authors,
+                grep: &grep,
+                ..LogOptions::default()
+            };
+            let walked: Vec<String> = Reader::new(refs::open(&repo.0).expect("opens"), &opts)
+                .expect("opens")
+                .log("main", &range, &opts)
+                .expect("walks")
+                .into_iter()
+                .map(|c| c.message)
+                .collect();
+            // What the `git log` fallback runs when the `regex` crate cannot
+            // take an author pattern.
+            let output = git::command()
+                .arg("-C")
+                .arg(&repo.0)
+                .args(["log", "main", "--extended-regexp", "--format=%s"])
+                .args(authors.iter().map(|a| format!("--author={a}")))
+                .args(grep.iter().map(|g| format!("--grep={g}")))
+                .output()
+                .expect("git log");
+            let logged: Vec<String> = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(str::to_string)
+                .collect();
+            assert!(!walked.is_empty(), "{authors:?} {grep:?} matches nothing");
+            assert_eq!(walked, logged, "{authors:?} {grep:?}");
+        }
+    }
+
+    #[test]
+    fn trailers_come_from_the_last_paragraph() {
+        let message =
+            "feat: x\n\nBody text: not a trailer.\n\nTime-Spent: 1h\nSigned-off-by: Jane <j@x>\n";
+        assert_eq!(
+            trailer_block(message),
+            [("Time-Spent", "1h"), ("Signed-off-by", "Jane <j@x>")]
+        );
+        assert!(trailer_block("Fixes: only a subject").is_empty());
+        assert!(trailer_block("feat: x\n\nSee: the docs\nfor details").is_empty());
+    }
+}
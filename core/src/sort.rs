@@ -0,0 +1,276 @@
+use std::cmp::Reverse;
+use std::str::FromStr;
+
+use crate::model::ProjectLog;
+use crate::zone::Time;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    /// Latest commit; also written `activity`.
+    Time,
+    Commits,
+    Name,
+    Lines,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Order of the projects in a report, written `<field>` or
+/// `<field>:<direction>`, e.g. `commits` or `name:desc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortSpec {
+    pub field: SortField,
+    pub direction: SortDirection,
+}
+
+impl Default for SortSpec {
+    fn default() -> Self {
+        Self {
+            field: SortField::Time,
+            direction: SortDirection::Desc,
+        }
+    }
+}
+
+impl FromStr for SortSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (field_str, dir_str) = match s.split_once(':') {
+            Some((f, d)) => (f, Some(d)),
+            None => (s, None),
+        };
+
+        let field = match field_str {
+            "time" | "activity" => SortField::Time,
+            "commits" => SortField::Commits,
+            "name" => SortField::Name,
+            "lines" => SortField::Lines,
+            _ => {
+                return Err(format!(
+                "unknown sort field: {field_str} (expected: time, activity, commits, name, lines)"
+            ))
+            }
+        };
+
+        let default_dir = match field {
+            SortField::Name => SortDirection::Asc,
+            _ => SortDirection::Desc,
+        };
+
+        let direction = match dir_str {
+            Some("asc") => SortDirection::Asc,
+            Some("desc") => SortDirection::Desc,
+            None => default_dir,
+            Some(d) => return Err(format!("unknown sort direction: {d} (expected: asc, desc)")),
+        };
+
+        Ok(Self { field, direction })
+    }
+}
+
+/// What projects are compared by, computed once per project.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum Key {
+    Time(Option<Time>),
+    Count(u64),
+    Name(String),
+}
+
+/// Sort `projects` by `spec`; projects that tie are listed by name.
+pub fn sort_projects(projects: &mut [ProjectLog], spec: SortSpec) {
+    let key = |p: &ProjectLog| match spec.field {
+        SortField::Time => Key::Time(
+            p.branches
+                .iter()
+                .flat_map(|br| br.commits.first())
+                .map(|c| c.time)
+                .max(),
+        ),
+        SortField::Commits => Key::Count(p.total_commits() as u64),
+        SortField::Name => Key::Name(p.project.to_lowercase()),
+        SortField::Lines => Key::Count(
+            p.branches
+                .iter()
+                .flat_map(|br| &br.commits)
+                .filter_map(|c| c.diff_stat.as_ref())
+                .map(|s| u64::from(s.insertions) + u64::from(s.deletions))
+                .sum(),
+        ),
+    };
+    match spec.direction {
+        SortDirection::Asc => {
+            projects.sort_by_cached_key(|p| (key(p), p.project.to_lowercase()));
+        }
+        SortDirection::Desc => {
+            projects.sort_by_cached_key(|p| (Reverse(key(p)), p.project.to_lowercase()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BranchLog, Commit, DiffStat};
+    use chrono::DateTime;
+
+    #[test]
+    fn parse_sort_field_only_defaults_direction() {
+        let spec: SortSpec = "time".parse().expect("parse time");
+        assert_eq!(spec.field, SortField::Time);
+        assert_eq!(spec.direction, SortDirection::Desc);
+
+        let spec: SortSpec = "activity".parse().expect("parse activity");
+        assert_eq!(spec.field, SortField::Time);
+        assert_eq!(spec.direction, SortDirection::Desc);
+
+        let spec: SortSpec = "commits".parse().expect("parse commits");
+        assert_eq!(spec.field, SortField::Commits);
+        assert_eq!(spec.direction, SortDirection::Desc);
+
+        let spec: SortSpec = "name".parse().expect("parse name");
+        assert_eq!(spec.field, SortField::Name);
+        assert_eq!(spec.direction, SortDirection::Asc);
+
+        let spec: SortSpec = "lines".parse().expect("parse lines");
+        assert_eq!(spec.field, SortField::Lines);
+        assert_eq!(spec.direction, SortDirection::Desc);
+    }
+
+    #[test]
+    fn parse_sort_with_explicit_direction() {
+        let spec: SortSpec = "time:asc".parse().expect("parse time:asc");
+        assert_eq!(spec.field, SortField::Time);
+        assert_eq!(spec.direction, SortDirection::Asc);
+
+        let spec: SortSpec = "name:desc".parse().expect("parse name:desc");
+        assert_eq!(spec.field, SortField::Name);
+        assert_eq!(spec.direction, SortDirection::Desc);
+
+        let spec: SortSpec = "commits:asc".parse().expect("parse commits:asc");
+        assert_eq!(spec.field, SortField::Commits);
+        assert_eq!(spec.direction, SortDirection::Asc);
+
+        let spec: SortSpec = "lines:asc".parse().expect("parse lines:asc");
+        assert_eq!(spec.field, SortField::Lines);
+        assert_eq!(spec.direction, SortDirection::Asc);
+    }
+
+    #[test]
+    fn parse_sort_unknown_field_errors() {
+        assert!("foo".parse::<SortSpec>().is_err());
+        assert!("foo:asc".parse::<SortSpec>().is_err());
+    }
+
+    #[test]
+    fn parse_sort_unknown_direction_errors() {
+        assert!("time:up".parse::<SortSpec>().is_err());
+    }
+
+    fn project(name: &str) -> ProjectLog {
+        ProjectLog {
+            project: name.to_string(),
+            path: format!("/src/{name}"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ties_are_listed_by_name() {
+        let mut projects = vec![project("shop"), project("Api"), project("crm")];
+        sort_projects(&mut projects, "commits".parse().expect("parse commits"));
+        let names: Vec<&str> = projects.iter().map(|p| p.project.as_str()).collect();
+        assert_eq!(names, ["Api", "crm", "shop"]);
+
+        sort_projects(&mut projects, "name:desc".parse().expect("parse name:desc"));
+        let names: Vec<&str> = projects.iter().map(|p| p.project.as_str()).collect();
+        assert_eq!(names, ["shop", "crm", "Api"]);
+    }
+
+    fn active(name: &str, times: &[&str]) -> ProjectLog {
+        let commits = times
+            .iter()
+            .enumerate()
+            .map(|(i, time)| Commit {
+                hash: format!("{name}{i}"),
+                time: DateTime::parse_from_rfc3339(time).expect("valid time"),
+                diff_stat: Some(DiffStat {
+                    files_changed: 1,
+                    insertions: u32::MAX,
+                    deletions: 1,
+                }),
+                ..Default::default()
+            })
+            .collect();
+        ProjectLog {
+            branches: vec![BranchLog {
+                name: "main".to_string(),
+                commits,
+                ..Default::default()
+            }],
+            ..project(name)
+        }
+    }
+
+    fn sorted(projects: &mut [ProjectLog], spec: &str) -> Vec<String> {
+        sort_projects(projects, spec.parse().expect("valid spec"));
+        projects.iter().map(|p| p.project.clone()).collect()
+    }
+
+    #[test]
+    fn sorts_by_commits_and_time_both_ways() {
+        let mut projects = vec![
+            active("web", &["2024-03-04T09:00:00+01:00"]),
+            active(
+                "api",
+                &["2024-03-03T18:00:00+01:00", "2024-03-02T10:00:00+01:00"],
+            ),
+            active("docs", &[]),
+            active(
+                "crm",
+                &[
+                    "2024-03-05T08:00:00+01:00",
+                    "2024-03-01T12:00:00+01:00",
+                    "2024-03-01T11:00:00+01:00",
+                ],
+            ),
+        ];
+        assert_eq!(
+            sorted(&mut projects, "commits"),
+            ["crm", "api", "web", "docs"]
+        );
+        assert_eq!(
+            sorted(&mut projects, "commits:asc"),
+            ["docs", "web", "api", "crm"]
+        );
+        assert_eq!(sorted(&mut projects, "time"), ["crm", "web", "api", "docs"]);
+        assert_eq!(
+            sorted(&mut projects, "time:asc"),
+            ["docs", "api", "web", "crm"]
+        );
+    }
+
+    #[test]
+    fn line_counts_do_not_overflow() {
+        let mut projects = vec![
+            active("web", &["2024-03-04T09:00:00+01:00"]),
+            active(
+                "api",
+                &["2024-03-03T18:00:00+01:00", "2024-03-02T10:00:00+01:00"],
+            ),
+        ];
+        assert_eq!(sorted(&mut projects, "lines"), ["api", "web"]);
+        assert_eq!(sorted(&mut projects, "lines:asc"), ["web", "api"]);
+    }
+
+    #[test]
+    fn default_sort_spec_is_time_desc() {
+        let spec = SortSpec::default();
+        assert_eq!(spec.field, SortField::Time);
+        assert_eq!(spec.direction, SortDirection::Desc);
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::activity;
+use crate::conventional::percent;
+use crate::holidays::{DayKind, HolidayCalendar};
+use crate::model::ProjectLog;
+use crate::period::TimeRange;
+use crate::zone::Time;
+
+/// A day off (weekend or holiday) on which commits were made.
+#[derive(Debug, Serialize)]
+pub struct OffDay {
+    pub date: NaiveDate,
+    /// Holiday name, `None` for weekends.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holiday: Option<String>,
+    pub commits: usize,
+}
+
+/// Working-day statistics for a period.
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    pub commits: usize,
+    pub projects: usize,
+    pub calendar_days: usize,
+    /// Weekdays in the period that are not holidays.
+    pub expected_workdays: usize,
+    pub holidays: usize,
+    /// Days with at least one commit.
+    pub active_days: usize,
+    /// Workdays with at least one commit.
+    pub active_workdays: usize,
+    pub off_days_worked: Vec<OffDay>,
+    /// Forge verification of the commits, when it was looked up.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verified: Option<Verified>,
+    /// Comparison with the previous period, when it was scanned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trend: Option<Trend>,
+}
+
+/// Commits marked as verified on GitHub or GitLab.
+#[derive(Debug, Serialize)]
+pub struct Verified {
+    /// Commits whose status is known.
+    pub checked: usize,
+    pub verified: usize,
+    pub percent: f64,
+}
+
+/// Commit frequency compared with the equivalent previous period.
+#[derive(Debug, Serialize)]
+pub struct Trend {
+    pub previous_commits: usize,
+    pub previous_active_days: usize,
+    /// Change in percent; `None` when the previous period had no commits.
+    pub commits_change: Option<f64>,
+    pub active_days_change: Option<f64>,
+}
+
+/// Compare `stats` with the projects of the previous period.
+pub fn trend(stats: &Stats, previous: &[ProjectLog]) -> Trend {
+    let per_day = daily_commits(previous);
+    let previous_commits = per_day.values().sum();
+    Trend {
+        previous_commits,
+        previous_active_days: per_day.len(),
+        commits_change: change(stats.commits, previous_commits),
+        active_days_change: change(stats.active_days, per_day.len()),
+    }
+}
+
+fn change(current: usize, previous: usize) -> Option<f64> {
+    (previous > 0).then(|| {
+        let delta = current as f64 - previous as f64;
+        (delta * 1000.0 / previous as f64).round() / 10.0
+    })
+}
+
+/// Unique commits per day; a commit on several branches counts once.
+fn daily_commits(projects: &[ProjectLog]) -> BTreeMap<NaiveDate, usize> {
+    let mut per_day: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    for project in projects {
+        let mut seen = HashSet::new();
+        for commit in project.branches.iter().flat_map(|b| &b.commits) {
+            if seen.insert(&commit.hash) {
+                *per_day.entry(commit.time.date_naive()).or_default() += 1;
+            }
+        }
+    }
+    per_day
+}
+
+pub fn compute(
+    projects: &[ProjectLog],
+    range: &TimeRange,
+    calendar: &HolidayCalendar,
+    now: Time,
+) -> Stats {
+    let days = activity::period_days(range, now);
+
+    let per_day = daily_commits(projects);
+    let commits = per_day.values().sum();
+    let (mut checked, mut verified) = (0, 0);
+    for project in projects {
+        let mut seen = HashSet::new();
+        for commit in project.branches.iter().flat_map(|b| &b.commits) {
+            if let (true, Some(v)) = (seen.insert(&commit.hash), commit.verified) {
+                checked += 1;
+                verified += usize::from(v);
+            }
+        }
+    }
+
+    let mut stats = Stats {
+        commits,
+        projects: projects.len(),
+        calendar_days: days.len(),
+        expected_workdays: 0,
+        holidays: 0,
+        active_days: per_day.len(),
+        active_workdays: 0,
+        off_days_worked: Vec::new(),
+        verified: (checked > 0).then(|| Verified {
+            checked,
+            verified,
+            percent: percent(verified, checked),
+        }),
+        trend: None,
+    };
+
+    for day in &days {
+        let kind = calendar.classify(*day);
+        match kind {
+            DayKind::Workday => stats.expected_workdays += 1,
+            DayKind::Holiday(_) => stats.holidays += 1,
+            DayKind::Weekend => {}
+        }
+        let Some(&count) = per_day.get(day) else {
+            continue;
+        };
+        match kind {
+            DayKind::Workday => stats.active_workdays += 1,
+            DayKind::Weekend => stats.off_days_worked.push(OffDay {
+                date: *day,
+                holiday: None,
+                commits: count,
+            }),
+            DayKind::Holiday(name) => stats.off_days_worked.push(OffDay {
+                date: *day,
+                holiday: Some(name),
+                commits: count,
+            }),
+        }
+    }
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BranchLog, Commit};
+    use crate::zone::Zone;
+    use chrono::{Local, TimeZone};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).expect("valid date")
+    }
+
+    fn commit(hash: &str, day: NaiveDate) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            message: "msg".to_string(),
+            time: Local
+                .from_local_datetime(&day.and_hms_opt(12, 0, 0).expect("valid time"))
+                .single()
+                .expect("unambiguous time")
+                .fixed_offset(),
+            relative_time: "1d ago".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn holiday_and_weekend_work_is_classified() {
+        // 2026-12-21 (Mon) .. 2026-12-27 (Sun); 25th is a holiday
+        let range = TimeRange::from_dates(date(2026, 12, 21), date(2026, 12, 27), Zone::Local)
+            .expect("range");
+        let mut calendar = HolidayCalendar::default();
+        calendar.add(date(2026, 12, 25), "Christmas Day");
+
+        let projects = vec![ProjectLog {
+            project: "api".to_string(),
+            path: "/src/api".to_string(),
+            branches: vec![BranchLog {
+                name: "main".to_string(),
+                commits: vec![
+                    commit("a", date(2026, 12, 21)),
+                    commit("b", date(2026, 12, 25)),
+                    commit("c", date(2026, 12, 26)),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }];
+
+        let stats = compute(&projects, &range, &calendar, Local::now().fixed_offset());
+        assert_eq!(stats.commits, 3);
+        assert_eq!(stats.calendar_days, 7);
+        assert_eq!(stats.expected_workdays, 4);
+        assert_eq!(stats.holidays, 1);
+        assert_eq!(stats.active_days, 3);
+        assert_eq!(stats.active_workdays, 1);
+        assert_eq!(stats.off_days_worked.len(), 2);
+        assert_eq!(
+            stats.off_days_worked[0].holiday.as_deref(),
+            Some("Christmas Day")
+        );
+        assert_eq!(stats.off_days_worked[1].holiday, None);
+        assert!(stats.verified.is_none());
+    }
+
+    #[test]
+    fn verified_share_counts_known_statuses() {
+        let range = TimeRange::from_dates(date(2026, 12, 21), date(2026, 12, 27), Zone::Local)
+            .expect("range");
+        let mut commits = vec![
+            commit("a", date(2026, 12, 21)),
+            commit("b", date(2026, 12, 22)),
+            commit("c", date(2026, 12, 23)),
+        ];
+        commits[0].verified = Some(true);
+        commits[1].verified = Some(false);
+        let projects = vec![ProjectLog {
+            project: "api".to_string(),
+            path: "/src/api".to_string(),
+            branches: vec![BranchLog {
+                name: "main".to_string(),
+                commits,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }];
+        let stats = compute(
+            &projects,
+            &range,
+            &HolidayCalendar::default(),
+            Local::now().fixed_offset(),
+        );
+        let verified = stats.verified.expect("verification known");
+        assert_eq!((verified.checked, verified.verified), (2, 1));
+        assert_eq!(verified.percent, 50.0);
+    }
+
+    #[test]
+    fn trend_compares_with_previous_period() {
+        let range =
+            TimeRange::from_dates(date(2026, 3, 2), date(2026, 3, 8), Zone::Local).expect("range");
+        let project = |commits| ProjectLog {
+            project: "api".to_string(),
+            path: "/src/api".to_string(),
+            branches: vec![BranchLog {
+                name: "main".to_string(),
+                commits,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let current = vec![project(vec![
+            commit("a", date(2026, 3, 2)),
+            commit("b", date(2026, 3, 3)),
+            commit("c", date(2026, 3, 3)),
+        ])];
+        let previous = vec![project(vec![
+            commit("d", date(2026, 2, 24)),
+            commit("e", date(2026, 2, 24)),
+        ])];
+        let stats = compute(
+            &current,
+            &range,
+            &HolidayCalendar::default(),
+            Local::now().fixed_offset(),
+        );
+        let trend = trend(&stats, &previous);
+        assert_eq!(trend.previous_commits, 2);
+        assert_eq!(trend.commits_change, Some(50.0));
+        assert_eq!(trend.active_days_change, Some(100.0));
+        assert_eq!(super::trend(&stats, &[]).commits_change, None);
+    }
+}
@@ -0,0 +1,129 @@
+use std::path::Path;
+
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::git;
+use crate::period::TimeRange;
+use crate::relative::RelativeTime;
+use crate::zone::{Time, Zone};
+
+/// Tags created in a repository during the period, for `--tags`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoTags {
+    pub project: String,
+    pub path: String,
+    /// Newest first.
+    pub tags: Vec<Tag>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tag {
+    pub name: String,
+    /// Short hash of the commit the tag points at.
+    pub commit: String,
+    /// Subject of the tag message; `None` for lightweight tags.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotation: Option<String>,
+    #[serde(rename = "timestamp")]
+    pub time: Time,
+    pub relative_time: String,
+}
+
+const FORMAT: &str = "--format=%(refname:short)%00%(creatordate:iso-strict)%00%(objecttype)\
+                      %00%(objectname:short)%00%(*objectname:short)%00%(contents:subject)";
+
+/// Every tag in `repo` created in `range`, release or not; `None` when there
+/// is none and for repositories git cannot read. Annotated tags use the
+/// tagger date, lightweight tags the commit date.
+pub fn check(repo: &Path, range: &TimeRange, relative: &RelativeTime) -> Option<RepoTags> {
+    let output = git::command()
+        .args([
+            "-C",
+            &repo.to_string_lossy(),
+            "for-each-ref",
+            "refs/tags",
+            "--sort=-creatordate",
+            FORMAT,
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let tags: Vec<Tag> = parse_tags(
+        &String::from_utf8_lossy(&output.stdout),
+        range.zone,
+        relative,
+    )
+    .into_iter()
+    .filter(|t| t.time >= range.since && range.until.is_none_or(|until| t.time < until))
+    .collect();
+    if tags.is_empty() {
+        return None;
+    }
+    Some(RepoTags {
+        project: git::project_name(repo)?,
+        path: repo.to_string_lossy().to_string(),
+        tags,
+    })
+}
+
+/// Tags from `git for-each-ref` with [`FORMAT`].
+fn parse_tags(list: &str, zone: Zone, relative: &RelativeTime) -> Vec<Tag> {
+    let now = zone.now();
+    list.lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\0');
+            let name = fields.next()?;
+            let time = zone.convert(DateTime::parse_from_rfc3339(fields.next()?).ok()?);
+            let annotated = fields.next()? == "tag";
+            let object = fields.next()?;
+            let peeled = fields.next()?;
+            let subject = fields.next().unwrap_or_default();
+            let (commit, annotation) = if annotated {
+                let annotation = (!subject.is_empty()).then(|| subject.to_string());
+                (peeled, annotation)
+            } else {
+                (object, None)
+            };
+            Some(Tag {
+                name: name.to_string(),
+                commit: commit.to_string(),
+                annotation,
+                time,
+                relative_time: relative.format(now, time),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_annotated_and_lightweight_tags() {
+        let list =
+            "v2.0.0\x002026-03-05T10:00:00+01:00\x00tag\x00aaaa111\x00b2c3d4e\x00Release 2.0\n\
+             deploy-42\x002026-03-04T09:00:00+01:00\x00commit\x00c3d4e5f\x00\x00fix: typo\n";
+        let tags = parse_tags(list, Zone::Local, &RelativeTime::default());
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].name, "v2.0.0");
+        assert_eq!(tags[0].commit, "b2c3d4e");
+        assert_eq!(tags[0].annotation.as_deref(), Some("Release 2.0"));
+        assert_eq!(tags[1].name, "deploy-42");
+        assert_eq!(tags[1].commit, "c3d4e5f");
+        assert!(tags[1].annotation.is_none());
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        assert!(parse_tags(
+            "v1.0.0\x00not a date\x00commit\x00abc\x00\x00\n",
+            Zone::Local,
+            &RelativeTime::default()
+        )
+        .is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::activity;
+use crate::model::{ProjectLog, WorkTime};
+use crate::zone::Time;
+
+/// The project with the most commits in the report.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Busiest {
+    pub project: String,
+    pub commits: usize,
+}
+
+/// Commits of one conventional commit type.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct TypeCount {
+    /// The type, or `other` for commits without one.
+    pub commit_type: String,
+    pub commits: usize,
+}
+
+/// Report-wide totals, summarized in the optional footer and `--summary`.
+#[derive(Debug, Serialize)]
+pub struct Totals {
+    pub commits: usize,
+    pub projects: usize,
+    /// Branches with at least one commit.
+    pub branches: usize,
+    /// Sum of the per-day working time estimates.
+    #[serde(rename = "estimated_minutes")]
+    pub estimated: WorkTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub busiest: Option<Busiest>,
+    /// Most frequent first; ties in alphabetical order.
+    pub by_type: Vec<TypeCount>,
+    /// Time of the earliest commit.
+    pub first: Option<Time>,
+    /// Time of the latest commit.
+    pub last: Option<Time>,
+}
+
+pub fn compute(projects: &[ProjectLog]) -> Totals {
+    let busiest = projects
+        .iter()
+        .map(|p| (p, p.total_commits()))
+        .filter(|(_, commits)| *commits > 0)
+        // Ties go to the project listed first.
+        .rev()
+        .max_by_key(|(_, commits)| *commits)
+        .map(|(p, commits)| Busiest {
+            project: p.project.clone(),
+            commits,
+        });
+    // A commit on several branches counts once, as in `total_commits`.
+    let commits: Vec<_> = projects
+        .iter()
+        .flat_map(|p| {
+            let mut seen = HashSet::new();
+            p.branches
+                .iter()
+                .flat_map(|b| &b.commits)
+                .filter(move |c| seen.insert(&c.hash))
+        })
+        .collect();
+    let mut types: HashMap<&str, usize> = HashMap::new();
+    for commit in &commits {
+        *types
+            .entry(commit.commit_type.as_deref().unwrap_or("other"))
+            .or_default() += 1;
+    }
+    let mut by_type: Vec<TypeCount> = types
+        .into_iter()
+        .map(|(commit_type, commits)| TypeCount {
+            commit_type: commit_type.to_string(),
+            commits,
+        })
+        .collect();
+    by_type.sort_by(|a, b| {
+        b.commits
+            .cmp(&a.commits)
+            .then_with(|| a.commit_type.cmp(&b.commit_type))
+    });
+    Totals {
+        commits: commits.len(),
+        projects: projects.len(),
+        branches: projects
+            .iter()
+            .flat_map(|p| &p.branches)
+            .filter(|b| !b.commits.is_empty())
+            .count(),
+        estimated: activity::daily_spans(projects)
+            .iter()
+            .map(|s| s.estimated)
+            .sum(),
+        busiest,
+        by_type,
+        first: commits.iter().map(|c| c.time).min(),
+        last: commits.iter().map(|c| c.time).max(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BranchLog, Commit};
+    use chrono::{Duration, Local, TimeZone};
+
+    fn commit(hash: &str, minutes: i64) -> Commit {
+        let base = Local
+            .with_ymd_and_hms(2026, 3, 2, 9, 0, 0)
+            .single()
+            .expect("unambiguous time")
+            .fixed_offset();
+        Commit {
+            hash: hash.to_string(),
+            message: "msg".to_string(),
+            time: base + Duration::minutes(minutes),
+            ..Default::default()
+        }
+    }
+
+    fn project(name: &str, branches: Vec<Vec<Commit>>) -> ProjectLog {
+        ProjectLog {
+            project: name.to_string(),
+            path: format!("/src/{name}"),
+            branches: branches
+                .into_iter()
+                .enumerate()
+                .map(|(i, commits)| BranchLog {
+                    name: format!("branch-{i}"),
+                    commits,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sums_commits_branches_and_estimate() {
+        let projects = vec![
+            project("web", vec![vec![commit("a", 0)]]),
+            project(
+                "api",
+                vec![
+                    vec![commit("b", 10), commit("c", 40)],
+                    vec![commit("c", 40)],
+                ],
+            ),
+        ];
+        let totals = compute(&projects);
+        assert_eq!(totals.commits, 3);
+        assert_eq!(totals.projects, 2);
+        assert_eq!(totals.branches, 3);
+        assert_eq!(totals.estimated, WorkTime::from_minutes(30 + 40));
+        assert_eq!(
+            totals.by_type,
+            [TypeCount {
+                commit_type: "other".to_string(),
+                commits: 3,
+            }]
+        );
+        assert_eq!(
+            totals.first.map(|t| t.format("%H:%M").to_string()),
+            Some("09:00".to_string())
+        );
+        assert_eq!(
+            totals.last.map(|t| t.format("%H:%M").to_string()),
+            Some("09:40".to_string())
+        );
+        assert_eq!(
+            totals.busiest,
+            Some(Busiest {
+                project: "api".to_string(),
+                commits: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn busiest_tie_keeps_report_order() {
+        let projects = vec![
+            project("web", vec![vec![commit("a", 0)]]),
+            project("api", vec![vec![commit("b", 10)]]),
+        ];
+        let busiest = compute(&projects).busiest.expect("a busiest project");
+        assert_eq!(busiest.project, "web");
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::model::ProjectLog;
+use crate::zone::Time;
+
+/// A commit that exists only in the local repository.
+#[derive(Debug, Serialize)]
+pub struct LocalCommit {
+    pub hash: String,
+    pub message: String,
+    /// Local branches containing the commit.
+    pub branches: Vec<String>,
+    #[serde(rename = "timestamp")]
+    pub time: Time,
+    pub relative_time: String,
+}
+
+/// Unpushed commits of one project.
+#[derive(Debug, Serialize)]
+pub struct UnpushedStatus {
+    pub project: String,
+    pub path: String,
+    pub commits: Vec<LocalCommit>,
+}
+
+/// Projects with unpushed commits, each listing them newest first.
+pub fn collect(projects: &[ProjectLog]) -> Vec<UnpushedStatus> {
+    projects.iter().filter_map(check).collect()
+}
+
+fn check(project: &ProjectLog) -> Option<UnpushedStatus> {
+    let mut commits: Vec<LocalCommit> = Vec::new();
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for branch in &project.branches {
+        for commit in branch.commits.iter().filter(|c| c.unpushed) {
+            if let Some(&i) = index.get(commit.hash.as_str()) {
+                commits[i].branches.push(branch.name.clone());
+                continue;
+            }
+            index.insert(&commit.hash, commits.len());
+            commits.push(LocalCommit {
+                hash: commit.hash.clone(),
+                message: commit.message.clone(),
+                branches: vec![branch.name.clone()],
+                time: commit.time,
+                relative_time: commit.relative_time.clone(),
+            });
+        }
+    }
+    if commits.is_empty() {
+        return None;
+    }
+    commits.sort_by_key(|c| std::cmp::Reverse(c.time));
+    Some(UnpushedStatus {
+        project: project.project.clone(),
+        path: project.path.clone(),
+        commits,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BranchLog, Commit};
+    use chrono::Local;
+
+    fn commit(hash: &str, unpushed: bool) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            message: format!("commit {hash}"),
+            author: "Jane Doe".to_string(),
+            time: Local::now().fixed_offset(),
+            unpushed,
+            ..Default::default()
+        }
+    }
+
+    fn project(name: &str, branches: Vec<(&str, Vec<Commit>)>) -> ProjectLog {
+        ProjectLog {
+            project: name.to_string(),
+            path: format!("/src/{name}"),
+            branches: branches
+                .into_iter()
+                .map(|(branch, commits)| BranchLog {
+                    name: branch.to_string(),
+                    commits,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn lists_local_commits_once_with_their_branches() {
+        let projects = vec![
+            project(
+                "web",
+                vec![
+                    ("main", vec![commit("a", false), commit("b", true)]),
+                    ("feature/x", vec![commit("c", true), commit("b", true)]),
+                ],
+            ),
+            project("api", vec![("main", vec![commit("d", false)])]),
+        ];
+        let statuses = collect(&projects);
+        assert_eq!(statuses.len(), 1);
+        let web = &statuses[0];
+        assert_eq!(web.project, "web");
+        let b = web
+            .commits
+            .iter()
+            .find(|c| c.hash == "b")
+            .expect("commit b listed");
+        assert_eq!(b.branches, ["main", "feature/x"]);
+        assert_eq!(web.commits.len(), 2);
+    }
+}
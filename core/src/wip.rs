@@ -0,0 +1,153 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::git;
+use crate::period::TimeRange;
+use crate::relative::RelativeTime;
+use crate::zone::{Time, Zone};
+
+/// Work that has not been committed yet: changes in the working copy and
+/// stashes created during the period.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkInProgress {
+    pub project: String,
+    pub path: String,
+    /// Checked-out branch; `None` on a detached HEAD.
+    pub branch: Option<String>,
+    /// Tracked files with changes, staged or not.
+    pub changed: u32,
+    /// Files with changes in the index.
+    pub staged: u32,
+    /// Tracked files with changes not yet staged.
+    pub unstaged: u32,
+    pub untracked: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stashes: Vec<Stash>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stash {
+    /// Stash subject, e.g. `WIP on main: 1a2b3c4 add login`.
+    pub message: String,
+    #[serde(rename = "timestamp")]
+    pub time: Time,
+    pub relative_time: String,
+}
+
+/// Uncommitted work in `repo`; `None` when the working copy is clean and no
+/// stash was created in `range`, and for repositories git cannot read.
+pub fn check(repo: &Path, range: &TimeRange, relative: &RelativeTime) -> Option<WorkInProgress> {
+    let repo_str = repo.to_string_lossy();
+    let status = git::command()
+        .args(["-C", &repo_str, "status", "--porcelain=v2", "--branch"])
+        .output()
+        .ok()?;
+    if !status.status.success() {
+        return None;
+    }
+    let mut wip = parse_status(&String::from_utf8_lossy(&status.stdout));
+
+    let stashes = git::command()
+        .args(["-C", &repo_str, "stash", "list", "--format=%ct%x09%gs"])
+        .output()
+        .ok()?;
+    if stashes.status.success() {
+        let list = String::from_utf8_lossy(&stashes.stdout);
+        wip.stashes = parse_stashes(&list, range.zone.now(), range.zone, relative)
+            .into_iter()
+            .filter(|s| s.time >= range.since && range.until.is_none_or(|until| s.time < until))
+            .collect();
+    }
+
+    if wip.changed + wip.untracked == 0 && wip.stashes.is_empty() {
+        return None;
+    }
+    wip.project = repo.file_name()?.to_string_lossy().to_string();
+    wip.path = repo.to_string_lossy().to_string();
+    Some(wip)
+}
+
+/// Branch and file counts from `git status --porcelain=v2 --branch`.
+fn parse_status(status: &str) -> WorkInProgress {
+    let mut wip = WorkInProgress::default();
+    for line in status.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            wip.branch = (head != "(detached)").then(|| head.to_string());
+            continue;
+        }
+        let mut fields = line.split(' ');
+        match (fields.next(), fields.next()) {
+            (Some("1" | "2"), Some(xy)) => {
+                wip.changed += 1;
+                let mut xy = xy.chars();
+                if xy.next().is_some_and(|x| x != '.') {
+                    wip.staged += 1;
+                }
+                if xy.next().is_some_and(|y| y != '.') {
+                    wip.unstaged += 1;
+                }
+            }
+            (Some("u"), _) => {
+                wip.changed += 1;
+                wip.unstaged += 1;
+            }
+            (Some("?"), _) => wip.untracked += 1,
+            _ => {}
+        }
+    }
+    wip
+}
+
+/// Stashes from `git stash list --format=%ct%x09%gs`, newest first.
+fn parse_stashes(list: &str, now: Time, zone: Zone, relative: &RelativeTime) -> Vec<Stash> {
+    list.lines()
+        .filter_map(|line| {
+            let (time, message) = line.split_once('\t')?;
+            let time = zone.timestamp(time.parse().ok()?)?;
+            Some(Stash {
+                message: message.to_string(),
+                time,
+                relative_time: relative.format(now, time),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_staged_unstaged_and_untracked_files() {
+        let status = "# branch.oid 194816b\n# branch.head feature/login\n\
+                      1 M. N... 100644 100644 100644 a b src/lib.rs\n\
+                      1 MM N... 100644 100644 100644 a b src/main.rs\n\
+                      1 .D N... 100644 100644 000000 a b old.rs\n\
+                      2 R. N... 100644 100644 100644 a b new.rs\told.rs\n\
+                      ? notes.txt\n";
+        let wip = parse_status(status);
+        assert_eq!(wip.branch.as_deref(), Some("feature/login"));
+        assert_eq!(wip.staged, 3);
+        assert_eq!(wip.unstaged, 2);
+        assert_eq!(wip.untracked, 1);
+        assert_eq!(wip.changed, 4);
+    }
+
+    #[test]
+    fn parses_stash_list() {
+        let now = Zone::Local
+            .timestamp(1772445600 + 3 * 3600)
+            .expect("valid time");
+        let stashes = parse_stashes(
+            "1772445600\tWIP on main: 1a2b3c4 add login\n",
+            now,
+            Zone::Local,
+            &RelativeTime::default(),
+        );
+        assert_eq!(stashes.len(), 1);
+        assert_eq!(stashes[0].message, "WIP on main: 1a2b3c4 add login");
+        assert_eq!(stashes[0].time.timestamp(), 1772445600);
+        assert_eq!(stashes[0].relative_time, "3h ago");
+    }
+}
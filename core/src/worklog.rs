@@ -0,0 +1,314 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use rayon::prelude::*;
+
+use crate::discovery::{self, DiscoveryOptions};
+use crate::git::{self, LogOptions};
+use crate::model::ProjectLog;
+use crate::period::{Period, TimeRange};
+use crate::progress::{Progress, ProgressEvent};
+use crate::relative::RelativeTime;
+use crate::sort::{self, SortSpec};
+use crate::zone::Zone;
+use crate::{bundle, model};
+
+/// The commits of a period across all repositories below some roots, as
+/// collected by [`Worklog::builder`].
+#[derive(Debug)]
+pub struct Worklog {
+    /// The period the commits were collected for.
+    pub range: TimeRange,
+    /// One entry per repository or bundle with matching commits, most
+    /// recent first unless [`WorklogBuilder::sort`] says otherwise.
+    pub projects: Vec<ProjectLog>,
+}
+
+impl Worklog {
+    /// Start configuring a collection. Without further settings it reads
+    /// today's commits of every author in the repositories below the
+    /// current directory.
+    pub fn builder<'a>() -> WorklogBuilder<'a> {
+        WorklogBuilder::default()
+    }
+
+    pub fn total_commits(&self) -> usize {
+        self.projects.iter().map(ProjectLog::total_commits).sum()
+    }
+
+    /// Sum of the time logged in commit trailers; see
+    /// [`WorklogBuilder::time_trailer`].
+    pub fn time_spent(&self) -> Option<model::WorkTime> {
+        model::total_time_spent(&self.projects)
+    }
+}
+
+/// Settings for [`Worklog::builder`]. Repositories are scanned in parallel.
+#[derive(Default)]
+pub struct WorklogBuilder<'a> {
+    roots: Vec<PathBuf>,
+    repos: Vec<PathBuf>,
+    period: Option<Period>,
+    range: Option<TimeRange>,
+    zone: Zone,
+    authors: Vec<String>,
+    grep: Vec<String>,
+    exclude_grep: Vec<String>,
+    with_stat: bool,
+    include_merges: bool,
+    remote_branches: bool,
+    dedupe: bool,
+    time_trailer: Option<String>,
+    relative: RelativeTime,
+    sort: SortSpec,
+    discovery: DiscoveryOptions,
+    progress: Option<&'a dyn Progress>,
+}
+
+impl<'a> WorklogBuilder<'a> {
+    /// Add a directory to search for repositories and `.bundle` files;
+    /// several roots may be given.
+    pub fn path(mut self, root: impl Into<PathBuf>) -> Self {
+        self.roots.push(root.into());
+        self
+    }
+
+    /// Add a repository (or a path inside one, or a bundle) to read as is,
+    /// without searching a directory tree.
+    pub fn repo(mut self, repo: impl Into<PathBuf>) -> Self {
+        self.repos.push(repo.into());
+        self
+    }
+
+    /// The period to collect; [`Period::Today`] by default.
+    pub fn period(mut self, period: Period) -> Self {
+        self.period = Some(period);
+        self.range = None;
+        self
+    }
+
+    /// The time zone the [`period`](Self::period) is counted in; the
+    /// machine's by default. A [`range`](Self::range) brings its own.
+    pub fn timezone(mut self, zone: Zone) -> Self {
+        self.zone = zone;
+        self
+    }
+
+    /// Exact bounds instead of a [`Period`].
+    pub fn range(mut self, range: TimeRange) -> Self {
+        self.range = Some(range);
+        self.period = None;
+        self
+    }
+
+    /// Keep commits whose author name or email matches this extended regex,
+    /// as with `git log -E --author`. May be given several times; without
+    /// it, every author's commits are kept.
+    pub fn author(mut self, pattern: impl Into<String>) -> Self {
+        self.authors.push(pattern.into());
+        self
+    }
+
+    /// Keep only commits whose message matches this extended regex. May be
+    /// given several times; a commit is kept when any pattern matches.
+    pub fn grep(mut self, pattern: impl Into<String>) -> Self {
+        self.grep.push(pattern.into());
+        self
+    }
+
+    /// Drop commits whose message matches this extended regex.
+    pub fn exclude_grep(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude_grep.push(pattern.into());
+        self
+    }
+
+    /// Skip directories matching this glob while searching the roots; see
+    /// [`DiscoveryOptions::exclude`].
+    pub fn exclude(mut self, glob: impl Into<String>) -> Self {
+        self.discovery.exclude.push(glob.into());
+        self
+    }
+
+    /// Look for repositories at most `depth` directory levels below each
+    /// root.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.discovery.max_depth = Some(depth);
+        self
+    }
+
+    /// Do not descend into network mounts below the roots.
+    pub fn skip_network(mut self, skip: bool) -> Self {
+        self.discovery.skip_network = skip;
+        self
+    }
+
+    /// Compute insertions, deletions and changed files per commit.
+    pub fn with_stat(mut self, with_stat: bool) -> Self {
+        self.with_stat = with_stat;
+        self
+    }
+
+    /// Keep merge commits, which are left out by default.
+    pub fn include_merges(mut self, include: bool) -> Self {
+        self.include_merges = include;
+        self
+    }
+
+    /// Also read remote-tracking branches that have no local branch of the
+    /// same name, e.g. work pushed from another machine.
+    pub fn remote_branches(mut self, include: bool) -> Self {
+        self.remote_branches = include;
+        self
+    }
+
+    /// List each commit under one branch only: the primary branch when it
+    /// was made there, the feature branch it was made on otherwise.
+    pub fn dedupe(mut self, dedupe: bool) -> Self {
+        self.dedupe = dedupe;
+        self
+    }
+
+    /// Read the time spent on each commit from this trailer, e.g.
+    /// `Time-Spent`. Keys other than letters, digits and dashes are ignored
+    /// (see [`git::check_trailer_key`](crate::git::check_trailer_key)).
+    pub fn time_trailer(mut self, key: impl Into<String>) -> Self {
+        self.time_trailer = Some(key.into());
+        self
+    }
+
+    /// Order of the projects; latest commit first by default, as in the
+    /// CLI.
+    pub fn sort(mut self, spec: SortSpec) -> Self {
+        self.sort = spec;
+        self
+    }
+
+    /// How each commit's `relative_time` is written.
+    pub fn relative_time(mut self, relative: RelativeTime) -> Self {
+        self.relative = relative;
+        self
+    }
+
+    /// Report discovery and scanning to `progress`.
+    pub fn progress(mut self, progress: &'a dyn Progress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Discover the repositories and collect their commits. Fails when git
+    /// cannot be run, an exclude glob is invalid or a [`repo`](Self::repo)
+    /// is neither in a repository nor a bundle; repositories that cannot be
+    /// read are skipped and reported to the progress receiver.
+    pub fn collect(self) -> Result<Worklog> {
+        if !git::is_available() {
+            bail!("git could not be run; is it installed and on $PATH?");
+        }
+        discovery::check_exclude(&self.discovery.exclude).map_err(anyhow::Error::msg)?;
+        let progress = self.progress.unwrap_or(&());
+        let range = match self.range {
+            Some(range) => range,
+            None => self
+                .period
+                .unwrap_or(Period::Today)
+                .to_time_range(self.zone),
+        };
+
+        let mut found = discovery::from_list(&self.repos, progress);
+        if let Some(path) = found.not_found.first() {
+            bail!("not a git repository or bundle: {}", path.display());
+        }
+        let roots = if self.roots.is_empty() && self.repos.is_empty() {
+            vec![PathBuf::from(".")]
+        } else {
+            self.roots
+        };
+        let walked = discovery::discover_all_with_progress(&roots, &self.discovery, progress);
+        for repo in walked.repos {
+            if !found.repos.contains(&repo) {
+                found.repos.push(repo);
+            }
+        }
+        for file in walked.bundles {
+            if !found.bundles.contains(&file) {
+                found.bundles.push(file);
+            }
+        }
+
+        let opts = LogOptions {
+            authors: &self.authors,
+            with_stat: self.with_stat,
+            time_trailer: self.time_trailer.as_deref(),
+            grep: &self.grep,
+            exclude_grep: &self.exclude_grep,
+            include_merges: self.include_merges,
+            remote_branches: self.remote_branches,
+            dedupe: self.dedupe,
+            relative: self.relative,
+        };
+        let mut projects: Vec<ProjectLog> = found
+            .repos
+            .par_iter()
+            .map(|repo| git::collect_project_log_with_progress(repo, &range, &opts, progress))
+            .chain(found.bundles.par_iter().map(|file| {
+                let project = bundle::collect_bundle_log(file, &range, &opts);
+                progress.event(ProgressEvent::RepoScanned {
+                    path: file.clone(),
+                    commits: project.as_ref().map_or(0, ProjectLog::total_commits),
+                });
+                project
+            }))
+            .flatten()
+            .collect();
+        sort::sort_projects(&mut projects, self.sort);
+        Ok(Worklog { range, projects })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    #[test]
+    fn collects_commits_of_a_repository() {
+        let dir = std::env::temp_dir().join(format!("devcap-worklog-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let repo = dir.join("app");
+        std::fs::create_dir_all(&repo).expect("create repo dir");
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(&repo)
+                .args([
+                    "-c",
+                    "user.name=Jane Doe",
+                    "-c",
+                    "user.email=jane@example.com",
+                ])
+                .args(args)
+                .status()
+                .expect("run git");
+            assert!(status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "feat: add export"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "chore: bump"]);
+
+        let worklog = Worklog::builder()
+            .path(&dir)
+            .period(Period::Today)
+            .author("Jane")
+            .exclude_grep("^chore")
+            .collect()
+            .expect("collects");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(worklog.projects.len(), 1);
+        assert_eq!(worklog.projects[0].project, "app");
+        assert_eq!(worklog.total_commits(), 1);
+        assert_eq!(
+            worklog.projects[0].branches[0].commits[0].message,
+            "feat: add export"
+        );
+    }
+}
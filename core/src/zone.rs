@@ -0,0 +1,132 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+
+/// A point in time as reports carry it: the instant, with the offset the
+/// report's [`Zone`] has at that instant, so that its date, hour and
+/// weekday are those of the zone.
+pub type Time = DateTime<FixedOffset>;
+
+/// The time zone days are counted in and times are shown in: the
+/// machine's by default, or a named IANA zone (`--timezone`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Zone {
+    #[default]
+    Local,
+    Named(chrono_tz::Tz),
+}
+
+impl Zone {
+    /// The current time in this zone.
+    pub fn now(self) -> Time {
+        self.convert(Utc::now())
+    }
+
+    /// The same instant as `time`, expressed in this zone.
+    pub fn convert<Tz: TimeZone>(self, time: DateTime<Tz>) -> Time {
+        match self {
+            Zone::Local => time.with_timezone(&Local).fixed_offset(),
+            Zone::Named(tz) => time.with_timezone(&tz).fixed_offset(),
+        }
+    }
+
+    /// The instant `secs` seconds after the Unix epoch, in this zone.
+    pub fn timestamp(self, secs: i64) -> Option<Time> {
+        DateTime::from_timestamp(secs, 0).map(|t| self.convert(t))
+    }
+
+    /// `date` at `time` on the clocks of this zone. The earlier instant
+    /// when clocks are turned back, `None` for a time skipped when they
+    /// are turned forward.
+    pub fn at(self, date: NaiveDate, time: NaiveTime) -> Option<Time> {
+        let naive = date.and_time(time);
+        match self {
+            Zone::Local => Local
+                .from_local_datetime(&naive)
+                .earliest()
+                .map(|t| t.fixed_offset()),
+            Zone::Named(tz) => tz
+                .from_local_datetime(&naive)
+                .earliest()
+                .map(|t| t.fixed_offset()),
+        }
+    }
+
+    /// The first instant of `date` in this zone.
+    pub fn midnight(self, date: NaiveDate) -> Option<Time> {
+        // Some zones skip midnight when switching to daylight saving time.
+        (0..3).find_map(|hour| self.at(date, NaiveTime::from_hms_opt(hour, 0, 0)?))
+    }
+}
+
+impl FromStr for Zone {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("local") {
+            return Ok(Zone::Local);
+        }
+        s.parse::<chrono_tz::Tz>()
+            .map(Zone::Named)
+            .map_err(|_| format!("Unknown time zone: {s} (use a name like Europe/Berlin or UTC)"))
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Zone::Local => f.write_str("local"),
+            Zone::Named(tz) => f.write_str(tz.name()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    fn zone(name: &str) -> Zone {
+        name.parse().expect("known zone")
+    }
+
+    #[test]
+    fn parses_iana_names() {
+        assert_eq!(zone("UTC"), Zone::Named(chrono_tz::UTC));
+        assert_eq!(zone("local"), Zone::Local);
+        assert!("Mars/Base".parse::<Zone>().is_err());
+    }
+
+    #[test]
+    fn instant_late_in_utc_is_next_day_further_east() {
+        let instant = Utc
+            .with_ymd_and_hms(2026, 3, 5, 23, 30, 0)
+            .single()
+            .expect("valid time");
+        let berlin = zone("Europe/Berlin").convert(instant);
+        assert_eq!(berlin.date_naive().to_string(), "2026-03-06");
+        assert_eq!(berlin.hour(), 0);
+        let utc = zone("UTC").convert(instant);
+        assert_eq!(utc.date_naive().to_string(), "2026-03-05");
+        assert_eq!(berlin, utc);
+    }
+
+    #[test]
+    fn midnight_follows_daylight_saving_time() {
+        let berlin = zone("Europe/Berlin");
+        let winter = berlin
+            .midnight(NaiveDate::from_ymd_opt(2026, 3, 5).expect("date"))
+            .expect("midnight");
+        assert_eq!(winter.to_rfc3339(), "2026-03-05T00:00:00+01:00");
+        let summer = berlin
+            .midnight(NaiveDate::from_ymd_opt(2026, 7, 5).expect("date"))
+            .expect("midnight");
+        assert_eq!(summer.to_rfc3339(), "2026-07-05T00:00:00+02:00");
+        // Santiago turns its clocks from 00:00 to 01:00 in September.
+        let skipped = zone("America/Santiago")
+            .midnight(NaiveDate::from_ymd_opt(2026, 9, 6).expect("date"))
+            .expect("first hour of the day");
+        assert_eq!(skipped.hour(), 1);
+    }
+}